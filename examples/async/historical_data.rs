@@ -173,13 +173,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("Daily bars received: {}", daily_data.bars.len());
         for bar in daily_data.bars.iter().take(5) {
             println!(
-                "{}: O: ${:.2}, H: ${:.2}, L: ${:.2}, C: ${:.2}, V: {:.0}K",
+                "{}: O: ${:.2}, H: ${:.2}, L: ${:.2}, C: ${:.2}, V: {}",
                 format!("{:04}-{:02}-{:02}", bar.date.year(), bar.date.month() as u8, bar.date.day()),
                 bar.open,
                 bar.high,
                 bar.low,
                 bar.close,
-                bar.volume / 1000.0
+                bar.volume
             );
         }
 
@@ -60,9 +60,16 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     println!("  Execution ID: {}", report.execution_id);
                     println!("  Commission: {} {}", report.commission, report.currency);
                 }
+                Ok(OrderUpdate::OrderBound(binding)) => {
+                    println!("Order Bound:");
+                    println!("  Perm ID: {}", binding.perm_id);
+                    println!("  Order ID: {}", binding.order_id);
+                    println!("  Client ID: {}", binding.client_id);
+                }
                 Ok(OrderUpdate::Message(notice)) => {
                     println!("Order Message: {} - {}", notice.code, notice.message);
                 }
+                Ok(_) => {}
                 Err(e) => {
                     eprintln!("Error in order stream: {e:?}");
                     break;
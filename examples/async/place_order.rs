@@ -82,6 +82,9 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 println!("  Execution ID: {}", report.execution_id);
                 println!("  Commission: {} {}", report.commission, report.currency);
             }
+            Ok(PlaceOrder::DeltaNeutralValidation(contract)) => {
+                println!("Delta Neutral Validation: {contract:?}");
+            }
             Ok(PlaceOrder::Message(notice)) => {
                 println!("Order Message: {} - {}", notice.code, notice.message);
 
@@ -91,6 +94,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     break;
                 }
             }
+            Ok(_) => {}
             Err(e) => {
                 eprintln!("Error in order subscription: {e}");
                 break;
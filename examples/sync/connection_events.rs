@@ -0,0 +1,32 @@
+//! Example streaming connection state changes with Client.connection_events()
+//!
+//! A heartbeat requests the server time every 30 seconds; if TWS/IB Gateway does not
+//! answer within 10 seconds the connection is treated as lost and re-established.
+
+use ibapi::client::blocking::Client;
+use ibapi::{ConnectionEvent, ConnectionOptions};
+use std::time::Duration;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    let options = ConnectionOptions::default().heartbeat(Duration::from_secs(30), Duration::from_secs(10));
+    let client = Client::connect_with_options("127.0.0.1:4002", 100, options)?;
+
+    println!("Monitoring connection events (press Ctrl+C to exit)...");
+
+    for event in client.connection_events()? {
+        match event {
+            ConnectionEvent::Connected { server_version, time } => println!("connected: server version {server_version}, time {time:?}"),
+            ConnectionEvent::Disconnected { error } => println!("disconnected: {error}"),
+            ConnectionEvent::Reconnecting { attempt, delay } => println!("reconnect attempt {attempt} in {delay:?}"),
+            ConnectionEvent::Reconnected => println!("reconnected"),
+            ConnectionEvent::GaveUp => {
+                println!("gave up reconnecting");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ use ibapi::client::blocking::Client;
 use ibapi::{
     contracts::{Contract, Currency, Exchange, SecurityType, Symbol},
     orders::{self, order_builder, PlaceOrder},
+    Decimal,
 };
 
 fn main() {
@@ -40,7 +41,7 @@ fn main() {
     for status in subscription {
         println!("{status:?}");
         if let PlaceOrder::OrderStatus(order_status) = status {
-            if order_status.remaining == 0.0 {
+            if order_status.remaining == Decimal::default() {
                 break;
             }
         }
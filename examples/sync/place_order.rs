@@ -60,7 +60,9 @@ fn main() {
             PlaceOrder::OpenOrder(open_order) => println!("open order: {open_order:?}"),
             PlaceOrder::ExecutionData(execution) => println!("execution: {execution:?}"),
             PlaceOrder::CommissionReport(report) => println!("commission report: {report:?}"),
+            PlaceOrder::DeltaNeutralValidation(contract) => println!("delta neutral validation: {contract:?}"),
             PlaceOrder::Message(message) => println!("notice: {message}"),
+            _ => {}
         }
     }
 }
@@ -59,9 +59,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         OrderUpdate::CommissionReport(report) => {
                             println!("[Monitor] Commission: ${} for execution {}", report.commission, report.execution_id);
                         }
+                        OrderUpdate::OrderBound(binding) => {
+                            println!(
+                                "[Monitor] Order {} bound to order id {} of client {}",
+                                binding.perm_id, binding.order_id, binding.client_id
+                            );
+                        }
                         OrderUpdate::Message(message) => {
                             println!("[Monitor] Message: {}", message.message);
                         }
+                        _ => {}
                     }
                 }
             }
@@ -1,7 +1,7 @@
 use ibapi::contracts::Contract;
 use ibapi::market_data::historical::{BarSize, Duration, WhatToShow};
 use ibapi::market_data::TradingHours;
-use ibapi::Client;
+use ibapi::{Client, Decimal};
 use ibapi_test::{rate_limit, ClientId, GATEWAY};
 use serial_test::serial;
 
@@ -62,7 +62,7 @@ async fn historical_data_daily() {
         .expect("historical_data failed");
 
     assert!(!data.bars.is_empty(), "expected non-empty bars");
-    assert!(data.bars[0].volume >= 0.0, "volume should be non-negative");
+    assert!(data.bars[0].volume >= Decimal::default(), "volume should be non-negative");
 }
 
 #[tokio::test]
@@ -1,6 +1,6 @@
 use ibapi::contracts::Contract;
 use ibapi::orders::{Action, BracketOrderIds, CancelOrder, ExecutionFilter, Order, OrderId};
-use ibapi::Client;
+use ibapi::{Client, Decimal};
 use ibapi_test::{rate_limit, ClientId, GATEWAY};
 use serial_test::serial;
 
@@ -11,10 +11,10 @@ async fn connect() -> (Client, ClientId) {
     (client, client_id)
 }
 
-fn limit_order(action: Action, quantity: f64, price: f64) -> Order {
+fn limit_order(action: Action, quantity: i32, price: f64) -> Order {
     Order {
         action,
-        total_quantity: quantity,
+        total_quantity: Decimal::from(quantity),
         order_type: "LMT".to_string(),
         limit_price: Some(price),
         ..Default::default()
@@ -108,7 +108,7 @@ async fn place_limit_buy() {
     let (client, _client_id) = connect().await;
 
     let contract = Contract::stock("AAPL").build();
-    let order = limit_order(Action::Buy, 1.0, 1.0);
+    let order = limit_order(Action::Buy, 1, 1.0);
 
     rate_limit();
     let order_id = client.next_order_id();
@@ -128,7 +128,7 @@ async fn place_limit_sell() {
     let (client, _client_id) = connect().await;
 
     let contract = Contract::stock("AAPL").build();
-    let order = limit_order(Action::Sell, 1.0, 9999.0);
+    let order = limit_order(Action::Sell, 1, 9999.0);
 
     rate_limit();
     let order_id = client.next_order_id();
@@ -148,7 +148,7 @@ async fn cancel_order_succeeds() {
     let (client, _client_id) = connect().await;
 
     let contract = Contract::stock("AAPL").build();
-    let order = limit_order(Action::Buy, 1.0, 1.0);
+    let order = limit_order(Action::Buy, 1, 1.0);
 
     rate_limit();
     let order_id = client.next_order_id();
@@ -4,6 +4,7 @@ use ibapi::accounts::types::{AccountGroup, AccountId, ContractId};
 use ibapi::client::blocking::Client;
 use ibapi::contracts::Contract;
 use ibapi::orders::{Action, Order, PlaceOrder};
+use ibapi::Decimal;
 use ibapi_test::{rate_limit, require_market_open, ClientId, GATEWAY};
 use serial_test::serial;
 
@@ -154,7 +155,7 @@ fn pnl_single_receives_updates() {
     // Buy 1 share to create a position
     let buy = Order {
         action: Action::Buy,
-        total_quantity: 1.0,
+        total_quantity: Decimal::from(1),
         order_type: "MKT".into(),
         ..Default::default()
     };
@@ -178,7 +179,7 @@ fn pnl_single_receives_updates() {
     // Clean up - sell the share
     let sell = Order {
         action: Action::Sell,
-        total_quantity: 1.0,
+        total_quantity: Decimal::from(1),
         order_type: "MKT".into(),
         ..Default::default()
     };
@@ -3,6 +3,7 @@ use std::time::Duration;
 use ibapi::client::blocking::Client;
 use ibapi::contracts::Contract;
 use ibapi::orders::{Action, BracketOrderIds, CancelOrder, ExecutionFilter, Order, OrderId};
+use ibapi::Decimal;
 use ibapi_test::{rate_limit, ClientId, GATEWAY};
 use serial_test::serial;
 
@@ -13,10 +14,10 @@ fn connect() -> (Client, ClientId) {
     (client, client_id)
 }
 
-fn limit_order(action: Action, quantity: f64, price: f64) -> Order {
+fn limit_order(action: Action, quantity: i32, price: f64) -> Order {
     Order {
         action,
-        total_quantity: quantity,
+        total_quantity: Decimal::from(quantity),
         order_type: "LMT".to_string(),
         limit_price: Some(price),
         ..Default::default()
@@ -108,7 +109,7 @@ fn place_limit_buy() {
     let (client, _client_id) = connect();
 
     let contract = Contract::stock("AAPL").build();
-    let order = limit_order(Action::Buy, 1.0, 1.0); // Far below market
+    let order = limit_order(Action::Buy, 1, 1.0); // Far below market
 
     rate_limit();
     let order_id = client.next_order_id();
@@ -129,7 +130,7 @@ fn place_limit_sell() {
     let (client, _client_id) = connect();
 
     let contract = Contract::stock("AAPL").build();
-    let order = limit_order(Action::Sell, 1.0, 9999.0); // Far above market
+    let order = limit_order(Action::Sell, 1, 9999.0); // Far above market
 
     rate_limit();
     let order_id = client.next_order_id();
@@ -148,7 +149,7 @@ fn cancel_order_succeeds() {
     let (client, _client_id) = connect();
 
     let contract = Contract::stock("AAPL").build();
-    let order = limit_order(Action::Buy, 1.0, 1.0);
+    let order = limit_order(Action::Buy, 1, 1.0);
 
     rate_limit();
     let order_id = client.next_order_id();
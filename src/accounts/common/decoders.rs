@@ -33,7 +33,7 @@ pub(crate) fn decode_position(message: &mut ResponseMessage) -> Result<Position,
         position.contract.trading_class = message.next_string()?;
     }
 
-    position.position = message.next_double()?;
+    position.position = message.next_decimal()?;
 
     if message_version >= 3 {
         position.average_cost = message.next_double()?;
@@ -120,7 +120,7 @@ pub(crate) fn decode_pnl_single(_server_version: i32, message: &mut ResponseMess
     message.skip(); // message type
     message.skip(); // request id
 
-    let position = message.next_double()?;
+    let position = message.next_decimal()?;
     let daily_pnl = message.next_double()?;
     let unrealized_pnl = message.next_double()?;
     let realized_pnl = message.next_double()?;
@@ -256,6 +256,7 @@ pub(crate) fn decode_account_multi_value(message: &mut ResponseMessage) -> Resul
 
 #[cfg(test)]
 mod tests {
+    use crate::Decimal;
     use crate::{
         accounts::AccountSummaryTags,
         contracts::{Currency, Exchange, Symbol},
@@ -288,7 +289,7 @@ mod tests {
         assert_eq!(position.contract.currency, Currency::from("USD"), "position.contract.currency");
         assert_eq!(position.contract.local_symbol, "TSLA", "position.contract.local_symbol");
         assert_eq!(position.contract.trading_class, "NMS", "position.contract.trading_class");
-        assert_eq!(position.position, 500.0, "position.position");
+        assert_eq!(position.position, Decimal::from(500), "position.position");
         assert_eq!(position.average_cost, 196.77, "position.average_cost");
     }
 
@@ -321,7 +322,7 @@ mod tests {
         assert_eq!(result.contract.exchange, Exchange::from("EXCH"), "contract.exchange");
         assert_eq!(result.contract.currency, Currency::from("USD"), "contract.currency");
         assert_eq!(result.contract.local_symbol, "LOCSYM", "contract.local_symbol");
-        assert_eq!(result.position, 100.0, "position");
+        assert_eq!(result.position, Decimal::from(100), "position");
         assert_eq!(result.contract.trading_class, "", "contract.trading_class should be empty for v1");
         assert_eq!(result.average_cost, 0.0, "average_cost should be 0.0 for v1");
     }
@@ -357,7 +358,7 @@ mod tests {
         assert_eq!(result.contract.currency, Currency::from("USD"), "contract.currency");
         assert_eq!(result.contract.local_symbol, "LOCSYM", "contract.local_symbol");
         assert_eq!(result.contract.trading_class, "TRDCLS", "contract.trading_class");
-        assert_eq!(result.position, 100.0, "position");
+        assert_eq!(result.position, Decimal::from(100), "position");
         assert_eq!(result.average_cost, 0.0, "average_cost should be 0.0 for v2");
     }
 
@@ -463,7 +464,7 @@ mod tests {
 
         let pnl_single = super::decode_pnl_single(server_versions::REALIZED_PNL, &mut message).expect("error decoding pnl single");
 
-        assert_eq!(pnl_single.position, 100., "pnl_single.position");
+        assert_eq!(pnl_single.position, Decimal::from(100), "pnl_single.position");
         assert_eq!(pnl_single.daily_pnl, 0.10, "pnl_single.daily_pnl");
         assert_eq!(pnl_single.unrealized_pnl, 0.20, "pnl_single.unrealized_pnl");
         assert_eq!(pnl_single.realized_pnl, 0.30, "pnl_single.realized_pnl");
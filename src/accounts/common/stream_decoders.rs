@@ -260,7 +260,7 @@ mod tests {
 
             let result = PnLSingle::decode(&test_context(), &mut message).unwrap();
 
-            assert_eq!(result.position, 100.0);
+            assert_eq!(result.position, crate::Decimal::from(100));
             assert_eq!(result.daily_pnl, 1234.56);
             assert_eq!(result.unrealized_pnl, 2345.67);
             assert_eq!(result.realized_pnl, 3456.78);
@@ -295,7 +295,7 @@ mod tests {
                 PositionUpdate::Position(pos) => {
                     assert_eq!(pos.account, TEST_ACCOUNT);
                     assert_eq!(pos.contract.contract_id, 12345);
-                    assert_eq!(pos.position as i32, 100);
+                    assert_eq!(pos.position, Decimal::from(100));
                     assert_eq!(pos.average_cost, 50.25);
                 }
                 _ => panic!("Expected Position variant"),
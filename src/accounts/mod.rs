@@ -16,6 +16,7 @@ mod common;
 pub mod types;
 
 use crate::contracts::Contract;
+use crate::Decimal;
 use serde::{Deserialize, Serialize};
 
 // Public types - always available regardless of feature flags
@@ -170,7 +171,7 @@ pub struct PnL {
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct PnLSingle {
     /// Current size of the position
-    pub position: f64,
+    pub position: Decimal,
     /// DailyPnL for the position
     pub daily_pnl: f64,
     /// UnrealizedPnL is the total unrealized PnL for the position (since inception) updating in real time
@@ -190,7 +191,7 @@ pub struct Position {
     /// Contract
     pub contract: Contract,
     /// Number of shares held
-    pub position: f64,
+    pub position: Decimal,
     /// Average cost of shares
     pub average_cost: f64,
 }
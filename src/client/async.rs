@@ -1,14 +1,14 @@
 //! Asynchronous client implementation
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use log::debug;
 use time::OffsetDateTime;
 use time_tz::Tz;
 
-use crate::connection::common::{ConnectionOptions, StartupMessageCallback};
-use crate::connection::{r#async::AsyncConnection, ConnectionMetadata};
+use crate::connection::common::{ConnectionOptions, RequestOptions, StartupMessageCallback};
+use crate::connection::{r#async::AsyncConnection, ConnectionEvent, ConnectionMetadata};
 use crate::messages::{OutgoingMessages, RequestMessage};
 use crate::transport::{
     r#async::{AsyncInternalSubscription, AsyncTcpMessageBus},
@@ -20,12 +20,14 @@ use super::id_generator::ClientIdManager;
 use crate::accounts;
 use crate::accounts::types::{AccountGroup, AccountId, ContractId, ModelCode};
 use crate::accounts::{AccountSummaryResult, AccountUpdate, AccountUpdateMulti, FamilyCode, PnL, PnLSingle, PositionUpdate, PositionUpdateMulti};
-use crate::contracts::Contract;
+use crate::contracts::{Combo, ComboBuilder, Contract, ContractCache};
 use crate::display_groups;
 use crate::market_data::builder::MarketDataBuilder;
 use crate::market_data::TradingHours;
+use crate::orders::journal::OrderJournal;
 use crate::orders::OrderBuilder;
 use crate::subscriptions::Subscription;
+use crate::trace::TraceBuffer;
 
 /// Asynchronous TWS API Client
 pub struct Client {
@@ -37,6 +39,9 @@ pub struct Client {
 
     client_id: i32,                   // ID of client.
     id_manager: Arc<ClientIdManager>, // Manages request and order ID generation
+    contract_cache: ContractCache,
+    request_timeout: Option<Duration>, // Default timeout of one-shot requests
+    order_journal: Option<Arc<Mutex<OrderJournal>>>,
 }
 
 impl Drop for Client {
@@ -147,6 +152,9 @@ impl Client {
     /// }
     /// ```
     pub async fn connect_with_options(address: &str, client_id: i32, options: ConnectionOptions) -> Result<Client, Error> {
+        let heartbeat = options.heartbeat;
+        let request_timeout = options.request_timeout;
+        let order_journal = options.order_journal.clone();
         let connection = AsyncConnection::connect_with_options(address, client_id, options).await?;
         let connection_metadata = connection.connection_metadata().await;
 
@@ -157,7 +165,34 @@ impl Client {
             .clone()
             .process_messages(connection_metadata.server_version, Duration::from_secs(1))?;
 
-        Client::new(connection_metadata, message_bus)
+        if let Some(heartbeat) = heartbeat {
+            message_bus.start_heartbeat(heartbeat);
+        }
+
+        let mut client = Client::new(connection_metadata, message_bus)?;
+        client.request_timeout = request_timeout;
+        client.order_journal = order_journal;
+        Ok(client)
+    }
+
+    /// Creates a client backed by a [SimulatedBroker](crate::simulator::SimulatedBroker) instead of a TWS connection.
+    ///
+    /// Orders placed through the client are filled against the prices fed to the broker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ibapi::simulator::SimulatedBroker;
+    /// use ibapi::Client;
+    ///
+    /// let broker = SimulatedBroker::default();
+    /// let client = Client::simulated(&broker);
+    ///
+    /// println!("next order id: {}", client.next_order_id());
+    /// ```
+    pub fn simulated(broker: &crate::simulator::SimulatedBroker) -> Client {
+        let message_bus = Arc::new(crate::simulator::r#async::AsyncSimulatedMessageBus::new(broker.clone()));
+        Client::new(broker.connection_metadata(), message_bus).expect("creating a client does not fail")
     }
 
     fn new(connection_metadata: ConnectionMetadata, message_bus: Arc<dyn AsyncMessageBus>) -> Result<Client, Error> {
@@ -168,6 +203,9 @@ impl Client {
             message_bus,
             client_id: connection_metadata.client_id,
             id_manager: Arc::new(ClientIdManager::new(connection_metadata.next_order_id)),
+            contract_cache: ContractCache::default(),
+            request_timeout: None,
+            order_journal: None,
         };
 
         Ok(client)
@@ -188,6 +226,16 @@ impl Client {
         self.time_zone
     }
 
+    /// Returns the default timeout of one-shot requests, set with [ConnectionOptions::request_timeout].
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// Returns the journal orders are recorded in, set with [ConnectionOptions::order_journal].
+    pub fn order_journal(&self) -> Option<&Arc<Mutex<OrderJournal>>> {
+        self.order_journal.as_ref()
+    }
+
     /// Returns a decoder context for this client
     pub(crate) fn decoder_context(&self) -> crate::subscriptions::DecoderContext {
         crate::subscriptions::DecoderContext::new(self.server_version, self.time_zone)
@@ -218,6 +266,70 @@ impl Client {
         self.message_bus.is_connected()
     }
 
+    /// Streams changes in the state of the connection to TWS/IB Gateway.
+    ///
+    /// The stream starts with the most recent event, typically [ConnectionEvent::Connected],
+    /// followed by disconnects and reconnection attempts as they happen. Enable
+    /// [ConnectionOptions::heartbeat] to also detect connections that stop answering without being closed.
+    ///
+    /// Returns [Error::NotImplemented] for clients without a TWS connection, such as simulated clients.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ibapi::{Client, ConnectionEvent, ConnectionOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let options = ConnectionOptions::default().heartbeat(Duration::from_secs(30), Duration::from_secs(10));
+    ///     let client = Client::connect_with_options("127.0.0.1:4002", 100, options).await.expect("connection failed");
+    ///
+    ///     let mut events = client.connection_events().expect("connection events failed");
+    ///     while let Some(Ok(event)) = events.next().await {
+    ///         match event {
+    ///             ConnectionEvent::Reconnecting { attempt, delay } => println!("reconnect attempt {attempt} in {delay:?}"),
+    ///             ConnectionEvent::GaveUp => break,
+    ///             event => println!("{event:?}"),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn connection_events(&self) -> Result<Subscription<ConnectionEvent>, Error> {
+        let hub = self.message_bus.connection_events().ok_or(Error::NotImplemented)?;
+
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        hub.subscribe(move |event| sender.send(Ok(event.clone())).is_ok());
+
+        Ok(Subscription::new(receiver))
+    }
+
+    /// Returns the buffer of recent interactions with TWS enabled by [ConnectionOptions::trace_buffer].
+    ///
+    /// The buffer records nothing unless a capacity was set. Returns [Error::NotImplemented] for clients
+    /// without a TWS connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::{Client, ConnectionOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let options = ConnectionOptions::default().trace_buffer(50);
+    ///     let client = Client::connect_with_options("127.0.0.1:4002", 100, options).await.expect("connection failed");
+    ///
+    ///     let time = client.server_time().await.expect("request failed");
+    ///
+    ///     let trace = client.trace_buffer().expect("trace buffer not available");
+    ///     println!("{}", trace.export().to_json().expect("export failed"));
+    /// }
+    /// ```
+    pub fn trace_buffer(&self) -> Result<TraceBuffer, Error> {
+        self.message_bus.trace_buffer().cloned().ok_or(Error::NotImplemented)
+    }
+
     /// Returns the ID assigned to the [Client].
     pub fn client_id(&self) -> i32 {
         self.client_id
@@ -993,6 +1105,45 @@ impl Client {
         crate::market_data::historical::head_timestamp(self, contract, what_to_show, trading_hours).await
     }
 
+    /// Returns the timestamp of earliest available historical data, overriding the client's [request timeout](Self::request_timeout).
+    ///
+    /// If TWS has not answered within the timeout, [Error::Timeout] is returned. The request is cancelled at TWS
+    /// when it times out or when the returned future is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::market_data::historical::WhatToShow;
+    /// use ibapi::market_data::TradingHours;
+    /// use ibapi::{Client, RequestOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+    ///
+    ///     let contract = Contract::stock("MSFT").build();
+    ///     let options = RequestOptions::default().timeout(Duration::from_secs(10));
+    ///
+    ///     let timestamp = client
+    ///         .head_timestamp_with_options(&contract, WhatToShow::Trades, TradingHours::Regular, options)
+    ///         .await
+    ///         .expect("error requesting head timestamp");
+    ///     println!("Earliest data available: {timestamp:?}");
+    /// }
+    /// ```
+    pub async fn head_timestamp_with_options(
+        &self,
+        contract: &crate::contracts::Contract,
+        what_to_show: crate::market_data::historical::WhatToShow,
+        trading_hours: TradingHours,
+        options: RequestOptions,
+    ) -> Result<OffsetDateTime, Error> {
+        crate::market_data::historical::head_timestamp_with_options(self, contract, what_to_show, trading_hours, options).await
+    }
+
     /// Requests historical bars data.
     ///
     /// When requesting historical data, a finishing time and date is required along with a duration string.
@@ -1052,6 +1203,34 @@ impl Client {
         crate::market_data::historical::historical_data(self, contract, end_date, duration, bar_size, what_to_show, trading_hours).await
     }
 
+    /// Requests historical bars data, overriding the client's [request timeout](Self::request_timeout).
+    ///
+    /// If TWS has not answered within the timeout, [Error::Timeout] is returned. The request is cancelled at TWS
+    /// when it times out or when the returned future is dropped.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn historical_data_with_options(
+        &self,
+        contract: &crate::contracts::Contract,
+        end_date: Option<OffsetDateTime>,
+        duration: crate::market_data::historical::Duration,
+        bar_size: crate::market_data::historical::BarSize,
+        what_to_show: Option<crate::market_data::historical::WhatToShow>,
+        trading_hours: TradingHours,
+        options: RequestOptions,
+    ) -> Result<crate::market_data::historical::HistoricalData, Error> {
+        crate::market_data::historical::historical_data_with_options(
+            self,
+            contract,
+            end_date,
+            duration,
+            bar_size,
+            what_to_show,
+            trading_hours,
+            options,
+        )
+        .await
+    }
+
     /// Requests historical data with optional streaming updates.
     ///
     /// This method returns a subscription that first yields the initial historical bars.
@@ -1299,6 +1478,20 @@ impl Client {
         crate::market_data::historical::histogram_data(self, contract, trading_hours, period).await
     }
 
+    /// Requests histogram data, overriding the client's [request timeout](Self::request_timeout).
+    ///
+    /// If TWS has not answered within the timeout, [Error::Timeout] is returned. The request is cancelled at TWS
+    /// when it times out or when the returned future is dropped.
+    pub async fn histogram_data_with_options(
+        &self,
+        contract: &crate::contracts::Contract,
+        trading_hours: TradingHours,
+        period: crate::market_data::historical::BarSize,
+        options: RequestOptions,
+    ) -> Result<Vec<crate::market_data::historical::HistogramEntry>, Error> {
+        crate::market_data::historical::histogram_data_with_options(self, contract, trading_hours, period, options).await
+    }
+
     // === Wall Street Horizon (WSH) Data ===
 
     /// Requests Wall Street Horizon metadata information.
@@ -1373,7 +1566,7 @@ impl Client {
     ///
     /// # Arguments
     ///
-    /// * `filter`    - Filter for the event request (e.g. JSON-encoded string).
+    /// * `filter`    - Filter for the event request (JSON-encoded string, see [crate::wsh::WshFilter]).
     /// * `limit`     - Number of events to return.
     /// * `auto_fill` - Autofill configuration for watchlist, portfolio, and position.
     ///
@@ -1381,14 +1574,19 @@ impl Client {
     ///
     /// ```no_run
     /// use ibapi::Client;
-    /// use ibapi::wsh::AutoFill;
+    /// use ibapi::wsh::{AutoFill, WshEventType, WshFilter};
     /// use futures::StreamExt;
     ///
     /// #[tokio::main]
     /// async fn main() {
     ///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
     ///
-    ///     let filter = r#"{"country": "US"}"#;
+    ///     let metadata = client.wsh_metadata().await.expect("error requesting wsh metadata");
+    ///     let filter = WshFilter::new()
+    ///         .country("US")
+    ///         .event_type(WshEventType::Earnings)
+    ///         .build(&metadata)
+    ///         .expect("invalid filter");
     ///     let limit = Some(100);
     ///     let auto_fill = Some(AutoFill {
     ///         competitors: true,
@@ -1397,12 +1595,12 @@ impl Client {
     ///     });
     ///
     ///     let mut event_data_subscription = client
-    ///         .wsh_event_data_by_filter(filter, limit, auto_fill)
+    ///         .wsh_event_data_by_filter(&filter, limit, auto_fill)
     ///         .await
     ///         .expect("error requesting wsh event data");
     ///     
     ///     while let Some(event_data) = event_data_subscription.next().await {
-    ///         println!("{event_data:?}")
+    ///         println!("{:?}", event_data.and_then(|data| data.events()));
     ///     }
     /// }
     /// ```
@@ -1422,6 +1620,9 @@ impl Client {
     /// This function returns all contracts that match the provided contract sample.
     /// It can be used to retrieve complete options and futures chains.
     ///
+    /// Every call is sent to TWS: the [contract cache](Self::contract_cache) is neither read nor filled.
+    /// Use [qualify_contracts](Self::qualify_contracts) to look contracts up through the cache.
+    ///
     /// # Arguments
     /// * `contract` - The Contract used as a sample to query available contracts
     ///
@@ -1444,7 +1645,133 @@ impl Client {
     /// }
     /// ```
     pub async fn contract_details(&self, contract: &crate::contracts::Contract) -> Result<Vec<crate::contracts::ContractDetails>, Error> {
-        crate::contracts::contract_details(self, contract).await
+        crate::contracts::contract_details(self, contract, RequestOptions::default()).await
+    }
+
+    /// Requests detailed contract information, overriding the client's [request timeout](Self::request_timeout).
+    ///
+    /// If TWS has not answered within the timeout, [Error::Timeout] is returned. The request is cancelled at TWS
+    /// when it times out or when the returned future is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::{Client, RequestOptions};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+    ///
+    ///     let contract = Contract::stock("AAPL").build();
+    ///     let options = RequestOptions::default().timeout(Duration::from_secs(5));
+    ///     let details = client.contract_details_with_options(&contract, options).await.expect("request failed");
+    ///     println!("{} contracts", details.len());
+    /// }
+    /// ```
+    pub async fn contract_details_with_options(
+        &self,
+        contract: &crate::contracts::Contract,
+        options: RequestOptions,
+    ) -> Result<Vec<crate::contracts::ContractDetails>, Error> {
+        crate::contracts::contract_details(self, contract, options).await
+    }
+
+    /// Requests the details of the bond with a CUSIP or ISIN.
+    ///
+    /// Bond terms such as coupon, maturity and call features are returned in [ContractDetails::bond](crate::contracts::ContractDetails::bond).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::contracts::Isin;
+    /// use ibapi::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+    ///
+    ///     let results = client.bond_contract_details(Isin::new("US912810TL81")).await.expect("request failed");
+    ///     for details in results {
+    ///         if let Some(bond) = details.bond {
+    ///             println!("{} {}% due {:?}", details.contract.symbol, bond.coupon, bond.maturity);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn bond_contract_details(
+        &self,
+        identifier: impl Into<crate::contracts::BondIdentifier>,
+    ) -> Result<Vec<crate::contracts::ContractDetails>, Error> {
+        crate::contracts::bond_contract_details(self, identifier.into()).await
+    }
+
+    /// Completes partially specified contracts with their definition from TWS.
+    ///
+    /// Each contract is replaced by the contract TWS matches it to, which fills in `contract_id`,
+    /// `primary_exchange`, `trading_class`, `local_symbol` and similar fields; the caller's `exchange` is kept.
+    /// Contracts missing from the [contract cache](Self::contract_cache) are requested concurrently, and
+    /// equivalent contracts share a request.
+    ///
+    /// Every contract that can be qualified is updated. The others are returned together in
+    /// [Error::QualificationFailed], each with its index and error: [Error::ContractNotFound] if nothing
+    /// matches, [Error::AmbiguousContract] listing the candidates if several contracts do, or the error of
+    /// the request. Adding a currency, primary exchange or contract id narrows an ambiguous contract down.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+    ///
+    ///     let mut contracts = vec![Contract::stock("AAPL").build(), Contract::stock("MSFT").build()];
+    ///     client.qualify_contracts(&mut contracts).await.expect("qualification failed");
+    ///
+    ///     for contract in &contracts {
+    ///         println!("{} {} {}", contract.symbol, contract.contract_id, contract.primary_exchange);
+    ///     }
+    /// }
+    /// ```
+    pub async fn qualify_contracts(&self, contracts: &mut [Contract]) -> Result<(), Error> {
+        crate::contracts::qualify_contracts(self, contracts).await
+    }
+
+    /// Cache of contract details used by [qualify_contracts](Self::qualify_contracts).
+    pub fn contract_cache(&self) -> &ContractCache {
+        &self.contract_cache
+    }
+
+    /// Resolves the legs of a [ComboBuilder] to contract ids and builds the BAG contract.
+    ///
+    /// The legs are qualified with [qualify_contracts](Self::qualify_contracts) and must share an underlying
+    /// symbol and a currency; otherwise [Error::InvalidArgument] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+    ///
+    ///     let vertical = Contract::combo()
+    ///         .buy(Contract::call("AAPL").strike(150.0).expires_on(2025, 1, 17).build(), 1)
+    ///         .sell(Contract::call("AAPL").strike(160.0).expires_on(2025, 1, 17).build(), 1);
+    ///
+    ///     let combo = client.resolve_combo(&vertical).await.expect("combo resolution failed");
+    ///     println!("{:?}", combo.contract.combo_legs);
+    /// }
+    /// ```
+    pub async fn resolve_combo(&self, combo: &ComboBuilder) -> Result<Combo, Error> {
+        crate::contracts::resolve_combo(self, combo).await
     }
 
     /// Cancels an in-flight contract details request.
@@ -1477,7 +1804,16 @@ impl Client {
     /// }
     /// ```
     pub async fn matching_symbols(&self, pattern: &str) -> Result<Vec<crate::contracts::ContractDescription>, Error> {
-        crate::contracts::matching_symbols(self, pattern).await
+        crate::contracts::matching_symbols(self, pattern, RequestOptions::default()).await
+    }
+
+    /// Searches for stock contracts matching the pattern, overriding the client's [request timeout](Self::request_timeout).
+    pub async fn matching_symbols_with_options(
+        &self,
+        pattern: &str,
+        options: RequestOptions,
+    ) -> Result<Vec<crate::contracts::ContractDescription>, Error> {
+        crate::contracts::matching_symbols(self, pattern, options).await
     }
 
     /// Retrieves market rule details for a specific market rule ID.
@@ -1504,7 +1840,12 @@ impl Client {
     /// }
     /// ```
     pub async fn market_rule(&self, market_rule_id: i32) -> Result<crate::contracts::MarketRule, Error> {
-        crate::contracts::market_rule(self, market_rule_id).await
+        crate::contracts::market_rule(self, market_rule_id, RequestOptions::default()).await
+    }
+
+    /// Retrieves market rule details, overriding the client's [request timeout](Self::request_timeout).
+    pub async fn market_rule_with_options(&self, market_rule_id: i32, options: RequestOptions) -> Result<crate::contracts::MarketRule, Error> {
+        crate::contracts::market_rule(self, market_rule_id, options).await
     }
 
     /// Calculates option price based on volatility and underlying price.
@@ -1622,6 +1963,37 @@ impl Client {
         crate::contracts::option_chain(self, symbol, exchange, security_type, contract_id).await
     }
 
+    /// Expands the option chain of `underlying` into qualified contracts, optionally with quotes.
+    ///
+    /// See [OptionChainBuilder](crate::contracts::options::OptionChainBuilder) for the available filters.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+    ///     let underlying = Contract::stock("AAPL").build();
+    ///
+    ///     let surface = client
+    ///         .option_surface(&underlying)
+    ///         .max_expirations(1)
+    ///         .strikes_around(185.0, 3)
+    ///         .with_market_data()
+    ///         .fetch()
+    ///         .await
+    ///         .expect("option surface failed");
+    ///
+    ///     println!("resolved {} options", surface.len());
+    /// }
+    /// ```
+    pub fn option_surface<'a>(&'a self, underlying: &'a crate::contracts::Contract) -> crate::contracts::options::OptionChainBuilder<'a, Self> {
+        crate::contracts::options::OptionChainBuilder::new(self, underlying)
+    }
+
     // === Order Management ===
 
     /// Subscribes to order update events. Only one subscription can be active at a time.
@@ -1796,6 +2168,37 @@ impl Client {
         crate::orders::auto_open_orders(self, auto_bind).await
     }
 
+    /// Returns the index of orders bound to API clients, keyed by permanent id.
+    ///
+    /// Bindings are recorded as TWS reports them, for example for orders placed in TWS after
+    /// [auto_open_orders](Self::auto_open_orders) with `auto_bind` set. Returns
+    /// [Error::NotImplemented] for clients without a TWS connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::orders::OrderUpdate;
+    /// use ibapi::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::connect("127.0.0.1:4002", 0).await.expect("connection failed");
+    ///     let bindings = client.order_bindings().expect("order bindings not available");
+    ///
+    ///     let mut updates = client.order_update_stream().await.expect("request failed");
+    ///     let _auto_open_orders = client.auto_open_orders(true).await.expect("request failed");
+    ///
+    ///     while let Some(Ok(update)) = updates.next().await {
+    ///         if let OrderUpdate::OrderBound(binding) = update {
+    ///             assert_eq!(bindings.order_id(binding.perm_id), Some(binding.order_id));
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn order_bindings(&self) -> Result<crate::orders::OrderBindings, Error> {
+        self.message_bus.order_bindings().cloned().ok_or(Error::NotImplemented)
+    }
+
     /// Requests current day's (since midnight) executions matching the filter.
     ///
     /// Only the current day's executions can be retrieved.
@@ -1807,6 +2210,37 @@ impl Client {
         crate::orders::executions(self, filter).await
     }
 
+    /// Requests executions matching the filter and joins each one with its commission report.
+    ///
+    /// Waits for the request to complete and returns one fill per execution.
+    ///
+    /// # Arguments
+    /// * `filter` - Filter criteria used to determine which execution reports are returned
+    pub async fn fills(&self, filter: crate::orders::ExecutionFilter) -> Result<Vec<crate::orders::ledger::Fill>, Error> {
+        crate::orders::ledger::r#async::fills(self, filter).await
+    }
+
+    /// Reconciles an order journal with the orders and executions currently reported by TWS.
+    ///
+    /// Requests open orders, completed orders and the executions matching `filter`, and streams
+    /// the fills missing from the journal, journaled orders that completed or vanished, and open
+    /// orders placed from other sessions.
+    ///
+    /// The journal is locked only after TWS has answered, so it can be the one attached with
+    /// [ConnectionOptions::order_journal], which records [place_order](Self::place_order) and the
+    /// order updates received by the client.
+    ///
+    /// # Arguments
+    /// * `journal` - Journal of orders and executions recorded by this application
+    /// * `filter` - Filter criteria used to determine which execution reports are requested
+    pub async fn reconcile_orders(
+        &self,
+        journal: &Mutex<OrderJournal>,
+        filter: crate::orders::ExecutionFilter,
+    ) -> Result<Subscription<crate::orders::journal::ReconciliationEvent>, Error> {
+        crate::orders::journal::r#async::reconcile(self, journal, filter).await
+    }
+
     /// Exercises an options contract.
     ///
     /// # Arguments
@@ -2124,6 +2558,7 @@ mod tests {
     use crate::client::common::tests::*;
     use crate::contracts::{Currency, Exchange, Symbol};
     use crate::market_data::TradingHours;
+    use crate::Decimal;
 
     const CLIENT_ID: i32 = 100;
 
@@ -2153,6 +2588,61 @@ mod tests {
         assert_eq!(requests[0], "49\01\0");
     }
 
+    #[tokio::test]
+    async fn test_trace_buffer() {
+        let (gateway, _expectations) = setup_server_time();
+
+        let options = crate::ConnectionOptions::default().trace_buffer(10);
+        let client = Client::connect_with_options(&gateway.address(), CLIENT_ID, options)
+            .await
+            .expect("Failed to connect");
+        client.server_time().await.unwrap();
+
+        let trace = client.trace_buffer().unwrap();
+        let interactions = trace.for_message_type(crate::messages::OutgoingMessages::RequestCurrentTime);
+        assert_eq!(interactions.len(), 1);
+        assert_eq!(interactions[0].responses.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_connection_events_heartbeat_stall() {
+        use crate::{ConnectionEvent, ConnectionOptions};
+        use std::time::Duration;
+
+        // The gateway answers the first heartbeat and ignores the second.
+        let (gateway, _) = setup_server_time();
+        let options = ConnectionOptions::default().heartbeat(Duration::from_millis(50), Duration::from_millis(200));
+
+        let client = Client::connect_with_options(&gateway.address(), CLIENT_ID, options)
+            .await
+            .expect("Failed to connect");
+        let mut events = client.connection_events().expect("connection events");
+
+        let mut received = Vec::new();
+        while received.len() < 3 {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+                .await
+                .expect("timed out waiting for event")
+                .expect("stream ended")
+                .expect("event error");
+            received.push(event);
+        }
+
+        assert!(matches!(
+            received[0],
+            ConnectionEvent::Connected { server_version, .. } if server_version == gateway.server_version()
+        ));
+        assert_eq!(
+            received[1],
+            ConnectionEvent::Disconnected {
+                error: "no reply to heartbeat within 200ms".to_string()
+            }
+        );
+        assert!(matches!(received[2], ConnectionEvent::Reconnecting { attempt: 1, .. }));
+
+        assert_eq!(gateway.requests()[..2], ["49\01\0", "49\01\0"]);
+    }
+
     #[tokio::test]
     async fn test_next_valid_order_id() {
         let (gateway, expectations) = setup_next_valid_order_id();
@@ -2193,7 +2683,7 @@ mod tests {
                 crate::accounts::PositionUpdate::Position(position) => {
                     assert_eq!(position.account, "DU1234567");
                     assert_eq!(position.contract.symbol, Symbol::from("AAPL"));
-                    assert_eq!(position.position, 500.0);
+                    assert_eq!(position.position, Decimal::from(500));
                     assert_eq!(position.average_cost, 150.25);
                     position_count += 1;
                 }
@@ -2320,7 +2810,7 @@ mod tests {
         let mut pnl_single = client.pnl_single(&account, contract_id, None).await.unwrap();
 
         let first_pnl = pnl_single.next().await.unwrap().unwrap();
-        assert_eq!(first_pnl.position, 100.0);
+        assert_eq!(first_pnl.position, Decimal::from(100));
         assert_eq!(first_pnl.daily_pnl, 150.25);
         assert_eq!(first_pnl.unrealized_pnl, 500.00);
         assert_eq!(first_pnl.realized_pnl, 250.00);
@@ -2793,18 +3283,18 @@ mod tests {
                     if order_status_count == 1 {
                         // First status: PreSubmitted
                         assert_eq!(status.status, "PreSubmitted");
-                        assert_eq!(status.filled, 0.0);
-                        assert_eq!(status.remaining, 100.0);
+                        assert_eq!(status.filled, Decimal::from(0));
+                        assert_eq!(status.remaining, Decimal::from(100));
                     } else if order_status_count == 2 {
                         // Second status: Submitted
                         assert_eq!(status.status, "Submitted");
-                        assert_eq!(status.filled, 0.0);
-                        assert_eq!(status.remaining, 100.0);
+                        assert_eq!(status.filled, Decimal::from(0));
+                        assert_eq!(status.remaining, Decimal::from(100));
                     } else if order_status_count == 3 {
                         // Third status: Filled
                         assert_eq!(status.status, "Filled");
-                        assert_eq!(status.filled, 100.0);
-                        assert_eq!(status.remaining, 0.0);
+                        assert_eq!(status.filled, Decimal::from(100));
+                        assert_eq!(status.remaining, Decimal::from(0));
                         assert_eq!(status.average_fill_price, 150.25);
                     }
                 }
@@ -2814,7 +3304,7 @@ mod tests {
                     assert_eq!(order_data.contract.symbol, Symbol::from("AAPL"));
                     assert_eq!(order_data.contract.contract_id, 265598);
                     assert_eq!(order_data.order.action, Action::Buy);
-                    assert_eq!(order_data.order.total_quantity, 100.0);
+                    assert_eq!(order_data.order.total_quantity, Decimal::from(100));
                     assert_eq!(order_data.order.order_type, "LMT");
                     assert_eq!(order_data.order.limit_price, Some(1.0));
                 }
@@ -2822,7 +3312,7 @@ mod tests {
                     execution_count += 1;
                     assert_eq!(exec_data.execution.order_id, order_id);
                     assert_eq!(exec_data.contract.symbol, Symbol::from("AAPL"));
-                    assert_eq!(exec_data.execution.shares, 100.0);
+                    assert_eq!(exec_data.execution.shares, Decimal::from(100));
                     assert_eq!(exec_data.execution.price, 150.25);
                 }
                 PlaceOrder::CommissionReport(report) => {
@@ -2830,7 +3320,7 @@ mod tests {
                     assert_eq!(report.commission, 1.25);
                     assert_eq!(report.currency, "USD");
                 }
-                PlaceOrder::Message(_) => {
+                PlaceOrder::DeltaNeutralValidation(_) | PlaceOrder::Message(_) => {
                     // Skip any messages
                 }
             }
@@ -2850,6 +3340,41 @@ mod tests {
         assert!(requests[0].contains(&format!("\0{}\0", order_id)), "Request should contain order ID");
     }
 
+    #[tokio::test]
+    async fn test_place_order_records_journal() {
+        use crate::connection::common::ConnectionOptions;
+        use crate::contracts::Contract;
+        use crate::orders::journal::OrderJournal;
+        use crate::orders::{order_builder, Action, PlaceOrder};
+        use std::sync::{Arc, Mutex};
+
+        let gateway = setup_place_order();
+        let journal = Arc::new(Mutex::new(OrderJournal::in_memory()));
+        let options = ConnectionOptions::default().order_journal(journal.clone());
+        let client = Client::connect_with_options(&gateway.address(), CLIENT_ID, options)
+            .await
+            .expect("Failed to connect");
+        assert!(client.order_journal().is_some());
+
+        let contract = Contract::stock("AAPL").build();
+        let order = order_builder::market_order(Action::Buy, 100.0);
+        let mut subscription = client.place_order(1001, &contract, &order).await.expect("Failed to place order");
+
+        // The commission report is the last message of the exchange, so everything before it has been journaled.
+        while let Some(event) = subscription.next().await {
+            if matches!(event, Ok(PlaceOrder::CommissionReport(_))) {
+                break;
+            }
+        }
+
+        let journal = journal.lock().unwrap();
+        let journaled = journal.order(CLIENT_ID, 1001).expect("order was not journaled");
+        assert_eq!(journaled.contract.symbol.to_string(), "AAPL");
+        assert_eq!(journaled.perm_id(), 123456);
+        assert!(!journaled.is_active(), "{journaled:?}");
+        assert!(journal.has_execution("000e1a2b.67890abc.01.01"));
+    }
+
     #[tokio::test]
     async fn test_submit_order_with_order_update_stream() {
         use crate::client::common::tests::setup_place_order;
@@ -2908,18 +3433,18 @@ mod tests {
                     if order_status_count == 1 {
                         // First status: PreSubmitted
                         assert_eq!(status.status, "PreSubmitted");
-                        assert_eq!(status.filled, 0.0);
-                        assert_eq!(status.remaining, 100.0);
+                        assert_eq!(status.filled, Decimal::from(0));
+                        assert_eq!(status.remaining, Decimal::from(100));
                     } else if order_status_count == 2 {
                         // Second status: Submitted
                         assert_eq!(status.status, "Submitted");
-                        assert_eq!(status.filled, 0.0);
-                        assert_eq!(status.remaining, 100.0);
+                        assert_eq!(status.filled, Decimal::from(0));
+                        assert_eq!(status.remaining, Decimal::from(100));
                     } else if order_status_count == 3 {
                         // Third status: Filled
                         assert_eq!(status.status, "Filled");
-                        assert_eq!(status.filled, 100.0);
-                        assert_eq!(status.remaining, 0.0);
+                        assert_eq!(status.filled, Decimal::from(100));
+                        assert_eq!(status.remaining, Decimal::from(0));
                         assert_eq!(status.average_fill_price, 150.25);
                     }
                 }
@@ -2929,7 +3454,7 @@ mod tests {
                     assert_eq!(order_data.contract.symbol, Symbol::from("AAPL"));
                     assert_eq!(order_data.contract.contract_id, 265598);
                     assert_eq!(order_data.order.action, Action::Buy);
-                    assert_eq!(order_data.order.total_quantity, 100.0);
+                    assert_eq!(order_data.order.total_quantity, Decimal::from(100));
                     assert_eq!(order_data.order.order_type, "LMT");
                     assert_eq!(order_data.order.limit_price, Some(1.0));
                 }
@@ -2937,7 +3462,7 @@ mod tests {
                     execution_count += 1;
                     assert_eq!(exec_data.execution.order_id, order_id);
                     assert_eq!(exec_data.contract.symbol, Symbol::from("AAPL"));
-                    assert_eq!(exec_data.execution.shares, 100.0);
+                    assert_eq!(exec_data.execution.shares, Decimal::from(100));
                     assert_eq!(exec_data.execution.price, 150.25);
                 }
                 OrderUpdate::CommissionReport(report) => {
@@ -2945,8 +3470,8 @@ mod tests {
                     assert_eq!(report.commission, 1.25);
                     assert_eq!(report.currency, "USD");
                 }
-                OrderUpdate::Message(_) => {
-                    // Skip any messages
+                OrderUpdate::OrderBound(_) | OrderUpdate::Message(_) => {
+                    // Skip bindings and messages
                 }
             }
         }
@@ -3006,7 +3531,7 @@ mod tests {
         assert_eq!(order1.contract.symbol, Symbol::from("AAPL"));
         assert_eq!(order1.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order1.order.action, Action::Buy);
-        assert_eq!(order1.order.total_quantity, 100.0);
+        assert_eq!(order1.order.total_quantity, Decimal::from(100));
         assert_eq!(order1.order.order_type, "MKT");
         assert_eq!(order1.order_state.status, "PreSubmitted");
 
@@ -3016,7 +3541,7 @@ mod tests {
         assert_eq!(order2.contract.symbol, Symbol::from("MSFT"));
         assert_eq!(order2.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order2.order.action, Action::Sell);
-        assert_eq!(order2.order.total_quantity, 50.0);
+        assert_eq!(order2.order.total_quantity, Decimal::from(50));
         assert_eq!(order2.order.order_type, "LMT");
         assert_eq!(order2.order.limit_price, Some(350.0));
         assert_eq!(order2.order_state.status, "Submitted");
@@ -3068,7 +3593,7 @@ mod tests {
         assert_eq!(order1.contract.symbol, Symbol::from("TSLA"));
         assert_eq!(order1.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order1.order.action, Action::Buy);
-        assert_eq!(order1.order.total_quantity, 10.0);
+        assert_eq!(order1.order.total_quantity, Decimal::from(10));
         assert_eq!(order1.order.order_type, "LMT");
         assert_eq!(order1.order.limit_price, Some(420.0));
         assert_eq!(order1.order.account, "DU1236110");
@@ -3078,7 +3603,7 @@ mod tests {
         assert_eq!(order2.order_id, 2002);
         assert_eq!(order2.contract.symbol, Symbol::from("AMZN"));
         assert_eq!(order2.order.action, Action::Sell);
-        assert_eq!(order2.order.total_quantity, 5.0);
+        assert_eq!(order2.order.total_quantity, Decimal::from(5));
         assert_eq!(order2.order.order_type, "MKT");
         assert_eq!(order2.order.account, "DU1236111");
 
@@ -3087,7 +3612,7 @@ mod tests {
         assert_eq!(order3.order_id, 1003);
         assert_eq!(order3.contract.symbol, Symbol::from("GOOGL"));
         assert_eq!(order3.order.action, Action::Buy);
-        assert_eq!(order3.order.total_quantity, 20.0);
+        assert_eq!(order3.order.total_quantity, Decimal::from(20));
         assert_eq!(order3.order.order_type, "LMT");
         assert_eq!(order3.order.limit_price, Some(2800.0));
         assert_eq!(order3.order.account, "DU1236109");
@@ -3155,10 +3680,15 @@ mod tests {
         assert_eq!(order.contract.symbol, Symbol::from("FB"));
         assert_eq!(order.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order.order.action, crate::orders::Action::Buy);
-        assert_eq!(order.order.total_quantity, 50.0);
+        assert_eq!(order.order.total_quantity, Decimal::from(50));
         assert_eq!(order.order.order_type, "MKT");
         assert_eq!(order.order.account, "TWS");
 
+        // Verify the binding sent ahead of the order was indexed
+        let bindings = client.order_bindings().expect("order bindings not available");
+        assert_eq!(bindings.order_id(123456), Some(3001));
+        assert_eq!(bindings.perm_id(0, 3001), Some(123456));
+
         // Verify the request was sent correctly
         let requests = gateway.requests();
         assert_eq!(requests.len(), 1, "Should have sent 1 request");
@@ -3210,7 +3740,7 @@ mod tests {
         assert_eq!(order1.contract.symbol, Symbol::from("ES"));
         assert_eq!(order1.contract.security_type, crate::contracts::SecurityType::Future);
         assert_eq!(order1.order.action, Action::Buy);
-        assert_eq!(order1.order.total_quantity, 1.0);
+        assert_eq!(order1.order.total_quantity, Decimal::from(1));
         assert_eq!(order1.order.order_type, "LMT");
         assert_eq!(order1.order_state.status, "Cancelled");
         assert_eq!(order1.order.perm_id, 616088517);
@@ -3221,7 +3751,7 @@ mod tests {
         assert_eq!(order2.contract.symbol, Symbol::from("AAPL"));
         assert_eq!(order2.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order2.order.action, Action::Buy);
-        assert_eq!(order2.order.total_quantity, 100.0);
+        assert_eq!(order2.order.total_quantity, Decimal::from(100));
         assert_eq!(order2.order.order_type, "MKT");
         assert_eq!(order2.order_state.status, "Filled");
         assert_eq!(order2.order.perm_id, 1377295418);
@@ -3263,8 +3793,8 @@ mod tests {
                         Ok(CancelOrder::OrderStatus(status)) => {
                             assert_eq!(status.order_id, order_id);
                             assert_eq!(status.status, "Cancelled");
-                            assert_eq!(status.filled, 0.0);
-                            assert_eq!(status.remaining, 100.0);
+                            assert_eq!(status.filled, Decimal::from(0));
+                            assert_eq!(status.remaining, Decimal::from(100));
                             order_status_received = true;
                             println!("Received OrderStatus: {:?}", status);
                         }
@@ -3382,7 +3912,7 @@ mod tests {
         assert_eq!(exec1.contract.security_type, SecurityType::Stock);
         assert_eq!(exec1.execution.execution_id, "000e1a2b.67890abc.01.01");
         assert_eq!(exec1.execution.side, "BOT");
-        assert_eq!(exec1.execution.shares, 100.0);
+        assert_eq!(exec1.execution.shares, Decimal::from(100));
         assert_eq!(exec1.execution.price, 150.25);
 
         // Verify first commission report
@@ -3399,7 +3929,7 @@ mod tests {
         assert_eq!(exec2.contract.security_type, SecurityType::Future);
         assert_eq!(exec2.execution.execution_id, "000e1a2b.67890def.02.01");
         assert_eq!(exec2.execution.side, "SLD");
-        assert_eq!(exec2.execution.shares, 5.0);
+        assert_eq!(exec2.execution.shares, Decimal::from(5));
         assert_eq!(exec2.execution.price, 5050.25);
 
         // Verify second commission report
@@ -3416,7 +3946,7 @@ mod tests {
         assert_eq!(exec3.contract.security_type, SecurityType::Option);
         assert_eq!(exec3.execution.execution_id, "000e1a2b.67890ghi.03.01");
         assert_eq!(exec3.execution.side, "BOT");
-        assert_eq!(exec3.execution.shares, 10.0);
+        assert_eq!(exec3.execution.shares, Decimal::from(10));
         assert_eq!(exec3.execution.price, 2.50);
 
         // Verify third commission report
@@ -3499,13 +4029,13 @@ mod tests {
 
         // Verify order statuses
         assert_eq!(order_statuses[0].status, "PreSubmitted");
-        assert_eq!(order_statuses[0].filled, 0.0);
-        assert_eq!(order_statuses[0].remaining, 10.0);
+        assert_eq!(order_statuses[0].filled, Decimal::from(0));
+        assert_eq!(order_statuses[0].remaining, Decimal::from(10));
 
         assert_eq!(order_statuses[1].status, "Submitted");
         assert_eq!(order_statuses[2].status, "Filled");
-        assert_eq!(order_statuses[2].filled, 10.0);
-        assert_eq!(order_statuses[2].remaining, 0.0);
+        assert_eq!(order_statuses[2].filled, Decimal::from(10));
+        assert_eq!(order_statuses[2].remaining, Decimal::from(0));
 
         // Verify open order
         let open_order = &open_orders[0];
@@ -3600,25 +4130,24 @@ mod tests {
                 }
                 TickTypes::Size(size_tick) => match size_tick.tick_type {
                     TickType::BidSize => {
-                        assert_eq!(size_tick.size, 100.0);
+                        assert_eq!(size_tick.size, Decimal::from(100));
                         has_bid_size = true;
                     }
                     TickType::AskSize => {
-                        assert_eq!(size_tick.size, 200.0);
+                        assert_eq!(size_tick.size, Decimal::from(200));
                         has_ask_size = true;
                     }
                     TickType::LastSize => {
-                        assert_eq!(size_tick.size, 50.0);
+                        assert_eq!(size_tick.size, Decimal::from(50));
                         has_last_size = true;
                     }
                     _ => {}
                 },
-                TickTypes::Generic(generic_tick) => {
-                    if generic_tick.tick_type == TickType::Volume {
-                        assert_eq!(generic_tick.value, 1500000.0);
-                        has_volume = true;
-                    }
+                TickTypes::Generic(generic_tick) if generic_tick.tick_type == TickType::Volume => {
+                    assert_eq!(generic_tick.value, 1500000.0);
+                    has_volume = true;
                 }
+                TickTypes::Generic(_) => {}
                 TickTypes::String(_) => {
                     // Ignore string ticks like LastTimestamp
                 }
@@ -3684,7 +4213,7 @@ mod tests {
         assert_eq!(bar1.high, 150.75);
         assert_eq!(bar1.low, 150.00);
         assert_eq!(bar1.close, 150.50);
-        assert_eq!(bar1.volume, 1000.0);
+        assert_eq!(bar1.volume, Decimal::from(1000));
         assert_eq!(bar1.wap, 150.40);
         assert_eq!(bar1.count, 25);
 
@@ -3694,7 +4223,7 @@ mod tests {
         assert_eq!(bar2.high, 151.00);
         assert_eq!(bar2.low, 150.40);
         assert_eq!(bar2.close, 150.90);
-        assert_eq!(bar2.volume, 1200.0);
+        assert_eq!(bar2.volume, Decimal::from(1200));
 
         // Verify third bar
         let bar3 = &bars[2];
@@ -4052,6 +4581,70 @@ mod tests {
         assert!(requests[0].contains("TRADES\0"), "Request should contain TRADES");
     }
 
+    #[tokio::test]
+    async fn test_head_timestamp_timeout() {
+        use crate::client::common::tests::setup_unanswered_head_timestamp;
+        use crate::connection::common::ConnectionOptions;
+        use crate::contracts::Contract;
+        use crate::market_data::historical::WhatToShow;
+        use crate::Error;
+        use std::time::Duration;
+
+        let gateway = setup_unanswered_head_timestamp();
+        let options = ConnectionOptions::default().request_timeout(Duration::from_millis(100));
+        let client = Client::connect_with_options(&gateway.address(), CLIENT_ID, options)
+            .await
+            .expect("Failed to connect");
+        assert_eq!(client.request_timeout(), Some(Duration::from_millis(100)));
+
+        let contract = Contract::stock("AAPL").build();
+        let result = client.head_timestamp(&contract, WhatToShow::Trades, TradingHours::Regular).await;
+        assert!(matches!(result, Err(Error::Timeout)), "expected timeout, got {result:?}");
+
+        // The cancel is sent from a task; let it run before waiting on the gateway.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let requests = gateway.requests();
+        let request_id = requests[0].split('\0').nth(1).expect("request id");
+        let cancel = gateway.wait_for_request("90\0").expect("head timestamp request was not cancelled");
+        assert_eq!(cancel, format!("90\0{request_id}\0"));
+    }
+
+    #[tokio::test]
+    async fn test_head_timestamp_dropped() {
+        use crate::client::common::tests::setup_unanswered_head_timestamp;
+        use crate::contracts::Contract;
+        use crate::market_data::historical::WhatToShow;
+        use std::time::Duration;
+
+        let gateway = setup_unanswered_head_timestamp();
+        let client = Client::connect(&gateway.address(), CLIENT_ID).await.expect("Failed to connect");
+
+        // Abandon the request by dropping its future.
+        let contract = Contract::stock("AAPL").build();
+        let request = client.head_timestamp(&contract, WhatToShow::Trades, TradingHours::Regular);
+        assert!(tokio::time::timeout(Duration::from_millis(100), request).await.is_err());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let cancel = gateway.wait_for_request("90\0");
+        assert!(cancel.is_some(), "dropped head timestamp request was not cancelled");
+    }
+
+    #[tokio::test]
+    async fn test_contract_details_timeout() {
+        use crate::client::common::tests::setup_unanswered_contract_details;
+        use crate::contracts::Contract;
+        use crate::{Error, RequestOptions};
+        use std::time::Duration;
+
+        let gateway = setup_unanswered_contract_details();
+        let client = Client::connect(&gateway.address(), CLIENT_ID).await.expect("Failed to connect");
+
+        let contract = Contract::stock("AAPL").build();
+        let options = RequestOptions::default().timeout(Duration::from_millis(100));
+        let result = client.contract_details_with_options(&contract, options).await;
+        assert!(matches!(result, Err(Error::Timeout)), "expected timeout, got {result:?}");
+    }
+
     #[tokio::test]
     async fn test_historical_data() {
         use crate::client::common::tests::setup_historical_data;
@@ -4084,7 +4677,7 @@ mod tests {
         assert_eq!(bars[0].high, 150.75);
         assert_eq!(bars[0].low, 150.00);
         assert_eq!(bars[0].close, 150.50);
-        assert_eq!(bars[0].volume, 1000.0);
+        assert_eq!(bars[0].volume, Decimal::from(1000));
         assert_eq!(bars[0].wap, 150.40);
         assert_eq!(bars[0].count, 25);
 
@@ -4093,7 +4686,7 @@ mod tests {
         assert_eq!(bars[1].high, 151.00);
         assert_eq!(bars[1].low, 150.40);
         assert_eq!(bars[1].close, 150.90);
-        assert_eq!(bars[1].volume, 1200.0);
+        assert_eq!(bars[1].volume, Decimal::from(1200));
 
         // Verify third bar
         assert_eq!(bars[2].open, 150.90);
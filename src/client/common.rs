@@ -19,6 +19,7 @@ pub mod mocks {
         handle: Option<thread::JoinHandle<()>>,
         requests: Arc<Mutex<Vec<String>>>,
         interactions: Vec<Interaction>,
+        rejected_client_ids: Vec<i32>,
         server_version: i32,
         address: Option<String>,
     }
@@ -29,6 +30,7 @@ pub mod mocks {
                 handle: None,
                 requests: Arc::new(Mutex::new(Vec::new())),
                 interactions: Vec::new(),
+                rejected_client_ids: Vec::new(),
                 server_version,
                 address: None,
             }
@@ -38,6 +40,17 @@ pub mod mocks {
             self.requests.lock().unwrap().clone()
         }
 
+        // Waits for a request the client sends after its call returned, such as a cancel.
+        pub fn wait_for_request(&self, prefix: &str) -> Option<String> {
+            for _ in 0..50 {
+                if let Some(request) = self.requests().into_iter().find(|request| request.starts_with(prefix)) {
+                    return Some(request);
+                }
+                thread::sleep(std::time::Duration::from_millis(20));
+            }
+            None
+        }
+
         pub fn address(&self) -> String {
             self.address.clone().unwrap_or_default()
         }
@@ -46,6 +59,12 @@ pub mod mocks {
             self.interactions.push(Interaction { request, responses });
         }
 
+        // Responds to connections with this client id with error 326 (client id in use)
+        // and waits for the next connection.
+        pub fn reject_client_id(&mut self, client_id: i32) {
+            self.rejected_client_ids.push(client_id);
+        }
+
         pub fn server_version(&self) -> i32 {
             self.server_version
         }
@@ -60,25 +79,33 @@ pub mod mocks {
 
             let requests = Arc::clone(&self.requests);
             let interactions = self.interactions.clone();
+            let rejected_client_ids = self.rejected_client_ids.clone();
             let server_version = self.server_version;
 
             let handle = thread::spawn(move || {
-                // Handle single request and exit
-                let stream = match listener.accept() {
-                    Ok((stream, addr)) => {
-                        println!("MockGateway: Accepted connection from {}", addr);
-                        stream
-                    }
-                    Err(e) => {
-                        eprintln!("MockGateway: Failed to accept connection: {}", e);
-                        return;
+                // Handle single accepted connection and exit
+                loop {
+                    let stream = match listener.accept() {
+                        Ok((stream, addr)) => {
+                            println!("MockGateway: Accepted connection from {}", addr);
+                            stream
+                        }
+                        Err(e) => {
+                            eprintln!("MockGateway: Failed to accept connection: {}", e);
+                            return;
+                        }
+                    };
+
+                    let mut handler = ConnectionHandler::new(server_version, requests.clone(), interactions.clone(), rejected_client_ids.clone());
+                    match handler.handle(stream) {
+                        Ok(false) => continue,
+                        Ok(true) => return,
+                        Err(err) => {
+                            // Error handling connection
+                            eprintln!("MockGateway: Error handling connection: {}", err);
+                            return;
+                        }
                     }
-                };
-
-                let mut handler = ConnectionHandler::new(server_version, requests.clone(), interactions.clone());
-                if let Err(err) = handler.handle(stream) {
-                    // Error handling connection
-                    eprintln!("MockGateway: Error handling connection: {}", err);
                 }
             });
 
@@ -100,15 +127,17 @@ pub mod mocks {
     struct ConnectionHandler {
         requests: Arc<Mutex<Vec<String>>>,
         interactions: Vec<Interaction>,
+        rejected_client_ids: Vec<i32>,
         current_interaction: usize,
         server_version: i32,
     }
 
     impl ConnectionHandler {
-        pub fn new(server_version: i32, requests: Arc<Mutex<Vec<String>>>, interactions: Vec<Interaction>) -> Self {
+        pub fn new(server_version: i32, requests: Arc<Mutex<Vec<String>>>, interactions: Vec<Interaction>, rejected_client_ids: Vec<i32>) -> Self {
             ConnectionHandler {
                 requests,
                 interactions,
+                rejected_client_ids,
                 current_interaction: 0,
                 server_version,
             }
@@ -131,12 +160,15 @@ pub mod mocks {
             Ok(())
         }
 
-        pub fn handle(&mut self, mut stream: TcpStream) -> Result<(), std::io::Error> {
-            self.handle_startup(&mut stream)?;
+        // Returns false if the connection was rejected.
+        pub fn handle(&mut self, mut stream: TcpStream) -> Result<bool, std::io::Error> {
+            if !self.handle_startup(&mut stream)? {
+                return Ok(false);
+            }
 
             if self.interactions.is_empty() {
                 self.send_shutdown(&mut stream)?;
-                return Ok(());
+                return Ok(true);
             }
 
             // Set a read timeout so we don't wait forever for requests
@@ -176,10 +208,11 @@ pub mod mocks {
             // Give the client a moment to read the shutdown message
             std::thread::sleep(std::time::Duration::from_millis(50));
 
-            Ok(())
+            Ok(true)
         }
 
-        pub fn handle_startup(&mut self, stream: &mut TcpStream) -> Result<(), std::io::Error> {
+        // Returns false if the client id was rejected.
+        pub fn handle_startup(&mut self, stream: &mut TcpStream) -> Result<bool, std::io::Error> {
             let magic_token = self.read_magic_token(stream)?;
             assert_eq!(magic_token, "API\0");
 
@@ -192,11 +225,20 @@ pub mod mocks {
 
             // Start API
             let message = self.read_message(stream)?;
+            let client_id: i32 = message.split('\0').nth(2).and_then(|id| id.parse().ok()).unwrap_or_default();
             // For server versions > 72 (OPTIONAL_CAPABILITIES), expect an extra empty field
             if self.server_version > 72 {
-                assert_eq!(message, "71\02\0100\0\0");
+                assert_eq!(message, format!("71\02\0{client_id}\0\0"));
             } else {
-                assert_eq!(message, "71\02\0100\0");
+                assert_eq!(message, format!("71\02\0{client_id}\0"));
+            }
+
+            if self.rejected_client_ids.contains(&client_id) {
+                self.write_message(
+                    stream,
+                    "4\02\0-1\0326\0Unable to connect as the client id is already in use. Retry with a unique client id.\0".to_string(),
+                )?;
+                return Ok(false);
             }
 
             // next valid order id
@@ -205,7 +247,7 @@ pub mod mocks {
             // managed accounts
             self.write_message(stream, "15\01\02334\0".to_string())?;
 
-            Ok(())
+            Ok(true)
         }
 
         pub fn send_shutdown(&mut self, stream: &mut TcpStream) -> Result<(), std::io::Error> {
@@ -453,6 +495,14 @@ pub mod tests {
         gateway
     }
 
+    // Gateway that never answers the contract details request.
+    pub fn setup_unanswered_contract_details() -> MockGateway {
+        let mut gateway = MockGateway::new(server_versions::IPO_PRICES);
+        gateway.add_interaction(OutgoingMessages::RequestContractData, vec![]);
+        gateway.start().expect("Failed to start mock gateway");
+        gateway
+    }
+
     pub fn setup_matching_symbols() -> MockGateway {
         let mut gateway = MockGateway::new(server_versions::BOND_ISSUERID);
 
@@ -587,6 +637,8 @@ pub mod tests {
         gateway.add_interaction(
             OutgoingMessages::RequestAutoOpenOrders,
             vec![
+                // OrderBound message - TWS order 123456 bound to API order 3001 of client 0
+                "100\0123456\00\03001\0".to_string(),
                 // OrderStatus message - Order 3001 status update
                 "3\03001\0PreSubmitted\00\0100\00\0123456\00\00\0100\0\00\0".to_string(),
                 // OpenOrder message - Order 3001: FB order from TWS
@@ -975,6 +1027,14 @@ pub mod tests {
         gateway
     }
 
+    // Gateway that never answers the head timestamp request, so the client times out and cancels it.
+    pub fn setup_unanswered_head_timestamp() -> MockGateway {
+        let mut gateway = MockGateway::new(server_versions::PEGBEST_PEGMID_OFFSETS);
+        gateway.add_interaction(OutgoingMessages::RequestHeadTimestamp, vec![]);
+        gateway.start().expect("Failed to start mock gateway");
+        gateway
+    }
+
     pub fn setup_historical_data() -> MockGateway {
         let mut gateway = MockGateway::new(server_versions::IPO_PRICES);
 
@@ -0,0 +1,265 @@
+//! Asynchronous client manager.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use log::{error, info};
+
+use super::{Attempt, ClientIdPool, Endpoint, ManagedConnection, DEFAULT_CLIENT_IDS};
+use crate::client::r#async::Client;
+use crate::{ConnectionOptions, Error};
+
+struct Managed {
+    client: Arc<Client>,
+    endpoint: usize,
+    client_id: i32,
+}
+
+struct State {
+    pool: ClientIdPool,
+    clients: HashMap<String, Managed>,
+}
+
+impl State {
+    // Returns the connected client registered as `name`, forgetting it if its connection was lost for good.
+    fn connected(&mut self, name: &str) -> Option<Arc<Client>> {
+        let managed = self.clients.get(name)?;
+        if managed.client.is_connected() {
+            return Some(managed.client.clone());
+        }
+
+        info!("client {name} is disconnected, connecting again");
+        let managed = self.clients.remove(name)?;
+        self.pool.release(managed.endpoint, managed.client_id);
+        None
+    }
+
+    // Registers a new connection as `name`, unless a concurrent call connected it first.
+    fn insert(&mut self, name: &str, managed: Managed) -> Arc<Client> {
+        if let Some(client) = self.connected(name) {
+            self.pool.release(managed.endpoint, managed.client_id);
+            return client;
+        }
+
+        let client = managed.client.clone();
+        self.clients.insert(name.to_string(), managed);
+        client
+    }
+}
+
+/// Owns connections to a set of endpoints and hands out shared [Client] handles by name.
+///
+/// Endpoints are tried in the order given; the first one that accepts a connection is used.
+/// Client ids are allocated from [client_ids](Self::client_ids) so clients of the same manager never
+/// collide, and ids already used by other processes (error 326) are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::client::manager::Endpoint;
+/// use ibapi::client::ClientManager;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let manager = ClientManager::new([
+///         Endpoint::new("primary", "10.0.0.1:4002"),
+///         Endpoint::new("backup", "10.0.0.2:4002"),
+///     ])
+///     .client_ids(100..=120);
+///
+///     let orders = manager.client("orders").await.expect("connection failed");
+///     let market_data = manager.client("market-data").await.expect("connection failed");
+///
+///     println!("orders use client id {}", orders.client_id());
+///     println!("market data uses client id {}", market_data.client_id());
+/// }
+/// ```
+pub struct ClientManager {
+    endpoints: Vec<Endpoint>,
+    options: ConnectionOptions,
+    state: Mutex<State>,
+}
+
+impl ClientManager {
+    /// Creates a manager for `endpoints`, in order of preference.
+    pub fn new(endpoints: impl IntoIterator<Item = Endpoint>) -> Self {
+        Self {
+            endpoints: endpoints.into_iter().collect(),
+            options: ConnectionOptions::default(),
+            state: Mutex::new(State {
+                pool: ClientIdPool::new(DEFAULT_CLIENT_IDS),
+                clients: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Sets the client ids to allocate from. Default: [DEFAULT_CLIENT_IDS](super::DEFAULT_CLIENT_IDS).
+    pub fn client_ids(mut self, client_ids: RangeInclusive<i32>) -> Self {
+        self.state.get_mut().unwrap_or_else(PoisonError::into_inner).pool = ClientIdPool::new(client_ids);
+        self
+    }
+
+    /// Sets the options used for every connection.
+    pub fn options(mut self, options: ConnectionOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the endpoints in order of preference.
+    pub fn endpoints(&self) -> &[Endpoint] {
+        &self.endpoints
+    }
+
+    // A panic while holding the lock cannot leave the state half updated, so a poisoned lock is used as is.
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the client registered as `name`, connecting it first if needed.
+    ///
+    /// A client whose connection was lost for good is replaced by a new connection, which fails over to
+    /// the next reachable endpoint. Returns [Error::ConnectionFailed] if no endpoint accepts a connection.
+    pub async fn client(&self, name: &str) -> Result<Arc<Client>, Error> {
+        if let Some(client) = self.state().connected(name) {
+            return Ok(client);
+        }
+
+        // The lock is not held while connecting. Reserving the id keeps concurrent calls from picking the same one.
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            let mut in_use_elsewhere = Vec::new();
+            loop {
+                let Some(client_id) = self.state().pool.take(index, &in_use_elsewhere) else {
+                    break;
+                };
+
+                match Client::connect_with_options(&endpoint.address, client_id, self.options.clone()).await {
+                    Ok(client) => {
+                        info!(
+                            "client {name} connected to {} ({}) as client id {client_id}",
+                            endpoint.name, endpoint.address
+                        );
+                        let managed = Managed {
+                            client: Arc::new(client),
+                            endpoint: index,
+                            client_id,
+                        };
+                        return Ok(self.state().insert(name, managed));
+                    }
+                    Err(err) => {
+                        self.state().pool.release(index, client_id);
+                        match Attempt::after(endpoint, client_id, &err) {
+                            Attempt::NextClientId => in_use_elsewhere.push(client_id),
+                            Attempt::NextEndpoint => break,
+                        }
+                    }
+                }
+            }
+        }
+
+        error!("no endpoint accepted a connection for client {name}");
+        Err(Error::ConnectionFailed)
+    }
+
+    /// Forgets the client registered as `name` and frees its client id.
+    ///
+    /// The connection closes once all handles to the client are dropped. Returns false if no such client exists.
+    pub fn release(&self, name: &str) -> bool {
+        let mut state = self.state();
+        match state.clients.remove(name) {
+            Some(managed) => {
+                state.pool.release(managed.endpoint, managed.client_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Lists the clients connected by the manager.
+    pub fn connections(&self) -> Vec<ManagedConnection> {
+        let state = self.state();
+        let mut connections: Vec<ManagedConnection> = state
+            .clients
+            .iter()
+            .map(|(name, managed)| ManagedConnection {
+                name: name.clone(),
+                endpoint: self.endpoints[managed.endpoint].clone(),
+                client_id: managed.client_id,
+                connected: managed.client.is_connected(),
+            })
+            .collect();
+        connections.sort_by(|a, b| a.name.cmp(&b.name));
+        connections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+    use crate::client::common::mocks::MockGateway;
+    use crate::messages::OutgoingMessages;
+    use crate::server_versions;
+
+    // An address nothing listens on.
+    fn unreachable_address() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_retries_with_next_client_id() {
+        let mut gateway = MockGateway::new(server_versions::IPO_PRICES);
+        gateway.reject_client_id(100);
+        // keeps the connection open while the test runs
+        gateway.add_interaction(OutgoingMessages::RequestCurrentTime, vec![]);
+        gateway.start().unwrap();
+
+        let manager = ClientManager::new([Endpoint::new("primary", gateway.address())]).client_ids(100..=102);
+
+        let client = manager.client("orders").await.expect("connect");
+        assert_eq!(client.client_id(), 101);
+
+        let again = manager.client("orders").await.expect("cached client");
+        assert!(Arc::ptr_eq(&client, &again), "same name returns the same client");
+
+        assert_eq!(
+            manager.connections(),
+            vec![ManagedConnection {
+                name: "orders".to_string(),
+                endpoint: Endpoint::new("primary", gateway.address()),
+                client_id: 101,
+                connected: true,
+            }]
+        );
+
+        assert!(manager.release("orders"));
+        assert!(!manager.release("orders"));
+        assert!(manager.connections().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_backup_endpoint() {
+        let mut gateway = MockGateway::new(server_versions::IPO_PRICES);
+        gateway.start().unwrap();
+
+        let manager = ClientManager::new([
+            Endpoint::new("primary", unreachable_address()),
+            Endpoint::new("backup", gateway.address()),
+        ])
+        .client_ids(7..=7);
+
+        let client = manager.client("orders").await.expect("connect to backup");
+        assert_eq!(client.client_id(), 7);
+        assert_eq!(manager.connections()[0].endpoint.name, "backup");
+    }
+
+    #[tokio::test]
+    async fn test_fails_when_no_endpoint_reachable() {
+        let manager = ClientManager::new([Endpoint::new("primary", unreachable_address())]);
+
+        assert!(matches!(manager.client("orders").await, Err(Error::ConnectionFailed)));
+        assert!(manager.connections().is_empty());
+    }
+}
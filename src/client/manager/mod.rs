@@ -0,0 +1,128 @@
+//! Connections to a set of TWS/IB Gateway endpoints with client id allocation and failover.
+//!
+//! A [ClientManager](crate::client::ClientManager) connects named clients to the first reachable
+//! [Endpoint], picking a client id that is not used by another client of the manager. When TWS
+//! rejects the id because another process already uses it (error 326), the next free id is tried.
+//! Endpoints are tried in order, so a backup Gateway is used while the primary is unreachable.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+use log::warn;
+
+use crate::messages::CLIENT_ID_IN_USE_CODE;
+use crate::Error;
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
+
+#[cfg(feature = "async")]
+pub(crate) mod r#async;
+
+#[cfg(test)]
+mod tests;
+
+/// Client ids handed out by default. TWS accepts at most 32 simultaneous API clients.
+pub const DEFAULT_CLIENT_IDS: RangeInclusive<i32> = 1..=32;
+
+/// A TWS or IB Gateway instance the manager may connect to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Endpoint {
+    /// Name used in logs and [ManagedConnection], e.g. "primary".
+    pub name: String,
+    /// Address of the API port, e.g. "127.0.0.1:4002".
+    pub address: String,
+}
+
+impl Endpoint {
+    /// Creates an endpoint.
+    pub fn new(name: impl Into<String>, address: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            address: address.into(),
+        }
+    }
+}
+
+/// A client connected by the manager.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManagedConnection {
+    /// Name the client was requested with.
+    pub name: String,
+    /// Endpoint the client is connected to.
+    pub endpoint: Endpoint,
+    /// Client id allocated to the client.
+    pub client_id: i32,
+    /// Whether the connection is currently up.
+    pub connected: bool,
+}
+
+/// Result of one connection attempt, deciding what to try next.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Attempt {
+    /// The client id is taken by another process. Try the next id on the same endpoint.
+    NextClientId,
+    /// The endpoint could not be used. Try the next endpoint.
+    NextEndpoint,
+}
+
+impl Attempt {
+    pub(crate) fn after(endpoint: &Endpoint, client_id: i32, err: &Error) -> Self {
+        match err {
+            Error::Message(CLIENT_ID_IN_USE_CODE, _) => {
+                warn!(
+                    "client id {client_id} is in use on {} ({}), trying next id",
+                    endpoint.name, endpoint.address
+                );
+                Attempt::NextClientId
+            }
+            err => {
+                warn!("failed to connect to {} ({}): {err}", endpoint.name, endpoint.address);
+                Attempt::NextEndpoint
+            }
+        }
+    }
+}
+
+/// Tracks which client ids the manager's own clients hold on each endpoint.
+#[derive(Debug)]
+pub(crate) struct ClientIdPool {
+    client_ids: RangeInclusive<i32>,
+    // endpoint index -> client ids in use
+    in_use: HashMap<usize, HashSet<i32>>,
+}
+
+impl ClientIdPool {
+    pub(crate) fn new(client_ids: RangeInclusive<i32>) -> Self {
+        Self {
+            client_ids,
+            in_use: HashMap::new(),
+        }
+    }
+
+    /// Ids not held by the manager on `endpoint`, lowest first.
+    pub(crate) fn candidates(&self, endpoint: usize) -> Vec<i32> {
+        let in_use = self.in_use.get(&endpoint);
+        self.client_ids
+            .clone()
+            .filter(|id| !in_use.is_some_and(|in_use| in_use.contains(id)))
+            .collect()
+    }
+
+    /// Reserves the lowest id on `endpoint` that is neither held by the manager nor in `skip`.
+    pub(crate) fn take(&mut self, endpoint: usize, skip: &[i32]) -> Option<i32> {
+        let client_id = self.candidates(endpoint).into_iter().find(|id| !skip.contains(id))?;
+        self.reserve(endpoint, client_id);
+        Some(client_id)
+    }
+
+    pub(crate) fn reserve(&mut self, endpoint: usize, client_id: i32) {
+        self.in_use.entry(endpoint).or_default().insert(client_id);
+    }
+
+    pub(crate) fn release(&mut self, endpoint: usize, client_id: i32) {
+        if let Some(in_use) = self.in_use.get_mut(&endpoint) {
+            in_use.remove(&client_id);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn test_pool_skips_reserved_ids_per_endpoint() {
+    let mut pool = ClientIdPool::new(1..=4);
+
+    pool.reserve(0, 1);
+    pool.reserve(0, 3);
+    pool.reserve(1, 2);
+
+    assert_eq!(pool.candidates(0), vec![2, 4]);
+    assert_eq!(pool.candidates(1), vec![1, 3, 4]);
+    assert_eq!(pool.candidates(2), vec![1, 2, 3, 4]);
+
+    pool.release(0, 1);
+    pool.release(5, 1);
+    assert_eq!(pool.candidates(0), vec![1, 2, 4]);
+}
+
+#[test]
+fn test_pool_take_reserves_lowest_free_id() {
+    let mut pool = ClientIdPool::new(1..=3);
+
+    assert_eq!(pool.take(0, &[]), Some(1));
+    assert_eq!(pool.take(0, &[2]), Some(3));
+    assert_eq!(pool.take(0, &[2]), None);
+    assert_eq!(pool.take(0, &[]), Some(2));
+    assert_eq!(pool.take(1, &[]), Some(1), "ids are reserved per endpoint");
+}
+
+#[test]
+fn test_attempt_after_error() {
+    let endpoint = Endpoint::new("primary", "127.0.0.1:4002");
+
+    let in_use = Error::Message(CLIENT_ID_IN_USE_CODE, "client id is already in use".to_string());
+    assert_eq!(Attempt::after(&endpoint, 1, &in_use), Attempt::NextClientId);
+
+    let refused = Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+    assert_eq!(Attempt::after(&endpoint, 1, &refused), Attempt::NextEndpoint);
+    assert_eq!(
+        Attempt::after(&endpoint, 1, &Error::Message(502, "no connection".to_string())),
+        Attempt::NextEndpoint
+    );
+}
@@ -4,6 +4,7 @@ pub(crate) mod builders;
 pub(crate) mod common;
 pub(crate) mod error_handler;
 pub(crate) mod id_generator;
+pub mod manager;
 
 #[cfg(feature = "sync")]
 pub mod sync;
@@ -14,8 +15,10 @@ pub mod r#async;
 /// Blocking client bindings for synchronous workflows.
 #[cfg(feature = "sync")]
 pub mod blocking {
+    pub use super::manager::sync::ClientManager;
     pub use super::sync::Client;
     pub(crate) use crate::client::builders::blocking::{ClientRequestBuilders, SubscriptionBuilderExt};
+    pub use crate::connection::sync::ConnectionEvents;
     pub use crate::subscriptions::sync::{
         SharesChannel, Subscription, SubscriptionIter, SubscriptionOwnedIter, SubscriptionTimeoutIter, SubscriptionTryIter,
     };
@@ -27,6 +30,11 @@ pub use r#async::Client;
 #[cfg(all(feature = "sync", not(feature = "async")))]
 pub use sync::Client;
 
+#[cfg(feature = "async")]
+pub use manager::r#async::ClientManager;
+#[cfg(all(feature = "sync", not(feature = "async")))]
+pub use manager::sync::ClientManager;
+
 #[cfg(feature = "sync")]
 pub(crate) use crate::subscriptions::StreamDecoder;
 
@@ -5,7 +5,7 @@
 //! subscriptions, and maintains the connection state.
 
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use log::debug;
@@ -14,9 +14,10 @@ use time_tz::Tz;
 
 use crate::accounts::types::{AccountGroup, AccountId, ContractId, ModelCode};
 use crate::accounts::{AccountSummaryResult, AccountUpdate, AccountUpdateMulti, FamilyCode, PnL, PnLSingle, PositionUpdate, PositionUpdateMulti};
-use crate::connection::common::{ConnectionOptions, StartupMessageCallback};
-use crate::connection::{sync::Connection, ConnectionMetadata};
-use crate::contracts::{Contract, OptionComputation, SecurityType};
+use crate::connection::common::{ConnectionOptions, RequestOptions, StartupMessageCallback};
+use crate::connection::sync::{Connection, ConnectionEvents};
+use crate::connection::ConnectionMetadata;
+use crate::contracts::{BondIdentifier, Combo, ComboBuilder, Contract, ContractCache, OptionComputation, SecurityType};
 use crate::errors::Error;
 use crate::market_data::builder::MarketDataBuilder;
 use crate::market_data::historical::{self, HistogramEntry};
@@ -24,9 +25,11 @@ use crate::market_data::realtime::{self, Bar, BarSize, DepthMarketDataDescriptio
 use crate::market_data::{MarketDataType, TradingHours};
 use crate::messages::{OutgoingMessages, RequestMessage};
 use crate::news::NewsArticle;
-use crate::orders::{CancelOrder, Executions, ExerciseOptions, Order, OrderBuilder, OrderUpdate, Orders, PlaceOrder};
+use crate::orders::journal::OrderJournal;
+use crate::orders::{CancelOrder, Executions, ExerciseOptions, Order, OrderBindings, OrderBuilder, OrderUpdate, Orders, PlaceOrder};
 use crate::scanner::ScannerData;
 use crate::subscriptions::sync::Subscription;
+use crate::trace::TraceBuffer;
 use crate::transport::{InternalSubscription, MessageBus, TcpMessageBus};
 use crate::wsh::AutoFill;
 use crate::{accounts, contracts, display_groups, market_data, news, orders, scanner, wsh};
@@ -47,6 +50,9 @@ pub struct Client {
 
     client_id: i32,              // ID of client.
     id_manager: ClientIdManager, // Manages request and order ID generation
+    contract_cache: ContractCache,
+    request_timeout: Option<Duration>, // Default timeout of one-shot requests
+    order_journal: Option<Arc<Mutex<OrderJournal>>>,
 }
 
 impl Client {
@@ -140,6 +146,9 @@ impl Client {
     ///     .expect("connection failed");
     /// ```
     pub fn connect_with_options(address: &str, client_id: i32, options: ConnectionOptions) -> Result<Client, Error> {
+        let heartbeat = options.heartbeat;
+        let request_timeout = options.request_timeout;
+        let order_journal = options.order_journal.clone();
         let connection = Connection::connect_with_options(address, client_id, options)?;
         let connection_metadata = connection.connection_metadata();
 
@@ -148,7 +157,34 @@ impl Client {
         // Starts thread to read messages from TWS
         message_bus.process_messages(connection_metadata.server_version, Duration::from_secs(1))?;
 
-        Client::new(connection_metadata, message_bus)
+        if let Some(heartbeat) = heartbeat {
+            message_bus.start_heartbeat(heartbeat);
+        }
+
+        let mut client = Client::new(connection_metadata, message_bus)?;
+        client.request_timeout = request_timeout;
+        client.order_journal = order_journal;
+        Ok(client)
+    }
+
+    /// Creates a client backed by a [SimulatedBroker](crate::simulator::SimulatedBroker) instead of a TWS connection.
+    ///
+    /// Orders placed through the client are filled against the prices fed to the broker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::simulator::SimulatedBroker;
+    ///
+    /// let broker = SimulatedBroker::default();
+    /// let client = Client::simulated(&broker);
+    ///
+    /// println!("next order id: {}", client.next_order_id());
+    /// ```
+    pub fn simulated(broker: &crate::simulator::SimulatedBroker) -> Client {
+        let message_bus = Arc::new(crate::simulator::sync::SimulatedMessageBus::new(broker.clone()));
+        Client::new(broker.connection_metadata(), message_bus).expect("creating a client does not fail")
     }
 
     fn new(connection_metadata: ConnectionMetadata, message_bus: Arc<dyn MessageBus>) -> Result<Client, Error> {
@@ -159,6 +195,9 @@ impl Client {
             message_bus,
             client_id: connection_metadata.client_id,
             id_manager: ClientIdManager::new(connection_metadata.next_order_id),
+            contract_cache: ContractCache::default(),
+            request_timeout: None,
+            order_journal: None,
         };
 
         Ok(client)
@@ -257,6 +296,16 @@ impl Client {
         self.time_zone
     }
 
+    /// Returns the default timeout of one-shot requests, set with [ConnectionOptions::request_timeout].
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// Returns the journal orders are recorded in, set with [ConnectionOptions::order_journal].
+    pub fn order_journal(&self) -> Option<&Arc<Mutex<OrderJournal>>> {
+        self.order_journal.as_ref()
+    }
+
     /// Returns a decoder context for this client
     pub(crate) fn decoder_context(&self) -> crate::subscriptions::DecoderContext {
         crate::subscriptions::DecoderContext::new(self.server_version, self.time_zone)
@@ -284,6 +333,63 @@ impl Client {
         self.message_bus.is_connected()
     }
 
+    /// Streams changes in the state of the connection to TWS/IB Gateway.
+    ///
+    /// The stream starts with the most recent event, typically [ConnectionEvent::Connected](crate::ConnectionEvent::Connected),
+    /// followed by disconnects and reconnection attempts as they happen. Enable
+    /// [ConnectionOptions::heartbeat] to also detect connections that stop answering without being closed.
+    ///
+    /// Returns [Error::NotImplemented] for clients without a TWS connection, such as simulated clients.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::{ConnectionEvent, ConnectionOptions};
+    ///
+    /// let options = ConnectionOptions::default().heartbeat(Duration::from_secs(30), Duration::from_secs(10));
+    /// let client = Client::connect_with_options("127.0.0.1:4002", 100, options).expect("connection failed");
+    ///
+    /// for event in client.connection_events().expect("connection events failed") {
+    ///     match event {
+    ///         ConnectionEvent::Reconnecting { attempt, delay } => println!("reconnect attempt {attempt} in {delay:?}"),
+    ///         ConnectionEvent::GaveUp => break,
+    ///         event => println!("{event:?}"),
+    ///     }
+    /// }
+    /// ```
+    pub fn connection_events(&self) -> Result<ConnectionEvents, Error> {
+        match self.message_bus.connection_events() {
+            Some(hub) => Ok(ConnectionEvents::subscribe(hub)),
+            None => Err(Error::NotImplemented),
+        }
+    }
+
+    /// Returns the buffer of recent interactions with TWS enabled by [ConnectionOptions::trace_buffer].
+    ///
+    /// The buffer records nothing unless a capacity was set. Returns [Error::NotImplemented] for clients
+    /// without a TWS connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::ConnectionOptions;
+    ///
+    /// let options = ConnectionOptions::default().trace_buffer(50);
+    /// let client = Client::connect_with_options("127.0.0.1:4002", 100, options).expect("connection failed");
+    ///
+    /// let time = client.server_time().expect("request failed");
+    ///
+    /// let trace = client.trace_buffer().expect("trace buffer not available");
+    /// println!("{}", trace.export().to_json().expect("export failed"));
+    /// ```
+    pub fn trace_buffer(&self) -> Result<TraceBuffer, Error> {
+        self.message_bus.trace_buffer().cloned().ok_or(Error::NotImplemented)
+    }
+
     // === Accounts ===
 
     /// TWS's current time. TWS is synchronized with the server (not local computer) using NTP and this function will receive the current time in TWS.
@@ -557,6 +663,9 @@ impl Client {
     ///
     /// Provides all the contracts matching the contract provided. It can also be used to retrieve complete options and futures chains. Though it is now (in API version > 9.72.12) advised to use [Client::option_chain] for that purpose.
     ///
+    /// Every call is sent to TWS: the [contract cache](Self::contract_cache) is neither read nor filled.
+    /// Use [qualify_contracts](Self::qualify_contracts) to look contracts up through the cache.
+    ///
     /// # Arguments
     /// * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
     ///
@@ -575,7 +684,114 @@ impl Client {
     /// }
     /// ```
     pub fn contract_details(&self, contract: &Contract) -> Result<Vec<contracts::ContractDetails>, Error> {
-        contracts::blocking::contract_details(self, contract)
+        contracts::blocking::contract_details(self, contract, RequestOptions::default())
+    }
+
+    /// Requests contract information, overriding the client's [request timeout](Self::request_timeout).
+    ///
+    /// If TWS has not answered within the timeout, the request is cancelled and [Error::Timeout] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::RequestOptions;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let contract = Contract::stock("TSLA").build();
+    /// let options = RequestOptions::default().timeout(Duration::from_secs(5));
+    /// let results = client.contract_details_with_options(&contract, options).expect("request failed");
+    /// println!("{} contracts", results.len());
+    /// ```
+    pub fn contract_details_with_options(&self, contract: &Contract, options: RequestOptions) -> Result<Vec<contracts::ContractDetails>, Error> {
+        contracts::blocking::contract_details(self, contract, options)
+    }
+
+    /// Requests the details of the bond with a CUSIP or ISIN.
+    ///
+    /// Bond terms such as coupon, maturity and call features are returned in [ContractDetails::bond](contracts::ContractDetails::bond).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::contracts::Cusip;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let results = client.bond_contract_details(Cusip::new("912810TL8")).expect("request failed");
+    /// for details in results {
+    ///     if let Some(bond) = details.bond {
+    ///         println!("{} {}% due {:?}", details.contract.symbol, bond.coupon, bond.maturity);
+    ///     }
+    /// }
+    /// ```
+    pub fn bond_contract_details(&self, identifier: impl Into<BondIdentifier>) -> Result<Vec<contracts::ContractDetails>, Error> {
+        contracts::blocking::bond_contract_details(self, identifier.into())
+    }
+
+    /// Completes partially specified contracts with their definition from TWS.
+    ///
+    /// Each contract is replaced by the contract TWS matches it to, which fills in `contract_id`,
+    /// `primary_exchange`, `trading_class`, `local_symbol` and similar fields; the caller's `exchange` is kept.
+    /// Contracts missing from the [contract cache](Self::contract_cache) are requested concurrently, and
+    /// equivalent contracts share a request.
+    ///
+    /// Every contract that can be qualified is updated. The others are returned together in
+    /// [Error::QualificationFailed], each with its index and error: [Error::ContractNotFound] if nothing
+    /// matches, [Error::AmbiguousContract] listing the candidates if several contracts do, or the error of
+    /// the request. Adding a currency, primary exchange or contract id narrows an ambiguous contract down.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::contracts::Contract;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let mut contracts = vec![Contract::stock("AAPL").build(), Contract::stock("MSFT").build()];
+    /// client.qualify_contracts(&mut contracts).expect("qualification failed");
+    ///
+    /// for contract in &contracts {
+    ///     println!("{} {} {}", contract.symbol, contract.contract_id, contract.primary_exchange);
+    /// }
+    /// ```
+    pub fn qualify_contracts(&self, contracts: &mut [Contract]) -> Result<(), Error> {
+        contracts::blocking::qualify_contracts(self, contracts)
+    }
+
+    /// Cache of contract details used by [qualify_contracts](Self::qualify_contracts).
+    pub fn contract_cache(&self) -> &ContractCache {
+        &self.contract_cache
+    }
+
+    /// Resolves the legs of a [ComboBuilder] to contract ids and builds the BAG contract.
+    ///
+    /// The legs are qualified with [qualify_contracts](Self::qualify_contracts) and must share an underlying
+    /// symbol and a currency; otherwise [Error::InvalidArgument] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::contracts::Contract;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let vertical = Contract::combo()
+    ///     .buy(Contract::call("AAPL").strike(150.0).expires_on(2025, 1, 17).build(), 1)
+    ///     .sell(Contract::call("AAPL").strike(160.0).expires_on(2025, 1, 17).build(), 1);
+    ///
+    /// let combo = client.resolve_combo(&vertical).expect("combo resolution failed");
+    /// let market_data = client.market_data(&combo.contract).subscribe().expect("market data request failed");
+    /// ```
+    pub fn resolve_combo(&self, combo: &ComboBuilder) -> Result<Combo, Error> {
+        contracts::blocking::resolve_combo(self, combo)
     }
 
     /// Cancels an in-flight contract details request.
@@ -597,7 +813,12 @@ impl Client {
     /// A list of market rule ids can be obtained by invoking [Self::contract_details()] for a particular contract.
     /// The returned market rule ID list will provide the market rule ID for the instrument in the correspond valid exchange list in [contracts::ContractDetails].
     pub fn market_rule(&self, market_rule_id: i32) -> Result<contracts::MarketRule, Error> {
-        contracts::blocking::market_rule(self, market_rule_id)
+        contracts::blocking::market_rule(self, market_rule_id, RequestOptions::default())
+    }
+
+    /// Requests details about a given market rule, overriding the client's [request timeout](Self::request_timeout).
+    pub fn market_rule_with_options(&self, market_rule_id: i32, options: RequestOptions) -> Result<contracts::MarketRule, Error> {
+        contracts::blocking::market_rule(self, market_rule_id, options)
     }
 
     /// Requests matching stock symbols.
@@ -618,7 +839,16 @@ impl Client {
     /// }
     /// ```
     pub fn matching_symbols(&self, pattern: &str) -> Result<impl Iterator<Item = contracts::ContractDescription>, Error> {
-        Ok(contracts::blocking::matching_symbols(self, pattern)?.into_iter())
+        self.matching_symbols_with_options(pattern, RequestOptions::default())
+    }
+
+    /// Requests matching stock symbols, overriding the client's [request timeout](Self::request_timeout).
+    pub fn matching_symbols_with_options(
+        &self,
+        pattern: &str,
+        options: RequestOptions,
+    ) -> Result<impl Iterator<Item = contracts::ContractDescription>, Error> {
+        Ok(contracts::blocking::matching_symbols(self, pattern, options)?.into_iter())
     }
 
     /// Calculates an option’s price based on the provided volatility and its underlying’s price.
@@ -706,6 +936,32 @@ impl Client {
         contracts::blocking::option_chain(self, symbol, exchange, security_type, contract_id)
     }
 
+    /// Expands the option chain of `underlying` into qualified contracts, optionally with quotes.
+    ///
+    /// See [OptionChainBuilder](crate::contracts::options::OptionChainBuilder) for the available filters.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::contracts::Contract;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    /// let underlying = Contract::stock("AAPL").build();
+    ///
+    /// let surface = client
+    ///     .option_surface(&underlying)
+    ///     .max_expirations(1)
+    ///     .strikes_around(185.0, 3)
+    ///     .fetch()
+    ///     .expect("option surface failed");
+    ///
+    /// println!("resolved {} options", surface.len());
+    /// ```
+    pub fn option_surface<'a>(&'a self, underlying: &'a Contract) -> contracts::options::OptionChainBuilder<'a, Self> {
+        contracts::options::OptionChainBuilder::new(self, underlying)
+    }
+
     // === Orders ===
 
     /// Requests all *current* open orders in associated accounts at the current moment.
@@ -748,6 +1004,36 @@ impl Client {
         orders::blocking::auto_open_orders(self, auto_bind)
     }
 
+    /// Returns the index of orders bound to API clients, keyed by permanent id.
+    ///
+    /// TWS reports a binding each time an order placed in TWS is assigned an API order id, for
+    /// example after [auto_open_orders](Self::auto_open_orders) with `auto_bind` set. The index is
+    /// kept for the lifetime of the connection. Returns [Error::NotImplemented] for clients without
+    /// a TWS connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::orders::OrderUpdate;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 0).expect("connection failed");
+    /// let bindings = client.order_bindings().expect("order bindings not available");
+    ///
+    /// let updates = client.order_update_stream().expect("request failed");
+    /// let _auto_open_orders = client.auto_open_orders(true).expect("request failed");
+    ///
+    /// for update in &updates {
+    ///     if let OrderUpdate::OpenOrder(order_data) = update {
+    ///         let perm_id = i64::from(order_data.order.perm_id);
+    ///         println!("order {perm_id} is bound to {:?}", bindings.get(perm_id));
+    ///     }
+    /// }
+    /// ```
+    pub fn order_bindings(&self) -> Result<OrderBindings, Error> {
+        self.message_bus.order_bindings().cloned().ok_or(Error::NotImplemented)
+    }
+
     /// Cancels an active [Order] placed by the same API client ID.
     ///
     /// # Arguments
@@ -823,6 +1109,74 @@ impl Client {
         orders::blocking::executions(self, filter)
     }
 
+    /// Requests executions matching the filter and joins each one with its commission report.
+    ///
+    /// Unlike [Client::executions], which streams execution and commission messages separately,
+    /// this waits for the request to complete and returns one [orders::ledger::Fill] per execution.
+    ///
+    /// # Arguments
+    /// * `filter` - filter criteria used to determine which execution reports are returned
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::orders::ledger::{Ledger, LotMethod};
+    /// use ibapi::orders::ExecutionFilter;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let fills = client.fills(ExecutionFilter::builder().symbol("AAPL").build()).expect("request failed");
+    ///
+    /// let mut ledger = Ledger::new(LotMethod::Fifo);
+    /// ledger.apply_all(&fills).expect("unexpected fill");
+    /// println!("realized P&L: {:.2}", ledger.realized_pnl());
+    /// ```
+    pub fn fills(&self, filter: orders::ExecutionFilter) -> Result<Vec<orders::ledger::Fill>, Error> {
+        orders::ledger::sync::fills(self, filter)
+    }
+
+    /// Reconciles an order journal with the orders and executions currently reported by TWS.
+    ///
+    /// Requests open orders, completed orders and the executions matching `filter`, and streams
+    /// the fills missing from the journal, journaled orders that completed or vanished, and open
+    /// orders placed from other sessions.
+    ///
+    /// The journal is locked only after TWS has answered, so it can be the one attached with
+    /// [ConnectionOptions::order_journal], which records [place_order](Self::place_order) and the
+    /// order updates received by the client.
+    ///
+    /// # Arguments
+    /// * `journal` - Journal of orders and executions recorded by this application
+    /// * `filter` - Filter criteria used to determine which execution reports are requested
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::orders::journal::OrderJournal;
+    /// use ibapi::orders::ExecutionFilter;
+    /// use ibapi::ConnectionOptions;
+    ///
+    /// let journal = Arc::new(Mutex::new(OrderJournal::open("orders.journal").expect("failed to open journal")));
+    /// let options = ConnectionOptions::default().order_journal(journal.clone());
+    /// let client = Client::connect_with_options("127.0.0.1:4002", 100, options).expect("connection failed");
+    ///
+    /// let events = client.reconcile_orders(&journal, ExecutionFilter::default()).expect("reconciliation failed");
+    /// for event in events {
+    ///     println!("{event:?}");
+    /// }
+    /// ```
+    pub fn reconcile_orders(
+        &self,
+        journal: &Mutex<OrderJournal>,
+        filter: orders::ExecutionFilter,
+    ) -> Result<orders::journal::blocking::Reconciliation, Error> {
+        orders::journal::sync::reconcile(self, journal, filter)
+    }
+
     /// Cancels all open [Order]s.
     ///
     /// # Examples
@@ -894,7 +1248,9 @@ impl Client {
     ///         PlaceOrder::OpenOrder(open_order) => println!("open order: {open_order:?}"),
     ///         PlaceOrder::ExecutionData(execution) => println!("execution: {execution:?}"),
     ///         PlaceOrder::CommissionReport(report) => println!("commission report: {report:?}"),
+    ///         PlaceOrder::DeltaNeutralValidation(contract) => println!("delta neutral validation: {contract:?}"),
     ///         PlaceOrder::Message(message) => println!("message: {message:?}"),
+    ///         _ => {}
     ///    }
     /// }
     /// ```
@@ -1019,9 +1375,13 @@ impl Client {
     ///             println!("Commission: ${} for execution {}",
     ///                 report.commission, report.execution_id);
     ///         },
+    ///         OrderUpdate::OrderBound(binding) => {
+    ///             println!("TWS order {} bound to order {}", binding.perm_id, binding.order_id);
+    ///         },
     ///         OrderUpdate::Message(notice) => {
     ///             println!("Order message: {}", notice.message);
     ///         }
+    ///         _ => {}
     ///     }
     /// }
     /// ```
@@ -1083,7 +1443,41 @@ impl Client {
         what_to_show: historical::WhatToShow,
         trading_hours: TradingHours,
     ) -> Result<OffsetDateTime, Error> {
-        historical::blocking::head_timestamp(self, contract, what_to_show, trading_hours)
+        historical::blocking::head_timestamp(self, contract, what_to_show, trading_hours, RequestOptions::default())
+    }
+
+    /// Returns the timestamp of earliest available historical data, overriding the client's [request timeout](Self::request_timeout).
+    ///
+    /// If TWS has not answered within the timeout, the request is cancelled and [Error::Timeout] is returned.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::market_data::historical::WhatToShow;
+    /// use ibapi::market_data::TradingHours;
+    /// use ibapi::RequestOptions;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    ///
+    /// let contract = Contract::stock("MSFT").build();
+    /// let options = RequestOptions::default().timeout(Duration::from_secs(10));
+    ///
+    /// let result = client
+    ///     .head_timestamp_with_options(&contract, WhatToShow::Trades, TradingHours::Regular, options)
+    ///     .expect("head timestamp failed");
+    ///
+    /// print!("head_timestamp: {result:?}");
+    /// ```
+    pub fn head_timestamp_with_options(
+        &self,
+        contract: &Contract,
+        what_to_show: historical::WhatToShow,
+        trading_hours: TradingHours,
+        options: RequestOptions,
+    ) -> Result<OffsetDateTime, Error> {
+        historical::blocking::head_timestamp(self, contract, what_to_show, trading_hours, options)
     }
 
     /// Requests interval of historical data ending at specified time for [Contract].
@@ -1129,7 +1523,42 @@ impl Client {
         what_to_show: historical::WhatToShow,
         trading_hours: TradingHours,
     ) -> Result<historical::HistoricalData, Error> {
-        historical::blocking::historical_data(self, contract, interval_end, duration, bar_size, Some(what_to_show), trading_hours)
+        historical::blocking::historical_data(
+            self,
+            contract,
+            interval_end,
+            duration,
+            bar_size,
+            Some(what_to_show),
+            trading_hours,
+            RequestOptions::default(),
+        )
+    }
+
+    /// Requests interval of historical data, overriding the client's [request timeout](Self::request_timeout).
+    ///
+    /// If TWS has not answered within the timeout, the request is cancelled and [Error::Timeout] is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn historical_data_with_options(
+        &self,
+        contract: &Contract,
+        interval_end: Option<OffsetDateTime>,
+        duration: historical::Duration,
+        bar_size: historical::BarSize,
+        what_to_show: historical::WhatToShow,
+        trading_hours: TradingHours,
+        options: RequestOptions,
+    ) -> Result<historical::HistoricalData, Error> {
+        historical::blocking::historical_data(
+            self,
+            contract,
+            interval_end,
+            duration,
+            bar_size,
+            Some(what_to_show),
+            trading_hours,
+            options,
+        )
     }
 
     /// Requests historical data with optional streaming updates.
@@ -1423,7 +1852,20 @@ impl Client {
         trading_hours: TradingHours,
         period: historical::BarSize,
     ) -> Result<Vec<HistogramEntry>, Error> {
-        historical::blocking::histogram_data(self, contract, trading_hours, period)
+        historical::blocking::histogram_data(self, contract, trading_hours, period, RequestOptions::default())
+    }
+
+    /// Requests histogram data, overriding the client's [request timeout](Self::request_timeout).
+    ///
+    /// If TWS has not answered within the timeout, the request is cancelled and [Error::Timeout] is returned.
+    pub fn histogram_data_with_options(
+        &self,
+        contract: &Contract,
+        trading_hours: TradingHours,
+        period: historical::BarSize,
+        options: RequestOptions,
+    ) -> Result<Vec<HistogramEntry>, Error> {
+        historical::blocking::histogram_data(self, contract, trading_hours, period, options)
     }
 
     // === Realtime Market Data ===
@@ -2056,7 +2498,7 @@ impl Client {
     ///
     /// # Arguments
     ///
-    /// * `filter`    - Json-formatted string containing all filter values.
+    /// * `filter`    - Json-formatted string containing all filter values. See [wsh::WshFilter] to build one.
     /// * `limit`     - Maximum number of events to return. Maximum of 100.
     /// * `auto_fill` - Fields to automatically fill in. See [AutoFill] for more information.
     ///
@@ -2064,13 +2506,22 @@ impl Client {
     ///
     /// ```no_run
     /// use ibapi::client::blocking::Client;
+    /// use ibapi::wsh::{WshEventType, WshFilter};
     ///
     /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
     ///
-    /// let filter = ""; // see https://www.interactivebrokers.com/campus/ibkr-api-page/twsapi-doc/#wsheventdata-object
-    /// let event_data = client.wsh_event_data_by_filter(filter, None, None).expect("request wsh event data failed");
+    /// let metadata = client.wsh_metadata().expect("request wsh metadata failed");
+    /// let filter = WshFilter::new()
+    ///     .event_type(WshEventType::Earnings)
+    ///     .contract_id(76792991)
+    ///     .build(&metadata)
+    ///     .expect("invalid filter");
+    ///
+    /// let event_data = client.wsh_event_data_by_filter(&filter, None, None).expect("request wsh event data failed");
     /// for result in event_data {
-    ///     println!("{result:?}");
+    ///     for event in result.events().expect("invalid event data") {
+    ///         println!("{:?} {:?} {:?}", event.event_type(), event.date(), event.confidence());
+    ///     }
     /// }
     /// ```
     pub fn wsh_event_data_by_filter(
@@ -2093,6 +2544,9 @@ impl Client {
             message_bus,
             client_id: 100,
             id_manager: ClientIdManager::new(-1),
+            contract_cache: ContractCache::default(),
+            request_timeout: None,
+            order_journal: None,
         }
     }
 
@@ -2184,6 +2638,7 @@ pub use crate::subscriptions::SharesChannel;
 
 #[cfg(test)]
 mod tests {
+    use crate::Decimal;
     use std::sync::Arc;
 
     use super::{Client, TradingHours};
@@ -2219,6 +2674,35 @@ mod tests {
         assert_eq!(requests[0], "49\01\0");
     }
 
+    #[test]
+    fn test_connection_events_not_available_without_connection() {
+        let message_bus = Arc::new(MessageBusStub::default());
+        let client = Client::stubbed(message_bus, crate::server_versions::SIZE_RULES);
+
+        assert!(matches!(client.connection_events(), Err(crate::Error::NotImplemented)));
+        assert!(matches!(client.trace_buffer(), Err(crate::Error::NotImplemented)));
+        assert!(matches!(client.order_bindings(), Err(crate::Error::NotImplemented)));
+    }
+
+    #[test]
+    fn test_trace_buffer() {
+        let (gateway, expectations) = setup_server_time();
+
+        let options = crate::ConnectionOptions::default().trace_buffer(10);
+        let client = Client::connect_with_options(&gateway.address(), CLIENT_ID, options).expect("Failed to connect");
+        client.server_time().unwrap();
+
+        let trace = client.trace_buffer().unwrap();
+        let interactions = trace.for_message_type(crate::messages::OutgoingMessages::RequestCurrentTime);
+        assert_eq!(interactions.len(), 1);
+        assert_eq!(interactions[0].request.as_ref().unwrap().raw, "49\01\0");
+        assert_eq!(
+            interactions[0].responses[0].raw,
+            format!("49\01\0{}\0", expectations.server_time.unix_timestamp())
+        );
+        assert_eq!(trace.export().header.server_version, crate::server_versions::IPO_PRICES);
+    }
+
     #[test]
     fn test_next_valid_order_id() {
         let (gateway, expectations) = setup_next_valid_order_id();
@@ -2259,7 +2743,7 @@ mod tests {
                 crate::accounts::PositionUpdate::Position(position) => {
                     assert_eq!(position.account, "DU1234567");
                     assert_eq!(position.contract.symbol, Symbol::from("AAPL"));
-                    assert_eq!(position.position, 500.0);
+                    assert_eq!(position.position, Decimal::from(500));
                     assert_eq!(position.average_cost, 150.25);
                     position_count += 1;
                 }
@@ -2386,7 +2870,7 @@ mod tests {
         let pnl_single = client.pnl_single(&account, contract_id, None).unwrap();
 
         let first_pnl = pnl_single.into_iter().next().unwrap();
-        assert_eq!(first_pnl.position, 100.0);
+        assert_eq!(first_pnl.position, Decimal::from(100));
         assert_eq!(first_pnl.daily_pnl, 150.25);
         assert_eq!(first_pnl.unrealized_pnl, 500.00);
         assert_eq!(first_pnl.realized_pnl, 250.00);
@@ -2917,6 +3401,7 @@ mod tests {
                 PlaceOrder::OpenOrder(_) => "OpenOrder",
                 PlaceOrder::ExecutionData(_) => "ExecutionData",
                 PlaceOrder::CommissionReport(_) => "CommissionReport",
+                PlaceOrder::DeltaNeutralValidation(_) => "DeltaNeutralValidation",
                 PlaceOrder::Message(_) => "Message",
             };
             println!("Event {}: {} received", events_received, event_type);
@@ -2928,18 +3413,18 @@ mod tests {
                     if order_status_count == 1 {
                         // First status: PreSubmitted
                         assert_eq!(status.status, "PreSubmitted");
-                        assert_eq!(status.filled, 0.0);
-                        assert_eq!(status.remaining, 100.0);
+                        assert_eq!(status.filled, Decimal::from(0));
+                        assert_eq!(status.remaining, Decimal::from(100));
                     } else if order_status_count == 2 {
                         // Second status: Submitted
                         assert_eq!(status.status, "Submitted");
-                        assert_eq!(status.filled, 0.0);
-                        assert_eq!(status.remaining, 100.0);
+                        assert_eq!(status.filled, Decimal::from(0));
+                        assert_eq!(status.remaining, Decimal::from(100));
                     } else if order_status_count == 3 {
                         // Third status: Filled
                         assert_eq!(status.status, "Filled");
-                        assert_eq!(status.filled, 100.0);
-                        assert_eq!(status.remaining, 0.0);
+                        assert_eq!(status.filled, Decimal::from(100));
+                        assert_eq!(status.remaining, Decimal::from(0));
                         assert_eq!(status.average_fill_price, 150.25);
                     }
                 }
@@ -2949,7 +3434,7 @@ mod tests {
                     assert_eq!(order_data.contract.symbol, Symbol::from("AAPL"));
                     assert_eq!(order_data.contract.contract_id, 265598);
                     assert_eq!(order_data.order.action, Action::Buy);
-                    assert_eq!(order_data.order.total_quantity, 100.0);
+                    assert_eq!(order_data.order.total_quantity, Decimal::from(100));
                     assert_eq!(order_data.order.order_type, "LMT");
                     assert_eq!(order_data.order.limit_price, Some(1.0));
                 }
@@ -2957,7 +3442,7 @@ mod tests {
                     execution_count += 1;
                     assert_eq!(exec_data.execution.order_id, order_id);
                     assert_eq!(exec_data.contract.symbol, Symbol::from("AAPL"));
-                    assert_eq!(exec_data.execution.shares, 100.0);
+                    assert_eq!(exec_data.execution.shares, Decimal::from(100));
                     assert_eq!(exec_data.execution.price, 150.25);
                 }
                 PlaceOrder::CommissionReport(report) => {
@@ -2965,7 +3450,7 @@ mod tests {
                     assert_eq!(report.commission, 1.25);
                     assert_eq!(report.currency, "USD");
                 }
-                PlaceOrder::Message(_) => {
+                PlaceOrder::DeltaNeutralValidation(_) | PlaceOrder::Message(_) => {
                     // Skip any messages
                 }
             }
@@ -2991,6 +3476,39 @@ mod tests {
         assert!(requests[0].contains(&format!("\0{}\0", order_id)), "Request should contain order ID");
     }
 
+    #[test]
+    fn test_place_order_records_journal() {
+        use crate::connection::common::ConnectionOptions;
+        use crate::contracts::Contract;
+        use crate::orders::journal::OrderJournal;
+        use crate::orders::{order_builder, Action, PlaceOrder};
+        use std::sync::Mutex;
+
+        let gateway = setup_place_order();
+        let journal = Arc::new(Mutex::new(OrderJournal::in_memory()));
+        let options = ConnectionOptions::default().order_journal(journal.clone());
+        let client = Client::connect_with_options(&gateway.address(), CLIENT_ID, options).expect("Failed to connect");
+        assert!(client.order_journal().is_some());
+
+        let contract = Contract::stock("AAPL").build();
+        let order = order_builder::market_order(Action::Buy, 100.0);
+        let subscription = client.place_order(1001, &contract, &order).expect("Failed to place order");
+
+        // The commission report is the last message of the exchange, so everything before it has been journaled.
+        for event in &subscription {
+            if matches!(event, PlaceOrder::CommissionReport(_)) {
+                break;
+            }
+        }
+
+        let journal = journal.lock().unwrap();
+        let journaled = journal.order(CLIENT_ID, 1001).expect("order was not journaled");
+        assert_eq!(journaled.contract.symbol.to_string(), "AAPL");
+        assert_eq!(journaled.perm_id(), 123456);
+        assert!(!journaled.is_active(), "{journaled:?}");
+        assert!(journal.has_execution("000e1a2b.67890abc.01.01"));
+    }
+
     #[test]
     fn test_submit_order_with_order_update_stream() {
         use crate::client::common::tests::setup_place_order;
@@ -3043,18 +3561,18 @@ mod tests {
                         if order_status_count == 1 {
                             // First status: PreSubmitted
                             assert_eq!(status.status, "PreSubmitted");
-                            assert_eq!(status.filled, 0.0);
-                            assert_eq!(status.remaining, 100.0);
+                            assert_eq!(status.filled, Decimal::from(0));
+                            assert_eq!(status.remaining, Decimal::from(100));
                         } else if order_status_count == 2 {
                             // Second status: Submitted
                             assert_eq!(status.status, "Submitted");
-                            assert_eq!(status.filled, 0.0);
-                            assert_eq!(status.remaining, 100.0);
+                            assert_eq!(status.filled, Decimal::from(0));
+                            assert_eq!(status.remaining, Decimal::from(100));
                         } else if order_status_count == 3 {
                             // Third status: Filled
                             assert_eq!(status.status, "Filled");
-                            assert_eq!(status.filled, 100.0);
-                            assert_eq!(status.remaining, 0.0);
+                            assert_eq!(status.filled, Decimal::from(100));
+                            assert_eq!(status.remaining, Decimal::from(0));
                             assert_eq!(status.average_fill_price, 150.25);
                         }
                     }
@@ -3064,7 +3582,7 @@ mod tests {
                         assert_eq!(order_data.contract.symbol, Symbol::from("AAPL"));
                         assert_eq!(order_data.contract.contract_id, 265598);
                         assert_eq!(order_data.order.action, Action::Buy);
-                        assert_eq!(order_data.order.total_quantity, 100.0);
+                        assert_eq!(order_data.order.total_quantity, Decimal::from(100));
                         assert_eq!(order_data.order.order_type, "LMT");
                         assert_eq!(order_data.order.limit_price, Some(1.0));
                     }
@@ -3072,7 +3590,7 @@ mod tests {
                         execution_count += 1;
                         assert_eq!(exec_data.execution.order_id, order_id);
                         assert_eq!(exec_data.contract.symbol, Symbol::from("AAPL"));
-                        assert_eq!(exec_data.execution.shares, 100.0);
+                        assert_eq!(exec_data.execution.shares, Decimal::from(100));
                         assert_eq!(exec_data.execution.price, 150.25);
                     }
                     OrderUpdate::CommissionReport(report) => {
@@ -3080,8 +3598,8 @@ mod tests {
                         assert_eq!(report.commission, 1.25);
                         assert_eq!(report.currency, "USD");
                     }
-                    OrderUpdate::Message(_) => {
-                        // Skip any messages
+                    OrderUpdate::OrderBound(_) | OrderUpdate::Message(_) => {
+                        // Skip bindings and messages
                     }
                 }
             } else {
@@ -3143,7 +3661,7 @@ mod tests {
         assert_eq!(order1.contract.symbol, Symbol::from("AAPL"));
         assert_eq!(order1.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order1.order.action, Action::Buy);
-        assert_eq!(order1.order.total_quantity, 100.0);
+        assert_eq!(order1.order.total_quantity, Decimal::from(100));
         assert_eq!(order1.order.order_type, "MKT");
         assert_eq!(order1.order_state.status, "PreSubmitted");
 
@@ -3153,7 +3671,7 @@ mod tests {
         assert_eq!(order2.contract.symbol, Symbol::from("MSFT"));
         assert_eq!(order2.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order2.order.action, Action::Sell);
-        assert_eq!(order2.order.total_quantity, 50.0);
+        assert_eq!(order2.order.total_quantity, Decimal::from(50));
         assert_eq!(order2.order.order_type, "LMT");
         assert_eq!(order2.order.limit_price, Some(350.0));
         assert_eq!(order2.order_state.status, "Submitted");
@@ -3203,7 +3721,7 @@ mod tests {
         assert_eq!(order1.contract.symbol, Symbol::from("TSLA"));
         assert_eq!(order1.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order1.order.action, Action::Buy);
-        assert_eq!(order1.order.total_quantity, 10.0);
+        assert_eq!(order1.order.total_quantity, Decimal::from(10));
         assert_eq!(order1.order.order_type, "LMT");
         assert_eq!(order1.order.limit_price, Some(420.0));
         assert_eq!(order1.order.account, "DU1236110");
@@ -3213,7 +3731,7 @@ mod tests {
         assert_eq!(order2.order_id, 2002);
         assert_eq!(order2.contract.symbol, Symbol::from("AMZN"));
         assert_eq!(order2.order.action, Action::Sell);
-        assert_eq!(order2.order.total_quantity, 5.0);
+        assert_eq!(order2.order.total_quantity, Decimal::from(5));
         assert_eq!(order2.order.order_type, "MKT");
         assert_eq!(order2.order.account, "DU1236111");
 
@@ -3222,7 +3740,7 @@ mod tests {
         assert_eq!(order3.order_id, 1003);
         assert_eq!(order3.contract.symbol, Symbol::from("GOOGL"));
         assert_eq!(order3.order.action, Action::Buy);
-        assert_eq!(order3.order.total_quantity, 20.0);
+        assert_eq!(order3.order.total_quantity, Decimal::from(20));
         assert_eq!(order3.order.order_type, "LMT");
         assert_eq!(order3.order.limit_price, Some(2800.0));
         assert_eq!(order3.order.account, "DU1236109");
@@ -3288,10 +3806,15 @@ mod tests {
         assert_eq!(order.contract.symbol, Symbol::from("FB"));
         assert_eq!(order.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order.order.action, crate::orders::Action::Buy);
-        assert_eq!(order.order.total_quantity, 50.0);
+        assert_eq!(order.order.total_quantity, Decimal::from(50));
         assert_eq!(order.order.order_type, "MKT");
         assert_eq!(order.order.account, "TWS");
 
+        // Verify the binding sent ahead of the order was indexed
+        let bindings = client.order_bindings().expect("order bindings not available");
+        assert_eq!(bindings.order_id(123456), Some(3001));
+        assert_eq!(bindings.perm_id(0, 3001), Some(123456));
+
         // Verify the request was sent correctly
         let requests = gateway.requests();
         assert_eq!(requests.len(), 1, "Should have sent 1 request");
@@ -3341,7 +3864,7 @@ mod tests {
         assert_eq!(order1.contract.symbol, Symbol::from("ES"));
         assert_eq!(order1.contract.security_type, crate::contracts::SecurityType::Future);
         assert_eq!(order1.order.action, Action::Buy);
-        assert_eq!(order1.order.total_quantity, 1.0);
+        assert_eq!(order1.order.total_quantity, Decimal::from(1));
         assert_eq!(order1.order.order_type, "LMT");
         assert_eq!(order1.order_state.status, "Cancelled");
         assert_eq!(order1.order.perm_id, 616088517);
@@ -3352,7 +3875,7 @@ mod tests {
         assert_eq!(order2.contract.symbol, Symbol::from("AAPL"));
         assert_eq!(order2.contract.security_type, crate::contracts::SecurityType::Stock);
         assert_eq!(order2.order.action, Action::Buy);
-        assert_eq!(order2.order.total_quantity, 100.0);
+        assert_eq!(order2.order.total_quantity, Decimal::from(100));
         assert_eq!(order2.order.order_type, "MKT");
         assert_eq!(order2.order_state.status, "Filled");
         assert_eq!(order2.order.perm_id, 1377295418);
@@ -3394,8 +3917,8 @@ mod tests {
                         CancelOrder::OrderStatus(status) => {
                             assert_eq!(status.order_id, order_id);
                             assert_eq!(status.status, "Cancelled");
-                            assert_eq!(status.filled, 0.0);
-                            assert_eq!(status.remaining, 100.0);
+                            assert_eq!(status.filled, Decimal::from(0));
+                            assert_eq!(status.remaining, Decimal::from(100));
                             order_status_received = true;
                             println!("Received OrderStatus: {:?}", status);
                         }
@@ -3510,7 +4033,7 @@ mod tests {
         assert_eq!(exec1.contract.security_type, SecurityType::Stock);
         assert_eq!(exec1.execution.execution_id, "000e1a2b.67890abc.01.01");
         assert_eq!(exec1.execution.side, "BOT");
-        assert_eq!(exec1.execution.shares, 100.0);
+        assert_eq!(exec1.execution.shares, Decimal::from(100));
         assert_eq!(exec1.execution.price, 150.25);
 
         // Verify first commission report
@@ -3527,7 +4050,7 @@ mod tests {
         assert_eq!(exec2.contract.security_type, SecurityType::Future);
         assert_eq!(exec2.execution.execution_id, "000e1a2b.67890def.02.01");
         assert_eq!(exec2.execution.side, "SLD");
-        assert_eq!(exec2.execution.shares, 5.0);
+        assert_eq!(exec2.execution.shares, Decimal::from(5));
         assert_eq!(exec2.execution.price, 5050.25);
 
         // Verify second commission report
@@ -3544,7 +4067,7 @@ mod tests {
         assert_eq!(exec3.contract.security_type, SecurityType::Option);
         assert_eq!(exec3.execution.execution_id, "000e1a2b.67890ghi.03.01");
         assert_eq!(exec3.execution.side, "BOT");
-        assert_eq!(exec3.execution.shares, 10.0);
+        assert_eq!(exec3.execution.shares, Decimal::from(10));
         assert_eq!(exec3.execution.price, 2.50);
 
         // Verify third commission report
@@ -3624,13 +4147,13 @@ mod tests {
 
         // Verify order statuses
         assert_eq!(order_statuses[0].status, "PreSubmitted");
-        assert_eq!(order_statuses[0].filled, 0.0);
-        assert_eq!(order_statuses[0].remaining, 10.0);
+        assert_eq!(order_statuses[0].filled, Decimal::from(0));
+        assert_eq!(order_statuses[0].remaining, Decimal::from(10));
 
         assert_eq!(order_statuses[1].status, "Submitted");
         assert_eq!(order_statuses[2].status, "Filled");
-        assert_eq!(order_statuses[2].filled, 10.0);
-        assert_eq!(order_statuses[2].remaining, 0.0);
+        assert_eq!(order_statuses[2].filled, Decimal::from(10));
+        assert_eq!(order_statuses[2].remaining, Decimal::from(0));
 
         // Verify open order
         let open_order = &open_orders[0];
@@ -3726,25 +4249,24 @@ mod tests {
                 }
                 TickTypes::Size(size_tick) => match size_tick.tick_type {
                     TickType::BidSize => {
-                        assert_eq!(size_tick.size, 100.0);
+                        assert_eq!(size_tick.size, Decimal::from(100));
                         has_bid_size = true;
                     }
                     TickType::AskSize => {
-                        assert_eq!(size_tick.size, 200.0);
+                        assert_eq!(size_tick.size, Decimal::from(200));
                         has_ask_size = true;
                     }
                     TickType::LastSize => {
-                        assert_eq!(size_tick.size, 50.0);
+                        assert_eq!(size_tick.size, Decimal::from(50));
                         has_last_size = true;
                     }
                     _ => {}
                 },
-                TickTypes::Generic(generic_tick) => {
-                    if generic_tick.tick_type == TickType::Volume {
-                        assert_eq!(generic_tick.value, 1500000.0);
-                        has_volume = true;
-                    }
+                TickTypes::Generic(generic_tick) if generic_tick.tick_type == TickType::Volume => {
+                    assert_eq!(generic_tick.value, 1500000.0);
+                    has_volume = true;
                 }
+                TickTypes::Generic(_) => {}
                 TickTypes::String(_) => {
                     // Ignore string ticks like LastTimestamp
                 }
@@ -3814,7 +4336,7 @@ mod tests {
         assert_eq!(bar1.high, 150.75);
         assert_eq!(bar1.low, 150.00);
         assert_eq!(bar1.close, 150.50);
-        assert_eq!(bar1.volume, 1000.0);
+        assert_eq!(bar1.volume, Decimal::from(1000));
         assert_eq!(bar1.wap, 150.40);
         assert_eq!(bar1.count, 25);
 
@@ -3824,7 +4346,7 @@ mod tests {
         assert_eq!(bar2.high, 151.00);
         assert_eq!(bar2.low, 150.40);
         assert_eq!(bar2.close, 150.90);
-        assert_eq!(bar2.volume, 1200.0);
+        assert_eq!(bar2.volume, Decimal::from(1200));
 
         // Verify third bar
         let bar3 = &bars[2];
@@ -4191,6 +4713,47 @@ mod tests {
         assert!(requests[0].contains("TRADES\0"), "Request should contain TRADES");
     }
 
+    #[test]
+    fn test_head_timestamp_timeout() {
+        use crate::client::common::tests::setup_unanswered_head_timestamp;
+        use crate::connection::common::ConnectionOptions;
+        use crate::contracts::Contract;
+        use crate::market_data::historical::WhatToShow;
+        use crate::Error;
+        use std::time::Duration;
+
+        let gateway = setup_unanswered_head_timestamp();
+        let options = ConnectionOptions::default().request_timeout(Duration::from_millis(100));
+        let client = Client::connect_with_options(&gateway.address(), CLIENT_ID, options).expect("Failed to connect");
+        assert_eq!(client.request_timeout(), Some(Duration::from_millis(100)));
+
+        let contract = Contract::stock("AAPL").build();
+        let result = client.head_timestamp(&contract, WhatToShow::Trades, TradingHours::Regular);
+        assert!(matches!(result, Err(Error::Timeout)), "expected timeout, got {result:?}");
+
+        let requests = gateway.requests();
+        let request_id = requests[0].split('\0').nth(1).expect("request id");
+        let cancel = gateway.wait_for_request("90\0").expect("head timestamp request was not cancelled");
+        assert_eq!(cancel, format!("90\0{request_id}\0"));
+    }
+
+    #[test]
+    fn test_contract_details_timeout() {
+        use crate::client::common::tests::setup_unanswered_contract_details;
+        use crate::contracts::Contract;
+        use crate::{Error, RequestOptions};
+        use std::time::Duration;
+
+        let gateway = setup_unanswered_contract_details();
+        let client = Client::connect(&gateway.address(), CLIENT_ID).expect("Failed to connect");
+        assert_eq!(client.request_timeout(), None);
+
+        let contract = Contract::stock("AAPL").build();
+        let options = RequestOptions::default().timeout(Duration::from_millis(100));
+        let result = client.contract_details_with_options(&contract, options);
+        assert!(matches!(result, Err(Error::Timeout)), "expected timeout, got {result:?}");
+    }
+
     #[test]
     fn test_historical_data() {
         use crate::client::common::tests::setup_historical_data;
@@ -4221,7 +4784,7 @@ mod tests {
         assert_eq!(bars[0].high, 150.75);
         assert_eq!(bars[0].low, 150.00);
         assert_eq!(bars[0].close, 150.50);
-        assert_eq!(bars[0].volume, 1000.0);
+        assert_eq!(bars[0].volume, Decimal::from(1000));
         assert_eq!(bars[0].wap, 150.40);
         assert_eq!(bars[0].count, 25);
 
@@ -4230,7 +4793,7 @@ mod tests {
         assert_eq!(bars[1].high, 151.00);
         assert_eq!(bars[1].low, 150.40);
         assert_eq!(bars[1].close, 150.90);
-        assert_eq!(bars[1].volume, 1200.0);
+        assert_eq!(bars[1].volume, Decimal::from(1200));
 
         // Verify third bar
         assert_eq!(bars[2].open, 150.90);
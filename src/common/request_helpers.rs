@@ -118,14 +118,72 @@ mod sync_helpers {
 // Async implementations
 #[cfg(feature = "async")]
 mod async_helpers {
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use log::warn;
+
     use crate::client::{Client, ClientRequestBuilders, SubscriptionBuilderExt};
     use crate::messages::{OutgoingMessages, RequestMessage, ResponseMessage};
     use crate::protocol::{check_version, ProtocolFeature};
     use crate::subscriptions::{StreamDecoder, Subscription};
+    use crate::transport::AsyncMessageBus;
     use crate::Error;
     #[allow(unused_imports)] // Used in one_shot_request
     use futures::StreamExt;
 
+    /// Awaits `request` for at most `timeout`, failing with [Error::Timeout] once it expires.
+    ///
+    /// The request future is dropped on expiry, which cancels it if it holds a [CancelOnDrop].
+    pub async fn with_timeout<R>(timeout: Option<Duration>, request: impl Future<Output = Result<R, Error>>) -> Result<R, Error> {
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, request).await.unwrap_or(Err(Error::Timeout)),
+            None => request.await,
+        }
+    }
+
+    /// Sends a cancel message to TWS when dropped before being disarmed.
+    ///
+    /// One-shot requests hold one while waiting for their answer, so a request that times out or whose
+    /// future is dropped by the caller is also cancelled at TWS.
+    pub struct CancelOnDrop {
+        message_bus: Arc<dyn AsyncMessageBus>,
+        message: Option<RequestMessage>,
+    }
+
+    impl CancelOnDrop {
+        /// Arms the guard with the `message` cancelling the request. `None` when the request cannot be cancelled.
+        pub fn new(client: &Client, message: Option<RequestMessage>) -> Self {
+            Self {
+                message_bus: client.message_bus.clone(),
+                message,
+            }
+        }
+
+        /// Called once TWS has answered; the request no longer needs cancelling.
+        pub fn disarm(mut self) {
+            self.message = None;
+        }
+    }
+
+    impl Drop for CancelOnDrop {
+        fn drop(&mut self) {
+            let Some(message) = self.message.take() else {
+                return;
+            };
+            // Drop can't be async, so the cancel message is sent from a task when a runtime is available.
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let message_bus = self.message_bus.clone();
+                runtime.spawn(async move {
+                    if let Err(e) = message_bus.send_message(message).await {
+                        warn!("error cancelling abandoned request: {e}");
+                    }
+                });
+            }
+        }
+    }
+
     /// Async helper for requests that need a request ID and return a subscription
     pub async fn request_with_id<T>(
         client: &Client,
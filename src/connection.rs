@@ -1,12 +1,16 @@
 //! Connection management for TWS communication
 
+use std::time::Duration;
+
 use time::OffsetDateTime;
 use time_tz::Tz;
 
 pub mod common;
 
 pub use common::ConnectionOptions;
+pub use common::RequestOptions;
 pub use common::StartupMessageCallback;
+pub(crate) use common::{ConnectionEventHub, Heartbeat};
 
 /// Metadata about the connection to TWS
 #[derive(Default, Clone, Debug)]
@@ -25,6 +29,43 @@ pub struct ConnectionMetadata {
     pub time_zone: Option<&'static Tz>,
 }
 
+impl ConnectionMetadata {
+    pub(crate) fn connected_event(&self) -> ConnectionEvent {
+        ConnectionEvent::Connected {
+            server_version: self.server_version,
+            time: self.connection_time,
+        }
+    }
+}
+
+/// Change in the state of the connection to TWS or IB Gateway, as streamed by `Client::connection_events()`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConnectionEvent {
+    /// The connection was established.
+    Connected {
+        /// Server version negotiated during the handshake.
+        server_version: i32,
+        /// Connection time reported by the server.
+        time: Option<OffsetDateTime>,
+    },
+    /// The connection was lost, or closed because the heartbeat went unanswered.
+    Disconnected {
+        /// Description of the failure.
+        error: String,
+    },
+    /// A reconnection attempt will be made after `delay`.
+    Reconnecting {
+        /// Attempt number, starting at 1.
+        attempt: i32,
+        /// Backoff delay before the attempt.
+        delay: Duration,
+    },
+    /// The connection was re-established. Subscriptions active at the time of the disconnect have ended.
+    Reconnected,
+    /// All reconnection attempts failed and the client has shut down.
+    GaveUp,
+}
+
 #[cfg(feature = "sync")]
 pub mod sync;
 
@@ -9,12 +9,15 @@ use tokio::net::TcpStream;
 use tokio::sync::Mutex;
 use tokio::time::sleep;
 
-use super::common::{parse_connection_time, AccountInfo, ConnectionHandler, ConnectionOptions, ConnectionProtocol, StartupMessageCallback};
-use super::ConnectionMetadata;
+use super::common::{
+    parse_connection_time, AccountInfo, ConnectionEventHub, ConnectionHandler, ConnectionOptions, ConnectionProtocol, StartupMessageCallback,
+};
+use super::{ConnectionEvent, ConnectionMetadata};
 use crate::errors::Error;
 use crate::messages::{RequestMessage, ResponseMessage};
 use crate::trace;
 use crate::transport::common::{FibonacciBackoff, MAX_RECONNECT_ATTEMPTS};
+use crate::transport::instrumentation::Instrumentation;
 use crate::transport::recorder::MessageRecorder;
 
 type Response = Result<ResponseMessage, Error>;
@@ -31,6 +34,8 @@ pub struct AsyncConnection {
     pub(crate) connection_handler: ConnectionHandler,
     pub(crate) connection_url: String,
     pub(crate) options: ConnectionOptions,
+    pub(crate) events: ConnectionEventHub,
+    pub(crate) instrumentation: Instrumentation,
 }
 
 impl AsyncConnection {
@@ -68,11 +73,14 @@ impl AsyncConnection {
             recorder: MessageRecorder::from_env(),
             connection_handler: ConnectionHandler::default(),
             connection_url: address.to_string(),
+            instrumentation: Instrumentation::new(&options),
             options,
+            events: ConnectionEventHub::default(),
         };
 
         let cb_ref = connection.options.startup_callback.as_deref();
         connection.establish_connection(cb_ref).await?;
+        connection.events.publish(connection.connection_metadata().await.connected_event());
 
         Ok(connection)
     }
@@ -102,6 +110,10 @@ impl AsyncConnection {
         for i in 0..MAX_RECONNECT_ATTEMPTS {
             let next_delay = backoff.next_delay();
             info!("next reconnection attempt in {next_delay:#?}");
+            self.events.publish(ConnectionEvent::Reconnecting {
+                attempt: i + 1,
+                delay: next_delay,
+            });
 
             sleep(next_delay).await;
 
@@ -121,6 +133,7 @@ impl AsyncConnection {
 
                     // Reconnection doesn't use startup callback
                     self.establish_connection(None).await?;
+                    self.events.publish(ConnectionEvent::Reconnected);
 
                     return Ok(());
                 }
@@ -157,6 +170,7 @@ impl AsyncConnection {
         let mut writer = self.writer.lock().await;
         writer.write_all(&length_encoded).await?;
         writer.flush().await?;
+        self.instrumentation.message_sent(message);
         Ok(())
     }
 
@@ -193,6 +207,7 @@ impl AsyncConnection {
         let message = ResponseMessage::from(&raw_string).with_server_version(self.server_version());
 
         self.recorder.record_response(&message);
+        self.instrumentation.message_received(&message);
 
         Ok(message)
     }
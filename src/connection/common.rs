@@ -1,16 +1,20 @@
 //! Common connection logic shared between sync and async implementations
 
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use log::{debug, error, info, warn};
 use time::macros::format_description;
 use time::OffsetDateTime;
 use time_tz::{OffsetResult, PrimitiveDateTimeExt, Tz};
 
+use super::ConnectionEvent;
 use crate::common::timezone::find_timezone;
 use crate::errors::Error;
-use crate::messages::{encode_length, IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::messages::{encode_length, IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage, CLIENT_ID_IN_USE_CODE};
+use crate::metrics::Metrics;
+use crate::orders::journal::OrderJournal;
 use crate::server_versions;
 
 /// Callback for handling unsolicited messages during connection setup.
@@ -37,6 +41,66 @@ pub type StartupMessageCallback = Box<dyn Fn(ResponseMessage) + Send + Sync>;
 pub struct ConnectionOptions {
     pub(crate) tcp_no_delay: bool,
     pub(crate) startup_callback: Option<Arc<dyn Fn(ResponseMessage) + Send + Sync>>,
+    pub(crate) heartbeat: Option<Heartbeat>,
+    pub(crate) metrics: Option<Arc<dyn Metrics>>,
+    pub(crate) trace_capacity: usize,
+    pub(crate) request_timeout: Option<Duration>,
+    pub(crate) order_journal: Option<Arc<Mutex<OrderJournal>>>,
+}
+
+/// Per-call options for one-shot requests such as `contract_details` or `head_timestamp`.
+///
+/// Pass them to the `*_with_options` variant of a request to override the client's
+/// [request timeout](ConnectionOptions::request_timeout) for that call.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use ibapi::RequestOptions;
+///
+/// let options = RequestOptions::default().timeout(Duration::from_secs(5));
+/// assert_eq!(options.resolve_timeout(None), Some(Duration::from_secs(5)));
+///
+/// let patient = RequestOptions::default().without_timeout();
+/// assert_eq!(patient.resolve_timeout(Some(Duration::from_secs(5))), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RequestOptions {
+    timeout: Option<Option<Duration>>,
+}
+
+impl RequestOptions {
+    /// Fails the request with [Error::Timeout] if TWS has not answered within `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(Some(timeout));
+        self
+    }
+
+    /// Waits for the answer however long it takes, even if the client has a request timeout.
+    pub fn without_timeout(mut self) -> Self {
+        self.timeout = Some(None);
+        self
+    }
+
+    /// Timeout of the request given the client's default timeout.
+    pub fn resolve_timeout(&self, default: Option<Duration>) -> Option<Duration> {
+        self.timeout.unwrap_or(default)
+    }
+
+    // Instant the request expires, starting now.
+    #[cfg(feature = "sync")]
+    pub(crate) fn deadline(&self, default: Option<Duration>) -> Option<std::time::Instant> {
+        self.resolve_timeout(default).map(|timeout| std::time::Instant::now() + timeout)
+    }
+}
+
+/// Interval and reply timeout of the server time heartbeat.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Heartbeat {
+    pub(crate) interval: Duration,
+    pub(crate) timeout: Duration,
 }
 
 impl ConnectionOptions {
@@ -57,6 +121,52 @@ impl ConnectionOptions {
         self.startup_callback = Some(Arc::new(callback));
         self
     }
+
+    /// Request the server time every `interval` and treat a connection that sends nothing within `timeout` as lost.
+    ///
+    /// A half-open socket, e.g. after Gateway's host drops off the network, never reports an error
+    /// on its own. With a heartbeat the stalled connection is closed and the usual reconnect logic
+    /// runs. Any message from TWS counts as a reply, and replies to the heartbeat are not delivered
+    /// to `server_time()` requests. Default: disabled.
+    pub fn heartbeat(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.heartbeat = Some(Heartbeat { interval, timeout });
+        self
+    }
+
+    /// Report message counts, decode errors, reconnects and request latencies to `metrics`.
+    ///
+    /// See the [metrics](crate::metrics) module. Default: disabled.
+    pub fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Keep the last `capacity` interactions with TWS in a [TraceBuffer](crate::trace::TraceBuffer).
+    ///
+    /// Read it from `Client::trace_buffer()`, e.g. to attach the exchange that led to an error to a
+    /// support ticket. Default: 0, nothing is recorded.
+    pub fn trace_buffer(mut self, capacity: usize) -> Self {
+        self.trace_capacity = capacity;
+        self
+    }
+
+    /// Fail one-shot requests with [Error::Timeout] when TWS has not answered within `timeout`.
+    ///
+    /// Requests TWS can cancel, like contract details or head timestamps, are cancelled when they
+    /// expire. Individual calls can override the timeout with [RequestOptions]. Default: no timeout.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Record orders placed by the client, and the order status and execution updates TWS sends, in `journal`.
+    ///
+    /// Keep a clone of the `Arc` to reconcile the journal after a reconnect, see
+    /// [journal](crate::orders::journal). Default: nothing is journaled.
+    pub fn order_journal(mut self, journal: Arc<Mutex<OrderJournal>>) -> Self {
+        self.order_journal = Some(journal);
+        self
+    }
 }
 
 impl From<Option<StartupMessageCallback>> for ConnectionOptions {
@@ -74,6 +184,53 @@ impl fmt::Debug for ConnectionOptions {
         f.debug_struct("ConnectionOptions")
             .field("tcp_no_delay", &self.tcp_no_delay)
             .field("startup_callback", &self.startup_callback.is_some())
+            .field("heartbeat", &self.heartbeat)
+            .field("metrics", &self.metrics.is_some())
+            .field("trace_capacity", &self.trace_capacity)
+            .field("request_timeout", &self.request_timeout)
+            .field("order_journal", &self.order_journal.is_some())
+            .finish()
+    }
+}
+
+type ConnectionEventListener = Box<dyn Fn(&ConnectionEvent) -> bool + Send + Sync>;
+
+/// Fans [ConnectionEvent]s out to subscribers.
+///
+/// The most recent event is replayed to each new subscriber, so a subscriber created after
+/// connecting still learns the current state.
+#[derive(Default)]
+pub struct ConnectionEventHub {
+    listeners: Mutex<Vec<ConnectionEventListener>>,
+    last: Mutex<Option<ConnectionEvent>>,
+}
+
+impl ConnectionEventHub {
+    /// Delivers `event` to all subscribers, dropping those whose receiver has gone away.
+    pub(crate) fn publish(&self, event: ConnectionEvent) {
+        debug!("connection event: {event:?}");
+        let mut listeners = self.listeners.lock().unwrap();
+        listeners.retain(|listener| listener(&event));
+        *self.last.lock().unwrap() = Some(event);
+    }
+
+    /// Registers a listener. It returns false once its receiver has been dropped.
+    pub(crate) fn subscribe(&self, listener: impl Fn(&ConnectionEvent) -> bool + Send + Sync + 'static) {
+        let mut listeners = self.listeners.lock().unwrap();
+        if let Some(event) = self.last.lock().unwrap().as_ref() {
+            if !listener(event) {
+                return;
+            }
+        }
+        listeners.push(Box::new(listener));
+    }
+}
+
+impl fmt::Debug for ConnectionEventHub {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionEventHub")
+            .field("listeners", &self.listeners.lock().map(|listeners| listeners.len()).unwrap_or_default())
+            .field("last", &self.last.lock().ok().and_then(|last| last.clone()))
             .finish()
     }
 }
@@ -194,6 +351,10 @@ impl ConnectionProtocol for ConnectionHandler {
             }
             IncomingMessages::Error => {
                 let notice = crate::messages::Notice::from(message);
+                if notice.code == CLIENT_ID_IN_USE_CODE {
+                    // TWS closes the socket after rejecting the client id
+                    return Err(Error::Message(notice.code, notice.message));
+                }
                 if notice.is_warning() || notice.is_system_message() {
                     info!("{notice}");
                 } else {
@@ -292,6 +453,19 @@ mod tests {
         assert_eq!(info.managed_accounts, Some("DU123,DU456".to_string()));
     }
 
+    #[test]
+    fn test_parse_account_info_client_id_in_use() {
+        let handler = ConnectionHandler::default();
+        let mut message =
+            ResponseMessage::from("4\02\0-1\0326\0Unable to connect as the client id is already in use. Retry with a unique client id.\0");
+
+        let result = handler.parse_account_info(&mut message, None);
+        assert!(
+            matches!(result, Err(Error::Message(CLIENT_ID_IN_USE_CODE, ref text)) if text.starts_with("Unable to connect")),
+            "{result:?}"
+        );
+    }
+
     #[test]
     fn test_parse_account_info_callback_invoked_for_open_order() {
         let handler = ConnectionHandler::default();
@@ -545,4 +719,88 @@ mod tests {
         assert!(debug_str.contains("tcp_no_delay: true"));
         assert!(debug_str.contains("startup_callback: false"));
     }
+
+    #[test]
+    fn test_connection_options_heartbeat() {
+        let opts = ConnectionOptions::default().heartbeat(Duration::from_secs(30), Duration::from_secs(5));
+        assert_eq!(
+            opts.heartbeat,
+            Some(Heartbeat {
+                interval: Duration::from_secs(30),
+                timeout: Duration::from_secs(5),
+            })
+        );
+        assert!(format!("{opts:?}").contains("interval: 30s"));
+        assert!(ConnectionOptions::default().heartbeat.is_none());
+    }
+
+    #[test]
+    fn test_connection_options_metrics() {
+        struct NoMetrics;
+        impl Metrics for NoMetrics {}
+
+        let opts = ConnectionOptions::default().metrics(Arc::new(NoMetrics));
+        assert!(opts.metrics.is_some());
+        assert!(format!("{opts:?}").contains("metrics: true"));
+        assert!(ConnectionOptions::default().metrics.is_none());
+    }
+
+    #[test]
+    fn test_connection_options_order_journal() {
+        let opts = ConnectionOptions::default().order_journal(Arc::new(Mutex::new(OrderJournal::in_memory())));
+        assert!(opts.order_journal.is_some());
+        assert!(format!("{opts:?}").contains("order_journal: true"));
+        assert!(ConnectionOptions::default().order_journal.is_none());
+    }
+
+    #[test]
+    fn test_request_timeout() {
+        let opts = ConnectionOptions::default().request_timeout(Duration::from_secs(10));
+        assert_eq!(opts.request_timeout, Some(Duration::from_secs(10)));
+        assert!(format!("{opts:?}").contains("request_timeout: Some(10s)"));
+        assert!(ConnectionOptions::default().request_timeout.is_none());
+
+        let client_default = Some(Duration::from_secs(10));
+        assert_eq!(RequestOptions::default().resolve_timeout(client_default), client_default);
+        assert_eq!(
+            RequestOptions::default().timeout(Duration::from_secs(2)).resolve_timeout(client_default),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(RequestOptions::default().without_timeout().resolve_timeout(client_default), None);
+        assert_eq!(RequestOptions::default().resolve_timeout(None), None);
+    }
+
+    #[test]
+    fn test_event_hub_replays_last_event() {
+        let hub = ConnectionEventHub::default();
+        hub.publish(ConnectionEvent::Reconnected);
+        hub.publish(ConnectionEvent::GaveUp);
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        hub.subscribe(move |event| {
+            sink.lock().unwrap().push(event.clone());
+            true
+        });
+        hub.publish(ConnectionEvent::Reconnected);
+
+        assert_eq!(*received.lock().unwrap(), vec![ConnectionEvent::GaveUp, ConnectionEvent::Reconnected]);
+    }
+
+    #[test]
+    fn test_event_hub_drops_closed_listeners() {
+        let hub = ConnectionEventHub::default();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        hub.subscribe(move |event| sender.send(event.clone()).is_ok());
+        hub.subscribe(|_| false);
+        assert_eq!(hub.listeners.lock().unwrap().len(), 2);
+
+        hub.publish(ConnectionEvent::Reconnected);
+        assert_eq!(hub.listeners.lock().unwrap().len(), 1, "listener returning false is removed");
+
+        drop(receiver);
+        hub.publish(ConnectionEvent::GaveUp);
+        assert_eq!(hub.listeners.lock().unwrap().len(), 0, "listener with dropped receiver is removed");
+    }
 }
@@ -1,15 +1,20 @@
 //! Synchronous connection implementation
 
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
+use crossbeam::channel::{self, Receiver};
 use log::{debug, info};
 
-use super::common::{parse_connection_time, AccountInfo, ConnectionHandler, ConnectionOptions, ConnectionProtocol, StartupMessageCallback};
-use super::ConnectionMetadata;
+use super::common::{
+    parse_connection_time, AccountInfo, ConnectionEventHub, ConnectionHandler, ConnectionOptions, ConnectionProtocol, StartupMessageCallback,
+};
+use super::{ConnectionEvent, ConnectionMetadata};
 use crate::errors::Error;
 use crate::messages::{RequestMessage, ResponseMessage};
+use crate::orders::journal::OrderJournal;
 use crate::trace;
 use crate::transport::common::{FibonacciBackoff, MAX_RECONNECT_ATTEMPTS};
+use crate::transport::instrumentation::Instrumentation;
 use crate::transport::recorder::MessageRecorder;
 use crate::transport::sync::Stream;
 use crate::transport::sync::TcpSocket;
@@ -25,6 +30,9 @@ pub struct Connection<S: Stream> {
     pub(crate) max_retries: i32,
     pub(crate) recorder: MessageRecorder,
     pub(crate) connection_handler: ConnectionHandler,
+    pub(crate) events: ConnectionEventHub,
+    pub(crate) instrumentation: Instrumentation,
+    pub(crate) order_journal: Option<Arc<Mutex<OrderJournal>>>,
 }
 
 impl Connection<TcpSocket> {
@@ -34,7 +42,7 @@ impl Connection<TcpSocket> {
     /// before performing the TWS handshake.
     pub fn connect_with_options(address: &str, client_id: i32, options: ConnectionOptions) -> Result<Self, Error> {
         let socket = TcpSocket::connect(address, options.tcp_no_delay)?;
-        Self::init(socket, client_id, options.startup_callback.as_deref())
+        Self::init(socket, client_id, options)
     }
 }
 
@@ -42,7 +50,7 @@ impl<S: Stream> Connection<S> {
     /// Create a new connection
     #[allow(dead_code)]
     pub fn connect(socket: S, client_id: i32) -> Result<Self, Error> {
-        Self::init(socket, client_id, None)
+        Self::init(socket, client_id, ConnectionOptions::default())
     }
 
     /// Create a new connection with a callback for unsolicited messages
@@ -51,10 +59,10 @@ impl<S: Stream> Connection<S> {
     /// setup that are not part of the normal handshake (e.g., OpenOrder, OrderStatus).
     #[allow(dead_code)]
     pub fn connect_with_callback(socket: S, client_id: i32, startup_callback: Option<StartupMessageCallback>) -> Result<Self, Error> {
-        Self::init(socket, client_id, startup_callback.as_deref())
+        Self::init(socket, client_id, startup_callback.into())
     }
 
-    fn init(socket: S, client_id: i32, startup_callback: Option<&(dyn Fn(ResponseMessage) + Send + Sync)>) -> Result<Self, Error> {
+    fn init(socket: S, client_id: i32, options: ConnectionOptions) -> Result<Self, Error> {
         let connection = Self {
             client_id,
             socket,
@@ -65,9 +73,13 @@ impl<S: Stream> Connection<S> {
             max_retries: MAX_RECONNECT_ATTEMPTS,
             recorder: MessageRecorder::from_env(),
             connection_handler: ConnectionHandler::default(),
+            events: ConnectionEventHub::default(),
+            instrumentation: Instrumentation::new(&options),
+            order_journal: options.order_journal.clone(),
         };
 
-        connection.establish_connection(startup_callback)?;
+        connection.establish_connection(options.startup_callback.as_deref())?;
+        connection.events.publish(connection.connection_metadata().connected_event());
 
         Ok(connection)
     }
@@ -91,6 +103,10 @@ impl<S: Stream> Connection<S> {
         for i in 0..self.max_retries {
             let next_delay = backoff.next_delay();
             info!("next reconnection attempt in {next_delay:#?}");
+            self.events.publish(ConnectionEvent::Reconnecting {
+                attempt: i + 1,
+                delay: next_delay,
+            });
 
             self.socket.sleep(next_delay);
 
@@ -99,6 +115,7 @@ impl<S: Stream> Connection<S> {
                     info!("reconnected !!!");
                     // Reconnection doesn't use startup callback
                     self.establish_connection(None)?;
+                    self.events.publish(ConnectionEvent::Reconnected);
 
                     return Ok(());
                 }
@@ -132,6 +149,7 @@ impl<S: Stream> Connection<S> {
 
         let length_encoded = crate::messages::encode_length(&encoded);
         self.socket.write_all(&length_encoded)?;
+        self.instrumentation.message_sent(message);
         Ok(())
     }
 
@@ -149,6 +167,7 @@ impl<S: Stream> Connection<S> {
         let message = ResponseMessage::from(&raw_string).with_server_version(self.server_version());
 
         self.recorder.record_response(&message);
+        self.instrumentation.message_received(&message);
 
         Ok(message)
     }
@@ -239,6 +258,49 @@ impl<S: Stream> Connection<S> {
             max_retries: MAX_RECONNECT_ATTEMPTS,
             recorder: MessageRecorder::new(false, String::from("")),
             connection_handler: ConnectionHandler::default(),
+            events: ConnectionEventHub::default(),
+            instrumentation: Instrumentation::default(),
+            order_journal: None,
         }
     }
 }
+
+/// Blocking stream of [ConnectionEvent]s returned by `Client::connection_events()`.
+///
+/// The most recent event is delivered first, so a stream created after connecting starts with
+/// [ConnectionEvent::Connected]. The stream ends once the client shuts down.
+#[derive(Debug)]
+pub struct ConnectionEvents {
+    receiver: Receiver<ConnectionEvent>,
+}
+
+impl ConnectionEvents {
+    pub(crate) fn subscribe(hub: &ConnectionEventHub) -> Self {
+        let (sender, receiver) = channel::unbounded();
+        hub.subscribe(move |event| sender.send(event.clone()).is_ok());
+        Self { receiver }
+    }
+
+    /// Blocks until the next event. Returns `None` once the client has shut down.
+    pub fn next(&self) -> Option<ConnectionEvent> {
+        self.receiver.recv().ok()
+    }
+
+    /// Returns the next event if one is pending.
+    pub fn try_next(&self) -> Option<ConnectionEvent> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for the next event.
+    pub fn next_timeout(&self, timeout: std::time::Duration) -> Option<ConnectionEvent> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+}
+
+impl Iterator for ConnectionEvents {
+    type Item = ConnectionEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
@@ -4,10 +4,11 @@ use super::common::{decoders, encoders};
 use super::*;
 use crate::client::ClientRequestBuilders;
 use crate::common::request_helpers;
+use crate::common::request_helpers::CancelOnDrop;
 use crate::messages::{IncomingMessages, OutgoingMessages};
 use crate::protocol::{check_version, Features};
 use crate::subscriptions::{StreamDecoder, Subscription};
-use crate::{Client, Error};
+use crate::{Client, Error, RequestOptions};
 use log::{error, info};
 
 /// Requests contract information.
@@ -17,36 +18,67 @@ use log::{error, info};
 /// # Arguments
 /// * `client` - [Client] with an active connection to gateway.
 /// * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
-pub async fn contract_details(client: &Client, contract: &Contract) -> Result<Vec<ContractDetails>, Error> {
+/// * `options` - Timeout of the request. The request is cancelled at TWS when it expires or the future is dropped.
+pub async fn contract_details(client: &Client, contract: &Contract, options: RequestOptions) -> Result<Vec<ContractDetails>, Error> {
     verify_contract(client, contract).await?;
 
     let builder = client.request();
     let request_id = builder.request_id();
     let packet = encoders::encode_request_contract_data(client.server_version(), request_id, contract)?;
 
-    let mut responses = builder.send_raw(packet).await?;
-
-    let mut contract_details: Vec<ContractDetails> = Vec::default();
+    // Older servers cannot cancel contract data requests; a late answer is discarded.
+    let cancel_message = match check_version(client.server_version(), Features::CANCEL_CONTRACT_DATA) {
+        Ok(()) => Some(encoders::encode_cancel_contract_data(request_id)?),
+        Err(_) => None,
+    };
 
-    while let Some(response_result) = responses.next().await {
-        match response_result {
-            Ok(mut response) => {
-                log::debug!("response: {response:#?}");
-                match response.message_type() {
-                    IncomingMessages::ContractData => {
-                        let decoded = decoders::decode_contract_details(client.server_version(), &mut response)?;
-                        contract_details.push(decoded);
+    let mut responses = builder.send_raw(packet).await?;
+    let cancel = CancelOnDrop::new(client, cancel_message);
+
+    request_helpers::with_timeout(options.resolve_timeout(client.request_timeout()), async move {
+        let mut contract_details: Vec<ContractDetails> = Vec::default();
+
+        while let Some(response_result) = responses.next().await {
+            match response_result {
+                Ok(mut response) => {
+                    log::debug!("response: {response:#?}");
+                    match response.message_type() {
+                        IncomingMessages::ContractData => {
+                            let decoded = decoders::decode_contract_details(client.server_version(), &mut response)?;
+                            contract_details.push(decoded);
+                        }
+                        IncomingMessages::BondContractData => {
+                            let decoded = decoders::decode_bond_contract_details(client.server_version(), &mut response)?;
+                            contract_details.push(decoded);
+                        }
+                        IncomingMessages::ContractDataEnd => {
+                            cancel.disarm();
+                            return Ok(contract_details);
+                        }
+                        IncomingMessages::Error => {
+                            cancel.disarm();
+                            return Err(Error::from(response));
+                        }
+                        _ => {
+                            cancel.disarm();
+                            return Err(Error::UnexpectedResponse(response));
+                        }
                     }
-                    IncomingMessages::ContractDataEnd => return Ok(contract_details),
-                    IncomingMessages::Error => return Err(Error::from(response)),
-                    _ => return Err(Error::UnexpectedResponse(response)),
                 }
+                Err(e) => return Err(e),
             }
-            Err(e) => return Err(e),
         }
-    }
 
-    Err(Error::UnexpectedEndOfStream)
+        Err(Error::UnexpectedEndOfStream)
+    })
+    .await
+}
+
+/// Requests the details of the bond with a CUSIP or ISIN.
+///
+/// Bond terms are returned in [ContractDetails::bond].
+pub async fn bond_contract_details(client: &Client, identifier: BondIdentifier) -> Result<Vec<ContractDetails>, Error> {
+    contract_details(client, &Contract::bond(identifier), RequestOptions::default()).await
 }
 
 pub async fn verify_contract(client: &Client, contract: &Contract) -> Result<(), Error> {
@@ -74,15 +106,17 @@ pub async fn verify_contract(client: &Client, contract: &Contract) -> Result<(),
 /// # Arguments
 /// * `client` - [Client] with an active connection to gateway.
 /// * `pattern` - Either start of ticker symbol or (for larger strings) company name.
-pub async fn matching_symbols(client: &Client, pattern: &str) -> Result<Vec<ContractDescription>, Error> {
+/// * `options` - Timeout of the request. TWS has no cancel message for matching symbols.
+pub async fn matching_symbols(client: &Client, pattern: &str, options: RequestOptions) -> Result<Vec<ContractDescription>, Error> {
     check_version(client.server_version(), Features::REQ_MATCHING_SYMBOLS)?;
 
     let builder = client.request();
     let request_id = builder.request_id();
     let request = encoders::encode_request_matching_symbols(request_id, pattern)?;
     let mut subscription = builder.send_raw(request).await?;
+    let response = request_helpers::with_timeout(options.resolve_timeout(client.request_timeout()), async { Ok(subscription.next().await) }).await?;
 
-    match subscription.next().await {
+    match response {
         Some(Ok(mut message)) => {
             match message.message_type() {
                 IncomingMessages::SymbolSamples => {
@@ -110,13 +144,16 @@ pub async fn matching_symbols(client: &Client, pattern: &str) -> Result<Vec<Cont
 ///
 /// The market rule for an instrument on a particular exchange provides details about how the minimum price increment changes with price.
 /// A list of market rule ids can be obtained by invoking [request_contract_details] on a particular contract. The returned market rule ID list will provide the market rule ID for the instrument in the correspond valid exchange list in [ContractDetails].
-pub async fn market_rule(client: &Client, market_rule_id: i32) -> Result<MarketRule, Error> {
+///
+/// TWS has no cancel message for market rules, so an expired request is only abandoned.
+pub async fn market_rule(client: &Client, market_rule_id: i32, options: RequestOptions) -> Result<MarketRule, Error> {
     check_version(client.server_version(), Features::MARKET_RULES)?;
 
     let request = encoders::encode_request_market_rule(market_rule_id)?;
     let mut subscription = client.shared_request(OutgoingMessages::RequestMarketRule).send_raw(request).await?;
+    let response = request_helpers::with_timeout(options.resolve_timeout(client.request_timeout()), async { Ok(subscription.next().await) }).await?;
 
-    match subscription.next().await {
+    match response {
         Some(Ok(mut message)) => Ok(decoders::decode_market_rule(&mut message)?),
         Some(Err(e)) => Err(e),
         None => Err(Error::Simple("no market rule found".into())),
@@ -217,7 +254,7 @@ mod tests {
             });
 
             let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
-            let result = contract_details(&client, &test_case.contract).await;
+            let result = contract_details(&client, &test_case.contract, RequestOptions::default()).await;
 
             let request_messages = client.message_bus.request_messages();
             assert_eq!(
@@ -244,7 +281,7 @@ mod tests {
             });
 
             let client = Client::stubbed(message_bus, server_versions::BOND_ISSUERID);
-            let result = matching_symbols(&client, test_case.pattern).await;
+            let result = matching_symbols(&client, test_case.pattern, RequestOptions::default()).await;
 
             let request_messages = client.message_bus.request_messages();
             assert_eq!(
@@ -269,7 +306,7 @@ mod tests {
             });
 
             let client = Client::stubbed(message_bus, server_versions::MARKET_RULES);
-            let result = market_rule(&client, test_case.market_rule_id).await;
+            let result = market_rule(&client, test_case.market_rule_id, RequestOptions::default()).await;
 
             let request_messages = client.message_bus.request_messages();
             assert_eq!(
@@ -528,13 +565,12 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "reason: need sample messages"]
     async fn request_bond_contract_details() {
         let message_bus = Arc::new(MessageBusStub{
         request_messages: RwLock::new(vec![]),
         response_messages: vec![
-            // Format similar to request_stock_contract_details but with bond-specific fields
-            "10|9001|TLT|BOND|20420815|0||||USD|TLT|US Treasury Bond|BOND|12345|0.01|1000|SMART|NYSE|SMART|NYSE|1|0|US Treasury Bond|SMART||Government||US/Eastern|20221229:0400-20221229:2000;20221230:0400-20221230:2000|20221229:0930-20221229:1600;20221230:0930-20221230:1600|||1|CUSIP|912810TL8|1|||26|20420815|GOVT|1|1|2.25|0|20420815|20120815|20320815|CALL|100.0|1|Government Bond Notes|0.1|0.01|1|".to_string(),
+            // TWS answers bond requests with bond contract data rather than contract data
+            "18|9001|TLT|BOND|912810TL8|2.25|20420815|20120815|Aaa AA+|GOVT|FIXED|0|1|0|T 2 1/4 08/15/42|SMART|USD|US Treasury Bond|TLT|12345|0.01|ACTIVETIM,AD,LMT|SMART,NYSE|20320815|CALL|1|Government Bond Notes|US Treasury Bond||0|1|CUSIP|912810TL8|1|26|1|1|1|".to_string(),
             "52|1|9001||".to_string(),
         ]
     });
@@ -578,6 +614,7 @@ mod tests {
         assert_eq!(contracts[0].next_option_type, "CALL");
         assert_eq!(contracts[0].next_option_partial, true);
         assert_eq!(contracts[0].notes, "Government Bond Notes");
+        assert_eq!(contracts[0].bond.as_ref().map(|bond| bond.ratings.as_str()), Some("Aaa AA+"));
     }
 
     #[tokio::test]
@@ -0,0 +1,56 @@
+//! Asynchronous combo resolution.
+
+use super::{Combo, ComboBuilder};
+use crate::contracts::Contract;
+use crate::{Client, Error};
+
+pub(crate) async fn resolve_combo(client: &Client, combo: &ComboBuilder) -> Result<Combo, Error> {
+    combo.validate()?;
+
+    let mut contracts: Vec<Contract> = combo.legs.iter().map(|(contract, _, _)| contract.clone()).collect();
+    client.qualify_contracts(&mut contracts).await?;
+
+    combo.assemble(contracts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::common::test_tables::contract_details_test_cases;
+    use crate::contracts::{ContractDetails, Exchange};
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+    use std::sync::{Arc, RwLock};
+
+    #[tokio::test]
+    async fn test_resolve_combo() {
+        let case = contract_details_test_cases()
+            .into_iter()
+            .find(|case| case.name == "stock contract details")
+            .expect("test case");
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: case.response_messages.clone(),
+        });
+        let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+        let call = Contract::call("TSLA").strike(250.0).expires_on(2025, 1, 17).build();
+        let call_details = ContractDetails {
+            contract: Contract {
+                contract_id: 654321,
+                exchange: Exchange::from("SMART"),
+                ..call.clone()
+            },
+            ..Default::default()
+        };
+        client.contract_cache().insert(&call, &[call_details]);
+
+        let combo = Contract::combo().buy(case.contract.clone(), 100).sell(call, 1);
+        let resolved = client.resolve_combo(&combo).await.expect("combo resolution failed");
+
+        assert_eq!(message_bus.request_messages.read().unwrap().len(), 1);
+        assert_eq!(resolved.contract.symbol.as_str(), "TSLA");
+        let ids: Vec<i32> = resolved.contract.combo_legs.iter().map(|leg| leg.contract_id).collect();
+        assert_eq!(ids, vec![459200101, 654321]);
+    }
+}
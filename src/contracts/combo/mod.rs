@@ -0,0 +1,249 @@
+//! Combo (BAG) contracts built from leg descriptions rather than contract ids.
+//!
+//! [SpreadBuilder](super::SpreadBuilder) needs the contract id of every leg up front. A [ComboBuilder] takes the legs as
+//! ordinary contracts, for example from [Contract::call] or [Contract::futures], and the client
+//! resolves them with contract details requests before assembling the BAG contract.
+
+use super::{ComboLeg, Contract, Currency, Exchange, LegAction, SecurityType, Symbol};
+use crate::Error;
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
+
+#[cfg(feature = "async")]
+pub(crate) mod r#async;
+
+#[cfg(test)]
+mod tests;
+
+/// Builder of a combo whose legs are described symbolically.
+///
+/// # Examples
+///
+/// ```
+/// use ibapi::contracts::Contract;
+///
+/// let combo = Contract::combo()
+///     .buy(Contract::call("AAPL").strike(150.0).expires_on(2025, 1, 17).build(), 1)
+///     .sell(Contract::call("AAPL").strike(160.0).expires_on(2025, 1, 17).build(), 1);
+/// assert_eq!(combo.legs().len(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ComboBuilder {
+    legs: Vec<(Contract, LegAction, i32)>,
+    exchange: Option<Exchange>,
+}
+
+/// Leg of a resolved [Combo].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolvedLeg {
+    /// Qualified contract of the leg.
+    pub contract: Contract,
+    /// Side of the leg when the combo is bought.
+    pub action: LegAction,
+    /// Number of leg contracts per combo unit.
+    pub ratio: i32,
+}
+
+/// Combo whose legs were resolved to contract ids.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Combo {
+    /// BAG contract for orders and combo market data.
+    pub contract: Contract,
+    /// Legs in the order they were added.
+    pub legs: Vec<ResolvedLeg>,
+}
+
+/// Bid and ask of one leg, in the leg's price units.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LegQuote {
+    /// Best bid.
+    pub bid: f64,
+    /// Best ask.
+    pub ask: f64,
+}
+
+/// Price of one combo unit computed from its leg quotes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ComboPrice {
+    /// Natural price to sell the combo: legs bought are sold at their bid and legs sold are bought at their ask.
+    pub bid: f64,
+    /// Natural price to buy the combo: legs bought are paid at their ask and legs sold receive their bid.
+    pub ask: f64,
+    /// Midpoint of the leg quotes.
+    pub mid: f64,
+}
+
+impl ComboBuilder {
+    /// Creates a combo without legs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `ratio` units of `contract` on the side given by `action`.
+    pub fn leg(mut self, contract: Contract, action: LegAction, ratio: i32) -> Self {
+        self.legs.push((contract, action, ratio));
+        self
+    }
+
+    /// Adds a leg that is bought when the combo is bought.
+    pub fn buy(self, contract: Contract, ratio: i32) -> Self {
+        self.leg(contract, LegAction::Buy, ratio)
+    }
+
+    /// Adds a leg that is sold when the combo is bought.
+    pub fn sell(self, contract: Contract, ratio: i32) -> Self {
+        self.leg(contract, LegAction::Sell, ratio)
+    }
+
+    /// Routes the combo to `exchange`. By default the exchange shared by every leg is used, or SMART.
+    pub fn on_exchange(mut self, exchange: impl Into<Exchange>) -> Self {
+        self.exchange = Some(exchange.into());
+        self
+    }
+
+    /// Legs added so far.
+    pub fn legs(&self) -> &[(Contract, LegAction, i32)] {
+        &self.legs
+    }
+
+    // Checks what can be checked before any leg is resolved.
+    fn validate(&self) -> Result<(), Error> {
+        if self.legs.is_empty() {
+            return Err(Error::InvalidArgument("combo must have at least one leg".to_string()));
+        }
+        if let Some((contract, _, ratio)) = self.legs.iter().find(|(_, _, ratio)| *ratio <= 0) {
+            return Err(Error::InvalidArgument(format!(
+                "combo leg {} has ratio {ratio}, expected a positive ratio",
+                contract.symbol
+            )));
+        }
+        if self.legs.iter().any(|(contract, _, _)| contract.security_type == SecurityType::Spread) {
+            return Err(Error::InvalidArgument("combo legs cannot be combos".to_string()));
+        }
+        Ok(())
+    }
+
+    // Builds the combo from the qualified leg contracts, in leg order.
+    fn assemble(&self, contracts: Vec<Contract>) -> Result<Combo, Error> {
+        let symbol = shared(&contracts, "underlyings", |contract| contract.symbol.as_str().to_ascii_uppercase())?;
+        let currency = shared(&contracts, "currencies", |contract| contract.currency.as_str().to_string())?;
+        let exchange = match &self.exchange {
+            Some(exchange) => exchange.clone(),
+            None => match shared(&contracts, "exchanges", |contract| contract.exchange.as_str().to_string()) {
+                Ok(exchange) if !exchange.is_empty() => Exchange::from(exchange.as_str()),
+                _ => Exchange::from("SMART"),
+            },
+        };
+
+        let legs: Vec<ResolvedLeg> = contracts
+            .into_iter()
+            .zip(&self.legs)
+            .map(|(contract, (_, action, ratio))| ResolvedLeg {
+                contract,
+                action: *action,
+                ratio: *ratio,
+            })
+            .collect();
+
+        let contract = Contract {
+            symbol: Symbol::from(symbol.as_str()),
+            security_type: SecurityType::Spread,
+            exchange,
+            currency: Currency::from(currency.as_str()),
+            combo_legs: legs
+                .iter()
+                .map(|leg| ComboLeg {
+                    contract_id: leg.contract.contract_id,
+                    ratio: leg.ratio,
+                    action: leg.action.to_string(),
+                    exchange: leg.contract.exchange.as_str().to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        Ok(Combo { contract, legs })
+    }
+}
+
+// The value of `field` common to every contract, or an error naming the differing values.
+fn shared(contracts: &[Contract], what: &str, field: impl Fn(&Contract) -> String) -> Result<String, Error> {
+    let mut values: Vec<String> = Vec::new();
+    for contract in contracts {
+        let value = field(contract);
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+    match values.len() {
+        1 => Ok(values.remove(0)),
+        _ => Err(Error::InvalidArgument(format!("combo legs have different {what}: {}", values.join(", ")))),
+    }
+}
+
+impl Combo {
+    /// Price of one combo unit given the quote of each leg, in leg order.
+    pub fn price(&self, quotes: &[LegQuote]) -> Result<ComboPrice, Error> {
+        ComboPrice::from_legs(&self.contract.combo_legs, quotes)
+    }
+}
+
+impl ComboPrice {
+    /// Prices the combo with `legs` given the quote of each leg, in leg order.
+    ///
+    /// Works for any BAG contract, including those built with [SpreadBuilder](super::SpreadBuilder). Quotes with a
+    /// negative bid or ask, which TWS sends when there is no market, are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ibapi::contracts::{ComboPrice, Contract, LegQuote};
+    ///
+    /// let vertical = Contract::spread().vertical(1001, 1002).build().unwrap();
+    /// let quotes = [LegQuote { bid: 5.0, ask: 5.2 }, LegQuote { bid: 2.0, ask: 2.1 }];
+    ///
+    /// let price = ComboPrice::from_legs(&vertical.combo_legs, &quotes).unwrap();
+    /// assert!((price.ask - 3.2).abs() < 1e-9);
+    /// assert!((price.bid - 2.9).abs() < 1e-9);
+    /// ```
+    pub fn from_legs(legs: &[ComboLeg], quotes: &[LegQuote]) -> Result<ComboPrice, Error> {
+        if legs.len() != quotes.len() {
+            return Err(Error::InvalidArgument(format!(
+                "{} quotes given for {} combo legs",
+                quotes.len(),
+                legs.len()
+            )));
+        }
+
+        let mut price = ComboPrice::default();
+        for (leg, quote) in legs.iter().zip(quotes) {
+            if quote.bid < 0.0 || quote.ask < 0.0 {
+                return Err(Error::InvalidArgument(format!("no market for combo leg {}", leg.contract_id)));
+            }
+
+            let ratio = leg.ratio as f64;
+            let mid = (quote.bid + quote.ask) / 2.0;
+            match leg.action.as_str() {
+                "BUY" => {
+                    price.bid += ratio * quote.bid;
+                    price.ask += ratio * quote.ask;
+                    price.mid += ratio * mid;
+                }
+                "SELL" => {
+                    price.bid -= ratio * quote.ask;
+                    price.ask -= ratio * quote.bid;
+                    price.mid -= ratio * mid;
+                }
+                action => {
+                    return Err(Error::InvalidArgument(format!(
+                        "combo leg {} has unknown action {action}",
+                        leg.contract_id
+                    )));
+                }
+            }
+        }
+        Ok(price)
+    }
+}
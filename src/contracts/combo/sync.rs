@@ -0,0 +1,74 @@
+//! Blocking combo resolution.
+
+use super::{Combo, ComboBuilder};
+use crate::client::sync::Client;
+use crate::contracts::Contract;
+use crate::Error;
+
+pub(crate) fn resolve_combo(client: &Client, combo: &ComboBuilder) -> Result<Combo, Error> {
+    combo.validate()?;
+
+    let mut contracts: Vec<Contract> = combo.legs.iter().map(|(contract, _, _)| contract.clone()).collect();
+    client.qualify_contracts(&mut contracts)?;
+
+    combo.assemble(contracts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::common::test_tables::contract_details_test_cases;
+    use crate::contracts::{ContractDetails, Exchange, SecurityType};
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+    use std::sync::{Arc, RwLock};
+
+    #[test]
+    fn test_resolve_combo() {
+        let case = contract_details_test_cases()
+            .into_iter()
+            .find(|case| case.name == "stock contract details")
+            .expect("test case");
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: case.response_messages.clone(),
+        });
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+        // Covered call: the stock leg is requested, the option leg comes from the cache.
+        let call = Contract::call("TSLA").strike(250.0).expires_on(2025, 1, 17).build();
+        let call_details = ContractDetails {
+            contract: Contract {
+                contract_id: 654321,
+                exchange: Exchange::from("SMART"),
+                ..call.clone()
+            },
+            ..Default::default()
+        };
+        client.contract_cache().insert(&call, &[call_details]);
+
+        let combo = Contract::combo().buy(case.contract.clone(), 100).sell(call, 1);
+        let resolved = client.resolve_combo(&combo).expect("combo resolution failed");
+
+        assert_eq!(client.message_bus.request_messages().len(), 1);
+        assert_eq!(resolved.contract.security_type, SecurityType::Spread);
+        assert_eq!(resolved.contract.symbol.as_str(), "TSLA");
+        let legs: Vec<(i32, i32, &str)> = resolved
+            .contract
+            .combo_legs
+            .iter()
+            .map(|leg| (leg.contract_id, leg.ratio, leg.action.as_str()))
+            .collect();
+        assert_eq!(legs, vec![(459200101, 100, "BUY"), (654321, 1, "SELL")]);
+    }
+
+    #[test]
+    fn test_resolve_combo_without_legs() {
+        let message_bus = Arc::new(MessageBusStub::default());
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+        let error = client.resolve_combo(&Contract::combo()).unwrap_err();
+        assert!(matches!(error, Error::InvalidArgument(_)));
+        assert!(client.message_bus.request_messages().is_empty());
+    }
+}
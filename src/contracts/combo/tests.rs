@@ -0,0 +1,150 @@
+use super::*;
+
+fn qualified(contract: Contract, contract_id: i32, exchange: &str) -> Contract {
+    Contract {
+        contract_id,
+        exchange: Exchange::from(exchange),
+        ..contract
+    }
+}
+
+fn vertical() -> ComboBuilder {
+    ComboBuilder::new()
+        .buy(Contract::call("AAPL").strike(150.0).expires_on(2025, 1, 17).build(), 1)
+        .sell(Contract::call("AAPL").strike(160.0).expires_on(2025, 1, 17).build(), 1)
+}
+
+fn resolved_vertical() -> Result<Combo, Error> {
+    let combo = vertical();
+    let contracts = combo.legs().iter().map(|(contract, _, _)| contract.clone()).collect::<Vec<_>>();
+    let contracts = vec![
+        qualified(contracts[0].clone(), 101, "SMART"),
+        qualified(contracts[1].clone(), 102, "SMART"),
+    ];
+    combo.assemble(contracts)
+}
+
+#[test]
+fn test_validate() {
+    assert!(vertical().validate().is_ok());
+    assert!(matches!(ComboBuilder::new().validate(), Err(Error::InvalidArgument(_))));
+
+    let zero_ratio = ComboBuilder::new().buy(Contract::stock("AAPL").build(), 0);
+    assert!(matches!(zero_ratio.validate(), Err(Error::InvalidArgument(_))));
+
+    let nested = ComboBuilder::new().buy(Contract::spread().vertical(1, 2).build().unwrap(), 1);
+    assert!(matches!(nested.validate(), Err(Error::InvalidArgument(_))));
+}
+
+#[test]
+fn test_assemble() {
+    let combo = resolved_vertical().expect("valid combo");
+
+    assert_eq!(combo.contract.security_type, SecurityType::Spread);
+    assert_eq!(combo.contract.symbol.as_str(), "AAPL");
+    assert_eq!(combo.contract.currency.as_str(), "USD");
+    assert_eq!(combo.contract.exchange.as_str(), "SMART");
+    assert_eq!(combo.contract.combo_legs.len(), 2);
+    assert_eq!(combo.contract.combo_legs[0].contract_id, 101);
+    assert_eq!(combo.contract.combo_legs[0].action, "BUY");
+    assert_eq!(combo.contract.combo_legs[1].contract_id, 102);
+    assert_eq!(combo.contract.combo_legs[1].action, "SELL");
+    assert_eq!(combo.legs[1].action, LegAction::Sell);
+    assert_eq!(combo.legs[1].contract.strike, 160.0);
+}
+
+#[test]
+fn test_assemble_exchange() {
+    let calendar = ComboBuilder::new()
+        .sell(Contract::futures("ES").front_month().build(), 1)
+        .buy(Contract::futures("ES").next_quarter().build(), 1);
+    let legs: Vec<Contract> = calendar.legs().iter().map(|(contract, _, _)| contract.clone()).collect();
+
+    let same = calendar
+        .assemble(vec![qualified(legs[0].clone(), 1, "CME"), qualified(legs[1].clone(), 2, "CME")])
+        .unwrap();
+    assert_eq!(same.contract.exchange.as_str(), "CME", "exchange shared by the legs");
+
+    let mixed = calendar
+        .assemble(vec![qualified(legs[0].clone(), 1, "CME"), qualified(legs[1].clone(), 2, "GLOBEX")])
+        .unwrap();
+    assert_eq!(mixed.contract.exchange.as_str(), "SMART");
+    assert_eq!(mixed.contract.combo_legs[1].exchange, "GLOBEX");
+
+    let routed = calendar
+        .clone()
+        .on_exchange("CME")
+        .assemble(vec![qualified(legs[0].clone(), 1, "CME"), qualified(legs[1].clone(), 2, "GLOBEX")])
+        .unwrap();
+    assert_eq!(routed.contract.exchange.as_str(), "CME");
+}
+
+#[test]
+fn test_assemble_mismatched_legs() {
+    let combo = ComboBuilder::new()
+        .buy(Contract::stock("AAPL").build(), 1)
+        .sell(Contract::stock("MSFT").build(), 1);
+    let legs = vec![
+        qualified(Contract::stock("AAPL").build(), 1, "SMART"),
+        qualified(Contract::stock("MSFT").build(), 2, "SMART"),
+    ];
+    let error = combo.assemble(legs).unwrap_err();
+    assert!(matches!(error, Error::InvalidArgument(ref message) if message == "combo legs have different underlyings: AAPL, MSFT"));
+
+    let combo = ComboBuilder::new()
+        .buy(Contract::stock("SHEL").build(), 1)
+        .sell(Contract::stock("SHEL").in_currency("GBP").build(), 1);
+    let legs = combo.legs().iter().map(|(contract, _, _)| contract.clone()).collect();
+    let error = combo.assemble(legs).unwrap_err();
+    assert!(matches!(error, Error::InvalidArgument(ref message) if message == "combo legs have different currencies: USD, GBP"));
+}
+
+#[test]
+fn test_price() {
+    let combo = resolved_vertical().unwrap();
+    let quotes = [LegQuote { bid: 5.0, ask: 5.2 }, LegQuote { bid: 2.0, ask: 2.1 }];
+
+    let price = combo.price(&quotes).unwrap();
+    assert!((price.ask - 3.2).abs() < 1e-9, "pay the long ask, receive the short bid");
+    assert!((price.bid - 2.9).abs() < 1e-9, "receive the long bid, pay the short ask");
+    assert!((price.mid - 3.05).abs() < 1e-9);
+
+    assert!(matches!(combo.price(&quotes[..1]), Err(Error::InvalidArgument(_))));
+    let no_market = [LegQuote { bid: -1.0, ask: -1.0 }, quotes[1]];
+    assert!(matches!(combo.price(&no_market), Err(Error::InvalidArgument(_))));
+}
+
+#[test]
+fn test_price_with_ratios() {
+    // Butterfly: buy 1 lower, sell 2 middle, buy 1 upper.
+    let legs = [
+        ComboLeg {
+            contract_id: 1,
+            ratio: 1,
+            action: "BUY".to_string(),
+            ..Default::default()
+        },
+        ComboLeg {
+            contract_id: 2,
+            ratio: 2,
+            action: "SELL".to_string(),
+            ..Default::default()
+        },
+        ComboLeg {
+            contract_id: 3,
+            ratio: 1,
+            action: "BUY".to_string(),
+            ..Default::default()
+        },
+    ];
+    let quotes = [
+        LegQuote { bid: 10.0, ask: 10.4 },
+        LegQuote { bid: 6.0, ask: 6.2 },
+        LegQuote { bid: 3.0, ask: 3.2 },
+    ];
+
+    let price = ComboPrice::from_legs(&legs, &quotes).unwrap();
+    assert!((price.ask - 1.6).abs() < 1e-9);
+    assert!((price.bid - 0.6).abs() < 1e-9);
+    assert!((price.mid - 1.1).abs() < 1e-9);
+}
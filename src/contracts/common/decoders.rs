@@ -9,8 +9,8 @@ use crate::{
 };
 
 use super::super::{
-    Contract, ContractDescription, ContractDetails, FundAssetType, FundDistributionPolicyIndicator, IneligibilityReason, MarketRule, OptionChain,
-    OptionComputation, PriceIncrement, TagValue,
+    BondDetails, Contract, ContractDescription, ContractDetails, FundAssetType, FundDistributionPolicyIndicator, IneligibilityReason, MarketRule,
+    OptionChain, OptionComputation, PriceIncrement, TagValue,
 };
 
 pub(in crate::contracts) fn decode_contract_details(server_version: i32, message: &mut ResponseMessage) -> Result<ContractDetails, Error> {
@@ -141,6 +141,111 @@ pub(in crate::contracts) fn decode_contract_details(server_version: i32, message
     Ok(contract)
 }
 
+pub(in crate::contracts) fn decode_bond_contract_details(server_version: i32, message: &mut ResponseMessage) -> Result<ContractDetails, Error> {
+    message.skip(); // message type
+
+    let mut message_version = 6;
+    if server_version < server_versions::SIZE_RULES {
+        message_version = message.next_int()?;
+    }
+
+    if message_version >= 3 {
+        // request id
+        message.skip();
+    }
+
+    let mut contract = ContractDetails::default();
+
+    contract.contract.symbol = Symbol::from(message.next_string()?);
+    contract.contract.security_type = SecurityType::from(&message.next_string()?);
+    contract.cusip = message.next_string()?;
+    contract.coupon = message.next_double()?;
+    read_last_trade_date(&mut contract, &message.next_string()?, true)?;
+    contract.issue_date = message.next_string()?;
+    contract.ratings = message.next_string()?;
+    contract.bond_type = message.next_string()?;
+    contract.coupon_type = message.next_string()?;
+    contract.convertible = message.next_bool()?;
+    contract.callable = message.next_bool()?;
+    contract.putable = message.next_bool()?;
+    contract.desc_append = message.next_string()?;
+    contract.contract.exchange = Exchange::from(message.next_string()?);
+    contract.contract.currency = Currency::from(message.next_string()?);
+    contract.market_name = message.next_string()?;
+    contract.contract.trading_class = message.next_string()?;
+    contract.contract.contract_id = message.next_int()?;
+    contract.min_tick = message.next_double()?;
+    if (server_versions::MD_SIZE_MULTIPLIER..server_versions::SIZE_RULES).contains(&server_version) {
+        message.next_int()?; // mdSizeMultiplier no longer used
+    }
+    contract.order_types = split_to_vec(&message.next_string()?);
+    contract.valid_exchanges = split_to_vec(&message.next_string()?);
+    if message_version >= 2 {
+        contract.next_option_date = message.next_string()?;
+        contract.next_option_type = message.next_string()?;
+        contract.next_option_partial = message.next_bool()?;
+        contract.notes = message.next_string()?;
+    }
+    if message_version >= 4 {
+        contract.long_name = message.next_string()?;
+    }
+    if server_version >= server_versions::BOND_TRADING_HOURS {
+        contract.time_zone_id = message.next_string()?;
+        contract.trading_hours = split_hours(&message.next_string()?);
+        contract.liquid_hours = split_hours(&message.next_string()?);
+    }
+    if message_version >= 6 {
+        contract.ev_rule = message.next_string()?;
+        contract.ev_multiplier = message.next_double()?;
+    }
+    if message_version >= 5 {
+        let sec_id_list_count = message.next_int()?;
+        for _ in 0..sec_id_list_count {
+            let tag = message.next_string()?;
+            let value = message.next_string()?;
+            contract.sec_id_list.push(TagValue { tag, value });
+        }
+    }
+    if server_version >= server_versions::AGG_GROUP {
+        contract.agg_group = message.next_int()?;
+    }
+    if server_version >= server_versions::MARKET_RULES {
+        contract.market_rule_ids = split_to_vec(&message.next_string()?);
+    }
+    if server_version >= server_versions::SIZE_RULES {
+        contract.min_size = message.next_double()?;
+        contract.size_increment = message.next_double()?;
+        contract.suggested_size_increment = message.next_double()?;
+    }
+
+    // The maturity is also the last day the bond trades.
+    contract.contract.last_trade_date_or_contract_month = contract.maturity.clone();
+    contract.bond = Some(BondDetails {
+        cusip: contract.cusip.clone(),
+        coupon: contract.coupon,
+        coupon_type: contract.coupon_type.clone(),
+        maturity: parse_bond_date(&contract.maturity),
+        issue_date: parse_bond_date(&contract.issue_date),
+        ratings: contract.ratings.clone(),
+        bond_type: contract.bond_type.clone(),
+        callable: contract.callable,
+        putable: contract.putable,
+        convertible: contract.convertible,
+        next_option_date: parse_bond_date(&contract.next_option_date),
+        next_option_type: contract.next_option_type.clone(),
+        next_option_partial: contract.next_option_partial,
+        description: contract.desc_append.clone(),
+        notes: contract.notes.clone(),
+    });
+
+    Ok(contract)
+}
+
+// Bond dates are sent as YYYYMMDD; anything else is left to the raw string fields.
+fn parse_bond_date(date: &str) -> Option<Date> {
+    Date::parse(date, format_description!("[year][month][day]")).ok()
+}
+
 fn split_hours(hours: &str) -> Vec<String> {
     hours.split(";").map(|s| s.to_string()).collect()
 }
@@ -319,6 +424,7 @@ pub(in crate::contracts) fn decode_option_chain(message: &mut ResponseMessage) -
 #[cfg(test)]
 mod tests {
     use super::*;
+    use time::macros::date;
 
     #[test]
     fn test_decode_market_rule() {
@@ -747,5 +853,72 @@ mod tests {
         assert_eq!(cd.contract.security_type, SecurityType::Stock);
         assert_eq!(cd.fund_name, "");
         assert!(cd.ineligibility_reasons.is_empty());
+        assert!(cd.bond.is_none());
+    }
+
+    #[test]
+    fn test_decode_bond_contract_details() {
+        let mut message = ResponseMessage::from_simple(
+            "18|9000|US-T|BOND|912810TL8|2.25|20420815|20120815|Aaa AA+|GOVT|FIXED|0|1|0|T 2 1/4 08/15/42|SMART|USD|US-T|US-T|12345|0.0001|\
+            LMT,MKT|SMART,BONDDESK|20320815|CALL|1|Government Bond Notes|United States Treasury|\
+            |0|1|CUSIP|912810TL8|1|26|1000|1000|1000|",
+        );
+
+        let cd = decode_bond_contract_details(server_versions::SIZE_RULES, &mut message).expect("error decoding bond");
+
+        assert_eq!(cd.contract.symbol, Symbol::from("US-T"));
+        assert_eq!(cd.contract.security_type, SecurityType::Bond);
+        assert_eq!(cd.contract.contract_id, 12345);
+        assert_eq!(cd.contract.exchange, Exchange::from("SMART"));
+        assert_eq!(cd.contract.currency, Currency::from("USD"));
+        assert_eq!(cd.contract.last_trade_date_or_contract_month, "20420815");
+        assert_eq!(cd.long_name, "United States Treasury");
+        assert_eq!(cd.valid_exchanges, vec!["SMART", "BONDDESK"]);
+        assert_eq!(cd.sec_id_list[0].value, "912810TL8");
+        assert_eq!(cd.market_rule_ids, vec!["26"]);
+        assert_eq!(cd.min_size, 1000.0);
+        assert_eq!(cd.maturity, "20420815");
+        assert_eq!(cd.cusip, "912810TL8");
+
+        let bond = cd.bond.expect("bond details");
+        assert_eq!(bond.cusip, "912810TL8");
+        assert_eq!(bond.coupon, 2.25);
+        assert_eq!(bond.coupon_type, "FIXED");
+        assert_eq!(bond.maturity, Some(date!(2042 - 08 - 15)));
+        assert_eq!(bond.issue_date, Some(date!(2012 - 08 - 15)));
+        assert_eq!(bond.ratings, "Aaa AA+");
+        assert_eq!(bond.bond_type, "GOVT");
+        assert!(!bond.convertible);
+        assert!(bond.callable);
+        assert!(!bond.putable);
+        assert_eq!(bond.next_option_date, Some(date!(2032 - 08 - 15)));
+        assert_eq!(bond.next_option_type, "CALL");
+        assert!(bond.next_option_partial);
+        assert_eq!(bond.description, "T 2 1/4 08/15/42");
+        assert_eq!(bond.notes, "Government Bond Notes");
+    }
+
+    #[test]
+    fn test_decode_bond_contract_details_with_trading_hours() {
+        let mut message = ResponseMessage::from_simple(
+            "18|9000|IBM|BOND||4.0|20300601 16:00 US/Eastern|n/a||||0|0|0||SMART|USD|IBM|IBM|987|0.001|\
+            LMT|SMART|||||International Business Machines|US/Eastern|20250102:0800-20250102:1700|20250102:0930-20250102:1600|\
+            |0|0|1|26|2000|1000|1000|",
+        );
+
+        let cd = decode_bond_contract_details(server_versions::BOND_TRADING_HOURS, &mut message).expect("error decoding bond");
+
+        assert_eq!(cd.contract.contract_id, 987);
+        assert_eq!(cd.maturity, "20300601");
+        assert_eq!(cd.last_trade_time, "16:00");
+        assert_eq!(cd.time_zone_id, "US/Eastern");
+        assert_eq!(cd.trading_hours, vec!["20250102:0800-20250102:1700"]);
+        assert_eq!(cd.liquid_hours, vec!["20250102:0930-20250102:1600"]);
+        assert_eq!(cd.min_size, 2000.0);
+
+        let bond = cd.bond.expect("bond details");
+        assert_eq!(bond.maturity, Some(date!(2030 - 06 - 01)));
+        assert_eq!(bond.issue_date, None, "unparseable dates are left out");
+        assert_eq!(bond.next_option_date, None);
     }
 }
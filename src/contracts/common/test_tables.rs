@@ -177,6 +177,25 @@ pub fn contract_details_test_cases() -> Vec<ContractDetailsTestCase> {
                 assert_eq!(contracts[0].market_name, "US Treasury Bond");
             }),
         },
+        ContractDetailsTestCase {
+            name: "bond contract data by CUSIP",
+            contract: Contract::bond_cusip("912810TL8"),
+            response_messages: vec![
+                "18\09000\0US-T\0BOND\0912810TL8\02.25\020420815\020120815\0Aaa AA+\0GOVT\0FIXED\00\01\00\0T 2 1/4 08/15/42\0SMART\0USD\0US-T\0US-T\012345\00.0001\0LMT,MKT\0SMART,BONDDESK\020320815\0CALL\01\0Government Bond Notes\0United States Treasury\0\00\01\0CUSIP\0912810TL8\01\026\01000\01000\01000\0".to_string(),
+                "52\01\09000\0\0".to_string(),
+            ],
+            expected_request: "9|8|9000|0|912810TL8|BOND||0|||SMART||USD|||0|CUSIP|912810TL8|",
+            expected_count: 1,
+            validations: Box::new(|contracts| {
+                assert_eq!(contracts[0].contract.security_type, SecurityType::Bond);
+                assert_eq!(contracts[0].contract.contract_id, 12345);
+                let bond = contracts[0].bond.as_ref().expect("bond details");
+                assert_eq!(bond.cusip, "912810TL8");
+                assert_eq!(bond.coupon, 2.25);
+                assert_eq!(bond.maturity, Some(time::macros::date!(2042 - 08 - 15)));
+                assert!(bond.callable);
+            }),
+        },
         ContractDetailsTestCase {
             name: "stock contract details - multiple exchanges",
             contract: Contract {
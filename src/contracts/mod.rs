@@ -39,6 +39,15 @@ mod r#async;
 /// Tick type constants used in option computations and market data.
 pub mod tick_types;
 
+/// Local option analytics.
+pub mod options;
+
+mod qualify;
+pub use qualify::{ContractCache, DEFAULT_CONTRACT_CACHE_TTL};
+
+mod combo;
+pub use combo::{Combo, ComboBuilder, ComboPrice, LegQuote, ResolvedLeg};
+
 // Models
 
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -476,6 +485,23 @@ impl Contract {
         SpreadBuilder::new()
     }
 
+    /// Creates a combo builder whose legs are contracts rather than contract ids.
+    ///
+    /// The legs are resolved by `Client::resolve_combo`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ibapi::contracts::Contract;
+    ///
+    /// let calendar = Contract::combo()
+    ///     .sell(Contract::futures("ES").front_month().build(), 1)
+    ///     .buy(Contract::futures("ES").next_quarter().build(), 1);
+    /// ```
+    pub fn combo() -> ComboBuilder {
+        ComboBuilder::new()
+    }
+
     /// Creates a news contract from the specified provider code.
     ///
     /// # Examples
@@ -753,6 +779,45 @@ pub struct ContractDetails {
 
     /// Ineligibility reasons for the contract.
     pub ineligibility_reasons: Vec<IneligibilityReason>,
+
+    /// Bond terms, decoded from bond contract data. `None` for other security types.
+    pub bond: Option<BondDetails>,
+}
+
+/// Terms of a bond, returned with its [ContractDetails].
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BondDetails {
+    /// The nine-character CUSIP. Requires a CUSIP market data subscription.
+    pub cusip: String,
+    /// Annual interest rate, in percent of the face value.
+    pub coupon: f64,
+    /// Type of coupon, such as "FIXED" or "ZERO".
+    pub coupon_type: String,
+    /// Date on which the issuer repays the face value.
+    pub maturity: Option<time::Date>,
+    /// Date the bond was issued.
+    pub issue_date: Option<time::Date>,
+    /// Credit ratings of the issuer, from Moody's and S&P.
+    pub ratings: String,
+    /// Type of bond, such as "CORP" or "GOVT".
+    pub bond_type: String,
+    /// True if the issuer can redeem the bond before maturity.
+    pub callable: bool,
+    /// True if the holder can sell the bond back to the issuer before maturity.
+    pub putable: bool,
+    /// True if the bond can be converted to stock.
+    pub convertible: bool,
+    /// Date of the next embedded option, for callable and putable bonds.
+    pub next_option_date: Option<time::Date>,
+    /// Type of the next embedded option, such as "CALL".
+    pub next_option_type: String,
+    /// True if the next embedded option applies to part of the issue only.
+    pub next_option_partial: bool,
+    /// Further description of the bond.
+    pub description: String,
+    /// Notes recorded for the bond in IB's database.
+    pub notes: String,
 }
 
 /// Fund distribution policy indicator.
@@ -930,20 +995,29 @@ pub struct PriceIncrement {
 #[cfg(feature = "sync")]
 /// Blocking contract lookup helpers backed by the synchronous transport.
 pub mod blocking {
+    pub(crate) use super::combo::sync::resolve_combo;
+    pub(crate) use super::qualify::sync::qualify_contracts;
     pub(crate) use super::sync::{
-        calculate_implied_volatility, calculate_option_price, cancel_contract_details, contract_details, market_rule, matching_symbols, option_chain,
+        bond_contract_details, calculate_implied_volatility, calculate_option_price, cancel_contract_details, contract_details, market_rule,
+        matching_symbols, option_chain,
     };
 }
 
 #[cfg(all(feature = "sync", not(feature = "async")))]
 #[allow(unused_imports)]
 pub(crate) use sync::{
-    calculate_implied_volatility, calculate_option_price, cancel_contract_details, contract_details, market_rule, matching_symbols, option_chain,
+    bond_contract_details, calculate_implied_volatility, calculate_option_price, cancel_contract_details, contract_details, market_rule,
+    matching_symbols, option_chain,
 };
 
+#[cfg(feature = "async")]
+pub(crate) use combo::r#async::resolve_combo;
+#[cfg(feature = "async")]
+pub(crate) use qualify::r#async::qualify_contracts;
 #[cfg(feature = "async")]
 pub(crate) use r#async::{
-    calculate_implied_volatility, calculate_option_price, cancel_contract_details, contract_details, market_rule, matching_symbols, option_chain,
+    bond_contract_details, calculate_implied_volatility, calculate_option_price, cancel_contract_details, contract_details, market_rule,
+    matching_symbols, option_chain,
 };
 
 // Public function for decoding option computation (used by market_data module)
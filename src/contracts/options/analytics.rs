@@ -0,0 +1,365 @@
+//! Local option pricing, greeks and implied volatility.
+//!
+//! [Client::calculate_option_price](crate::Client::calculate_option_price) and
+//! [Client::calculate_implied_volatility](crate::Client::calculate_implied_volatility) ask TWS
+//! to run its model for one contract at a time. The functions here evaluate Black-Scholes
+//! (with a continuous dividend yield) or Black-76 (options on futures) in-process, so whole
+//! chains can be priced without pacing limits.
+//!
+//! Results are returned as [OptionComputation] using the same conventions as IB's option
+//! computation ticks:
+//!
+//! * `vega` is the price change for a one point (1%) move in volatility.
+//! * `theta` is the price change for one calendar day.
+//! * `implied_volatility` is annualized, e.g. `0.25` for 25%.
+//!
+//! [rho] is not part of [OptionComputation] and is available separately.
+//!
+//! # Examples
+//!
+//! ```
+//! use ibapi::contracts::options::analytics::{self, OptionInputs};
+//! use ibapi::contracts::OptionRight;
+//!
+//! // 100 strike call, 3 months out, spot at 105.
+//! let inputs = OptionInputs::new(OptionRight::Call, 105.0, 100.0, 0.25)
+//!     .risk_free_rate(0.05)
+//!     .dividend_yield(0.01);
+//!
+//! let computation = analytics::price(&inputs, 0.20).expect("invalid inputs");
+//! let option_price = computation.option_price.unwrap();
+//!
+//! let implied = analytics::implied_volatility(&inputs, option_price).expect("no solution");
+//! assert!((implied.implied_volatility.unwrap() - 0.20).abs() < 1e-6);
+//! ```
+
+use std::f64::consts::PI;
+
+use time::OffsetDateTime;
+
+use super::super::tick_types::TickType;
+use super::super::{OptionComputation, OptionRight};
+use crate::Error;
+
+// Days per year used to annualize time to expiry and to express theta per day.
+const DAYS_PER_YEAR: f64 = 365.0;
+
+// Search range and tolerances for the implied volatility solver.
+const MIN_VOLATILITY: f64 = 1e-6;
+const MAX_VOLATILITY: f64 = 10.0;
+const PRICE_TOLERANCE: f64 = 1e-10;
+const VOLATILITY_TOLERANCE: f64 = 1e-12;
+const MAX_ITERATIONS: usize = 100;
+
+/// Model used to value an option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PricingModel {
+    /// Black-Scholes-Merton for options on spot, with a continuous dividend yield.
+    #[default]
+    BlackScholes,
+    /// Black-76 for options on futures. The underlying price is the futures price and the dividend yield is ignored.
+    Black76,
+}
+
+/// Market inputs for valuing a single European option.
+///
+/// Rates and yields are continuously compounded and annualized, e.g. `0.05` for 5%.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionInputs {
+    pub(crate) right: OptionRight,
+    pub(crate) underlying_price: f64,
+    pub(crate) strike: f64,
+    pub(crate) time_to_expiry: f64,
+    pub(crate) risk_free_rate: f64,
+    pub(crate) dividend_yield: f64,
+    pub(crate) model: PricingModel,
+}
+
+impl OptionInputs {
+    /// Creates inputs for an option expiring in `time_to_expiry` years, with zero rates and Black-Scholes pricing.
+    ///
+    /// Use [year_fraction] to convert an expiration date into years.
+    pub fn new(right: OptionRight, underlying_price: f64, strike: f64, time_to_expiry: f64) -> Self {
+        Self {
+            right,
+            underlying_price,
+            strike,
+            time_to_expiry,
+            risk_free_rate: 0.0,
+            dividend_yield: 0.0,
+            model: PricingModel::default(),
+        }
+    }
+
+    /// Risk-free interest rate. Default: `0.0`.
+    pub fn risk_free_rate(mut self, rate: f64) -> Self {
+        self.risk_free_rate = rate;
+        self
+    }
+
+    /// Continuous dividend yield of the underlying. Default: `0.0`.
+    pub fn dividend_yield(mut self, dividend_yield: f64) -> Self {
+        self.dividend_yield = dividend_yield;
+        self
+    }
+
+    /// Pricing model. Default: [PricingModel::BlackScholes].
+    pub fn model(mut self, model: PricingModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if !(self.underlying_price.is_finite() && self.underlying_price > 0.0) {
+            return Err(Error::InvalidArgument(format!(
+                "underlying price must be positive: {}",
+                self.underlying_price
+            )));
+        }
+        if !(self.strike.is_finite() && self.strike > 0.0) {
+            return Err(Error::InvalidArgument(format!("strike must be positive: {}", self.strike)));
+        }
+        if !(self.time_to_expiry.is_finite() && self.time_to_expiry > 0.0) {
+            return Err(Error::InvalidArgument(format!(
+                "time to expiry must be positive: {}",
+                self.time_to_expiry
+            )));
+        }
+        if !self.risk_free_rate.is_finite() || !self.dividend_yield.is_finite() {
+            return Err(Error::InvalidArgument("rates must be finite".to_string()));
+        }
+        Ok(())
+    }
+
+    // Cost of carry: r - q on spot, zero on futures.
+    fn carry(&self) -> f64 {
+        match self.model {
+            PricingModel::BlackScholes => self.risk_free_rate - self.dividend_yield,
+            PricingModel::Black76 => 0.0,
+        }
+    }
+
+    // Discount factor applied to the underlying leg.
+    fn underlying_discount(&self) -> f64 {
+        ((self.carry() - self.risk_free_rate) * self.time_to_expiry).exp()
+    }
+
+    fn strike_discount(&self) -> f64 {
+        (-self.risk_free_rate * self.time_to_expiry).exp()
+    }
+
+    fn d1_d2(&self, volatility: f64) -> (f64, f64) {
+        let sqrt_time = self.time_to_expiry.sqrt();
+        let d1 = ((self.underlying_price / self.strike).ln() + (self.carry() + volatility * volatility / 2.0) * self.time_to_expiry)
+            / (volatility * sqrt_time);
+        (d1, d1 - volatility * sqrt_time)
+    }
+
+    // Theoretical price without input validation.
+    fn value(&self, volatility: f64) -> f64 {
+        let (d1, d2) = self.d1_d2(volatility);
+        let forward_leg = self.underlying_price * self.underlying_discount();
+        let strike_leg = self.strike * self.strike_discount();
+
+        match self.right {
+            OptionRight::Call => forward_leg * norm_cdf(d1) - strike_leg * norm_cdf(d2),
+            OptionRight::Put => strike_leg * norm_cdf(-d2) - forward_leg * norm_cdf(-d1),
+        }
+    }
+
+    // Vega per unit of volatility, used by the solver.
+    fn raw_vega(&self, volatility: f64) -> f64 {
+        let (d1, _) = self.d1_d2(volatility);
+        self.underlying_price * self.underlying_discount() * norm_pdf(d1) * self.time_to_expiry.sqrt()
+    }
+
+    // Prices every option must trade between to rule out arbitrage.
+    fn price_bounds(&self) -> (f64, f64) {
+        let forward_leg = self.underlying_price * self.underlying_discount();
+        let strike_leg = self.strike * self.strike_discount();
+
+        match self.right {
+            OptionRight::Call => ((forward_leg - strike_leg).max(0.0), forward_leg),
+            OptionRight::Put => ((strike_leg - forward_leg).max(0.0), strike_leg),
+        }
+    }
+}
+
+/// Prices an option at the given annualized `volatility` and computes its greeks.
+///
+/// The returned computation is tagged [TickType::CustOptionComputation], the tick type TWS
+/// uses for the results of [Client::calculate_option_price](crate::Client::calculate_option_price).
+pub fn price(inputs: &OptionInputs, volatility: f64) -> Result<OptionComputation, Error> {
+    inputs.validate()?;
+    if !(volatility.is_finite() && volatility > 0.0) {
+        return Err(Error::InvalidArgument(format!("volatility must be positive: {volatility}")));
+    }
+
+    let (d1, d2) = inputs.d1_d2(volatility);
+    let sqrt_time = inputs.time_to_expiry.sqrt();
+    let underlying_discount = inputs.underlying_discount();
+    let forward_leg = inputs.underlying_price * underlying_discount;
+    let strike_leg = inputs.strike * inputs.strike_discount();
+    let carry_less_rate = inputs.carry() - inputs.risk_free_rate;
+    let rate = inputs.risk_free_rate;
+
+    let decay = -forward_leg * norm_pdf(d1) * volatility / (2.0 * sqrt_time);
+    let (delta, annual_theta) = match inputs.right {
+        OptionRight::Call => (
+            underlying_discount * norm_cdf(d1),
+            decay - carry_less_rate * forward_leg * norm_cdf(d1) - rate * strike_leg * norm_cdf(d2),
+        ),
+        OptionRight::Put => (
+            underlying_discount * (norm_cdf(d1) - 1.0),
+            decay + carry_less_rate * forward_leg * norm_cdf(-d1) + rate * strike_leg * norm_cdf(-d2),
+        ),
+    };
+
+    let present_value_dividend = match inputs.model {
+        PricingModel::BlackScholes => Some(inputs.underlying_price * (1.0 - (-inputs.dividend_yield * inputs.time_to_expiry).exp())),
+        PricingModel::Black76 => None,
+    };
+
+    Ok(OptionComputation {
+        field: TickType::CustOptionComputation,
+        tick_attribute: Some(1),
+        implied_volatility: Some(volatility),
+        delta: Some(delta),
+        option_price: Some(inputs.value(volatility)),
+        present_value_dividend,
+        gamma: Some(underlying_discount * norm_pdf(d1) / (inputs.underlying_price * volatility * sqrt_time)),
+        vega: Some(inputs.raw_vega(volatility) / 100.0),
+        theta: Some(annual_theta / DAYS_PER_YEAR),
+        underlying_price: Some(inputs.underlying_price),
+    })
+}
+
+/// Price change for a one point (1%) move in the risk-free rate.
+pub fn rho(inputs: &OptionInputs, volatility: f64) -> Result<f64, Error> {
+    let computation = price(inputs, volatility)?;
+    let time = inputs.time_to_expiry;
+
+    let rho = match inputs.model {
+        PricingModel::BlackScholes => {
+            let (_, d2) = inputs.d1_d2(volatility);
+            let strike_leg = inputs.strike * inputs.strike_discount();
+            match inputs.right {
+                OptionRight::Call => time * strike_leg * norm_cdf(d2),
+                OptionRight::Put => -time * strike_leg * norm_cdf(-d2),
+            }
+        }
+        // Only discounting depends on the rate when the underlying is a future.
+        PricingModel::Black76 => -time * computation.option_price.unwrap_or_default(),
+    };
+
+    Ok(rho / 100.0)
+}
+
+/// Solves for the volatility at which the model reproduces `option_price`, and computes the greeks at that volatility.
+///
+/// Returns [Error::InvalidArgument] when the price lies outside the no-arbitrage bounds of the option,
+/// for instance below its discounted intrinsic value.
+pub fn implied_volatility(inputs: &OptionInputs, option_price: f64) -> Result<OptionComputation, Error> {
+    inputs.validate()?;
+
+    let (lower, upper) = inputs.price_bounds();
+    if !(option_price.is_finite() && option_price > lower && option_price < upper) {
+        return Err(Error::InvalidArgument(format!(
+            "option price {option_price} is outside the arbitrage bounds ({lower}, {upper})"
+        )));
+    }
+
+    // Newton-Raphson, falling back to bisection whenever a step leaves the bracket.
+    let mut low = MIN_VOLATILITY;
+    let mut high = MAX_VOLATILITY;
+    let mut volatility = (2.0 * PI / inputs.time_to_expiry).sqrt() * option_price / inputs.underlying_price;
+    if !(low..high).contains(&volatility) {
+        volatility = 0.5;
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let difference = inputs.value(volatility) - option_price;
+        if difference.abs() < PRICE_TOLERANCE {
+            return price(inputs, volatility);
+        }
+
+        if difference > 0.0 {
+            high = volatility;
+        } else {
+            low = volatility;
+        }
+
+        let vega = inputs.raw_vega(volatility);
+        let step = volatility - difference / vega;
+        volatility = if vega > f64::EPSILON && step > low && step < high {
+            step
+        } else {
+            (low + high) / 2.0
+        };
+
+        if high - low < VOLATILITY_TOLERANCE {
+            return price(inputs, volatility);
+        }
+    }
+
+    Err(Error::Simple(format!(
+        "implied volatility did not converge for option price {option_price}"
+    )))
+}
+
+/// Years between `from` and `to` on an actual/365 basis, as used for `time_to_expiry`.
+///
+/// Returns zero when `to` is not after `from`.
+pub fn year_fraction(from: OffsetDateTime, to: OffsetDateTime) -> f64 {
+    let seconds = (to - from).as_seconds_f64().max(0.0);
+    seconds / (DAYS_PER_YEAR * 24.0 * 60.0 * 60.0)
+}
+
+fn norm_pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * PI).sqrt()
+}
+
+// Cumulative standard normal distribution, accurate to double precision
+// (Hart's algorithm as given by G. West, "Better approximations to cumulative normal functions").
+fn norm_cdf(x: f64) -> f64 {
+    const NUMERATOR: [f64; 7] = [
+        0.035_262_496_599_891_1,
+        0.700_383_064_443_688,
+        6.373_962_203_531_65,
+        33.912_866_078_383,
+        112.079_291_497_871,
+        221.213_596_169_931,
+        220.206_867_912_376,
+    ];
+    const DENOMINATOR: [f64; 8] = [
+        0.088_388_347_648_318_4,
+        1.755_667_163_182_64,
+        16.064_177_579_207,
+        86.780_732_202_946_1,
+        296.564_248_779_674,
+        637.333_633_378_831,
+        793.826_512_519_948,
+        440.413_735_824_752,
+    ];
+
+    let z = x.abs();
+    let horner = |coefficients: &[f64]| coefficients.iter().fold(0.0, |sum, coefficient| sum * z + coefficient);
+
+    let tail = if z > 37.0 {
+        0.0
+    } else if z < 7.071_067_811_865_47 {
+        (-z * z / 2.0).exp() * horner(&NUMERATOR) / horner(&DENOMINATOR)
+    } else {
+        let continued_fraction = z + 1.0 / (z + 2.0 / (z + 3.0 / (z + 4.0 / (z + 0.65))));
+        (-z * z / 2.0).exp() / (continued_fraction * 2.506_628_274_631)
+    };
+
+    if x > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+#[cfg(test)]
+mod tests;
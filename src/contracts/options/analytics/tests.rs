@@ -0,0 +1,166 @@
+use time::macros::datetime;
+
+use super::*;
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!((actual - expected).abs() < tolerance, "expected {expected} ± {tolerance}, got {actual}");
+}
+
+#[test]
+fn test_black_scholes_prices() {
+    // Hull, Options, Futures and Other Derivatives, example 15.6.
+    let call = OptionInputs::new(OptionRight::Call, 42.0, 40.0, 0.5).risk_free_rate(0.10);
+    let put = OptionInputs::new(OptionRight::Put, 42.0, 40.0, 0.5).risk_free_rate(0.10);
+
+    assert_close(price(&call, 0.20).unwrap().option_price.unwrap(), 4.7594, 1e-4);
+    assert_close(price(&put, 0.20).unwrap().option_price.unwrap(), 0.8086, 1e-4);
+}
+
+#[test]
+fn test_dividend_yield() {
+    // Haug, The Complete Guide to Option Pricing Formulas, generalized Black-Scholes example.
+    let put = OptionInputs::new(OptionRight::Put, 100.0, 95.0, 0.5)
+        .risk_free_rate(0.10)
+        .dividend_yield(0.05);
+    let computation = price(&put, 0.20).unwrap();
+
+    assert_close(computation.option_price.unwrap(), 2.4648, 1e-4);
+    assert_close(computation.present_value_dividend.unwrap(), 100.0 * (1.0 - (-0.025f64).exp()), 1e-12);
+    assert_eq!(computation.underlying_price, Some(100.0));
+    assert_eq!(computation.field, TickType::CustOptionComputation);
+}
+
+#[test]
+fn test_black_76() {
+    // Haug, Black-76 example: at-the-money calls and puts on futures are worth the same.
+    let call = OptionInputs::new(OptionRight::Call, 19.0, 19.0, 0.75)
+        .risk_free_rate(0.10)
+        .model(PricingModel::Black76);
+    let put = OptionInputs {
+        right: OptionRight::Put,
+        ..call
+    };
+
+    let call_computation = price(&call, 0.28).unwrap();
+    assert_close(call_computation.option_price.unwrap(), 1.7011, 1e-4);
+    assert_close(price(&put, 0.28).unwrap().option_price.unwrap(), 1.7011, 1e-4);
+    assert_eq!(call_computation.present_value_dividend, None);
+
+    // Haug delta example on a futures option.
+    let call = OptionInputs::new(OptionRight::Call, 105.0, 100.0, 0.5)
+        .risk_free_rate(0.10)
+        .model(PricingModel::Black76);
+    let put = OptionInputs {
+        right: OptionRight::Put,
+        ..call
+    };
+    assert_close(price(&call, 0.36).unwrap().delta.unwrap(), 0.5946, 1e-4);
+    assert_close(price(&put, 0.36).unwrap().delta.unwrap(), -0.3566, 1e-4);
+
+    let rho_per_point = rho(&call, 0.36).unwrap();
+    assert_close(rho_per_point, -0.5 * price(&call, 0.36).unwrap().option_price.unwrap() / 100.0, 1e-12);
+}
+
+#[test]
+fn test_greeks() {
+    // Reference values from Haug, chapter 2, quoted per unit and converted to IB's per point and per day conventions.
+    let gamma = OptionInputs::new(OptionRight::Call, 55.0, 60.0, 0.75).risk_free_rate(0.10);
+    assert_close(price(&gamma, 0.30).unwrap().gamma.unwrap(), 0.0278, 1e-4);
+
+    // Vega is checked against a central difference of one volatility point.
+    let vega = OptionInputs::new(OptionRight::Call, 55.0, 60.0, 0.75)
+        .risk_free_rate(0.105)
+        .dividend_yield(0.0355);
+    let bumped = |volatility: f64| price(&vega, volatility).unwrap().option_price.unwrap();
+    assert_close(price(&vega, 0.30).unwrap().vega.unwrap(), bumped(0.305) - bumped(0.295), 1e-5);
+
+    let theta = OptionInputs::new(OptionRight::Put, 430.0, 405.0, 0.0833)
+        .risk_free_rate(0.07)
+        .dividend_yield(0.05);
+    assert_close(price(&theta, 0.20).unwrap().theta.unwrap(), -31.1924 / 365.0, 1e-4);
+
+    let call = OptionInputs::new(OptionRight::Call, 72.0, 75.0, 1.0).risk_free_rate(0.09);
+    assert_close(rho(&call, 0.19).unwrap(), 0.387_325, 1e-5);
+}
+
+#[test]
+fn test_put_call_parity() {
+    let call = OptionInputs::new(OptionRight::Call, 187.3, 190.0, 0.12)
+        .risk_free_rate(0.045)
+        .dividend_yield(0.005);
+    let put = OptionInputs {
+        right: OptionRight::Put,
+        ..call
+    };
+
+    let call_price = price(&call, 0.27).unwrap().option_price.unwrap();
+    let put_price = price(&put, 0.27).unwrap().option_price.unwrap();
+    let forward_leg = 187.3 * (-0.005f64 * 0.12).exp();
+    let strike_leg = 190.0 * (-0.045f64 * 0.12).exp();
+
+    assert_close(call_price - put_price, forward_leg - strike_leg, 1e-10);
+}
+
+#[test]
+fn test_implied_volatility_round_trip() {
+    for strike in [50.0, 80.0, 100.0, 120.0, 180.0] {
+        for right in [OptionRight::Call, OptionRight::Put] {
+            for volatility in [0.05, 0.25, 1.5] {
+                let inputs = OptionInputs::new(right, 100.0, strike, 0.4).risk_free_rate(0.03).dividend_yield(0.01);
+                let option_price = price(&inputs, volatility).unwrap().option_price.unwrap();
+                let (lower, upper) = inputs.price_bounds();
+                if option_price - lower < 1e-8 || upper - option_price < 1e-8 {
+                    // Too close to the bounds for the price to identify the volatility.
+                    continue;
+                }
+
+                let computation = implied_volatility(&inputs, option_price).unwrap();
+                assert_close(computation.implied_volatility.unwrap(), volatility, 1e-6);
+                assert_close(computation.option_price.unwrap(), option_price, 1e-8);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_implied_volatility_outside_bounds() {
+    let call = OptionInputs::new(OptionRight::Call, 110.0, 100.0, 0.5);
+
+    assert!(
+        matches!(implied_volatility(&call, 9.0), Err(Error::InvalidArgument(_))),
+        "below intrinsic value"
+    );
+    assert!(
+        matches!(implied_volatility(&call, 111.0), Err(Error::InvalidArgument(_))),
+        "above the underlying"
+    );
+}
+
+#[test]
+fn test_invalid_inputs() {
+    let expired = OptionInputs::new(OptionRight::Call, 100.0, 100.0, 0.0);
+    assert!(matches!(price(&expired, 0.2), Err(Error::InvalidArgument(_))));
+
+    let no_strike = OptionInputs::new(OptionRight::Put, 100.0, -5.0, 0.5);
+    assert!(matches!(price(&no_strike, 0.2), Err(Error::InvalidArgument(_))));
+
+    let valid = OptionInputs::new(OptionRight::Put, 100.0, 100.0, 0.5);
+    assert!(matches!(price(&valid, 0.0), Err(Error::InvalidArgument(_))));
+}
+
+#[test]
+fn test_year_fraction() {
+    let from = datetime!(2024-01-02 16:00 UTC);
+
+    assert_close(year_fraction(from, datetime!(2025-01-01 16:00 UTC)), 1.0, 1e-12);
+    assert_close(year_fraction(from, datetime!(2024-01-09 16:00 UTC)), 7.0 / 365.0, 1e-12);
+    assert_eq!(year_fraction(from, datetime!(2024-01-01 16:00 UTC)), 0.0);
+}
+
+#[test]
+fn test_norm_cdf() {
+    assert_close(norm_cdf(0.0), 0.5, 1e-15);
+    assert_close(norm_cdf(1.96), 0.975_002_104_851_780, 1e-14);
+    assert_close(norm_cdf(-1.0) + norm_cdf(1.0), 1.0, 1e-15);
+    assert_eq!(norm_cdf(-40.0), 0.0);
+}
@@ -0,0 +1,640 @@
+//! Expansion of option chains into qualified contracts.
+//!
+//! [Client::option_chain](crate::Client::option_chain) lists the expirations and strikes an
+//! underlying trades, without contract ids. [OptionChainBuilder] narrows that list down to the
+//! expirations and strikes of interest, resolves the remaining options through one
+//! `contract_details` request per expiration, and optionally snapshots their quotes. The
+//! result is an [OptionSurface] keyed by expiration, strike and right.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::mem;
+use std::time::Duration;
+
+use log::warn;
+use time::macros::format_description;
+use time::Date;
+
+use super::super::qualify::resolve;
+use super::super::tick_types::TickType;
+use super::super::{Contract, ContractDetails, OptionChain, OptionComputation, OptionRight, SecurityType};
+use crate::market_data::realtime::TickTypes;
+use crate::Error;
+
+#[cfg(test)]
+mod tests;
+
+// Exchange whose chain is used when the underlying is not a future.
+const DEFAULT_EXCHANGE: &str = "SMART";
+
+// Snapshots normally complete within 11 seconds.
+const DEFAULT_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(15);
+
+// Snapshots in flight at once. Each holds a market data line until it completes, and accounts start with 100 lines.
+const MAX_CONCURRENT_SNAPSHOTS: usize = 50;
+
+// Strikes listed in chains and returned by contract details can differ in the last digits.
+const STRIKE_TOLERANCE: f64 = 1e-6;
+
+/// Builder that expands the option chain of an underlying into an [OptionSurface].
+///
+/// Created with `Client::option_surface`. By default every expiration and strike listed on
+/// `SMART` is resolved; narrow the request with the filters below, as each expiration costs
+/// one `contract_details` request and each option one market data line when quotes are requested.
+pub struct OptionChainBuilder<'a, C> {
+    client: &'a C,
+    underlying: &'a Contract,
+    filter: ChainFilter,
+    market_data: bool,
+    snapshot_timeout: Duration,
+}
+
+// Selection criteria, kept apart from the client so they can be applied without one.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChainFilter {
+    pub(crate) exchange: Option<String>,
+    pub(crate) trading_class: Option<String>,
+    pub(crate) first_expiration: Option<Date>,
+    pub(crate) last_expiration: Option<Date>,
+    pub(crate) max_expirations: Option<usize>,
+    pub(crate) strikes: StrikeFilter,
+    pub(crate) rights: Vec<OptionRight>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) enum StrikeFilter {
+    #[default]
+    All,
+    Around {
+        spot: f64,
+        count: usize,
+    },
+    Moneyness {
+        spot: f64,
+        lower: f64,
+        upper: f64,
+    },
+}
+
+// Contract details query for one expiration, with the strikes to keep from its results.
+#[derive(Debug, Clone)]
+pub(crate) struct ExpirationRequest {
+    pub(crate) expiration: String,
+    pub(crate) query: Contract,
+    pub(crate) strikes: Vec<f64>,
+}
+
+impl<'a, C> OptionChainBuilder<'a, C> {
+    /// Creates a builder for the options on `underlying`.
+    pub fn new(client: &'a C, underlying: &'a Contract) -> Self {
+        Self {
+            client,
+            underlying,
+            filter: ChainFilter::default(),
+            market_data: false,
+            snapshot_timeout: DEFAULT_SNAPSHOT_TIMEOUT,
+        }
+    }
+
+    /// Exchange whose chain is expanded. Default: the underlying's exchange for futures, `SMART` otherwise.
+    pub fn exchange(mut self, exchange: &str) -> Self {
+        self.filter.exchange = Some(exchange.to_string());
+        self
+    }
+
+    /// Restricts the surface to one trading class, e.g. `SPXW`. Default: all trading classes on the exchange.
+    pub fn trading_class(mut self, trading_class: &str) -> Self {
+        self.filter.trading_class = Some(trading_class.to_string());
+        self
+    }
+
+    /// Keeps expirations from `first` to `last`, inclusive.
+    pub fn expirations_between(mut self, first: Date, last: Date) -> Self {
+        self.filter.first_expiration = Some(first);
+        self.filter.last_expiration = Some(last);
+        self
+    }
+
+    /// Keeps only the `count` nearest expirations that pass the date window.
+    pub fn max_expirations(mut self, count: usize) -> Self {
+        self.filter.max_expirations = Some(count);
+        self
+    }
+
+    /// Keeps the `count` strikes below `spot` and the `count` strikes at or above it.
+    pub fn strikes_around(mut self, spot: f64, count: usize) -> Self {
+        self.filter.strikes = StrikeFilter::Around { spot, count };
+        self
+    }
+
+    /// Keeps strikes whose ratio to `spot` lies between `lower` and `upper`, e.g. `0.9` and `1.1` for ±10%.
+    pub fn moneyness(mut self, spot: f64, lower: f64, upper: f64) -> Self {
+        self.filter.strikes = StrikeFilter::Moneyness { spot, lower, upper };
+        self
+    }
+
+    /// Keeps only the given rights. Default: calls and puts.
+    pub fn rights(mut self, rights: &[OptionRight]) -> Self {
+        self.filter.rights = rights.to_vec();
+        self
+    }
+
+    /// Snapshots bid, ask, last and model greeks for every resolved option.
+    pub fn with_market_data(mut self) -> Self {
+        self.market_data = true;
+        self
+    }
+
+    /// How long to wait for each market data snapshot. Default: 15 seconds.
+    pub fn snapshot_timeout(mut self, timeout: Duration) -> Self {
+        self.snapshot_timeout = timeout;
+        self
+    }
+}
+
+impl ChainFilter {
+    // Picks the chains to expand and the contract details queries to send for them.
+    pub(crate) fn plan(&self, underlying: &Contract, chains: &[OptionChain]) -> Vec<ExpirationRequest> {
+        let exchange = match &self.exchange {
+            Some(exchange) => exchange.clone(),
+            None if underlying.security_type == SecurityType::Future => underlying.exchange.to_string(),
+            None => DEFAULT_EXCHANGE.to_string(),
+        };
+        let security_type = match underlying.security_type {
+            SecurityType::Future => SecurityType::FuturesOption,
+            _ => SecurityType::Option,
+        };
+
+        let mut requests = Vec::new();
+        for chain in chains {
+            if chain.exchange != exchange {
+                continue;
+            }
+            if self
+                .trading_class
+                .as_ref()
+                .is_some_and(|trading_class| *trading_class != chain.trading_class)
+            {
+                continue;
+            }
+
+            let strikes = self.strikes.apply(&chain.strikes);
+            if strikes.is_empty() {
+                continue;
+            }
+
+            for expiration in self.expirations(&chain.expirations) {
+                let query = Contract {
+                    symbol: underlying.symbol.clone(),
+                    security_type: security_type.clone(),
+                    last_trade_date_or_contract_month: expiration.clone(),
+                    exchange: chain.exchange.as_str().into(),
+                    currency: underlying.currency.clone(),
+                    trading_class: chain.trading_class.clone(),
+                    multiplier: chain.multiplier.clone(),
+                    ..Default::default()
+                };
+
+                requests.push(ExpirationRequest {
+                    expiration,
+                    query,
+                    strikes: strikes.clone(),
+                });
+            }
+        }
+
+        requests
+    }
+
+    // Expirations inside the date window, nearest first.
+    fn expirations(&self, expirations: &[String]) -> Vec<String> {
+        let mut dated: Vec<(Date, &String)> = expirations
+            .iter()
+            .filter_map(|expiration| parse_expiration(expiration).map(|date| (date, expiration)))
+            .filter(|(date, _)| self.first_expiration.is_none_or(|first| *date >= first))
+            .filter(|(date, _)| self.last_expiration.is_none_or(|last| *date <= last))
+            .collect();
+        dated.sort();
+
+        let count = self.max_expirations.unwrap_or(dated.len());
+        dated.into_iter().take(count).map(|(_, expiration)| expiration.clone()).collect()
+    }
+
+    // Contracts from a contract details response that belong on the surface.
+    pub(crate) fn accept(&self, request: &ExpirationRequest, details: Vec<ContractDetails>) -> Vec<(OptionKey, Contract)> {
+        details
+            .into_iter()
+            .filter_map(|detail| {
+                let contract = detail.contract;
+                let right = parse_right(&contract.right)?;
+                if !self.rights.is_empty() && !self.rights.contains(&right) {
+                    return None;
+                }
+                let strike = *request
+                    .strikes
+                    .iter()
+                    .find(|strike| (**strike - contract.strike).abs() < STRIKE_TOLERANCE)?;
+
+                let key = OptionKey {
+                    expiration: request.expiration.clone(),
+                    strike,
+                    right,
+                };
+                Some((key, contract))
+            })
+            .collect()
+    }
+}
+
+impl StrikeFilter {
+    fn apply(&self, strikes: &[f64]) -> Vec<f64> {
+        let mut strikes: Vec<f64> = strikes.iter().copied().filter(|strike| strike.is_finite()).collect();
+        strikes.sort_by(f64::total_cmp);
+        strikes.dedup();
+
+        match *self {
+            StrikeFilter::All => strikes,
+            StrikeFilter::Around { spot, count } => {
+                let at_or_above = strikes.partition_point(|strike| *strike < spot);
+                let first = at_or_above.saturating_sub(count);
+                let last = (at_or_above + count).min(strikes.len());
+                strikes[first..last].to_vec()
+            }
+            StrikeFilter::Moneyness { spot, lower, upper } => {
+                strikes.into_iter().filter(|strike| (lower..=upper).contains(&(strike / spot))).collect()
+            }
+        }
+    }
+}
+
+fn parse_expiration(expiration: &str) -> Option<Date> {
+    let format = format_description!("[year][month][day]");
+    Date::parse(expiration.get(..8)?, format).ok()
+}
+
+fn parse_right(right: &str) -> Option<OptionRight> {
+    match right.chars().next()?.to_ascii_uppercase() {
+        'C' => Some(OptionRight::Call),
+        'P' => Some(OptionRight::Put),
+        _ => None,
+    }
+}
+
+/// Position of an option on an [OptionSurface].
+///
+/// Strikes are compared with [f64::total_cmp], so equality and ordering agree for every value.
+#[derive(Debug, Clone)]
+pub struct OptionKey {
+    /// Expiration as listed in the option chain, `YYYYMMDD`.
+    pub expiration: String,
+    /// Strike price.
+    pub strike: f64,
+    /// Call or put.
+    pub right: OptionRight,
+}
+
+impl PartialEq for OptionKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OptionKey {}
+
+impl Ord for OptionKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let right = |key: &OptionKey| key.right == OptionRight::Put;
+
+        self.expiration
+            .cmp(&other.expiration)
+            .then(self.strike.total_cmp(&other.strike))
+            .then(right(self).cmp(&right(other)))
+    }
+}
+
+impl PartialOrd for OptionKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A qualified option contract and, when requested and completed in time, its market data snapshot.
+#[derive(Debug, Default)]
+pub struct OptionQuote {
+    /// Fully qualified contract, including its contract id.
+    pub contract: Contract,
+    /// Bid price.
+    pub bid: Option<f64>,
+    /// Ask price.
+    pub ask: Option<f64>,
+    /// Last traded price.
+    pub last: Option<f64>,
+    /// Implied volatility and greeks from TWS's option model.
+    pub computation: Option<OptionComputation>,
+}
+
+impl OptionQuote {
+    /// Midpoint of bid and ask, when both are quoted.
+    pub fn midpoint(&self) -> Option<f64> {
+        match (self.bid, self.ask) {
+            (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => Some((bid + ask) / 2.0),
+            _ => None,
+        }
+    }
+
+    // Folds a snapshot tick into the quote.
+    pub(crate) fn apply(&mut self, tick: TickTypes) {
+        let price = match &tick {
+            TickTypes::Price(price) => Some((&price.tick_type, price.price)),
+            TickTypes::PriceSize(price) => Some((&price.price_tick_type, price.price)),
+            _ => None,
+        };
+
+        if let Some((tick_type, price)) = price {
+            // TWS sends -1 when there is no quote.
+            let price = (price > 0.0).then_some(price);
+            match tick_type {
+                TickType::Bid | TickType::DelayedBid => self.bid = price,
+                TickType::Ask | TickType::DelayedAsk => self.ask = price,
+                TickType::Last | TickType::DelayedLast => self.last = price,
+                _ => {}
+            }
+        }
+
+        if let TickTypes::OptionComputation(computation) = tick {
+            let model = matches!(computation.field, TickType::ModelOption | TickType::DelayedModelOption);
+            // Model values are preferred over the bid and ask computations.
+            if model || self.computation.is_none() {
+                self.computation = Some(computation);
+            }
+        }
+    }
+
+    // Takes the market data of a completed snapshot.
+    fn complete(&mut self, snapshot: OptionQuote) {
+        *self = OptionQuote {
+            contract: mem::take(&mut self.contract),
+            ..snapshot
+        };
+    }
+}
+
+/// Qualified options of an underlying, keyed by expiration, strike and right.
+#[derive(Debug, Default)]
+pub struct OptionSurface {
+    quotes: BTreeMap<OptionKey, OptionQuote>,
+}
+
+impl OptionSurface {
+    /// Returns the option at the given expiration (`YYYYMMDD`), strike and right.
+    pub fn get(&self, expiration: &str, strike: f64, right: OptionRight) -> Option<&OptionQuote> {
+        self.quotes.get(&OptionKey {
+            expiration: expiration.to_string(),
+            strike,
+            right,
+        })
+    }
+
+    /// Expirations on the surface, nearest first.
+    pub fn expirations(&self) -> Vec<&str> {
+        let mut expirations: Vec<&str> = self.quotes.keys().map(|key| key.expiration.as_str()).collect();
+        expirations.dedup();
+        expirations
+    }
+
+    /// Strikes listed for `expiration`, in ascending order.
+    pub fn strikes(&self, expiration: &str) -> Vec<f64> {
+        let mut strikes: Vec<f64> = self
+            .quotes
+            .keys()
+            .filter(|key| key.expiration == expiration)
+            .map(|key| key.strike)
+            .collect();
+        strikes.dedup();
+        strikes
+    }
+
+    /// Iterates over the options ordered by expiration, strike and right.
+    pub fn iter(&self) -> impl Iterator<Item = (&OptionKey, &OptionQuote)> {
+        self.quotes.iter()
+    }
+
+    /// Number of options on the surface.
+    pub fn len(&self) -> usize {
+        self.quotes.len()
+    }
+
+    /// Returns `true` if no options were resolved.
+    pub fn is_empty(&self) -> bool {
+        self.quotes.is_empty()
+    }
+
+    pub(crate) fn insert(&mut self, key: OptionKey, contract: Contract) {
+        self.quotes.insert(
+            key,
+            OptionQuote {
+                contract,
+                ..Default::default()
+            },
+        );
+    }
+
+    pub(crate) fn quotes_mut(&mut self) -> impl Iterator<Item = &mut OptionQuote> {
+        self.quotes.values_mut()
+    }
+}
+
+// Resolves the contract id of the underlying, which the option chain request requires.
+// Fails with Error::AmbiguousContract when the details match more than one contract.
+fn qualified_underlying(underlying: &Contract, details: Option<Vec<ContractDetails>>) -> Result<Contract, Error> {
+    if underlying.contract_id != 0 {
+        return Ok(underlying.clone());
+    }
+
+    resolve(underlying, &details.unwrap_or_default())
+}
+
+#[cfg(feature = "sync")]
+impl OptionChainBuilder<'_, crate::client::sync::Client> {
+    /// Resolves the selected options and, if requested, snapshots their market data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::client::blocking::Client;
+    /// use ibapi::contracts::{Contract, OptionRight};
+    /// use time::macros::date;
+    ///
+    /// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+    /// let underlying = Contract::stock("AAPL").build();
+    ///
+    /// let surface = client
+    ///     .option_surface(&underlying)
+    ///     .expirations_between(date!(2025 - 01 - 01), date!(2025 - 03 - 31))
+    ///     .strikes_around(185.0, 5)
+    ///     .rights(&[OptionRight::Call])
+    ///     .with_market_data()
+    ///     .fetch()
+    ///     .expect("option surface failed");
+    ///
+    /// for (key, quote) in surface.iter() {
+    ///     println!("{} {} {}: {:?} / {:?}", key.expiration, key.strike, key.right, quote.bid, quote.ask);
+    /// }
+    /// ```
+    pub fn fetch(self) -> Result<OptionSurface, Error> {
+        let client = self.client;
+
+        let details = if self.underlying.contract_id == 0 {
+            Some(client.contract_details(self.underlying)?)
+        } else {
+            None
+        };
+        let underlying = qualified_underlying(self.underlying, details)?;
+
+        let subscription = client.option_chain(underlying.symbol.as_str(), "", underlying.security_type.clone(), underlying.contract_id)?;
+        let chains: Vec<OptionChain> = subscription.iter().collect();
+        if let Some(error) = subscription.error() {
+            return Err(error);
+        }
+
+        let mut surface = OptionSurface::default();
+        for request in self.filter.plan(&underlying, &chains) {
+            let details = client.contract_details(&request.query)?;
+            for (key, contract) in self.filter.accept(&request, details) {
+                surface.insert(key, contract);
+            }
+        }
+
+        if self.market_data {
+            // Each chunk is requested in full before any snapshot is read, so its snapshots are served in parallel.
+            let mut quotes: Vec<&mut OptionQuote> = surface.quotes_mut().collect();
+            for chunk in quotes.chunks_mut(MAX_CONCURRENT_SNAPSHOTS) {
+                let mut pending = Vec::new();
+                for quote in chunk.iter_mut() {
+                    match client.market_data(&quote.contract).snapshot().subscribe() {
+                        Ok(subscription) => pending.push((quote, subscription)),
+                        Err(error) => warn!("market data snapshot failed for {}: {error}", quote.contract.local_symbol),
+                    }
+                }
+
+                // Quotes are only updated once their snapshot completes, so a timeout leaves them empty.
+                let deadline = std::time::Instant::now() + self.snapshot_timeout;
+                for (quote, subscription) in pending {
+                    let mut snapshot = OptionQuote::default();
+                    loop {
+                        match subscription.next_timeout(deadline.saturating_duration_since(std::time::Instant::now())) {
+                            Some(TickTypes::SnapshotEnd) => {
+                                quote.complete(snapshot);
+                                break;
+                            }
+                            Some(tick) => snapshot.apply(tick),
+                            None => {
+                                warn!("market data snapshot timed out for {}", quote.contract.local_symbol);
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(surface)
+    }
+}
+
+#[cfg(feature = "async")]
+impl OptionChainBuilder<'_, crate::client::r#async::Client> {
+    /// Resolves the selected options and, if requested, snapshots their market data.
+    ///
+    /// Contract details for all expirations are requested concurrently.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ibapi::contracts::Contract;
+    /// use ibapi::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+    ///     let underlying = Contract::stock("AAPL").build();
+    ///
+    ///     let surface = client
+    ///         .option_surface(&underlying)
+    ///         .max_expirations(2)
+    ///         .moneyness(185.0, 0.95, 1.05)
+    ///         .fetch()
+    ///         .await
+    ///         .expect("option surface failed");
+    ///
+    ///     for expiration in surface.expirations() {
+    ///         println!("{expiration}: {:?}", surface.strikes(expiration));
+    ///     }
+    /// }
+    /// ```
+    pub async fn fetch(self) -> Result<OptionSurface, Error> {
+        use futures::future::join_all;
+
+        let client = self.client;
+
+        let details = if self.underlying.contract_id == 0 {
+            Some(client.contract_details(self.underlying).await?)
+        } else {
+            None
+        };
+        let underlying = qualified_underlying(self.underlying, details)?;
+
+        let mut subscription = client
+            .option_chain(underlying.symbol.as_str(), "", underlying.security_type.clone(), underlying.contract_id)
+            .await?;
+        let mut chains = Vec::new();
+        while let Some(chain) = subscription.next().await {
+            chains.push(chain?);
+        }
+
+        let requests = self.filter.plan(&underlying, &chains);
+        let responses = join_all(requests.iter().map(|request| client.contract_details(&request.query))).await;
+
+        let mut surface = OptionSurface::default();
+        for (request, details) in requests.iter().zip(responses) {
+            for (key, contract) in self.filter.accept(request, details?) {
+                surface.insert(key, contract);
+            }
+        }
+
+        if self.market_data {
+            let timeout = self.snapshot_timeout;
+            let mut quotes: Vec<&mut OptionQuote> = surface.quotes_mut().collect();
+            for chunk in quotes.chunks_mut(MAX_CONCURRENT_SNAPSHOTS) {
+                join_all(chunk.iter_mut().map(|quote| async move {
+                    let mut subscription = match client.market_data(&quote.contract).snapshot().subscribe().await {
+                        Ok(subscription) => subscription,
+                        Err(error) => {
+                            warn!("market data snapshot failed for {}: {error}", quote.contract.local_symbol);
+                            return;
+                        }
+                    };
+
+                    // Quotes are only updated once their snapshot completes, so a timeout leaves them empty.
+                    let collect = async {
+                        let mut snapshot = OptionQuote::default();
+                        while let Some(Ok(tick)) = subscription.next().await {
+                            if matches!(tick, TickTypes::SnapshotEnd) {
+                                return Some(snapshot);
+                            }
+                            snapshot.apply(tick);
+                        }
+                        None
+                    };
+                    match tokio::time::timeout(timeout, collect).await {
+                        Ok(Some(snapshot)) => quote.complete(snapshot),
+                        _ => warn!("market data snapshot timed out for {}", quote.contract.local_symbol),
+                    }
+                }))
+                .await;
+            }
+        }
+
+        Ok(surface)
+    }
+}
@@ -0,0 +1,284 @@
+use time::macros::date;
+
+use super::*;
+use crate::market_data::realtime::{TickAttribute, TickPrice};
+
+fn chain(exchange: &str, trading_class: &str) -> OptionChain {
+    OptionChain {
+        underlying_contract_id: 265598,
+        trading_class: trading_class.to_string(),
+        multiplier: "100".to_string(),
+        exchange: exchange.to_string(),
+        expirations: vec![
+            "20250321".to_string(),
+            "20250117".to_string(),
+            "20250221".to_string(),
+            "20250620".to_string(),
+        ],
+        strikes: vec![170.0, 175.0, 180.0, 185.0, 190.0, 195.0, 200.0, 182.5],
+    }
+}
+
+fn underlying() -> Contract {
+    Contract {
+        contract_id: 265598,
+        ..Contract::stock("AAPL").build()
+    }
+}
+
+fn details(strike: f64, right: &str) -> ContractDetails {
+    ContractDetails {
+        contract: Contract {
+            contract_id: (strike * 10.0) as i32 + if right == "C" { 1 } else { 2 },
+            strike,
+            right: right.to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_plan_selects_exchange_and_trading_class() {
+    let chains = vec![chain("SMART", "AAPL"), chain("CBOE", "AAPL"), chain("SMART", "2AAPL")];
+
+    let filter = ChainFilter::default();
+    let requests = filter.plan(&underlying(), &chains);
+    assert_eq!(requests.len(), 8, "both SMART trading classes, four expirations each");
+
+    let filter = ChainFilter {
+        trading_class: Some("AAPL".to_string()),
+        ..Default::default()
+    };
+    let requests = filter.plan(&underlying(), &chains);
+    assert_eq!(requests.len(), 4);
+
+    let query = &requests[0].query;
+    assert_eq!(query.security_type, SecurityType::Option);
+    assert_eq!(query.symbol.as_str(), "AAPL");
+    assert_eq!(query.exchange.as_str(), "SMART");
+    assert_eq!(query.trading_class, "AAPL");
+    assert_eq!(query.multiplier, "100");
+    assert_eq!(query.strike, 0.0, "one request covers every strike of an expiration");
+    assert_eq!(query.right, "");
+}
+
+#[test]
+fn test_plan_for_futures_options() {
+    let future = Contract {
+        contract_id: 495512563,
+        symbol: "ES".into(),
+        security_type: SecurityType::Future,
+        exchange: "CME".into(),
+        ..Default::default()
+    };
+
+    let requests = ChainFilter::default().plan(&future, &[chain("CME", "ES"), chain("SMART", "ES")]);
+
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[0].query.security_type, SecurityType::FuturesOption);
+    assert_eq!(requests[0].query.exchange.as_str(), "CME");
+}
+
+#[test]
+fn test_expiration_window() {
+    let filter = ChainFilter {
+        first_expiration: Some(date!(2025 - 02 - 01)),
+        last_expiration: Some(date!(2025 - 06 - 20)),
+        ..Default::default()
+    };
+    let expirations: Vec<String> = filter
+        .plan(&underlying(), &[chain("SMART", "AAPL")])
+        .into_iter()
+        .map(|request| request.expiration)
+        .collect();
+    assert_eq!(expirations, vec!["20250221", "20250321", "20250620"]);
+
+    let filter = ChainFilter {
+        max_expirations: Some(2),
+        ..Default::default()
+    };
+    let expirations: Vec<String> = filter
+        .plan(&underlying(), &[chain("SMART", "AAPL")])
+        .into_iter()
+        .map(|request| request.expiration)
+        .collect();
+    assert_eq!(expirations, vec!["20250117", "20250221"], "nearest expirations first");
+}
+
+#[test]
+fn test_strike_filters() {
+    let strikes = chain("SMART", "AAPL").strikes;
+
+    assert_eq!(StrikeFilter::All.apply(&strikes).len(), 8);
+    assert_eq!(
+        StrikeFilter::Around { spot: 183.2, count: 2 }.apply(&strikes),
+        vec![180.0, 182.5, 185.0, 190.0]
+    );
+    assert_eq!(
+        StrikeFilter::Around { spot: 185.0, count: 1 }.apply(&strikes),
+        vec![182.5, 185.0],
+        "a strike equal to spot counts as above"
+    );
+    assert_eq!(
+        StrikeFilter::Around { spot: 160.0, count: 2 }.apply(&strikes),
+        vec![170.0, 175.0],
+        "window is clipped at the lowest strike"
+    );
+    assert_eq!(
+        StrikeFilter::Moneyness {
+            spot: 185.0,
+            lower: 0.95,
+            upper: 1.05
+        }
+        .apply(&strikes),
+        vec![180.0, 182.5, 185.0, 190.0]
+    );
+}
+
+#[test]
+fn test_accept_matches_strikes_and_rights() {
+    let filter = ChainFilter {
+        strikes: StrikeFilter::Around { spot: 183.0, count: 1 },
+        rights: vec![OptionRight::Call],
+        ..Default::default()
+    };
+    let requests = filter.plan(&underlying(), &[chain("SMART", "AAPL")]);
+    let request = &requests[0];
+    assert_eq!(request.strikes, vec![182.5, 185.0]);
+
+    let accepted = filter.accept(
+        request,
+        vec![details(180.0, "C"), details(182.500_000_1, "C"), details(182.5, "P"), details(185.0, "C")],
+    );
+
+    let keys: Vec<(f64, OptionRight)> = accepted.iter().map(|(key, _)| (key.strike, key.right)).collect();
+    assert_eq!(keys, vec![(182.5, OptionRight::Call), (185.0, OptionRight::Call)]);
+    assert!(accepted.iter().all(|(key, _)| key.expiration == "20250117"));
+}
+
+#[test]
+fn test_surface_lookup_and_ordering() {
+    let mut surface = OptionSurface::default();
+    assert!(surface.is_empty());
+
+    for (expiration, strike, right) in [
+        ("20250221", 185.0, OptionRight::Put),
+        ("20250117", 190.0, OptionRight::Call),
+        ("20250117", 185.0, OptionRight::Put),
+        ("20250117", 185.0, OptionRight::Call),
+    ] {
+        let key = OptionKey {
+            expiration: expiration.to_string(),
+            strike,
+            right,
+        };
+        surface.insert(key, details(strike, right.as_str()).contract);
+    }
+
+    assert_eq!(surface.len(), 4);
+    assert_eq!(surface.expirations(), vec!["20250117", "20250221"]);
+    assert_eq!(surface.strikes("20250117"), vec![185.0, 190.0]);
+
+    let quote = surface.get("20250117", 185.0, OptionRight::Put).expect("put exists");
+    assert_eq!(quote.contract.right, "P");
+    assert!(surface.get("20250117", 190.0, OptionRight::Put).is_none());
+
+    let order: Vec<(&str, f64, OptionRight)> = surface.iter().map(|(key, _)| (key.expiration.as_str(), key.strike, key.right)).collect();
+    assert_eq!(
+        order,
+        vec![
+            ("20250117", 185.0, OptionRight::Call),
+            ("20250117", 185.0, OptionRight::Put),
+            ("20250117", 190.0, OptionRight::Call),
+            ("20250221", 185.0, OptionRight::Put),
+        ]
+    );
+}
+
+#[test]
+fn test_quote_applies_snapshot_ticks() {
+    let price = |tick_type: TickType, price: f64| {
+        TickTypes::Price(TickPrice {
+            tick_type,
+            price,
+            attributes: TickAttribute::default(),
+        })
+    };
+    let computation = |field: TickType, delta: f64| {
+        TickTypes::OptionComputation(OptionComputation {
+            field,
+            delta: Some(delta),
+            ..Default::default()
+        })
+    };
+
+    let mut quote = OptionQuote::default();
+    quote.apply(price(TickType::Bid, 4.10));
+    quote.apply(price(TickType::Ask, 4.30));
+    quote.apply(price(TickType::Last, -1.0));
+    quote.apply(computation(TickType::ModelOption, 0.52));
+    quote.apply(computation(TickType::BidOption, 0.50));
+
+    assert_eq!(quote.bid, Some(4.10));
+    assert_eq!(quote.ask, Some(4.30));
+    assert_eq!(quote.last, None, "no last trade");
+    assert!((quote.midpoint().unwrap() - 4.20).abs() < 1e-12);
+    assert_eq!(quote.computation.as_ref().and_then(|computation| computation.delta), Some(0.52));
+}
+
+#[test]
+fn test_quote_completes_with_snapshot() {
+    let mut quote = OptionQuote {
+        contract: underlying(),
+        ..Default::default()
+    };
+
+    let mut snapshot = OptionQuote::default();
+    snapshot.apply(TickTypes::Price(TickPrice {
+        tick_type: TickType::Bid,
+        price: 4.10,
+        attributes: TickAttribute::default(),
+    }));
+    quote.complete(snapshot);
+
+    assert_eq!(quote.contract.contract_id, 265598);
+    assert_eq!(quote.bid, Some(4.10));
+    assert_eq!(quote.ask, None);
+}
+
+#[test]
+fn test_qualified_underlying() {
+    let qualified = qualified_underlying(&underlying(), None).unwrap();
+    assert_eq!(qualified.contract_id, 265598);
+
+    let unqualified = Contract::stock("AAPL").build();
+    let resolved = qualified_underlying(&unqualified, Some(vec![details(0.0, "")])).unwrap();
+    assert_eq!(resolved.contract_id, 2);
+
+    assert!(matches!(
+        qualified_underlying(&unqualified, Some(vec![])),
+        Err(Error::ContractNotFound(_))
+    ));
+
+    let listings = vec![details(0.0, ""), details(0.0, "C")];
+    let Err(Error::AmbiguousContract(_, candidates)) = qualified_underlying(&unqualified, Some(listings)) else {
+        panic!("expected an ambiguous underlying");
+    };
+    assert_eq!(candidates.len(), 2);
+}
+
+#[test]
+fn test_option_key_equality_matches_ordering() {
+    let key = |strike: f64| OptionKey {
+        expiration: "20250117".to_string(),
+        strike,
+        right: OptionRight::Call,
+    };
+
+    assert_eq!(key(185.0), key(185.0));
+    assert_eq!(key(f64::NAN), key(f64::NAN));
+    assert_eq!(key(f64::NAN).cmp(&key(f64::NAN)), Ordering::Equal);
+    assert_ne!(key(0.0), key(-0.0));
+    assert_ne!(key(0.0).cmp(&key(-0.0)), Ordering::Equal);
+}
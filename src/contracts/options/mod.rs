@@ -0,0 +1,6 @@
+//! Option-specific tooling that runs locally, without a round-trip to TWS.
+
+pub mod analytics;
+pub mod chain;
+
+pub use chain::{OptionChainBuilder, OptionKey, OptionQuote, OptionSurface};
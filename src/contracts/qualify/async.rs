@@ -0,0 +1,63 @@
+//! Asynchronous contract qualification.
+
+use futures::future::join_all;
+
+use super::{apply, plan, MAX_CONCURRENT_REQUESTS};
+use crate::contracts::r#async::contract_details;
+use crate::contracts::Contract;
+use crate::{Client, Error, RequestOptions};
+
+pub(crate) async fn qualify_contracts(client: &Client, contracts: &mut [Contract]) -> Result<(), Error> {
+    let cache = client.contract_cache();
+    let plan = plan(cache, contracts);
+
+    let mut responses = Vec::with_capacity(plan.queries.len());
+    for chunk in plan.queries.chunks(MAX_CONCURRENT_REQUESTS) {
+        responses.extend(join_all(chunk.iter().map(|contract| contract_details(client, contract, RequestOptions::default()))).await);
+    }
+
+    apply(cache, contracts, plan, responses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::common::test_tables::contract_details_test_cases;
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+    use std::sync::{Arc, RwLock};
+
+    #[tokio::test]
+    async fn test_qualify_contracts() {
+        let case = contract_details_test_cases()
+            .into_iter()
+            .find(|case| case.name == "stock contract details - multiple exchanges")
+            .expect("test case");
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: case.response_messages.clone(),
+        });
+        let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+        let mut contracts = vec![case.contract.clone(), Contract::stock("aapl").build()];
+        client.qualify_contracts(&mut contracts).await.expect("qualification failed");
+
+        for contract in &contracts {
+            assert_eq!(contract.contract_id, 265598);
+            assert_eq!(contract.exchange.as_str(), "SMART");
+            assert_eq!(contract.trading_class, "NMS");
+        }
+        assert_eq!(
+            message_bus.request_messages.read().unwrap().len(),
+            1,
+            "equivalent contracts share a request"
+        );
+
+        client.qualify_contracts(&mut contracts).await.expect("qualification failed");
+        assert_eq!(
+            message_bus.request_messages.read().unwrap().len(),
+            1,
+            "qualified contracts are served from the cache"
+        );
+    }
+}
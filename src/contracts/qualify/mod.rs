@@ -0,0 +1,317 @@
+//! Contract qualification backed by a cache of [ContractDetails].
+//!
+//! `Client::qualify_contracts` completes partially specified contracts with the definition TWS
+//! returns for them. Results are kept in the client's [ContractCache], keyed by contract id and by
+//! a normalized description of the requested contract, so repeated qualification does not
+//! round-trip to TWS until the entries expire.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{Contract, ContractDetails};
+use crate::Error;
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
+
+#[cfg(feature = "async")]
+pub(crate) mod r#async;
+
+#[cfg(test)]
+mod tests;
+
+/// Time a cached [ContractDetails] stays valid unless changed with [ContractCache::set_ttl].
+pub const DEFAULT_CONTRACT_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+// Contract details requests in flight at once. Keeps large batches below TWS's pacing limit of 50 messages per second.
+const MAX_CONCURRENT_REQUESTS: usize = 40;
+
+/// Cache of [ContractDetails] whose entries expire after a time to live.
+///
+/// Entries are keyed by contract id and by a normalized description of the contract they were
+/// requested for, so that `Contract::stock("aapl")` and `Contract::stock("AAPL")` share an entry.
+#[derive(Debug)]
+pub struct ContractCache {
+    inner: Mutex<CacheEntries>,
+}
+
+#[derive(Debug)]
+struct CacheEntries {
+    ttl: Duration,
+    by_id: HashMap<i32, (Instant, ContractDetails)>,
+    by_description: HashMap<String, (Instant, Vec<i32>)>,
+}
+
+impl ContractCache {
+    /// Creates an empty cache whose entries expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            inner: Mutex::new(CacheEntries {
+                ttl,
+                by_id: HashMap::new(),
+                by_description: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Time an entry stays valid.
+    pub fn ttl(&self) -> Duration {
+        self.inner.lock().unwrap().ttl
+    }
+
+    /// Changes the time to live of current and future entries.
+    pub fn set_ttl(&self, ttl: Duration) {
+        self.inner.lock().unwrap().ttl = ttl;
+    }
+
+    /// Details of the contract with `contract_id`, if cached and not expired.
+    pub fn get_by_id(&self, contract_id: i32) -> Option<ContractDetails> {
+        let inner = self.inner.lock().unwrap();
+        inner.fresh_by_id(contract_id).cloned()
+    }
+
+    /// Every contract matching `contract`, as cached by a previous request for the same description.
+    ///
+    /// A contract with a contract id is looked up by id.
+    pub fn get(&self, contract: &Contract) -> Option<Vec<ContractDetails>> {
+        if contract.contract_id != 0 {
+            return self.get_by_id(contract.contract_id).map(|details| vec![details]);
+        }
+
+        let inner = self.inner.lock().unwrap();
+        let (inserted, ids) = inner.by_description.get(&description_key(contract))?;
+        if inserted.elapsed() >= inner.ttl {
+            return None;
+        }
+        ids.iter().map(|id| inner.fresh_by_id(*id).cloned()).collect()
+    }
+
+    /// Stores `details`, the response to a request for `contract`.
+    pub fn insert(&self, contract: &Contract, details: &[ContractDetails]) {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        inner.prune(now);
+
+        // A contract listed on several exchanges is returned once per exchange; the first listing is kept.
+        let mut ids: Vec<i32> = Vec::with_capacity(details.len());
+        for entry in details {
+            if !ids.contains(&entry.contract.contract_id) {
+                ids.push(entry.contract.contract_id);
+                inner.by_id.insert(entry.contract.contract_id, (now, entry.clone()));
+            }
+        }
+        if contract.contract_id == 0 {
+            inner.by_description.insert(description_key(contract), (now, ids));
+        }
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.by_id.clear();
+        inner.by_description.clear();
+    }
+
+    /// Number of cached contracts, including expired ones not yet removed.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().by_id.len()
+    }
+
+    /// Returns true if no contract is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for ContractCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONTRACT_CACHE_TTL)
+    }
+}
+
+impl CacheEntries {
+    fn fresh_by_id(&self, contract_id: i32) -> Option<&ContractDetails> {
+        match self.by_id.get(&contract_id) {
+            Some((inserted, details)) if inserted.elapsed() < self.ttl => Some(details),
+            _ => None,
+        }
+    }
+
+    fn prune(&mut self, now: Instant) {
+        let ttl = self.ttl;
+        self.by_id.retain(|_, (inserted, _)| now.duration_since(*inserted) < ttl);
+        self.by_description.retain(|_, (inserted, _)| now.duration_since(*inserted) < ttl);
+    }
+}
+
+// Fields that identify the requested contract, normalized so equivalent requests share a key.
+fn description_key(contract: &Contract) -> String {
+    let right = match contract.right.trim().to_ascii_uppercase().as_str() {
+        "CALL" => "C".to_string(),
+        "PUT" => "P".to_string(),
+        right => right.to_string(),
+    };
+    let strike = if contract.strike == 0.0 {
+        String::new()
+    } else {
+        contract.strike.to_string()
+    };
+
+    [
+        contract.security_type.to_string(),
+        contract.symbol.as_str().to_string(),
+        contract.exchange.as_str().to_string(),
+        contract.primary_exchange.as_str().to_string(),
+        contract.currency.as_str().to_string(),
+        contract.last_trade_date_or_contract_month.clone(),
+        strike,
+        right,
+        contract.multiplier.clone(),
+        contract.local_symbol.clone(),
+        contract.trading_class.clone(),
+        contract.security_id_type.clone(),
+        contract.security_id.clone(),
+        contract.include_expired.to_string(),
+    ]
+    .iter()
+    .map(|field| field.trim().to_ascii_uppercase())
+    .collect::<Vec<_>>()
+    .join("|")
+}
+
+// Short human readable form of a contract for error messages, e.g. "STK AAPL SMART USD".
+fn describe(contract: &Contract) -> String {
+    let mut parts = vec![contract.security_type.to_string(), contract.symbol.as_str().to_string()];
+    if !contract.last_trade_date_or_contract_month.is_empty() {
+        parts.push(contract.last_trade_date_or_contract_month.clone());
+    }
+    if contract.strike != 0.0 {
+        parts.push(format!("{}{}", contract.strike, contract.right));
+    }
+    for field in [contract.exchange.as_str(), contract.primary_exchange.as_str(), contract.currency.as_str()] {
+        if !field.is_empty() {
+            parts.push(field.to_string());
+        }
+    }
+    if contract.contract_id != 0 {
+        parts.push(format!("(conid {})", contract.contract_id));
+    }
+    parts.join(" ")
+}
+
+// Picks the definition of `contract` among the `details` TWS returned for it.
+pub(super) fn resolve(contract: &Contract, details: &[ContractDetails]) -> Result<Contract, Error> {
+    let mut candidates: Vec<&Contract> = Vec::with_capacity(details.len());
+    for entry in details {
+        if !candidates.iter().any(|candidate| candidate.contract_id == entry.contract.contract_id) {
+            candidates.push(&entry.contract);
+        }
+    }
+
+    let chosen = match candidates.as_slice() {
+        [] => return Err(Error::ContractNotFound(describe(contract))),
+        [only] => *only,
+        _ => {
+            let matching: Vec<&Contract> = candidates
+                .iter()
+                .copied()
+                .filter(|candidate| contract.contract_id == 0 || candidate.contract_id == contract.contract_id)
+                .filter(|candidate| contract.primary_exchange.is_empty() || candidate.primary_exchange == contract.primary_exchange)
+                .collect();
+            match matching.as_slice() {
+                [only] => *only,
+                _ => return Err(Error::AmbiguousContract(describe(contract), candidates.into_iter().cloned().collect())),
+            }
+        }
+    };
+
+    let mut qualified = chosen.clone();
+    // TWS echoes the routing exchange of the request; keep the caller's choice.
+    if !contract.exchange.is_empty() {
+        qualified.exchange = contract.exchange.clone();
+    }
+    Ok(qualified)
+}
+
+// Which contracts are answered from the cache and which requests are needed for the rest.
+struct Plan {
+    cached: Vec<Option<Vec<ContractDetails>>>,
+    // Request resolving each contract missing from the cache, as an index into queries.
+    query_of: Vec<Option<usize>>,
+    // Contracts to request, without duplicate descriptions.
+    queries: Vec<Contract>,
+}
+
+fn plan(cache: &ContractCache, contracts: &[Contract]) -> Plan {
+    let mut plan = Plan {
+        cached: Vec::with_capacity(contracts.len()),
+        query_of: Vec::with_capacity(contracts.len()),
+        queries: Vec::new(),
+    };
+    let mut keys: HashMap<String, usize> = HashMap::new();
+
+    for contract in contracts {
+        match cache.get(contract) {
+            Some(details) => {
+                plan.cached.push(Some(details));
+                plan.query_of.push(None);
+            }
+            None => {
+                let key = if contract.contract_id != 0 {
+                    format!("conid:{}", contract.contract_id)
+                } else {
+                    description_key(contract)
+                };
+                let index = *keys.entry(key).or_insert_with(|| {
+                    plan.queries.push(contract.clone());
+                    plan.queries.len() - 1
+                });
+                plan.cached.push(None);
+                plan.query_of.push(Some(index));
+            }
+        }
+    }
+    plan
+}
+
+// Caches the responses and updates every contract that resolves. The others are reported together, by index.
+fn apply(
+    cache: &ContractCache,
+    contracts: &mut [Contract],
+    plan: Plan,
+    mut responses: Vec<Result<Vec<ContractDetails>, Error>>,
+) -> Result<(), Error> {
+    for (query, response) in plan.queries.iter().zip(&responses) {
+        if let Ok(details) = response {
+            cache.insert(query, details);
+        }
+    }
+
+    let mut failures = Vec::new();
+    for (i, contract) in contracts.iter_mut().enumerate() {
+        let result = match (&plan.cached[i], plan.query_of[i]) {
+            (Some(details), _) => resolve(contract, details),
+            (None, Some(query)) => match &mut responses[query] {
+                Ok(details) => resolve(contract, details),
+                // Errors are not Clone, so contracts sharing a failed request after the first get its message.
+                Err(error) => {
+                    let shared = Error::Simple(error.to_string());
+                    Err(std::mem::replace(error, shared))
+                }
+            },
+            (None, None) => continue,
+        };
+
+        match result {
+            Ok(qualified) => *contract = qualified,
+            Err(error) => failures.push((i, error)),
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::QualificationFailed(failures))
+    }
+}
@@ -0,0 +1,106 @@
+//! Blocking contract qualification.
+
+use super::{apply, plan, MAX_CONCURRENT_REQUESTS};
+use crate::client::sync::Client;
+use crate::contracts::sync::{read_contract_details, request_contract_details};
+use crate::contracts::Contract;
+use crate::{Error, RequestOptions};
+
+pub(crate) fn qualify_contracts(client: &Client, contracts: &mut [Contract]) -> Result<(), Error> {
+    let cache = client.contract_cache();
+    let plan = plan(cache, contracts);
+
+    // Each chunk is sent in full before any response is read, so the requests are served concurrently.
+    let mut responses = Vec::with_capacity(plan.queries.len());
+    for chunk in plan.queries.chunks(MAX_CONCURRENT_REQUESTS) {
+        let pending: Vec<_> = chunk.iter().map(|contract| request_contract_details(client, contract)).collect();
+        let deadline = RequestOptions::default().deadline(client.request_timeout());
+        responses.extend(
+            pending
+                .into_iter()
+                .map(|request| request.and_then(|subscription| read_contract_details(client, &subscription, deadline))),
+        );
+    }
+
+    apply(cache, contracts, plan, responses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::common::test_tables::{contract_details_test_cases, ContractDetailsTestCase};
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+    use std::sync::{Arc, RwLock};
+
+    fn test_case(name: &str) -> ContractDetailsTestCase {
+        contract_details_test_cases()
+            .into_iter()
+            .find(|case| case.name == name)
+            .expect("test case")
+    }
+
+    #[test]
+    fn test_qualify_contracts() {
+        let case = test_case("stock contract details - multiple exchanges");
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: case.response_messages.clone(),
+        });
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+        // The same stock listed on two exchanges is one contract.
+        let mut contracts = vec![case.contract.clone(), Contract::stock("aapl").build()];
+        client.qualify_contracts(&mut contracts).expect("qualification failed");
+
+        for contract in &contracts {
+            assert_eq!(contract.contract_id, 265598);
+            assert_eq!(contract.exchange.as_str(), "SMART");
+            assert_eq!(contract.trading_class, "NMS");
+        }
+        let request_messages = client.message_bus.request_messages();
+        assert_eq!(request_messages.len(), 1, "equivalent contracts share a request");
+        assert_eq!(request_messages[0].encode_simple(), case.expected_request);
+
+        let mut again = vec![Contract::stock("AAPL").build()];
+        client.qualify_contracts(&mut again).expect("qualification failed");
+        assert_eq!(again[0].contract_id, 265598);
+        assert_eq!(
+            client.message_bus.request_messages().len(),
+            1,
+            "second qualification is served from the cache"
+        );
+        assert!(client.contract_cache().get_by_id(265598).is_some());
+    }
+
+    #[test]
+    fn test_qualify_contracts_ambiguous() {
+        let aapl = test_case("stock contract details - multiple exchanges");
+        let tsla = test_case("stock contract details");
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: vec![
+                aapl.response_messages[0].clone(),
+                tsla.response_messages[0].clone(),
+                aapl.response_messages[2].clone(),
+            ],
+        });
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+        let mut contracts = vec![Contract::stock("AAPL").build()];
+        let error = client.qualify_contracts(&mut contracts).unwrap_err();
+
+        let Error::QualificationFailed(mut failures) = error else {
+            panic!("unexpected error: {error:?}");
+        };
+        let (index, error) = failures.remove(0);
+        assert_eq!(index, 0);
+        let Error::AmbiguousContract(description, candidates) = error else {
+            panic!("unexpected error: {error:?}");
+        };
+        assert_eq!(description, "STK AAPL SMART USD");
+        let ids: Vec<i32> = candidates.iter().map(|candidate| candidate.contract_id).collect();
+        assert_eq!(ids, vec![265598, 459200101]);
+        assert_eq!(contracts[0].contract_id, 0, "ambiguous contracts are left unchanged");
+    }
+}
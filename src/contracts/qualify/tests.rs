@@ -0,0 +1,193 @@
+use super::*;
+use crate::contracts::{Currency, Exchange, SecurityType, Symbol};
+
+fn stock(symbol: &str) -> Contract {
+    Contract {
+        symbol: Symbol::from(symbol),
+        security_type: SecurityType::Stock,
+        exchange: Exchange::from("SMART"),
+        currency: Currency::from("USD"),
+        ..Default::default()
+    }
+}
+
+fn listing(symbol: &str, contract_id: i32, exchange: &str, primary_exchange: &str) -> ContractDetails {
+    ContractDetails {
+        contract: Contract {
+            contract_id,
+            exchange: Exchange::from(exchange),
+            primary_exchange: Exchange::from(primary_exchange),
+            trading_class: "NMS".to_string(),
+            ..stock(symbol)
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_description_key() {
+    assert_eq!(description_key(&stock("aapl")), description_key(&stock("AAPL")));
+    assert_ne!(description_key(&stock("AAPL")), description_key(&stock("MSFT")));
+
+    let option = |right: &str| Contract {
+        security_type: SecurityType::Option,
+        last_trade_date_or_contract_month: "20250117".to_string(),
+        strike: 150.0,
+        right: right.to_string(),
+        ..stock("AAPL")
+    };
+    assert_eq!(description_key(&option("CALL")), description_key(&option("C")));
+    assert_eq!(description_key(&option("put")), description_key(&option("P")));
+    assert_ne!(description_key(&option("C")), description_key(&option("P")));
+}
+
+#[test]
+fn test_describe() {
+    assert_eq!(describe(&stock("AAPL")), "STK AAPL SMART USD");
+
+    let contract = Contract {
+        contract_id: 265598,
+        primary_exchange: Exchange::from("NASDAQ"),
+        ..stock("AAPL")
+    };
+    assert_eq!(describe(&contract), "STK AAPL SMART NASDAQ USD (conid 265598)");
+}
+
+#[test]
+fn test_cache() {
+    let cache = ContractCache::default();
+    assert_eq!(cache.ttl(), DEFAULT_CONTRACT_CACHE_TTL);
+    assert!(cache.is_empty());
+    assert!(cache.get(&stock("AAPL")).is_none());
+
+    cache.insert(
+        &stock("aapl"),
+        &[listing("AAPL", 265598, "SMART", "NASDAQ"), listing("AAPL", 265598, "NYSE", "NYSE")],
+    );
+    assert_eq!(cache.len(), 1, "listings of the same contract are cached once");
+
+    let details = cache.get(&stock("AAPL")).expect("cached by description");
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0].contract.exchange.as_str(), "SMART");
+
+    let by_id = Contract {
+        contract_id: 265598,
+        ..Default::default()
+    };
+    assert!(cache.get(&by_id).is_some(), "cached by contract id");
+    assert!(cache.get_by_id(265598).is_some());
+    assert!(cache.get_by_id(1).is_none());
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert!(cache.get(&stock("AAPL")).is_none());
+}
+
+#[test]
+fn test_cache_expiry() {
+    let cache = ContractCache::new(Duration::from_secs(60));
+    cache.insert(&stock("AAPL"), &[listing("AAPL", 265598, "SMART", "NASDAQ")]);
+    assert!(cache.get(&stock("AAPL")).is_some());
+
+    cache.set_ttl(Duration::ZERO);
+    assert!(cache.get(&stock("AAPL")).is_none());
+    assert!(cache.get_by_id(265598).is_none());
+
+    cache.insert(&stock("MSFT"), &[listing("MSFT", 272093, "SMART", "NASDAQ")]);
+    assert_eq!(cache.len(), 1, "expired entries are pruned on insert");
+}
+
+#[test]
+fn test_resolve() {
+    let error = resolve(&stock("AAPL"), &[]).unwrap_err();
+    assert!(matches!(error, Error::ContractNotFound(ref description) if description == "STK AAPL SMART USD"));
+
+    let details = [listing("AAPL", 265598, "SMART", "NASDAQ"), listing("AAPL", 265598, "NYSE", "NYSE")];
+    let qualified = resolve(&stock("AAPL"), &details).expect("unique contract");
+    assert_eq!(qualified.contract_id, 265598);
+    assert_eq!(qualified.primary_exchange.as_str(), "NASDAQ");
+    assert_eq!(qualified.trading_class, "NMS");
+
+    let routed = Contract {
+        exchange: Exchange::from("ISLAND"),
+        ..stock("AAPL")
+    };
+    assert_eq!(
+        resolve(&routed, &details).unwrap().exchange.as_str(),
+        "ISLAND",
+        "caller's exchange is kept"
+    );
+}
+
+#[test]
+fn test_resolve_ambiguous() {
+    let details = [listing("ABC", 1, "SMART", "NYSE"), listing("ABC", 2, "SMART", "ARCA")];
+
+    let error = resolve(&stock("ABC"), &details).unwrap_err();
+    let Error::AmbiguousContract(description, candidates) = error else {
+        panic!("unexpected error: {error:?}");
+    };
+    assert_eq!(description, "STK ABC SMART USD");
+    assert_eq!(candidates.iter().map(|candidate| candidate.contract_id).collect::<Vec<_>>(), vec![1, 2]);
+
+    let on_arca = Contract {
+        primary_exchange: Exchange::from("ARCA"),
+        ..stock("ABC")
+    };
+    assert_eq!(resolve(&on_arca, &details).unwrap().contract_id, 2);
+
+    let by_id = Contract {
+        contract_id: 1,
+        ..stock("ABC")
+    };
+    assert_eq!(resolve(&by_id, &details).unwrap().contract_id, 1);
+}
+
+#[test]
+fn test_plan_and_apply() {
+    let cache = ContractCache::default();
+    cache.insert(&stock("AAPL"), &[listing("AAPL", 265598, "SMART", "NASDAQ")]);
+
+    let mut contracts = vec![stock("AAPL"), stock("msft"), stock("MSFT"), stock("XYZ")];
+    let plan = plan(&cache, &contracts);
+    assert_eq!(plan.queries.len(), 2, "cached and duplicate contracts are not requested");
+
+    let responses = vec![Ok(vec![listing("MSFT", 272093, "SMART", "NASDAQ")]), Ok(vec![])];
+    let error = apply(&cache, &mut contracts, plan, responses).unwrap_err();
+
+    let Error::QualificationFailed(failures) = error else {
+        panic!("unexpected error: {error:?}");
+    };
+    assert_eq!(failures.len(), 1);
+    assert!(matches!(failures[0], (3, Error::ContractNotFound(_))));
+    assert_eq!(contracts[0].contract_id, 265598);
+    assert_eq!(contracts[1].contract_id, 272093);
+    assert_eq!(contracts[2].contract_id, 272093);
+    assert_eq!(contracts[3].contract_id, 0);
+    assert!(cache.get(&stock("MSFT")).is_some());
+}
+
+#[test]
+fn test_apply_reports_every_failure() {
+    let cache = ContractCache::default();
+    let mut contracts = vec![stock("ABC"), stock("MSFT"), stock("XYZ"), stock("xyz")];
+    let plan = plan(&cache, &contracts);
+    assert_eq!(plan.queries.len(), 3);
+
+    let responses = vec![
+        Ok(vec![listing("ABC", 1, "SMART", "NYSE"), listing("ABC", 2, "SMART", "ARCA")]),
+        Ok(vec![listing("MSFT", 272093, "SMART", "NASDAQ")]),
+        Err(Error::Message(200, "No security definition has been found for the request".to_string())),
+    ];
+    let error = apply(&cache, &mut contracts, plan, responses).unwrap_err();
+
+    let Error::QualificationFailed(failures) = error else {
+        panic!("unexpected error: {error:?}");
+    };
+    let indexes: Vec<usize> = failures.iter().map(|(index, _)| *index).collect();
+    assert_eq!(indexes, vec![0, 2, 3], "every failed contract is reported");
+    assert!(matches!(failures[0].1, Error::AmbiguousContract(..)));
+    assert!(matches!(failures[1].1, Error::Message(200, _)));
+    assert!(matches!(&failures[2].1, Error::Simple(message) if message.contains("No security definition")));
+    assert_eq!(contracts[1].contract_id, 272093, "contracts that resolve are still updated");
+}
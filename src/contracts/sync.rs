@@ -5,8 +5,11 @@ use crate::common::request_helpers;
 use crate::messages::{IncomingMessages, OutgoingMessages};
 use crate::protocol::{check_version, Features};
 use crate::subscriptions::StreamDecoder;
+use crate::transport::InternalSubscription;
+use crate::RequestOptions;
 use crate::{client::sync::Client, Error};
-use log::{error, info};
+use log::{error, info, warn};
+use std::time::Instant;
 
 // Requests contract information.
 //
@@ -15,27 +18,59 @@ use log::{error, info};
 // # Arguments
 // * `client` - [Client] with an active connection to gateway.
 // * `contract` - The [Contract] used as sample to query the available contracts. Typically, it will contain the [Contract]'s symbol, currency, security_type, and exchange.
-pub(crate) fn contract_details(client: &Client, contract: &Contract) -> Result<Vec<ContractDetails>, Error> {
+// * `options` - Timeout of the request. On expiry the request is cancelled at TWS.
+pub(crate) fn contract_details(client: &Client, contract: &Contract, options: RequestOptions) -> Result<Vec<ContractDetails>, Error> {
+    let responses = request_contract_details(client, contract)?;
+    read_contract_details(client, &responses, options.deadline(client.request_timeout()))
+}
+
+// Requests the details of the bond with a CUSIP or ISIN. Bond terms are returned in ContractDetails::bond.
+pub(crate) fn bond_contract_details(client: &Client, identifier: BondIdentifier) -> Result<Vec<ContractDetails>, Error> {
+    contract_details(client, &Contract::bond(identifier), RequestOptions::default())
+}
+
+// Sends a contract details request without waiting for the response, so several can be in flight.
+pub(crate) fn request_contract_details(client: &Client, contract: &Contract) -> Result<InternalSubscription, Error> {
     verify_contract(client, contract)?;
 
     let builder = client.request();
     let request_id = builder.request_id();
     let packet = encoders::encode_request_contract_data(client.server_version, request_id, contract)?;
 
-    let responses = builder.send_raw(packet)?;
+    builder.send_raw(packet)
+}
 
+// Collects the responses to a request sent by request_contract_details, cancelling the request if the deadline passes first.
+pub(crate) fn read_contract_details(
+    client: &Client,
+    responses: &InternalSubscription,
+    deadline: Option<Instant>,
+) -> Result<Vec<ContractDetails>, Error> {
     let mut contract_details: Vec<ContractDetails> = Vec::default();
 
-    while let Some(response) = responses.next() {
+    while let Some(response) = responses.next_until(deadline) {
         log::debug!("response: {response:#?}");
         match response {
             Ok(mut message) if message.message_type() == IncomingMessages::ContractData => {
                 let decoded = decoders::decode_contract_details(client.server_version, &mut message)?;
                 contract_details.push(decoded);
             }
+            Ok(mut message) if message.message_type() == IncomingMessages::BondContractData => {
+                let decoded = decoders::decode_bond_contract_details(client.server_version, &mut message)?;
+                contract_details.push(decoded);
+            }
             Ok(message) if message.message_type() == IncomingMessages::ContractDataEnd => return Ok(contract_details),
             Ok(message) if message.message_type() == IncomingMessages::Error => return Err(Error::from(message)),
             Ok(message) => return Err(Error::UnexpectedResponse(message)),
+            Err(Error::Timeout) => {
+                // Older servers cannot cancel contract data requests; the late answer is discarded.
+                if let (Some(request_id), Ok(())) = (responses.request_id, check_version(client.server_version, Features::CANCEL_CONTRACT_DATA)) {
+                    if let Err(e) = cancel_contract_details(client, request_id) {
+                        warn!("error cancelling expired contract details request {request_id}: {e}");
+                    }
+                }
+                return Err(Error::Timeout);
+            }
             Err(e) => return Err(e),
         }
     }
@@ -68,7 +103,8 @@ pub(crate) fn verify_contract(client: &Client, contract: &Contract) -> Result<()
 // # Arguments
 // * `client` - [Client] with an active connection to gateway.
 // * `pattern` - Either start of ticker symbol or (for larger strings) company name.
-pub(crate) fn matching_symbols(client: &Client, pattern: &str) -> Result<Vec<ContractDescription>, Error> {
+// * `options` - Timeout of the request. TWS has no cancel message for matching symbols.
+pub(crate) fn matching_symbols(client: &Client, pattern: &str, options: RequestOptions) -> Result<Vec<ContractDescription>, Error> {
     check_version(client.server_version, Features::REQ_MATCHING_SYMBOLS)?;
 
     let builder = client.request();
@@ -76,8 +112,8 @@ pub(crate) fn matching_symbols(client: &Client, pattern: &str) -> Result<Vec<Con
     let request = encoders::encode_request_matching_symbols(request_id, pattern)?;
     let subscription = builder.send_raw(request)?;
 
-    if let Some(Ok(mut message)) = subscription.next() {
-        match message.message_type() {
+    match subscription.next_until(options.deadline(client.request_timeout())) {
+        Some(Ok(mut message)) => match message.message_type() {
             IncomingMessages::SymbolSamples => {
                 return decoders::decode_contract_descriptions(client.server_version, &mut message);
             }
@@ -90,7 +126,9 @@ pub(crate) fn matching_symbols(client: &Client, pattern: &str) -> Result<Vec<Con
                 info!("unexpected message: {message:?}");
                 return Err(Error::Simple(format!("unexpected message: {message:?}")));
             }
-        }
+        },
+        Some(Err(Error::Timeout)) => return Err(Error::Timeout),
+        _ => {}
     }
 
     Ok(Vec::default())
@@ -100,13 +138,15 @@ pub(crate) fn matching_symbols(client: &Client, pattern: &str) -> Result<Vec<Con
 //
 // The market rule for an instrument on a particular exchange provides details about how the minimum price increment changes with price.
 // A list of market rule ids can be obtained by invoking [request_contract_details] on a particular contract. The returned market rule ID list will provide the market rule ID for the instrument in the correspond valid exchange list in [ContractDetails].
-pub(crate) fn market_rule(client: &Client, market_rule_id: i32) -> Result<MarketRule, Error> {
+//
+// TWS has no cancel message for market rules, so an expired request is only abandoned.
+pub(crate) fn market_rule(client: &Client, market_rule_id: i32, options: RequestOptions) -> Result<MarketRule, Error> {
     check_version(client.server_version, Features::MARKET_RULES)?;
 
     let request = encoders::encode_request_market_rule(market_rule_id)?;
     let subscription = client.shared_request(OutgoingMessages::RequestMarketRule).send_raw(request)?;
 
-    match subscription.next() {
+    match subscription.next_until(options.deadline(client.request_timeout())) {
         Some(Ok(mut message)) => Ok(decoders::decode_market_rule(&mut message)?),
         Some(Err(e)) => Err(e),
         None => Err(Error::Simple("no market rule found".into())),
@@ -205,7 +245,7 @@ mod tests {
             });
 
             let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
-            let result = contract_details(&client, &test_case.contract);
+            let result = contract_details(&client, &test_case.contract, RequestOptions::default());
 
             let request_messages = client.message_bus.request_messages();
             assert_eq!(
@@ -223,6 +263,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bond_contract_details() {
+        let test_case = contract_details_test_cases()
+            .into_iter()
+            .find(|test_case| test_case.name == "bond contract data by CUSIP")
+            .expect("test case");
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: test_case.response_messages.clone(),
+        });
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+        let details = bond_contract_details(&client, BondIdentifier::Cusip(Cusip::new("912810TL8"))).expect("request failed");
+
+        assert_eq!(client.message_bus.request_messages()[0].encode_simple(), test_case.expected_request);
+        (test_case.validations)(&details);
+    }
+
     #[test]
     fn test_matching_symbols() {
         for test_case in matching_symbols_test_cases() {
@@ -232,7 +290,7 @@ mod tests {
             });
 
             let client = Client::stubbed(message_bus, server_versions::BOND_ISSUERID);
-            let result = matching_symbols(&client, test_case.pattern);
+            let result = matching_symbols(&client, test_case.pattern, RequestOptions::default());
 
             let request_messages = client.message_bus.request_messages();
             assert_eq!(
@@ -257,7 +315,7 @@ mod tests {
             });
 
             let client = Client::stubbed(message_bus, server_versions::MARKET_RULES);
-            let result = market_rule(&client, test_case.market_rule_id);
+            let result = market_rule(&client, test_case.market_rule_id, RequestOptions::default());
 
             let request_messages = client.message_bus.request_messages();
             assert_eq!(
@@ -535,7 +593,7 @@ mod tests {
             });
 
             let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
-            let result = contract_details(&client, &test_case.contract);
+            let result = contract_details(&client, &test_case.contract, RequestOptions::default());
 
             if test_case.should_error {
                 assert!(result.is_err(), "Test '{}' should have failed", test_case.name);
@@ -0,0 +1,130 @@
+//! Numeric representation for quantities, sizes and volumes.
+//!
+//! TWS reports fractional sizes (`FRACTIONAL_SIZE_SUPPORT`) as decimal strings. By default
+//! they are decoded into `f64`. Enabling the `decimal` feature switches [Decimal] to
+//! [rust_decimal::Decimal] so crypto and fractional-share quantities keep the exact
+//! precision sent on the wire, and are written back unchanged.
+
+/// Type used for order quantities, position sizes, tick sizes, bar volumes and execution shares.
+///
+/// Resolves to `f64` unless the `decimal` feature is enabled.
+#[cfg(not(feature = "decimal"))]
+pub type Decimal = f64;
+
+/// Type used for order quantities, position sizes, tick sizes, bar volumes and execution shares.
+///
+/// Resolves to [rust_decimal::Decimal] because the `decimal` feature is enabled.
+#[cfg(feature = "decimal")]
+pub type Decimal = rust_decimal::Decimal;
+
+/// Sentinel TWS sends for an unset decimal value (`Decimal.INVALID_VALUE` in the reference client).
+pub(crate) const UNSET_DECIMAL: &str = "170141183460469231731687303715884105727";
+
+/// Value of an unset [Decimal], as decoded from the TWS sentinel.
+///
+/// Fields holding it are written back to TWS as the sentinel, so an unset value round-trips.
+#[cfg(not(feature = "decimal"))]
+pub const UNSET: Decimal = 170141183460469231731687303715884105727.0;
+
+/// Value of an unset [Decimal], as decoded from the TWS sentinel.
+///
+/// The sentinel does not fit in [rust_decimal::Decimal], so [Decimal::MAX] stands in for it and is
+/// written back to TWS as the sentinel.
+#[cfg(feature = "decimal")]
+pub const UNSET: Decimal = Decimal::MAX;
+
+/// Converts an `f64` (e.g. a builder argument) into a [Decimal].
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn from_f64(value: f64) -> Decimal {
+    value
+}
+
+/// Converts an `f64` (e.g. a builder argument) into a [Decimal].
+///
+/// Values are rounded to their shortest decimal representation, so `0.1` becomes `0.1`
+/// rather than the nearest binary fraction. Non-finite values map to zero.
+#[cfg(feature = "decimal")]
+pub(crate) fn from_f64(value: f64) -> Decimal {
+    use rust_decimal::prelude::FromPrimitive;
+    Decimal::from_f64(value).unwrap_or_default()
+}
+
+/// Converts a [Decimal] into an `f64` for arithmetic that does not need exact precision.
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn to_f64(value: Decimal) -> f64 {
+    value
+}
+
+/// Converts a [Decimal] into an `f64` for arithmetic that does not need exact precision.
+#[cfg(feature = "decimal")]
+pub(crate) fn to_f64(value: Decimal) -> f64 {
+    use rust_decimal::prelude::ToPrimitive;
+    value.to_f64().unwrap_or_default()
+}
+
+/// Parses a decimal field as sent by TWS.
+///
+/// Empty fields decode as zero and the unset sentinel decodes as [UNSET].
+pub(crate) fn parse(field: &str) -> Result<Decimal, String> {
+    if field.is_empty() {
+        return Ok(Decimal::default());
+    }
+    if field == UNSET_DECIMAL {
+        return Ok(UNSET);
+    }
+    parse_value(field)
+}
+
+#[cfg(not(feature = "decimal"))]
+fn parse_value(field: &str) -> Result<Decimal, String> {
+    field.parse::<f64>().map_err(|err| err.to_string())
+}
+
+#[cfg(feature = "decimal")]
+fn parse_value(field: &str) -> Result<Decimal, String> {
+    use std::str::FromStr;
+    Decimal::from_str(field)
+        .or_else(|_| Decimal::from_scientific(field))
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(feature = "decimal")]
+impl crate::ToField for Decimal {
+    fn to_field(&self) -> String {
+        if *self == UNSET {
+            UNSET_DECIMAL.to_string()
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ToField;
+
+    #[test]
+    fn test_parse_empty_and_unset() {
+        assert_eq!(parse("").unwrap(), Decimal::default());
+        assert_eq!(parse(UNSET_DECIMAL).unwrap(), UNSET);
+        assert!(parse("abc").is_err());
+    }
+
+    #[test]
+    fn test_unset_round_trip() {
+        let unset = parse(UNSET_DECIMAL).unwrap();
+        assert_eq!(unset.to_field(), UNSET_DECIMAL);
+        assert_eq!(parse(&unset.to_field()).unwrap(), UNSET);
+
+        let quantity = parse("100.5").unwrap();
+        assert_eq!(quantity.to_field(), "100.5");
+    }
+
+    #[test]
+    fn test_f64_round_trip() {
+        assert_eq!(to_f64(from_f64(100.0)), 100.0);
+        assert_eq!(to_f64(from_f64(0.25)), 0.25);
+    }
+}
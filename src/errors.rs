@@ -6,6 +6,7 @@
 use std::{num::ParseIntError, string::FromUtf8Error};
 use thiserror::Error;
 
+use crate::contracts::Contract;
 use crate::market_data::historical::HistoricalParseError;
 use crate::messages::ResponseMessage;
 use crate::orders::builder::ValidationError;
@@ -73,6 +74,10 @@ pub enum Error {
     #[error("Cancelled")]
     Cancelled,
 
+    /// TWS did not answer a request within its timeout.
+    #[error("Timeout")]
+    Timeout,
+
     /// Client is shutting down.
     #[error("Shutdown")]
     Shutdown,
@@ -101,6 +106,21 @@ pub enum Error {
     /// Wraps errors parsing historical data parameters.
     #[error("HistoricalParseError: {0}")]
     HistoricalParseError(HistoricalParseError),
+
+    /// No contract matches the description.
+    /// Contains: (contract_description)
+    #[error("ContractNotFound: {0}")]
+    ContractNotFound(String),
+
+    /// More than one contract matches the description.
+    /// Contains: (contract_description, candidates)
+    #[error("AmbiguousContract: {0} matches {count} contracts", count = .1.len())]
+    AmbiguousContract(String, Vec<Contract>),
+
+    /// Some of the contracts passed to `qualify_contracts` could not be qualified.
+    /// Contains: (failures as index of the contract and its error)
+    #[error("QualificationFailed: {count} contracts could not be qualified", count = .0.len())]
+    QualificationFailed(Vec<(usize, Error)>),
 }
 
 impl From<ResponseMessage> for Error {
@@ -178,6 +198,7 @@ mod tests {
             ),
             (Error::ConnectionFailed, "ConnectionFailed"),
             (Error::Cancelled, "Cancelled"),
+            (Error::Timeout, "Timeout"),
             (Error::Simple("simple error".to_string()), "error occurred: simple error"),
         ];
 
@@ -52,6 +52,7 @@ pub(crate) mod transport;
 /// Connection management
 pub(crate) mod connection;
 
+pub use connection::ConnectionEvent;
 /// Callback for handling unsolicited messages during connection setup.
 ///
 /// When TWS sends messages like `OpenOrder` or `OrderStatus` during the connection
@@ -87,6 +88,7 @@ pub(crate) mod connection;
 /// }
 /// ```
 pub use connection::ConnectionOptions;
+pub use connection::RequestOptions;
 pub use connection::StartupMessageCallback;
 
 /// Common utilities shared across modules
@@ -113,12 +115,17 @@ pub mod news;
 pub mod orders;
 /// APIs for working with the market scanner.
 pub mod scanner;
+/// Offline broker simulator for testing strategies without a Gateway.
+pub mod simulator;
 /// APIs for working with Wall Street Horizon: Earnings Calendar & Event Data.
 pub mod wsh;
 
 /// Server interaction tracing for debugging and monitoring
 pub mod trace;
 
+/// Hooks for exporting client metrics.
+pub mod metrics;
+
 /// A prelude module for convenient importing of commonly used types.
 pub mod prelude;
 
@@ -131,6 +138,11 @@ pub mod proto;
 
 mod server_versions;
 
+mod decimal;
+
+#[doc(inline)]
+pub use decimal::{Decimal, UNSET as UNSET_DECIMAL};
+
 #[doc(inline)]
 pub use errors::Error;
 
@@ -211,6 +223,11 @@ impl ToField for Option<i32> {
 
 impl ToField for f64 {
     fn to_field(&self) -> String {
+        // Without the decimal feature, unset quantities are f64 and must go back to TWS as its sentinel.
+        #[cfg(not(feature = "decimal"))]
+        if *self == decimal::UNSET {
+            return decimal::UNSET_DECIMAL.to_string();
+        }
         self.to_string()
     }
 }
@@ -222,7 +239,7 @@ impl ToField for Option<f64> {
 }
 
 fn date_format() -> Vec<BorrowedFormatItem<'static>> {
-    format_description::parse("[year][month][day]").unwrap()
+    format_description::parse_borrowed::<2>("[year][month][day]").unwrap()
 }
 
 static DATE_FORMAT: LazyLock<Vec<BorrowedFormatItem<'static>>> = LazyLock::new(date_format);
@@ -89,6 +89,7 @@ impl<'a> MarketDataBuilder<'a, crate::client::sync::Client> {
     /// Subscribe to market data
     ///
     /// Returns a subscription that yields TickTypes as market data arrives.
+    /// For contracts such as CFDs, TWS may answer with [TickTypes::Rerouted] naming the contract to subscribe to instead.
     ///
     /// # Examples
     ///
@@ -122,6 +123,7 @@ impl<'a> MarketDataBuilder<'a, crate::client::r#async::Client> {
     /// Subscribe to market data
     ///
     /// Returns a subscription that yields TickTypes as market data arrives.
+    /// For contracts such as CFDs, TWS may answer with [TickTypes::Rerouted] naming the contract to subscribe to instead.
     ///
     /// # Examples
     ///
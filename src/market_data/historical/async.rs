@@ -7,11 +7,12 @@ use time::OffsetDateTime;
 use time_tz::Tz;
 
 use crate::client::ClientRequestBuilders;
+use crate::common::request_helpers::{self, CancelOnDrop};
 use crate::contracts::Contract;
 use crate::messages::IncomingMessages;
 use crate::protocol::{check_version, Features};
 use crate::transport::{AsyncInternalSubscription, AsyncMessageBus};
-use crate::{Client, Error, MAX_RETRIES};
+use crate::{Client, Error, RequestOptions, MAX_RETRIES};
 
 use super::common::{decoders, encoders};
 use super::{
@@ -27,22 +28,44 @@ pub async fn head_timestamp(
     contract: &Contract,
     what_to_show: WhatToShow,
     trading_hours: TradingHours,
+) -> Result<OffsetDateTime, Error> {
+    head_timestamp_with_options(client, contract, what_to_show, trading_hours, RequestOptions::default()).await
+}
+
+/// Returns the timestamp of earliest available historical data, failing with [Error::Timeout] if TWS does not answer in time.
+///
+/// The request is cancelled at TWS when it times out or the future is dropped.
+pub async fn head_timestamp_with_options(
+    client: &Client,
+    contract: &Contract,
+    what_to_show: WhatToShow,
+    trading_hours: TradingHours,
+    options: RequestOptions,
 ) -> Result<OffsetDateTime, Error> {
     check_version(client.server_version(), Features::HEAD_TIMESTAMP)?;
 
-    let builder = client.request();
-    let request = encoders::encode_request_head_timestamp(builder.request_id(), contract, what_to_show, trading_hours.use_rth())?;
-    let mut subscription = builder.send_raw(request).await?;
-
-    match subscription.next().await {
-        Some(Ok(mut message)) if message.message_type() == IncomingMessages::HeadTimestamp => Ok(decoders::decode_head_timestamp(&mut message)?),
-        Some(Ok(message)) => Err(Error::UnexpectedResponse(message)),
-        Some(Err(e)) => Err(e),
-        None => {
-            // Connection might have been reset, retry
-            Box::pin(head_timestamp(client, contract, what_to_show, trading_hours)).await
+    request_helpers::with_timeout(options.resolve_timeout(client.request_timeout()), async {
+        loop {
+            let builder = client.request();
+            let request_id = builder.request_id();
+            let request = encoders::encode_request_head_timestamp(request_id, contract, what_to_show, trading_hours.use_rth())?;
+            let mut subscription = builder.send_raw(request).await?;
+            let cancel = CancelOnDrop::new(client, Some(encoders::encode_cancel_head_timestamp(request_id)?));
+
+            let response = subscription.next().await;
+            cancel.disarm();
+
+            match response {
+                Some(Ok(mut message)) if message.message_type() == IncomingMessages::HeadTimestamp => {
+                    return decoders::decode_head_timestamp(&mut message)
+                }
+                Some(Ok(message)) => return Err(Error::UnexpectedResponse(message)),
+                Some(Err(e)) => return Err(e),
+                None => continue, // Connection might have been reset, retry
+            }
         }
-    }
+    })
+    .await
 }
 
 /// Requests historical data for a contract.
@@ -58,6 +81,33 @@ pub async fn historical_data(
     bar_size: BarSize,
     what_to_show: Option<WhatToShow>,
     trading_hours: TradingHours,
+) -> Result<HistoricalData, Error> {
+    historical_data_with_options(
+        client,
+        contract,
+        end_date,
+        duration,
+        bar_size,
+        what_to_show,
+        trading_hours,
+        RequestOptions::default(),
+    )
+    .await
+}
+
+/// Requests historical data for a contract, failing with [Error::Timeout] if TWS does not answer in time.
+///
+/// The request is cancelled at TWS when it times out or the future is dropped.
+#[allow(clippy::too_many_arguments)]
+pub async fn historical_data_with_options(
+    client: &Client,
+    contract: &Contract,
+    end_date: Option<OffsetDateTime>,
+    duration: Duration,
+    bar_size: BarSize,
+    what_to_show: Option<WhatToShow>,
+    trading_hours: TradingHours,
+    options: RequestOptions,
 ) -> Result<HistoricalData, Error> {
     if !contract.trading_class.is_empty() || contract.contract_id > 0 {
         check_version(client.server_version(), Features::TRADING_CLASS)?;
@@ -73,45 +123,53 @@ pub async fn historical_data(
         ));
     }
 
-    for _ in 0..MAX_RETRIES {
-        let builder = client.request();
-        let request = encoders::encode_request_historical_data(
-            client.server_version(),
-            builder.request_id(),
-            contract,
-            end_date,
-            duration,
-            bar_size,
-            what_to_show,
-            trading_hours.use_rth(),
-            false,
-            Vec::<crate::contracts::TagValue>::default(),
-        )?;
-
-        let mut subscription = builder.send_raw(request).await?;
-
-        match subscription.next().await {
-            Some(Ok(mut message)) if message.message_type() == IncomingMessages::HistoricalData => {
-                let mut data = decoders::decode_historical_data(client.server_version(), time_zone(client), &mut message)?;
-
-                if client.server_version() >= crate::server_versions::HISTORICAL_DATA_END {
-                    if let Some(Ok(mut end_msg)) = subscription.next().await {
-                        let (start, end) = decoders::decode_historical_data_end(client.server_version(), time_zone(client), &mut end_msg)?;
-                        data.start = start;
-                        data.end = end;
+    request_helpers::with_timeout(options.resolve_timeout(client.request_timeout()), async {
+        for _ in 0..MAX_RETRIES {
+            let builder = client.request();
+            let request_id = builder.request_id();
+            let request = encoders::encode_request_historical_data(
+                client.server_version(),
+                request_id,
+                contract,
+                end_date,
+                duration,
+                bar_size,
+                what_to_show,
+                trading_hours.use_rth(),
+                false,
+                Vec::<crate::contracts::TagValue>::default(),
+            )?;
+
+            let mut subscription = builder.send_raw(request).await?;
+            let cancel = CancelOnDrop::new(client, Some(encoders::encode_cancel_historical_data(request_id)?));
+
+            let response = subscription.next().await;
+            cancel.disarm();
+
+            match response {
+                Some(Ok(mut message)) if message.message_type() == IncomingMessages::HistoricalData => {
+                    let mut data = decoders::decode_historical_data(client.server_version(), time_zone(client), &mut message)?;
+
+                    if client.server_version() >= crate::server_versions::HISTORICAL_DATA_END {
+                        if let Some(Ok(mut end_msg)) = subscription.next().await {
+                            let (start, end) = decoders::decode_historical_data_end(client.server_version(), time_zone(client), &mut end_msg)?;
+                            data.start = start;
+                            data.end = end;
+                        }
                     }
-                }
 
-                return Ok(data);
+                    return Ok(data);
+                }
+                Some(Ok(message)) if message.message_type() == IncomingMessages::Error => return Err(Error::from(message)),
+                Some(Ok(message)) => return Err(Error::UnexpectedResponse(message)),
+                Some(Err(e)) => return Err(e),
+                None => continue, // Connection reset, retry
             }
-            Some(Ok(message)) if message.message_type() == IncomingMessages::Error => return Err(Error::from(message)),
-            Some(Ok(message)) => return Err(Error::UnexpectedResponse(message)),
-            Some(Err(e)) => return Err(e),
-            None => continue, // Connection reset, retry
         }
-    }
 
-    Err(Error::ConnectionReset)
+        Err(Error::ConnectionReset)
+    })
+    .await
 }
 
 pub(crate) fn time_zone(client: &Client) -> &time_tz::Tz {
@@ -262,19 +320,40 @@ pub async fn histogram_data(
     trading_hours: TradingHours,
     period: BarSize,
 ) -> Result<Vec<HistogramEntry>, Error> {
-    check_version(client.server_version(), Features::HISTOGRAM)?;
+    histogram_data_with_options(client, contract, trading_hours, period, RequestOptions::default()).await
+}
 
-    loop {
-        let builder = client.request();
-        let request = encoders::encode_request_histogram_data(builder.request_id(), contract, trading_hours.use_rth(), period)?;
-        let mut subscription = builder.send_raw(request).await?;
+/// Requests histogram data for a contract, failing with [Error::Timeout] if TWS does not answer in time.
+///
+/// The request is cancelled at TWS when it times out or the future is dropped.
+pub async fn histogram_data_with_options(
+    client: &Client,
+    contract: &Contract,
+    trading_hours: TradingHours,
+    period: BarSize,
+    options: RequestOptions,
+) -> Result<Vec<HistogramEntry>, Error> {
+    check_version(client.server_version(), Features::HISTOGRAM)?;
 
-        match subscription.next().await {
-            Some(Ok(mut message)) => return decoders::decode_histogram_data(&mut message),
-            Some(Err(e)) => return Err(e),
-            None => continue, // Connection reset, retry
+    request_helpers::with_timeout(options.resolve_timeout(client.request_timeout()), async {
+        loop {
+            let builder = client.request();
+            let request_id = builder.request_id();
+            let request = encoders::encode_request_histogram_data(request_id, contract, trading_hours.use_rth(), period)?;
+            let mut subscription = builder.send_raw(request).await?;
+            let cancel = CancelOnDrop::new(client, Some(encoders::encode_cancel_histogram_data(request_id)?));
+
+            let response = subscription.next().await;
+            cancel.disarm();
+
+            match response {
+                Some(Ok(mut message)) => return decoders::decode_histogram_data(&mut message),
+                Some(Err(e)) => return Err(e),
+                None => continue, // Connection reset, retry
+            }
         }
-    }
+    })
+    .await
 }
 
 // === TickSubscription and related types ===
@@ -607,6 +686,7 @@ mod tests {
     use crate::messages::OutgoingMessages;
     use crate::server_versions;
     use crate::stubs::MessageBusStub;
+    use crate::Decimal;
     use crate::ToField;
     use std::sync::Arc;
     use std::sync::RwLock;
@@ -775,7 +855,7 @@ mod tests {
         assert_eq!(bar.high, 186.00, "Wrong high for first bar");
         assert_eq!(bar.low, 185.25, "Wrong low for first bar");
         assert_eq!(bar.close, 185.75, "Wrong close for first bar");
-        assert_eq!(bar.volume, 1000.0, "Wrong volume for first bar");
+        assert_eq!(bar.volume, Decimal::from(1000), "Wrong volume for first bar");
         assert_eq!(bar.wap, 185.70, "Wrong WAP for first bar");
         assert_eq!(bar.count, 100, "Wrong count for first bar");
 
@@ -787,7 +867,7 @@ mod tests {
         assert_eq!(bar.high, 186.25, "Wrong high for second bar");
         assert_eq!(bar.low, 185.50, "Wrong low for second bar");
         assert_eq!(bar.close, 186.00, "Wrong close for second bar");
-        assert_eq!(bar.volume, 1500.0, "Wrong volume for second bar");
+        assert_eq!(bar.volume, Decimal::from(1500), "Wrong volume for second bar");
         assert_eq!(bar.wap, 185.85, "Wrong WAP for second bar");
         assert_eq!(bar.count, 150, "Wrong count for second bar");
 
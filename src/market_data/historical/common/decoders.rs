@@ -45,7 +45,7 @@ pub(crate) fn decode_historical_data(server_version: i32, time_zone: &Tz, messag
         let high = message.next_double()?;
         let low = message.next_double()?;
         let close = message.next_double()?;
-        let volume = message.next_double()?;
+        let volume = message.next_decimal()?;
         let wap = message.next_double()?;
 
         if server_version < server_versions::SYNT_REALTIME_BARS {
@@ -264,7 +264,7 @@ pub(crate) fn decode_historical_data_update(time_zone: &Tz, message: &mut Respon
     let high = message.next_double()?;
     let low = message.next_double()?;
     let close = message.next_double()?;
-    let volume = message.next_double()?;
+    let volume = message.next_decimal()?;
     let wap = message.next_double()?;
     // count field is optional in streaming updates - may not be present
     let count = message.next_int().unwrap_or(0);
@@ -327,7 +327,9 @@ fn parse_bar_date(text: &str, time_zone: &Tz) -> Result<OffsetDateTime, Error> {
 
         Ok(bar_date.assume_timezone_utc(time_tz::timezones::db::UTC))
     } else {
-        let timestamp: i64 = text.parse().map_err(|e: std::num::ParseIntError| Error::Parse(0, text.to_string(), e.to_string()))?;
+        let timestamp: i64 = text
+            .parse()
+            .map_err(|e: std::num::ParseIntError| Error::Parse(0, text.to_string(), e.to_string()))?;
         let date_utc = OffsetDateTime::from_unix_timestamp(timestamp).unwrap();
         Ok(date_utc.to_timezone(time_zone))
     }
@@ -336,6 +338,7 @@ fn parse_bar_date(text: &str, time_zone: &Tz) -> Result<OffsetDateTime, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Decimal;
     use time::macros::{date, datetime};
     use time_tz;
 
@@ -413,7 +416,11 @@ mod tests {
         assert_eq!(historical_data.bars[0].high, 186.50, "historical_data.bars[0].high");
         assert_eq!(historical_data.bars[0].low, 180.94, "historical_data.bars[0].low");
         assert_eq!(historical_data.bars[0].close, 185.90, "historical_data.bars[0].close");
-        assert_eq!(historical_data.bars[0].volume, 948837.22, "historical_data.bars[0].volume");
+        assert_eq!(
+            historical_data.bars[0].volume,
+            "948837.22".parse::<Decimal>().unwrap(),
+            "historical_data.bars[0].volume"
+        );
         assert_eq!(historical_data.bars[0].wap, 184.869, "historical_data.bars[0].wap");
         assert_eq!(historical_data.bars[0].count, 324891, "historical_data.bars[0].count");
     }
@@ -529,7 +536,7 @@ mod tests {
         assert_eq!(bar.high, 186.00, "bar.high");
         assert_eq!(bar.low, 185.00, "bar.low");
         assert_eq!(bar.close, 185.75, "bar.close");
-        assert_eq!(bar.volume, 1000.5, "bar.volume");
+        assert_eq!(bar.volume, "1000.5".parse::<Decimal>().unwrap(), "bar.volume");
         assert_eq!(bar.wap, 185.625, "bar.wap");
         assert_eq!(bar.count, 150, "bar.count");
     }
@@ -548,7 +555,7 @@ mod tests {
         assert_eq!(bar.high, 186.00, "bar.high");
         assert_eq!(bar.low, 185.00, "bar.low");
         assert_eq!(bar.close, 185.75, "bar.close");
-        assert_eq!(bar.volume, 1000.5, "bar.volume");
+        assert_eq!(bar.volume, "1000.5".parse::<Decimal>().unwrap(), "bar.volume");
         assert_eq!(bar.wap, 185.625, "bar.wap");
         assert_eq!(bar.count, 0, "bar.count should default to 0 when missing");
     }
@@ -169,6 +169,20 @@ pub(crate) fn encode_cancel_historical_ticks(request_id: i32) -> Result<RequestM
     Ok(message)
 }
 
+pub(crate) fn encode_cancel_head_timestamp(request_id: i32) -> Result<RequestMessage, Error> {
+    let mut message = RequestMessage::default();
+    message.push_field(&OutgoingMessages::CancelHeadTimestamp);
+    message.push_field(&request_id);
+    Ok(message)
+}
+
+pub(crate) fn encode_cancel_histogram_data(request_id: i32) -> Result<RequestMessage, Error> {
+    let mut message = RequestMessage::default();
+    message.push_field(&OutgoingMessages::CancelHistogramData);
+    message.push_field(&request_id);
+    Ok(message)
+}
+
 pub(crate) fn encode_request_histogram_data(request_id: i32, contract: &Contract, use_rth: bool, period: BarSize) -> Result<RequestMessage, Error> {
     let mut message = RequestMessage::default();
 
@@ -369,6 +383,20 @@ mod tests {
         assert_eq!(message[1], request_id.to_field(), "message.request_id");
     }
 
+    #[test]
+    fn test_encode_cancel_head_timestamp() {
+        let message = encode_cancel_head_timestamp(9000).expect("error encoding cancel head timestamp");
+
+        assert_eq!(message.encode(), "90\09000\0");
+    }
+
+    #[test]
+    fn test_encode_cancel_histogram_data() {
+        let message = encode_cancel_histogram_data(3000).expect("error encoding cancel histogram data");
+
+        assert_eq!(message.encode(), "89\03000\0");
+    }
+
     #[test]
     fn test_encode_request_histogram_data() {
         let request_id = 3000;
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use time::{Date, OffsetDateTime};
 
 use crate::messages::{IncomingMessages, ResponseMessage};
-use crate::{Error, ToField};
+use crate::{Decimal, Error, ToField};
 
 pub(crate) mod common;
 
@@ -52,7 +52,7 @@ pub struct Bar {
     /// The bar's close price.
     pub close: f64,
     /// The bar's traded volume if available (only available for TRADES)
-    pub volume: f64,
+    pub volume: Decimal,
     /// The bar's Weighted Average Price (only available for TRADES)
     pub wap: f64,
     /// The number of trades during the bar's timespan (only available for TRADES)
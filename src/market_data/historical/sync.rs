@@ -7,10 +7,10 @@ use time::OffsetDateTime;
 
 use crate::client::blocking::ClientRequestBuilders;
 use crate::contracts::Contract;
-use crate::messages::IncomingMessages;
+use crate::messages::{IncomingMessages, RequestMessage};
 use crate::protocol::{check_version, Features};
 use crate::transport::{InternalSubscription, MessageBus, Response};
-use crate::{client::sync::Client, Error, MAX_RETRIES};
+use crate::{client::sync::Client, Error, RequestOptions, MAX_RETRIES};
 
 use time_tz::Tz;
 
@@ -26,20 +26,30 @@ pub(crate) fn head_timestamp(
     contract: &Contract,
     what_to_show: WhatToShow,
     trading_hours: TradingHours,
+    options: RequestOptions,
 ) -> Result<OffsetDateTime, Error> {
     check_version(client.server_version(), Features::HEAD_TIMESTAMP)?;
 
-    let builder = client.request();
-    let request = encoders::encode_request_head_timestamp(builder.request_id(), contract, what_to_show, trading_hours.use_rth())?;
-    let subscription = builder.send_raw(request)?;
+    let deadline = options.deadline(client.request_timeout());
+    for _ in 0..MAX_RETRIES {
+        let builder = client.request();
+        let request_id = builder.request_id();
+        let request = encoders::encode_request_head_timestamp(request_id, contract, what_to_show, trading_hours.use_rth())?;
+        let subscription = builder.send_raw(request)?;
 
-    match subscription.next() {
-        Some(Ok(mut message)) if message.message_type() == IncomingMessages::HeadTimestamp => Ok(decoders::decode_head_timestamp(&mut message)?),
-        Some(Ok(message)) => Err(Error::UnexpectedResponse(message)),
-        Some(Err(Error::ConnectionReset)) => head_timestamp(client, contract, what_to_show, trading_hours),
-        Some(Err(e)) => Err(e),
-        None => Err(Error::UnexpectedEndOfStream),
+        match subscription.next_until(deadline) {
+            Some(Ok(mut message)) if message.message_type() == IncomingMessages::HeadTimestamp => {
+                return decoders::decode_head_timestamp(&mut message)
+            }
+            Some(Ok(message)) => return Err(Error::UnexpectedResponse(message)),
+            Some(Err(Error::ConnectionReset)) => {}
+            Some(Err(Error::Timeout)) => return Err(cancel_expired(client, encoders::encode_cancel_head_timestamp(request_id)?)),
+            Some(Err(e)) => return Err(e),
+            None => return Err(Error::UnexpectedEndOfStream),
+        }
     }
+
+    Err(Error::ConnectionReset)
 }
 
 /// Requests historical data for a contract.
@@ -47,6 +57,7 @@ pub(crate) fn head_timestamp(
 /// # See Also
 /// * [TWS API Documentation](https://interactivebrokers.github.io/tws-api/historical_bars.html#hd_duration)
 /// * IB also recommends [IBKR Campus](https://ibkrcampus.com/ibkr-api-page/trader-workstation-api/)
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn historical_data(
     client: &Client,
     contract: &Contract,
@@ -55,6 +66,7 @@ pub(crate) fn historical_data(
     bar_size: BarSize,
     what_to_show: Option<WhatToShow>,
     trading_hours: TradingHours,
+    options: RequestOptions,
 ) -> Result<HistoricalData, Error> {
     if !contract.trading_class.is_empty() || contract.contract_id > 0 {
         check_version(client.server_version(), Features::TRADING_CLASS)?;
@@ -70,11 +82,13 @@ pub(crate) fn historical_data(
         ));
     }
 
+    let deadline = options.deadline(client.request_timeout());
     for _ in 0..MAX_RETRIES {
         let builder = client.request();
+        let request_id = builder.request_id();
         let request = encoders::encode_request_historical_data(
             client.server_version(),
-            builder.request_id(),
+            request_id,
             contract,
             end_date,
             duration,
@@ -87,12 +101,12 @@ pub(crate) fn historical_data(
 
         let subscription = builder.send_raw(request)?;
 
-        match subscription.next() {
+        match subscription.next_until(deadline) {
             Some(Ok(mut message)) if message.message_type() == IncomingMessages::HistoricalData => {
                 let mut data = decoders::decode_historical_data(client.server_version, time_zone(client), &mut message)?;
 
                 if client.server_version >= crate::server_versions::HISTORICAL_DATA_END {
-                    if let Some(Ok(mut end_msg)) = subscription.next() {
+                    if let Some(Ok(mut end_msg)) = subscription.next_until(deadline) {
                         let (start, end) = decoders::decode_historical_data_end(client.server_version, time_zone(client), &mut end_msg)?;
                         data.start = start;
                         data.end = end;
@@ -104,6 +118,7 @@ pub(crate) fn historical_data(
             Some(Ok(message)) if message.message_type() == IncomingMessages::Error => return Err(Error::from(message)),
             Some(Ok(message)) => return Err(Error::UnexpectedResponse(message)),
             Some(Err(Error::ConnectionReset)) => {}
+            Some(Err(Error::Timeout)) => return Err(cancel_expired(client, encoders::encode_cancel_historical_data(request_id)?)),
             Some(Err(e)) => return Err(e),
             None => return Err(Error::UnexpectedEndOfStream),
         }
@@ -112,6 +127,14 @@ pub(crate) fn historical_data(
     Err(Error::ConnectionReset)
 }
 
+// Cancels a request that expired before TWS answered. Failing to cancel does not mask the timeout.
+fn cancel_expired(client: &Client, message: RequestMessage) -> Error {
+    if let Err(e) = client.send_message(message) {
+        warn!("error cancelling expired request: {e}");
+    }
+    Error::Timeout
+}
+
 pub(crate) fn time_zone(client: &Client) -> &time_tz::Tz {
     if let Some(tz) = client.time_zone {
         tz
@@ -255,17 +278,21 @@ pub(crate) fn histogram_data(
     contract: &Contract,
     trading_hours: TradingHours,
     period: BarSize,
+    options: RequestOptions,
 ) -> Result<Vec<HistogramEntry>, Error> {
     check_version(client.server_version(), Features::HISTOGRAM)?;
 
+    let deadline = options.deadline(client.request_timeout());
     loop {
         let builder = client.request();
-        let request = encoders::encode_request_histogram_data(builder.request_id(), contract, trading_hours.use_rth(), period)?;
+        let request_id = builder.request_id();
+        let request = encoders::encode_request_histogram_data(request_id, contract, trading_hours.use_rth(), period)?;
         let subscription = builder.send_raw(request)?;
 
-        match subscription.next() {
+        match subscription.next_until(deadline) {
             Some(Ok(mut message)) => return decoders::decode_histogram_data(&mut message),
             Some(Err(Error::ConnectionReset)) => continue,
+            Some(Err(Error::Timeout)) => return Err(cancel_expired(client, encoders::encode_cancel_histogram_data(request_id)?)),
             Some(Err(e)) => return Err(e),
             None => return Ok(Vec::new()),
         }
@@ -670,6 +697,7 @@ mod tests {
     use crate::messages::{OutgoingMessages, RequestMessage};
     use crate::server_versions;
     use crate::stubs::MessageBusStub;
+    use crate::Decimal;
     use crate::ToField;
     use std::sync::{Arc, RwLock};
     use time::macros::{date, datetime};
@@ -797,7 +825,7 @@ mod tests {
         assert_eq!(historical_data.bars[0].high, 186.50, "bar.high");
         assert_eq!(historical_data.bars[0].low, 180.94, "bar.low");
         assert_eq!(historical_data.bars[0].close, 185.90, "bar.close");
-        assert_eq!(historical_data.bars[0].volume, 948837.22, "bar.volume");
+        assert_eq!(historical_data.bars[0].volume, "948837.22".parse::<Decimal>().unwrap(), "bar.volume");
         assert_eq!(historical_data.bars[0].wap, 184.869, "bar.wap");
         assert_eq!(historical_data.bars[0].count, 324891, "bar.count");
 
@@ -105,12 +105,17 @@ impl StreamDecoder<Trade> for Trade {
 
 #[cfg(not(feature = "sync"))]
 impl StreamDecoder<MarketDepths> for MarketDepths {
-    const RESPONSE_MESSAGE_IDS: &'static [IncomingMessages] =
-        &[IncomingMessages::MarketDepth, IncomingMessages::MarketDepthL2, IncomingMessages::Error];
+    const RESPONSE_MESSAGE_IDS: &'static [IncomingMessages] = &[
+        IncomingMessages::MarketDepth,
+        IncomingMessages::MarketDepthL2,
+        IncomingMessages::Error,
+        IncomingMessages::RerouteMktDepthReq,
+    ];
 
     fn decode(context: &DecoderContext, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
             IncomingMessages::MarketDepth => Ok(MarketDepths::MarketDepth(decoders::decode_market_depth(message)?)),
+            IncomingMessages::RerouteMktDepthReq => Ok(MarketDepths::Rerouted(decoders::decode_rerouted(message)?)),
             IncomingMessages::MarketDepthL2 => Ok(MarketDepths::MarketDepthL2(decoders::decode_market_depth_l2(
                 context.server_version,
                 message,
@@ -149,6 +154,7 @@ impl StreamDecoder<TickTypes> for TickTypes {
         IncomingMessages::TickSnapshotEnd,
         IncomingMessages::Error,
         IncomingMessages::TickReqParams,
+        IncomingMessages::RerouteMktDataReq,
     ];
 
     fn decode(context: &DecoderContext, message: &mut ResponseMessage) -> Result<Self, Error> {
@@ -164,6 +170,7 @@ impl StreamDecoder<TickTypes> for TickTypes {
             )?)),
             IncomingMessages::TickReqParams => Ok(TickTypes::RequestParameters(decoders::decode_tick_request_parameters(message)?)),
             IncomingMessages::TickSnapshotEnd => Ok(TickTypes::SnapshotEnd),
+            IncomingMessages::RerouteMktDataReq => Ok(TickTypes::Rerouted(decoders::decode_rerouted(message)?)),
             IncomingMessages::Error => Ok(TickTypes::Notice(Notice::from(message))),
             _ => Err(Error::NotImplemented),
         }
@@ -343,6 +350,7 @@ mod tests {
     use crate::messages::OutgoingMessages;
     use crate::server_versions;
     use crate::stubs::MessageBusStub;
+    use crate::Decimal;
     use crate::ToField;
     use std::sync::Arc;
     use std::sync::RwLock;
@@ -423,7 +431,7 @@ mod tests {
             "Wrong timestamp for first bar"
         );
         assert_eq!(received_bars[0].open, 4028.75, "Wrong open price for first bar");
-        assert_eq!(received_bars[0].volume, 2.0, "Wrong volume for first bar");
+        assert_eq!(received_bars[0].volume, Decimal::from(2), "Wrong volume for first bar");
 
         // Verify second bar
         assert_eq!(
@@ -432,7 +440,7 @@ mod tests {
             "Wrong timestamp for second bar"
         );
         assert_eq!(received_bars[1].open, 4028.80, "Wrong open price for second bar");
-        assert_eq!(received_bars[1].volume, 3.0, "Wrong volume for second bar");
+        assert_eq!(received_bars[1].volume, Decimal::from(3), "Wrong volume for second bar");
 
         // Verify request messages
         let request_messages = message_bus.request_messages.read().unwrap();
@@ -770,6 +778,47 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_market_data_rerouted() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: vec!["91|9000|265598|SMART|".to_owned(), "92|9001|265598|ISLAND|".to_owned()],
+        });
+
+        let client = Client::stubbed(message_bus, server_versions::SMART_DEPTH);
+        let cfd = Contract {
+            symbol: Symbol::from("AAPL"),
+            security_type: SecurityType::CFD,
+            exchange: Exchange::from("SMART"),
+            currency: Currency::from("USD"),
+            ..Contract::default()
+        };
+
+        let mut subscription = client
+            .market_data(&cfd)
+            .subscribe()
+            .await
+            .expect("Failed to create market data subscription");
+        match subscription.next().await {
+            Some(Ok(TickTypes::Rerouted(rerouted))) => {
+                assert_eq!(rerouted.contract_id, 265598);
+                assert_eq!(rerouted.exchange, "SMART");
+            }
+            other => panic!("Expected Rerouted, got {other:?}"),
+        }
+
+        let mut depth = market_depth(&client, &cfd, 5, false)
+            .await
+            .expect("Failed to create market depth subscription");
+        match depth.next().await {
+            Some(Ok(MarketDepths::Rerouted(rerouted))) => {
+                assert_eq!(rerouted.contract_id, 265598);
+                assert_eq!(rerouted.exchange, "ISLAND");
+            }
+            other => panic!("Expected Rerouted, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_basic_market_data() {
         let message_bus = Arc::new(MessageBusStub {
@@ -817,7 +866,7 @@ mod tests {
                 }
                 TickTypes::Size(tick) => {
                     assert_eq!(tick.tick_type, TickType::BidSize, "Wrong tick type");
-                    assert_eq!(tick.size, 150.0, "Wrong size");
+                    assert_eq!(tick.size, Decimal::from(150), "Wrong size");
                 }
                 TickTypes::PriceSize(tick) => {
                     assert_eq!(tick.price_tick_type, TickType::Bid, "Wrong tick type");
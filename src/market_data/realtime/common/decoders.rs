@@ -4,8 +4,8 @@ use crate::Error;
 use crate::{messages::ResponseMessage, server_versions};
 
 use crate::market_data::realtime::{
-    Bar, BidAsk, BidAskAttribute, DepthMarketDataDescription, MarketDepth, MarketDepthL2, MidPoint, TickEFP, TickGeneric, TickPrice, TickPriceSize,
-    TickRequestParameters, TickSize, TickString, TickType, TickTypes, Trade, TradeAttribute,
+    Bar, BidAsk, BidAskAttribute, DepthMarketDataDescription, MarketDepth, MarketDepthL2, MidPoint, Rerouted, TickEFP, TickGeneric, TickPrice,
+    TickPriceSize, TickRequestParameters, TickSize, TickString, TickType, TickTypes, Trade, TradeAttribute,
 };
 
 pub(crate) fn decode_realtime_bar(message: &mut ResponseMessage) -> Result<Bar, Error> {
@@ -18,7 +18,7 @@ pub(crate) fn decode_realtime_bar(message: &mut ResponseMessage) -> Result<Bar,
         high: message.next_double()?,
         low: message.next_double()?,
         close: message.next_double()?,
-        volume: message.next_double()?,
+        volume: message.next_decimal()?,
         wap: message.next_double()?,
         count: message.next_int()?,
     })
@@ -190,7 +190,7 @@ pub(crate) fn decode_tick_size(message: &mut ResponseMessage) -> Result<TickSize
     message.skip(); // message request id
     Ok(TickSize {
         tick_type: TickType::from(message.next_int()?),
-        size: message.next_double()?,
+        size: message.next_decimal()?,
     })
 }
 pub(crate) fn decode_tick_string(message: &mut ResponseMessage) -> Result<TickString, Error> {
@@ -239,6 +239,16 @@ pub(crate) fn decode_tick_request_parameters(message: &mut ResponseMessage) -> R
     })
 }
 
+// Reroute messages carry no version: type, request id, contract id, exchange.
+pub(crate) fn decode_rerouted(message: &mut ResponseMessage) -> Result<Rerouted, Error> {
+    message.skip(); // message type
+    message.skip(); // message request id
+    Ok(Rerouted {
+        contract_id: message.next_int()?,
+        exchange: message.next_string()?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,6 +258,7 @@ mod tests {
     #[cfg(test)]
     mod realtime_bar_tests {
         use super::*;
+        use crate::Decimal;
 
         #[test]
         fn test_decode_realtime_bar() {
@@ -260,7 +271,7 @@ mod tests {
             assert_eq!(bar.high, 4029.00, "Wrong high price");
             assert_eq!(bar.low, 4028.25, "Wrong low price");
             assert_eq!(bar.close, 4028.50, "Wrong close price");
-            assert_eq!(bar.volume, 2.0, "Wrong volume");
+            assert_eq!(bar.volume, Decimal::from(2), "Wrong volume");
             assert_eq!(bar.wap, 4026.75, "Wrong WAP");
             assert_eq!(bar.count, 1, "Wrong count");
         }
@@ -556,6 +567,7 @@ mod tests {
     #[cfg(test)]
     mod tick_size_tests {
         use super::*;
+        use crate::Decimal;
 
         #[test]
         fn test_decode_tick_size() {
@@ -564,7 +576,7 @@ mod tests {
             let tick = decode_tick_size(&mut message).expect("Failed to decode tick size");
 
             assert_eq!(tick.tick_type, TickType::BidSize, "Wrong tick type");
-            assert_eq!(tick.size, 100.0, "Wrong size");
+            assert_eq!(tick.size, Decimal::from(100), "Wrong size");
         }
 
         #[test]
@@ -690,4 +702,19 @@ mod tests {
             }
         }
     }
+
+    #[cfg(test)]
+    mod rerouted_tests {
+        use super::*;
+
+        #[test]
+        fn test_decode_rerouted() {
+            let mut message = ResponseMessage::from_simple("91|9000|265598|SMART|");
+
+            let rerouted = decode_rerouted(&mut message).expect("error decoding reroute");
+
+            assert_eq!(rerouted.contract_id, 265598);
+            assert_eq!(rerouted.exchange, "SMART");
+        }
+    }
 }
@@ -1,9 +1,9 @@
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-use crate::ToField;
+use crate::{Decimal, ToField};
 
-use crate::contracts::OptionComputation;
+use crate::contracts::{Contract, Exchange, OptionComputation};
 use crate::messages::Notice;
 #[cfg(feature = "sync")]
 use crate::messages::{IncomingMessages, RequestMessage, ResponseMessage};
@@ -139,7 +139,7 @@ pub struct Bar {
     /// Closing price of the bar period
     pub close: f64,
     /// Total volume traded during the bar period
-    pub volume: f64,
+    pub volume: Decimal,
     /// Volume weighted average price
     pub wap: f64,
     /// Number of trades during the bar period
@@ -240,8 +240,11 @@ impl ToField for WhatToShow {
 }
 
 /// Market depth data types.
+///
+/// New kinds of updates may be added, so matches need a wildcard arm.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
 pub enum MarketDepths {
     /// Level-1 depth update.
     MarketDepth(MarketDepth),
@@ -249,6 +252,8 @@ pub enum MarketDepths {
     MarketDepthL2(MarketDepthL2),
     /// Informational notice (e.g., depth data unavailable).
     Notice(Notice),
+    /// TWS sends depth for another contract instead, typically the underlying of a CFD.
+    Rerouted(Rerouted),
 }
 
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -289,11 +294,17 @@ pub struct MarketDepthL2 {
 
 #[cfg(feature = "sync")]
 impl StreamDecoder<MarketDepths> for MarketDepths {
-    const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[IncomingMessages::MarketDepth, IncomingMessages::MarketDepthL2, IncomingMessages::Error];
+    const RESPONSE_MESSAGE_IDS: &[IncomingMessages] = &[
+        IncomingMessages::MarketDepth,
+        IncomingMessages::MarketDepthL2,
+        IncomingMessages::Error,
+        IncomingMessages::RerouteMktDepthReq,
+    ];
 
     fn decode(context: &DecoderContext, message: &mut ResponseMessage) -> Result<Self, Error> {
         match message.message_type() {
             IncomingMessages::MarketDepth => Ok(MarketDepths::MarketDepth(common::decoders::decode_market_depth(message)?)),
+            IncomingMessages::RerouteMktDepthReq => Ok(MarketDepths::Rerouted(common::decoders::decode_rerouted(message)?)),
             IncomingMessages::MarketDepthL2 => Ok(MarketDepths::MarketDepthL2(common::decoders::decode_market_depth_l2(
                 context.server_version,
                 message,
@@ -316,6 +327,31 @@ impl StreamDecoder<MarketDepths> for MarketDepths {
     }
 }
 
+/// Contract whose data TWS sends in place of the requested one.
+///
+/// TWS reroutes requests for contracts without their own market data, such as CFDs, to the
+/// contract the data comes from. No further data arrives on the original subscription; subscribe
+/// to [contract](Self::contract) for quotes.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Rerouted {
+    /// Contract id of the contract providing the data.
+    pub contract_id: i32,
+    /// Exchange to request the data from.
+    pub exchange: String,
+}
+
+impl Rerouted {
+    /// Contract to request the rerouted data for.
+    pub fn contract(&self) -> Contract {
+        Contract {
+            contract_id: self.contract_id,
+            exchange: Exchange::from(self.exchange.as_str()),
+            ..Default::default()
+        }
+    }
+}
+
 /// Stores depth market data description.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Debug, Default)]
@@ -333,8 +369,11 @@ pub struct DepthMarketDataDescription {
 }
 
 /// Various types of market data ticks.
+///
+/// New kinds of updates may be added, so matches need a wildcard arm.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum TickTypes {
     /// Price update tick.
     Price(TickPrice),
@@ -356,6 +395,8 @@ pub enum TickTypes {
     RequestParameters(TickRequestParameters),
     /// Combined price and size tick.
     PriceSize(TickPriceSize),
+    /// TWS sends market data for another contract instead, typically the underlying of a CFD.
+    Rerouted(Rerouted),
 }
 
 #[cfg(feature = "sync")]
@@ -370,6 +411,7 @@ impl StreamDecoder<TickTypes> for TickTypes {
         IncomingMessages::TickSnapshotEnd,
         IncomingMessages::Error,
         IncomingMessages::TickReqParams,
+        IncomingMessages::RerouteMktDataReq,
     ];
 
     fn decode(context: &DecoderContext, message: &mut ResponseMessage) -> Result<Self, Error> {
@@ -385,6 +427,7 @@ impl StreamDecoder<TickTypes> for TickTypes {
             )?)),
             IncomingMessages::TickReqParams => Ok(TickTypes::RequestParameters(common::decoders::decode_tick_request_parameters(message)?)),
             IncomingMessages::TickSnapshotEnd => Ok(TickTypes::SnapshotEnd),
+            IncomingMessages::RerouteMktDataReq => Ok(TickTypes::Rerouted(common::decoders::decode_rerouted(message)?)),
             IncomingMessages::Error => Ok(TickTypes::Notice(Notice::from(message))),
             _ => Err(Error::NotImplemented),
         }
@@ -431,7 +474,7 @@ pub struct TickSize {
     /// Type of size tick (bid size, ask size, etc.).
     pub tick_type: TickType,
     /// The size value.
-    pub size: f64,
+    pub size: Decimal,
 }
 
 /// Combined price and size tick data.
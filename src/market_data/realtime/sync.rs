@@ -178,6 +178,7 @@ mod tests {
     use crate::messages::OutgoingMessages;
     use crate::server_versions;
     use crate::stubs::MessageBusStub;
+    use crate::Decimal;
     use crate::ToField;
     use std::sync::Arc;
     use std::sync::RwLock;
@@ -252,7 +253,7 @@ mod tests {
             "Wrong timestamp for first bar"
         );
         assert_eq!(received_bars[0].open, 4028.75, "Wrong open price for first bar");
-        assert_eq!(received_bars[0].volume, 2.0, "Wrong volume for first bar");
+        assert_eq!(received_bars[0].volume, Decimal::from(2), "Wrong volume for first bar");
 
         // Verify second bar
         assert_eq!(
@@ -261,7 +262,7 @@ mod tests {
             "Wrong timestamp for second bar"
         );
         assert_eq!(received_bars[1].open, 4028.80, "Wrong open price for second bar");
-        assert_eq!(received_bars[1].volume, 3.0, "Wrong volume for second bar");
+        assert_eq!(received_bars[1].volume, Decimal::from(3), "Wrong volume for second bar");
 
         // Verify request messages
         let request_messages = client.message_bus.request_messages();
@@ -560,7 +561,7 @@ mod tests {
                 }
                 TickTypes::Size(tick) => {
                     assert_eq!(tick.tick_type, TickType::BidSize, "Wrong tick type");
-                    assert_eq!(tick.size, 150.0, "Wrong size");
+                    assert_eq!(tick.size, Decimal::from(150), "Wrong size");
                 }
                 TickTypes::PriceSize(tick) => {
                     assert_eq!(tick.price_tick_type, TickType::Bid, "Wrong tick type");
@@ -677,6 +678,61 @@ mod tests {
         assert_eq!(request[17], "1", "Wrong regulatory snapshot flag");
     }
 
+    #[test]
+    fn test_market_data_rerouted() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: vec!["91|9000|265598|SMART|".to_owned()],
+        });
+
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+        let cfd = Contract {
+            symbol: Symbol::from("AAPL"),
+            security_type: SecurityType::CFD,
+            exchange: Exchange::from("SMART"),
+            currency: Currency::from("USD"),
+            ..Contract::default()
+        };
+
+        let subscription = client.market_data(&cfd).subscribe().expect("Failed to create market data subscription");
+        let tick = subscription.next().expect("Expected a reroute");
+
+        let TickTypes::Rerouted(rerouted) = tick else {
+            panic!("Expected Rerouted, got {tick:?}");
+        };
+        assert_eq!(rerouted.contract_id, 265598);
+        assert_eq!(rerouted.exchange, "SMART");
+        assert_eq!(rerouted.contract().contract_id, 265598);
+        assert_eq!(rerouted.contract().exchange, Exchange::from("SMART"));
+    }
+
+    #[test]
+    fn test_market_depth_rerouted() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: vec!["92|9000|265598|ISLAND|".to_owned()],
+        });
+
+        let client = Client::stubbed(message_bus, server_versions::SMART_DEPTH);
+        let cfd = Contract {
+            symbol: Symbol::from("AAPL"),
+            security_type: SecurityType::CFD,
+            exchange: Exchange::from("SMART"),
+            currency: Currency::from("USD"),
+            ..Contract::default()
+        };
+
+        let depth = client.market_depth(&cfd, 5, false).expect("Failed to create market depth subscription");
+
+        match depth.next() {
+            Some(MarketDepths::Rerouted(rerouted)) => {
+                assert_eq!(rerouted.contract_id, 265598);
+                assert_eq!(rerouted.exchange, "ISLAND");
+            }
+            other => panic!("Expected Rerouted, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_market_data_error_handling() {
         let message_bus = Arc::new(MessageBusStub {
@@ -15,7 +15,7 @@ use log::debug;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-use crate::{Error, ToField};
+use crate::{decimal, Decimal, Error, ToField};
 
 pub mod parser_registry;
 pub(crate) mod shared_channel_configuration;
@@ -101,7 +101,7 @@ mod from_str_tests {
     }
 }
 
-const INFINITY_STR: &str = "Infinity";
+pub(crate) const INFINITY_STR: &str = "Infinity";
 const UNSET_DOUBLE: &str = "1.7976931348623157E308";
 const UNSET_INTEGER: &str = "2147483647";
 const UNSET_LONG: &str = "9223372036854775807";
@@ -398,6 +398,8 @@ pub fn order_id_index(kind: IncomingMessages) -> Option<usize> {
     match kind {
         IncomingMessages::OpenOrder | IncomingMessages::OrderStatus => Some(1),
         IncomingMessages::ExecutionData | IncomingMessages::ExecutionDataEnd => Some(2),
+        IncomingMessages::DeltaNeutralValidation => Some(2),
+        IncomingMessages::OrderBound => Some(3),
         _ => None,
     }
 }
@@ -409,6 +411,7 @@ pub fn request_id_index(kind: IncomingMessages) -> Option<usize> {
         IncomingMessages::AccountSummaryEnd => Some(2),
         IncomingMessages::AccountUpdateMulti => Some(2),
         IncomingMessages::AccountUpdateMultiEnd => Some(2),
+        IncomingMessages::BondContractData => Some(1),
         IncomingMessages::ContractData => Some(1),
         IncomingMessages::ContractDataEnd => Some(2),
         // Error uses version-dependent indices; use ResponseMessage::error_request_id() instead.
@@ -434,6 +437,8 @@ pub fn request_id_index(kind: IncomingMessages) -> Option<usize> {
         IncomingMessages::PositionMulti => Some(2),
         IncomingMessages::PositionMultiEnd => Some(2),
         IncomingMessages::RealTimeBars => Some(2),
+        IncomingMessages::RerouteMktDataReq => Some(1),
+        IncomingMessages::RerouteMktDepthReq => Some(1),
         IncomingMessages::ScannerData => Some(2),
         IncomingMessages::SecurityDefinitionOptionParameter => Some(1),
         IncomingMessages::SecurityDefinitionOptionParameterEnd => Some(1),
@@ -1046,6 +1051,36 @@ impl ResponseMessage {
         }
     }
 
+    /// Consume and parse the next decimal field (quantities, sizes, volumes).
+    ///
+    /// With the `decimal` feature enabled the original string precision is preserved.
+    pub fn next_decimal(&mut self) -> Result<Decimal, Error> {
+        if self.i >= self.fields.len() {
+            return Err(Error::Simple("expected decimal and found end of message".into()));
+        }
+
+        let field = &self.fields[self.i];
+        self.i += 1;
+
+        decimal::parse(field).map_err(|err| Error::Parse(self.i, field.into(), err))
+    }
+
+    /// Consume the next field as an optional decimal value.
+    pub fn next_optional_decimal(&mut self) -> Result<Option<Decimal>, Error> {
+        if self.i >= self.fields.len() {
+            return Err(Error::Simple("expected optional decimal and found end of message".into()));
+        }
+
+        let field = &self.fields[self.i];
+        self.i += 1;
+
+        if field.is_empty() || field == UNSET_DOUBLE || field == decimal::UNSET_DECIMAL {
+            return Ok(None);
+        }
+
+        decimal::parse(field).map(Some).map_err(|err| Error::Parse(self.i, field.into(), err))
+    }
+
     /// Consume the next field as an optional floating-point value.
     pub fn next_optional_double(&mut self) -> Result<Option<f64>, Error> {
         if self.i >= self.fields.len() {
@@ -1188,6 +1223,9 @@ pub struct Notice {
 /// Error code indicating an order was cancelled (confirmation, not an error).
 pub const ORDER_CANCELLED_CODE: i32 = 202;
 
+/// Error code sent during the handshake when another connection already uses the requested client id.
+pub const CLIENT_ID_IN_USE_CODE: i32 = 326;
+
 /// Range of error codes that are considered warnings (2100-2169).
 pub const WARNING_CODE_RANGE: std::ops::RangeInclusive<i32> = 2100..=2169;
 
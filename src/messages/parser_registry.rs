@@ -5,10 +5,11 @@
 //! and mock server development.
 
 use super::{IncomingMessages, OutgoingMessages};
+use serde::Serialize;
 use std::collections::HashMap;
 
 /// Represents a parsed field in a TWS message
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ParsedField {
     /// Human-readable field name.
     pub name: String,
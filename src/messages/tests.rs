@@ -435,12 +435,17 @@ fn test_order_id_index() {
     assert_eq!(order_id_index(IncomingMessages::ExecutionData), Some(2));
     assert_eq!(order_id_index(IncomingMessages::ExecutionDataEnd), Some(2));
 
+    assert_eq!(order_id_index(IncomingMessages::OrderBound), Some(3));
+
     assert_eq!(order_id_index(IncomingMessages::NotValid), None);
 }
 
 #[test]
 fn test_request_id_index() {
     assert_eq!(request_id_index(IncomingMessages::ContractData), Some(1));
+    assert_eq!(request_id_index(IncomingMessages::BondContractData), Some(1));
+    assert_eq!(request_id_index(IncomingMessages::RerouteMktDataReq), Some(1));
+    assert_eq!(request_id_index(IncomingMessages::RerouteMktDepthReq), Some(1));
     assert_eq!(request_id_index(IncomingMessages::TickByTick), Some(1));
     assert_eq!(request_id_index(IncomingMessages::SymbolSamples), Some(1));
     assert_eq!(request_id_index(IncomingMessages::OpenOrder), Some(1));
@@ -0,0 +1,61 @@
+//! Hooks for exporting client metrics, e.g. to Prometheus.
+//!
+//! Implement [Metrics] and pass it to [ConnectionOptions::metrics](crate::ConnectionOptions::metrics).
+//! Every method has an empty default, so implementations only override what they export.
+//! Methods are called on the dispatcher thread/task and should return quickly.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use std::sync::Mutex;
+//! use std::time::Duration;
+//!
+//! use ibapi::messages::{IncomingMessages, OutgoingMessages};
+//! use ibapi::metrics::Metrics;
+//!
+//! #[derive(Default)]
+//! struct Counters {
+//!     received: Mutex<HashMap<IncomingMessages, u64>>,
+//!     latencies: Mutex<Vec<(OutgoingMessages, Duration)>>,
+//! }
+//!
+//! impl Metrics for Counters {
+//!     fn message_received(&self, message_type: IncomingMessages) {
+//!         *self.received.lock().unwrap().entry(message_type).or_default() += 1;
+//!     }
+//!
+//!     fn request_latency(&self, message_type: OutgoingMessages, latency: Duration) {
+//!         self.latencies.lock().unwrap().push((message_type, latency));
+//!     }
+//! }
+//!
+//! let counters = Counters::default();
+//! counters.message_received(IncomingMessages::TickPrice);
+//! assert_eq!(counters.received.lock().unwrap()[&IncomingMessages::TickPrice], 1);
+//! ```
+
+use std::time::Duration;
+
+use crate::messages::{IncomingMessages, OutgoingMessages};
+
+/// Receives counts and timings from a connected client.
+pub trait Metrics: Send + Sync {
+    /// A message was written to TWS.
+    fn message_sent(&self, _message_type: OutgoingMessages) {}
+
+    /// A message was read from TWS.
+    fn message_received(&self, _message_type: IncomingMessages) {}
+
+    /// A message routed to a subscription could not be decoded.
+    fn decode_error(&self, _message_type: IncomingMessages) {}
+
+    /// A reconnection after a lost connection finished. `succeeded` is false when all attempts failed.
+    fn reconnect(&self, _succeeded: bool) {}
+
+    /// The number of requests and orders with a dedicated response channel changed.
+    fn active_subscriptions(&self, _count: usize) {}
+
+    /// Time from sending a request with a request or order id to its first response.
+    fn request_latency(&self, _message_type: OutgoingMessages, _latency: Duration) {}
+}
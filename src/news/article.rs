@@ -0,0 +1,427 @@
+//! Decoding of article bodies and of the metadata IB attaches to headlines.
+
+use serde::{Deserialize, Serialize};
+
+use super::{ArticleType, NewsArticle, NewsArticleBody};
+use crate::Error;
+
+/// Metadata IB prefixes to headlines, such as `{A:800015:L:en:K:0.97:C:0.9}`.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct HeadlineMetadata {
+    /// Language of the article (`L`), e.g. `en`.
+    pub language: Option<String>,
+    /// Sentiment score (`K`) from -1 (negative) to 1 (positive).
+    pub sentiment: Option<f64>,
+    /// Confidence in the sentiment score (`C`) from 0 to 1.
+    pub confidence: Option<f64>,
+    /// Every key/value pair in the order received, including keys without a typed field.
+    pub fields: Vec<(String, String)>,
+}
+
+impl HeadlineMetadata {
+    /// Parses a metadata block, with or without its enclosing braces.
+    ///
+    /// Returns `None` if `text` holds no key/value pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ibapi::news::HeadlineMetadata;
+    ///
+    /// let metadata = HeadlineMetadata::parse("{A:800015:L:en:K:0.97:C:0.9}").unwrap();
+    /// assert_eq!(metadata.language.as_deref(), Some("en"));
+    /// assert_eq!(metadata.sentiment, Some(0.97));
+    /// assert_eq!(metadata.get("A"), Some("800015"));
+    /// ```
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text.strip_prefix('{').and_then(|inner| inner.strip_suffix('}')).unwrap_or(text);
+
+        let parts: Vec<&str> = text.split(':').collect();
+        let fields: Vec<(String, String)> = parts
+            .chunks_exact(2)
+            .filter(|pair| !pair[0].is_empty())
+            .map(|pair| (pair[0].to_string(), pair[1].to_string()))
+            .collect();
+        if fields.is_empty() {
+            return None;
+        }
+
+        let mut metadata = HeadlineMetadata {
+            fields,
+            ..Default::default()
+        };
+        metadata.language = metadata.get("L").filter(|language| !language.is_empty()).map(str::to_string);
+        // Scores are "n/a" when the article was not rated.
+        metadata.sentiment = metadata.get("K").and_then(|score| score.parse().ok());
+        metadata.confidence = metadata.get("C").and_then(|score| score.parse().ok());
+        Some(metadata)
+    }
+
+    /// Value of `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.iter().find(|(existing, _)| existing == key).map(|(_, value)| value.as_str())
+    }
+}
+
+impl NewsArticle {
+    /// Metadata from the headline prefix, or from [extra_data](Self::extra_data) if the headline has none.
+    pub fn metadata(&self) -> Option<HeadlineMetadata> {
+        match split_headline(&self.headline) {
+            (Some(prefix), _) => HeadlineMetadata::parse(prefix),
+            (None, _) => HeadlineMetadata::parse(&self.extra_data),
+        }
+    }
+
+    /// The headline without its metadata prefix.
+    pub fn headline_text(&self) -> &str {
+        split_headline(&self.headline).1
+    }
+}
+
+// Splits "{...}Headline" into the metadata block and the headline.
+fn split_headline(headline: &str) -> (Option<&str>, &str) {
+    if headline.starts_with('{') {
+        if let Some(end) = headline.find('}') {
+            return (Some(&headline[..=end]), headline[end + 1..].trim_start());
+        }
+    }
+    (None, headline)
+}
+
+impl NewsArticleBody {
+    /// Raw content of the article: base64 decoded for [ArticleType::Binary], UTF-8 bytes otherwise.
+    pub fn bytes(&self) -> Result<Vec<u8>, Error> {
+        match self.article_type {
+            ArticleType::Binary => decode_base64(&self.article_text),
+            ArticleType::Text => Ok(self.article_text.clone().into_bytes()),
+        }
+    }
+
+    /// Readable text of a [ArticleType::Text] article, with HTML markup removed.
+    ///
+    /// Returns `None` for binary articles (typically PDFs); use [bytes](Self::bytes) for those.
+    pub fn plain_text(&self) -> Option<String> {
+        match self.article_type {
+            ArticleType::Text => Some(html_to_text(&self.article_text)),
+            ArticleType::Binary => None,
+        }
+    }
+}
+
+// Decodes standard base64, ignoring whitespace and tolerating missing padding.
+pub(crate) fn decode_base64(encoded: &str) -> Result<Vec<u8>, Error> {
+    let invalid = |reason: &str| Error::Parse(0, encoded.chars().take(32).collect(), format!("invalid base64: {reason}"));
+
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    let mut padding = false;
+
+    for c in encoded.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => {
+                padding = true;
+                continue;
+            }
+            c if c.is_ascii_whitespace() => continue,
+            _ => return Err(invalid(&format!("unexpected character {:?}", c as char))),
+        };
+        if padding {
+            return Err(invalid("data after padding"));
+        }
+
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    // A single leftover character cannot encode a whole byte.
+    if bits >= 6 {
+        return Err(invalid("truncated input"));
+    }
+    Ok(bytes)
+}
+
+// Elements separated from the surrounding text by a blank line.
+const PARAGRAPH_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "dl",
+    "div",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+// Elements that start a new line.
+const LINE_ELEMENTS: &[&str] = &["dd", "dt", "li", "td", "th", "tr"];
+
+// Elements whose content is not part of the article text.
+const HIDDEN_ELEMENTS: &[&str] = &["head", "script", "style", "title"];
+
+// Extracts readable text from an HTML article. Plain text is returned unchanged.
+pub(crate) fn html_to_text(html: &str) -> String {
+    if !looks_like_html(html) {
+        return html.to_string();
+    }
+
+    let mut text = String::with_capacity(html.len());
+    let mut hidden: Option<String> = None;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        if hidden.is_none() {
+            push_text(&mut text, &rest[..start]);
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        if let Some(element) = &hidden {
+            if closing && &name == element {
+                hidden = None;
+            }
+            continue;
+        }
+        if !closing && !tag.ends_with('/') && HIDDEN_ELEMENTS.contains(&name.as_str()) {
+            hidden = Some(name);
+        } else if name == "br" {
+            end_line(&mut text, 0);
+        } else if LINE_ELEMENTS.contains(&name.as_str()) {
+            end_line(&mut text, 1);
+        } else if PARAGRAPH_ELEMENTS.contains(&name.as_str()) {
+            end_line(&mut text, 2);
+        }
+    }
+    if hidden.is_none() {
+        push_text(&mut text, rest);
+    }
+
+    // Trim every line and keep at most one blank line between paragraphs.
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if !line.is_empty() || lines.last().is_some_and(|last| !last.is_empty()) {
+            lines.push(line);
+        }
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+// Breaks the line unless the text already ends with `newlines` line breaks; 0 always breaks.
+fn end_line(text: &mut String, newlines: usize) {
+    text.truncate(text.trim_end_matches(' ').len());
+    let trailing = text.len() - text.trim_end_matches('\n').len();
+    if newlines == 0 {
+        text.push('\n');
+    } else if !text.is_empty() {
+        for _ in trailing..newlines {
+            text.push('\n');
+        }
+    }
+}
+
+fn looks_like_html(text: &str) -> bool {
+    text.match_indices('<')
+        .any(|(i, _)| text[i + 1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!'))
+}
+
+// Appends text content, collapsing whitespace the way a browser renders it.
+fn push_text(text: &mut String, content: &str) {
+    let mut space = false;
+    for c in decode_entities(content).chars() {
+        if c.is_whitespace() && c != '\u{a0}' {
+            space = true;
+            continue;
+        }
+        if space && !text.is_empty() && !text.ends_with([' ', '\n']) {
+            text.push(' ');
+        }
+        space = false;
+        text.push(if c == '\u{a0}' { ' ' } else { c });
+    }
+    if space && !text.is_empty() && !text.ends_with([' ', '\n']) {
+        text.push(' ');
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..].find(';').filter(|end| *end <= 10).and_then(|end| {
+            let name = &rest[1..=end];
+            let c = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => match name.strip_prefix('#') {
+                    Some(code) => match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => code.parse().ok(),
+                    }
+                    .and_then(char::from_u32),
+                    None => None,
+                },
+            };
+            c.map(|c| (c, end + 2))
+        });
+
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::OffsetDateTime;
+
+    fn article(headline: &str, extra_data: &str) -> NewsArticle {
+        NewsArticle {
+            time: OffsetDateTime::UNIX_EPOCH,
+            provider_code: "BRFG".to_string(),
+            article_id: "BRFG$1a2b".to_string(),
+            headline: headline.to_string(),
+            extra_data: extra_data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_headline_metadata() {
+        let news = article("{A:800015:L:en:K:-0.97:C:0.9}Tesla recalls vehicles", "");
+        let metadata = news.metadata().expect("metadata");
+
+        assert_eq!(metadata.language.as_deref(), Some("en"));
+        assert_eq!(metadata.sentiment, Some(-0.97));
+        assert_eq!(metadata.confidence, Some(0.9));
+        assert_eq!(metadata.get("A"), Some("800015"));
+        assert_eq!(metadata.fields.len(), 4);
+        assert_eq!(news.headline_text(), "Tesla recalls vehicles");
+
+        let news = article("Fed holds rates", "A:800015,8314:L:de:K:n/a:C:n/a");
+        let metadata = news.metadata().expect("metadata");
+        assert_eq!(metadata.language.as_deref(), Some("de"));
+        assert_eq!(metadata.sentiment, None);
+        assert_eq!(metadata.confidence, None);
+        assert_eq!(metadata.get("A"), Some("800015,8314"));
+        assert_eq!(news.headline_text(), "Fed holds rates");
+
+        assert_eq!(article("Plain headline", "").metadata(), None);
+        assert_eq!(HeadlineMetadata::parse("{}"), None);
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64("JVBERi0xLjQK").unwrap(), b"%PDF-1.4\n");
+        assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGVsbG8").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGVs\r\nbG8h").unwrap(), b"hello!");
+        assert_eq!(decode_base64("").unwrap(), b"");
+
+        assert!(matches!(decode_base64("aGV*bG8="), Err(Error::Parse(..))));
+        assert!(matches!(decode_base64("aGVsbG8=x"), Err(Error::Parse(..))));
+        assert!(matches!(decode_base64("aGVsb"), Err(Error::Parse(..))));
+    }
+
+    #[test]
+    fn test_article_body() {
+        let binary = NewsArticleBody {
+            article_type: ArticleType::Binary,
+            article_text: "JVBERi0xLjQK".to_string(),
+        };
+        assert_eq!(binary.bytes().unwrap(), b"%PDF-1.4\n");
+        assert_eq!(binary.plain_text(), None);
+
+        let text = NewsArticleBody {
+            article_type: ArticleType::Text,
+            article_text: "<p>Shares rose&nbsp;5%</p>".to_string(),
+        };
+        assert_eq!(text.bytes().unwrap(), b"<p>Shares rose&nbsp;5%</p>");
+        assert_eq!(text.plain_text().as_deref(), Some("Shares rose 5%"));
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = r#"<html><head><title>Ignored</title><style>p { color: red; }</style></head>
+            <body>
+              <h1>Q3   results</h1>
+              <!-- generated <b>by</b> feed -->
+              <p>Revenue rose <b>12%</b> to &#36;1.2B &amp; margins
+                 widened.<br/>Guidance &lt;unchanged&gt;.</p>
+              <script>alert("x")</script>
+              <ul><li>EPS&#x3a; $0.42</li><li>Caf&eacute; R&amp;D</li></ul>
+            </body></html>"#;
+
+        assert_eq!(
+            html_to_text(html),
+            "Q3 results\n\nRevenue rose 12% to $1.2B & margins widened.\nGuidance <unchanged>.\n\nEPS: $0.42\nCaf&eacute; R&D"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_is_unchanged() {
+        let text = "Line one\n\n  indented line with a < b & c";
+        assert_eq!(html_to_text(text), text);
+    }
+}
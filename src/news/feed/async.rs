@@ -0,0 +1,150 @@
+//! Asynchronous news feed.
+
+use futures::future::select_all;
+
+use super::{NewsItem, NewsSource, SeenArticles};
+use crate::contracts::Contract;
+use crate::news::NewsArticle;
+use crate::subscriptions::Subscription;
+use crate::{Client, Error};
+
+/// Headlines from several broad tape and contract news subscriptions, without duplicates.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::contracts::Contract;
+/// use ibapi::news::NewsFeed;
+/// use ibapi::Client;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+///
+///     let mut feed = NewsFeed::new(&client);
+///     feed.broad_tape("BRFG").await.expect("broad tape request failed");
+///     feed.contract(&Contract::stock("AAPL").build(), &["BRFG", "DJNL"]).await.expect("contract news request failed");
+///
+///     while let Some(item) = feed.next().await {
+///         match item {
+///             Ok(item) => println!("{:?}: {}", item.source, item.article.headline_text()),
+///             Err(error) => eprintln!("news subscription failed: {error}"),
+///         }
+///     }
+/// }
+/// ```
+pub struct NewsFeed<'a> {
+    client: &'a Client,
+    subscriptions: Vec<(NewsSource, Subscription<NewsArticle>)>,
+    seen: SeenArticles,
+}
+
+impl<'a> NewsFeed<'a> {
+    /// Creates an empty feed whose subscriptions are made on `client`.
+    pub fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            subscriptions: Vec::new(),
+            seen: SeenArticles::default(),
+        }
+    }
+
+    /// Subscribes to all headlines of the provider with `provider_code`.
+    pub async fn broad_tape(&mut self, provider_code: &str) -> Result<(), Error> {
+        let subscription = self.client.broad_tape_news(provider_code).await?;
+        self.subscriptions.push((NewsSource::BroadTape(provider_code.to_string()), subscription));
+        Ok(())
+    }
+
+    /// Subscribes to headlines about `contract` from the providers with `provider_codes`.
+    pub async fn contract(&mut self, contract: &Contract, provider_codes: &[&str]) -> Result<(), Error> {
+        let subscription = self.client.contract_news(contract, provider_codes).await?;
+        self.subscriptions.push((NewsSource::Contract(contract.clone()), subscription));
+        Ok(())
+    }
+
+    /// Number of active subscriptions.
+    pub fn len(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Returns true if the feed has no active subscription.
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Waits for the next article. Returns `None` once no subscription is left.
+    ///
+    /// A subscription that fails or ends is removed from the feed; failures are reported with their error.
+    pub async fn next(&mut self) -> Option<Result<NewsItem, Error>> {
+        loop {
+            if self.subscriptions.is_empty() {
+                return None;
+            }
+
+            let (article, index) = {
+                let receivers = self.subscriptions.iter_mut().map(|(_, subscription)| Box::pin(subscription.next()));
+                let (article, index, _) = select_all(receivers).await;
+                (article, index)
+            };
+
+            match article {
+                Some(Ok(article)) => {
+                    if self.seen.insert(&article) {
+                        let source = self.subscriptions[index].0.clone();
+                        return Some(Ok(NewsItem { source, article }));
+                    }
+                }
+                Some(Err(error)) => {
+                    self.subscriptions.remove(index);
+                    return Some(Err(error));
+                }
+                None => {
+                    self.subscriptions.remove(index);
+                }
+            }
+        }
+    }
+
+    /// Cancels every subscription.
+    pub async fn cancel(&mut self) {
+        for (_, subscription) in self.subscriptions.drain(..) {
+            subscription.cancel().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{server_versions, stubs::MessageBusStub};
+    use std::sync::{Arc, RwLock};
+
+    #[tokio::test]
+    async fn test_news_feed_drops_duplicates() {
+        // The stub delivers every response to both subscriptions.
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: vec![
+                "84|9000|1672531200|BZ|BZ$123|{A:800015:L:en:K:0.97:C:0.9}Tesla beats estimates||".to_owned(),
+                "84|9000|1672531300|BZ|BZ$124|Tesla opens new plant|L:en:K:n/a:C:n/a|".to_owned(),
+            ],
+        });
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+        let mut feed = NewsFeed::new(&client);
+        feed.broad_tape("BZ").await.expect("broad tape request failed");
+        feed.contract(&Contract::stock("TSLA").build(), &["BZ"])
+            .await
+            .expect("contract news request failed");
+        assert_eq!(feed.len(), 2);
+
+        let mut ids = Vec::new();
+        while let Some(item) = feed.next().await {
+            ids.push(item.expect("news subscription failed").article.article_id);
+        }
+
+        assert_eq!(ids, vec!["BZ$123", "BZ$124"]);
+        assert!(feed.is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+//! Single stream of news headlines merged from several subscriptions.
+//!
+//! A `NewsFeed` combines broad tape subscriptions for whole providers with news subscriptions for
+//! individual contracts. An article delivered by more than one subscription is reported once.
+
+use std::collections::{HashSet, VecDeque};
+
+use super::NewsArticle;
+use crate::contracts::Contract;
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
+
+#[cfg(feature = "async")]
+pub(crate) mod r#async;
+
+// Number of recent articles remembered to drop duplicates.
+const SEEN_CAPACITY: usize = 4096;
+
+/// Subscription of a `NewsFeed` that delivered an article.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum NewsSource {
+    /// Broad tape news of the provider with this code.
+    BroadTape(String),
+    /// News for the contract.
+    Contract(Contract),
+}
+
+/// Article delivered by a `NewsFeed`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewsItem {
+    /// Subscription the article arrived on first.
+    pub source: NewsSource,
+    /// The headline.
+    pub article: NewsArticle,
+}
+
+// Recently delivered articles, oldest first.
+#[derive(Debug, Default)]
+struct SeenArticles {
+    ids: HashSet<(String, String)>,
+    order: VecDeque<(String, String)>,
+}
+
+impl SeenArticles {
+    // Records the article and returns true if it was not seen recently.
+    fn insert(&mut self, article: &NewsArticle) -> bool {
+        let key = (article.provider_code.clone(), article.article_id.clone());
+        if !self.ids.insert(key.clone()) {
+            return false;
+        }
+        self.order.push_back(key);
+        if self.order.len() > SEEN_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::OffsetDateTime;
+
+    fn article(provider_code: &str, article_id: &str) -> NewsArticle {
+        NewsArticle {
+            time: OffsetDateTime::UNIX_EPOCH,
+            provider_code: provider_code.to_string(),
+            article_id: article_id.to_string(),
+            headline: String::new(),
+            extra_data: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_seen_articles() {
+        let mut seen = SeenArticles::default();
+
+        assert!(seen.insert(&article("BZ", "BZ$1")));
+        assert!(!seen.insert(&article("BZ", "BZ$1")));
+        assert!(seen.insert(&article("DJ", "BZ$1")));
+
+        for i in 0..SEEN_CAPACITY {
+            seen.insert(&article("FLY", &i.to_string()));
+        }
+        assert_eq!(seen.order.len(), SEEN_CAPACITY);
+        assert!(seen.insert(&article("BZ", "BZ$1")), "old articles are forgotten");
+        assert!(!seen.insert(&article("FLY", &(SEEN_CAPACITY - 1).to_string())));
+    }
+}
@@ -0,0 +1,203 @@
+//! Blocking news feed.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{NewsItem, NewsSource, SeenArticles};
+use crate::client::sync::Client;
+use crate::contracts::Contract;
+use crate::news::NewsArticle;
+use crate::subscriptions::sync::Subscription;
+use crate::Error;
+
+// Pause between polling rounds when no subscription had an article.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Headlines from several broad tape and contract news subscriptions, without duplicates.
+///
+/// Iterating blocks until the next article. A subscription that fails is removed from the feed and
+/// its error is yielded; iteration ends once no subscription is left.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::client::blocking::Client;
+/// use ibapi::contracts::Contract;
+/// use ibapi::news::blocking::NewsFeed;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let mut feed = NewsFeed::new(&client);
+/// feed.broad_tape("BRFG").expect("broad tape request failed");
+/// feed.contract(&Contract::stock("AAPL").build(), &["BRFG", "DJNL"]).expect("contract news request failed");
+///
+/// for item in feed {
+///     match item {
+///         Ok(item) => println!("{:?}: {}", item.source, item.article.headline_text()),
+///         Err(error) => eprintln!("news subscription failed: {error}"),
+///     }
+/// }
+/// ```
+pub struct NewsFeed<'a> {
+    client: &'a Client,
+    subscriptions: Vec<(NewsSource, Subscription<NewsArticle>)>,
+    seen: SeenArticles,
+    // Index of the subscription polled first in the next round, so busy providers cannot starve the others.
+    cursor: usize,
+}
+
+impl<'a> NewsFeed<'a> {
+    /// Creates an empty feed whose subscriptions are made on `client`.
+    pub fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            subscriptions: Vec::new(),
+            seen: SeenArticles::default(),
+            cursor: 0,
+        }
+    }
+
+    /// Subscribes to all headlines of the provider with `provider_code`.
+    pub fn broad_tape(&mut self, provider_code: &str) -> Result<(), Error> {
+        let subscription = self.client.broad_tape_news(provider_code)?;
+        self.subscriptions.push((NewsSource::BroadTape(provider_code.to_string()), subscription));
+        Ok(())
+    }
+
+    /// Subscribes to headlines about `contract` from the providers with `provider_codes`.
+    pub fn contract(&mut self, contract: &Contract, provider_codes: &[&str]) -> Result<(), Error> {
+        let subscription = self.client.contract_news(contract, provider_codes)?;
+        self.subscriptions.push((NewsSource::Contract(contract.clone()), subscription));
+        Ok(())
+    }
+
+    /// Number of active subscriptions.
+    pub fn len(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    /// Returns true if the feed has no active subscription.
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Returns an article that already arrived, without blocking.
+    pub fn try_next(&mut self) -> Option<Result<NewsItem, Error>> {
+        while !self.subscriptions.is_empty() {
+            match self.poll() {
+                Some(Ok(Some(item))) => return Some(Ok(item)),
+                Some(Ok(None)) => continue,
+                Some(Err(error)) => return Some(Err(error)),
+                None => return None,
+            }
+        }
+        None
+    }
+
+    /// Waits up to `timeout` for the next article.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<Result<NewsItem, Error>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(item) = self.try_next() {
+                return Some(item);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if self.subscriptions.is_empty() || remaining.is_zero() {
+                return None;
+            }
+            thread::sleep(remaining.min(POLL_INTERVAL));
+        }
+    }
+
+    /// Cancels every subscription.
+    pub fn cancel(&mut self) {
+        for (_, subscription) in self.subscriptions.drain(..) {
+            subscription.cancel();
+        }
+    }
+
+    // Checks every subscription once. Yields Ok(None) for a duplicate article.
+    fn poll(&mut self) -> Option<Result<Option<NewsItem>, Error>> {
+        for offset in 0..self.subscriptions.len() {
+            let index = (self.cursor + offset) % self.subscriptions.len();
+            let (source, subscription) = &self.subscriptions[index];
+
+            if let Some(article) = subscription.try_next() {
+                self.cursor = index + 1;
+                if !self.seen.insert(&article) {
+                    return Some(Ok(None));
+                }
+                let source = source.clone();
+                return Some(Ok(Some(NewsItem { source, article })));
+            }
+
+            if let Some(error) = subscription.error() {
+                self.subscriptions.remove(index);
+                self.cursor = index;
+                return Some(Err(error));
+            }
+        }
+        None
+    }
+}
+
+impl Iterator for NewsFeed<'_> {
+    type Item = Result<NewsItem, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.try_next() {
+                return Some(item);
+            }
+            if self.subscriptions.is_empty() {
+                return None;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{server_versions, stubs::MessageBusStub};
+    use std::sync::{Arc, RwLock};
+
+    #[test]
+    fn test_news_feed_drops_duplicates() {
+        // The stub delivers every response to both subscriptions.
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: vec![
+                "84|9000|1672531200|BZ|BZ$123|{A:800015:L:en:K:0.97:C:0.9}Tesla beats estimates||".to_owned(),
+                "84|9000|1672531300|BZ|BZ$124|Tesla opens new plant|L:en:K:n/a:C:n/a|".to_owned(),
+            ],
+        });
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+        let mut feed = NewsFeed::new(&client);
+        feed.broad_tape("BZ").expect("broad tape request failed");
+        feed.contract(&Contract::stock("TSLA").build(), &["BZ"])
+            .expect("contract news request failed");
+        assert_eq!(feed.len(), 2);
+
+        let request_messages = client.message_bus.request_messages();
+        assert!(request_messages[0].encode().contains("mdoff,292"));
+        assert!(request_messages[1].encode().contains("mdoff,292:BZ"));
+
+        let mut items = Vec::new();
+        while let Some(item) = feed.next_timeout(Duration::from_millis(100)) {
+            items.push(item.expect("news subscription failed"));
+        }
+
+        let ids: Vec<&str> = items.iter().map(|item| item.article.article_id.as_str()).collect();
+        assert_eq!(ids, vec!["BZ$123", "BZ$124"]);
+        assert_eq!(items[0].source, NewsSource::BroadTape("BZ".to_string()));
+        assert_eq!(items[0].article.headline_text(), "Tesla beats estimates");
+        assert_eq!(items[0].article.metadata().and_then(|metadata| metadata.sentiment), Some(0.97));
+        assert_eq!(items[1].article.metadata().and_then(|metadata| metadata.language), Some("en".to_string()));
+
+        feed.cancel();
+        assert!(feed.is_empty());
+    }
+}
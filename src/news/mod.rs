@@ -8,7 +8,12 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 // Common implementation modules
+mod article;
 mod common;
+mod feed;
+
+pub use article::HeadlineMetadata;
+pub use feed::{NewsItem, NewsSource};
 
 // Feature-specific implementations
 #[cfg(feature = "sync")]
@@ -99,9 +104,15 @@ pub struct NewsArticleBody {
 #[cfg(feature = "sync")]
 /// Blocking news data helpers that wrap the synchronous client.
 pub mod blocking {
+    pub use super::feed::sync::NewsFeed;
     pub(crate) use super::sync::{broad_tape_news, contract_news, historical_news, news_article, news_bulletins, news_providers};
 }
 
+#[cfg(feature = "async")]
+pub use feed::r#async::NewsFeed;
+#[cfg(all(feature = "sync", not(feature = "async")))]
+pub use feed::sync::NewsFeed;
+
 #[cfg(all(feature = "sync", not(feature = "async")))]
 #[allow(unused_imports)]
 pub(crate) use sync::{broad_tape_news, contract_news, historical_news, news_article, news_bulletins, news_providers};
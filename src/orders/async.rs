@@ -24,6 +24,7 @@ impl StreamDecoder<PlaceOrder> for PlaceOrder {
         IncomingMessages::OrderStatus,
         IncomingMessages::ExecutionData,
         IncomingMessages::CommissionsReport,
+        IncomingMessages::DeltaNeutralValidation,
         IncomingMessages::Error,
     ];
 
@@ -42,6 +43,10 @@ impl StreamDecoder<PlaceOrder> for PlaceOrder {
                 context.server_version,
                 message,
             )?)),
+            IncomingMessages::DeltaNeutralValidation => Ok(PlaceOrder::DeltaNeutralValidation(decoders::decode_delta_neutral_validation(
+                context.server_version,
+                message,
+            )?)),
             IncomingMessages::Error => Ok(PlaceOrder::Message(Notice::from(message))),
             _ => Err(Error::UnexpectedResponse(message.clone())),
         }
@@ -73,6 +78,7 @@ impl StreamDecoder<OrderUpdate> for OrderUpdate {
                 context.server_version,
                 message,
             )?)),
+            IncomingMessages::OrderBound => Ok(OrderUpdate::OrderBound(decoders::decode_order_bound(message)?)),
             IncomingMessages::Error => Ok(OrderUpdate::Message(Notice::from(message))),
             _ => Err(Error::UnexpectedResponse(message.clone())),
         }
@@ -200,6 +206,7 @@ pub(crate) async fn submit_order(client: &Client, order_id: i32, contract: &Cont
     verify::verify_order_contract(client, contract, order_id)?;
 
     let request = encoders::encode_place_order(client.server_version(), order_id, contract, order)?;
+    journal::record_submission(client.order_journal(), client.client_id(), order_id, contract, order)?;
     client.send_message(request).await?;
 
     Ok(())
@@ -213,6 +220,7 @@ pub(crate) async fn place_order(client: &Client, order_id: i32, contract: &Contr
     verify::verify_order_contract(client, contract, order_id)?;
 
     let request = encoders::encode_place_order(client.server_version(), order_id, contract, order)?;
+    journal::record_submission(client.order_journal(), client.client_id(), order_id, contract, order)?;
     let internal_subscription = client.send_order(order_id, request).await?;
 
     Ok(Subscription::new_from_internal_simple::<PlaceOrder>(
@@ -376,6 +384,7 @@ mod tests {
     use super::*;
     use crate::contracts::{Contract, SecurityType};
     use crate::contracts::{Currency, Exchange, Symbol};
+    use crate::orders::OrderBound;
     use crate::stubs::MessageBusStub;
     // use crate::testdata::responses;  // No order responses defined yet
     use crate::{server_versions, Client};
@@ -703,6 +712,8 @@ mod tests {
                 "11|1|1|637533641|ES|FUT|20250919|0.0||50|CME|USD|ESU5|ES|0001f4e5.58bbad52.01.01|20250708 02:35:00 America/New_York|DU1234567|CME|BOT|1.0|5800.0|2126726143|100|0|1.0|5800.0|||0.0||1|".to_string(),
                 // Mock CommissionReport response
                 "59|1|0001f4e5.58bbad52.01.01|2.25|USD|0.0|0.0||".to_string(),
+                // Mock OrderBound response
+                "100|2126726143|0|100|".to_string(),
             ],
         });
 
@@ -721,6 +732,17 @@ mod tests {
         // Test that we can receive CommissionReport
         let update = stream.next().await.unwrap().unwrap();
         assert!(matches!(update, OrderUpdate::CommissionReport(_)));
+
+        // Test that we can receive OrderBound
+        let update = stream.next().await.unwrap().unwrap();
+        assert!(matches!(
+            update,
+            OrderUpdate::OrderBound(OrderBound {
+                perm_id: 2126726143,
+                client_id: 0,
+                order_id: 100
+            })
+        ));
     }
 
     #[tokio::test]
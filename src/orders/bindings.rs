@@ -0,0 +1,85 @@
+//! Index of orders bound to API clients.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use super::OrderBound;
+
+#[cfg(test)]
+mod tests;
+
+/// Index of [OrderBound] bindings keyed by permanent id.
+///
+/// The client records every binding TWS reports, so an order placed manually in TWS can be
+/// found by its permanent id and then cancelled or modified through its API order id. Clones
+/// share the same index.
+///
+/// # Examples
+///
+/// ```
+/// use ibapi::orders::{OrderBindings, OrderBound};
+///
+/// let bindings = OrderBindings::new();
+/// bindings.record(OrderBound { perm_id: 1376327563, client_id: 0, order_id: -12 });
+///
+/// assert_eq!(bindings.order_id(1376327563), Some(-12));
+/// assert_eq!(bindings.perm_id(0, -12), Some(1376327563));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OrderBindings {
+    inner: Arc<RwLock<HashMap<i64, OrderBound>>>,
+}
+
+impl OrderBindings {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `binding`, replacing any earlier binding of the same permanent id.
+    pub fn record(&self, binding: OrderBound) {
+        self.inner.write().unwrap().insert(binding.perm_id, binding);
+    }
+
+    /// Binding of the order with permanent id `perm_id`.
+    pub fn get(&self, perm_id: i64) -> Option<OrderBound> {
+        self.inner.read().unwrap().get(&perm_id).copied()
+    }
+
+    /// API order id of the order with permanent id `perm_id`.
+    pub fn order_id(&self, perm_id: i64) -> Option<i32> {
+        self.get(perm_id).map(|binding| binding.order_id)
+    }
+
+    /// Permanent id of the order bound to `client_id` with `order_id`.
+    pub fn perm_id(&self, client_id: i32, order_id: i32) -> Option<i64> {
+        self.inner
+            .read()
+            .unwrap()
+            .values()
+            .find(|binding| binding.client_id == client_id && binding.order_id == order_id)
+            .map(|binding| binding.perm_id)
+    }
+
+    /// All bindings ordered by permanent id.
+    pub fn bindings(&self) -> Vec<OrderBound> {
+        let mut bindings: Vec<OrderBound> = self.inner.read().unwrap().values().copied().collect();
+        bindings.sort_by_key(|binding| binding.perm_id);
+        bindings
+    }
+
+    /// Number of bound orders.
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().len()
+    }
+
+    /// Returns true if no order was bound.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().unwrap().is_empty()
+    }
+
+    /// Forgets every binding.
+    pub fn clear(&self) {
+        self.inner.write().unwrap().clear();
+    }
+}
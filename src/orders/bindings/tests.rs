@@ -0,0 +1,49 @@
+use super::*;
+
+fn binding(perm_id: i64, client_id: i32, order_id: i32) -> OrderBound {
+    OrderBound {
+        perm_id,
+        client_id,
+        order_id,
+    }
+}
+
+#[test]
+fn test_record_and_lookup() {
+    let bindings = OrderBindings::new();
+    assert!(bindings.is_empty());
+
+    bindings.record(binding(2000, 0, -5));
+    bindings.record(binding(1000, 7, 42));
+
+    assert_eq!(bindings.len(), 2);
+    assert_eq!(bindings.get(1000), Some(binding(1000, 7, 42)));
+    assert_eq!(bindings.order_id(2000), Some(-5));
+    assert_eq!(bindings.order_id(3000), None);
+    assert_eq!(bindings.perm_id(7, 42), Some(1000));
+    assert_eq!(bindings.perm_id(0, 42), None, "order ids are scoped by client id");
+    assert_eq!(bindings.bindings(), vec![binding(1000, 7, 42), binding(2000, 0, -5)]);
+}
+
+#[test]
+fn test_rebind_replaces_binding() {
+    let bindings = OrderBindings::new();
+    bindings.record(binding(1000, 0, -1));
+    bindings.record(binding(1000, 7, 43));
+
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings.get(1000), Some(binding(1000, 7, 43)));
+    assert_eq!(bindings.perm_id(0, -1), None);
+}
+
+#[test]
+fn test_clones_share_index() {
+    let bindings = OrderBindings::new();
+    let shared = bindings.clone();
+
+    shared.record(binding(1000, 7, 42));
+    assert_eq!(bindings.order_id(1000), Some(42));
+
+    bindings.clear();
+    assert!(shared.is_empty());
+}
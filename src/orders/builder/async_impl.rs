@@ -1,4 +1,4 @@
-use super::{BracketOrderBuilder, BracketOrderIds, OrderBuilder, OrderId};
+use super::{BracketOrderBuilder, BracketOrderIds, OrderBuilder, OrderId, ScaleOrderBuilder, VolatilityOrderBuilder};
 use crate::client::r#async::Client;
 use crate::errors::Error;
 use crate::orders;
@@ -48,6 +48,42 @@ impl<'a> OrderBuilder<'a, Client> {
     }
 }
 
+impl<'a> ScaleOrderBuilder<'a, Client> {
+    /// Submit the scale order asynchronously
+    /// Returns the order ID assigned to the submitted order
+    pub async fn submit(self) -> Result<OrderId, Error> {
+        let client = self.parent_builder.client;
+        let contract = self.parent_builder.contract;
+        let order_id = client.next_order_id();
+        let order = self.build()?;
+        orders::submit_order(client, order_id, contract, &order).await?;
+        Ok(OrderId::new(order_id))
+    }
+
+    /// Build the scale order and return it without submitting
+    pub fn build_order(self) -> Result<crate::orders::Order, Error> {
+        self.build().map_err(Into::into)
+    }
+}
+
+impl<'a> VolatilityOrderBuilder<'a, Client> {
+    /// Submit the volatility order asynchronously
+    /// Returns the order ID assigned to the submitted order
+    pub async fn submit(self) -> Result<OrderId, Error> {
+        let client = self.parent_builder.client;
+        let contract = self.parent_builder.contract;
+        let order_id = client.next_order_id();
+        let order = self.build()?;
+        orders::submit_order(client, order_id, contract, &order).await?;
+        Ok(OrderId::new(order_id))
+    }
+
+    /// Build the volatility order and return it without submitting
+    pub fn build_order(self) -> Result<crate::orders::Order, Error> {
+        self.build().map_err(Into::into)
+    }
+}
+
 impl<'a> BracketOrderBuilder<'a, Client> {
     /// Submit bracket orders asynchronously
     /// Returns BracketOrderIds containing all three order IDs
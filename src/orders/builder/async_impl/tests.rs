@@ -3,6 +3,7 @@ use crate::errors::Error;
 use crate::orders::builder::tests::async_mock_client::mock::AsyncMockClient;
 use crate::orders::builder::{BracketOrderBuilder, BracketOrderIds, OrderBuilder, OrderId};
 use crate::orders::{Action, Order, OrderData, OrderState, OrderStatus, OrderUpdate, PlaceOrder};
+use crate::Decimal;
 use futures::{Stream, StreamExt};
 use std::pin::Pin;
 
@@ -138,7 +139,7 @@ async fn test_async_order_submit() {
     assert_eq!(submitted.len(), 1);
     assert_eq!(submitted[0].0, 100);
     assert_eq!(submitted[0].2.action, Action::Buy);
-    assert_eq!(submitted[0].2.total_quantity, 100.0);
+    assert_eq!(submitted[0].2.total_quantity, Decimal::from(100));
     assert_eq!(submitted[0].2.order_type, "LMT");
     assert_eq!(submitted[0].2.limit_price, Some(50.00));
 }
@@ -302,8 +303,8 @@ async fn test_async_order_update_stream() {
         OrderUpdate::OrderStatus(OrderStatus {
             order_id: 100,
             status: "PendingSubmit".to_string(),
-            filled: 0.0,
-            remaining: 100.0,
+            filled: Decimal::from(0),
+            remaining: Decimal::from(100),
             average_fill_price: 0.0,
             perm_id: 12345,
             parent_id: 0,
@@ -315,8 +316,8 @@ async fn test_async_order_update_stream() {
         OrderUpdate::OrderStatus(OrderStatus {
             order_id: 100,
             status: "Submitted".to_string(),
-            filled: 0.0,
-            remaining: 100.0,
+            filled: Decimal::from(0),
+            remaining: Decimal::from(100),
             average_fill_price: 0.0,
             perm_id: 12345,
             parent_id: 0,
@@ -328,8 +329,8 @@ async fn test_async_order_update_stream() {
         OrderUpdate::OrderStatus(OrderStatus {
             order_id: 100,
             status: "Filled".to_string(),
-            filled: 100.0,
-            remaining: 0.0,
+            filled: Decimal::from(100),
+            remaining: Decimal::from(0),
             average_fill_price: 50.00,
             perm_id: 12345,
             parent_id: 0,
@@ -365,7 +366,7 @@ async fn test_async_order_update_stream() {
 
     if let OrderUpdate::OrderStatus(status) = &updates[2] {
         assert_eq!(status.status, "Filled");
-        assert_eq!(status.filled, 100.0);
+        assert_eq!(status.filled, Decimal::from(100));
         assert_eq!(status.average_fill_price, 50.00);
     }
 }
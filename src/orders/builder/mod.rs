@@ -18,5 +18,8 @@ mod tests;
 pub use algo_builders::{AlgoParams, ArrivalPriceBuilder, PctVolBuilder, RiskAversion, TwapBuilder, TwapStrategyType, VwapBuilder};
 pub use algo_helpers::{arrival_price, pct_vol, twap, vwap};
 pub use condition_helpers::{execution, margin, percent_change, price, time, volume};
-pub use order_builder::{BracketOrderBuilder, OrderBuilder};
-pub use types::{AuctionType, BracketOrderIds, OrderAnalysis, OrderId, OrderType, Price, Quantity, TimeInForce, ValidationError};
+pub use order_builder::{AdjustedStopBuilder, BracketOrderBuilder, OrderBuilder, ScaleOrderBuilder, VolatilityOrderBuilder};
+pub use types::{
+    AuctionType, BracketOrderIds, FaMethod, HedgeType, OrderAnalysis, OrderId, OrderType, Price, Quantity, TimeInForce, TrailingAmount,
+    ValidationError,
+};
@@ -2,8 +2,9 @@ use super::algo_builders::AlgoParams;
 use super::types::*;
 use super::validation;
 use crate::contracts::Contract;
+use crate::decimal;
 use crate::market_data::TradingHours;
-use crate::orders::{Action, Order, OrderComboLeg, OrderCondition, TagValue};
+use crate::orders::{Action, Order, OrderComboLeg, OrderCondition, OrderOpenClose, ReferencePriceType, ShortSaleSlot, TagValue, VolatilityType};
 
 #[cfg(test)]
 mod tests;
@@ -84,8 +85,16 @@ pub struct OrderBuilder<'a, C> {
     // Starting price
     starting_price: Option<f64>,
 
-    // Hedge type
-    hedge_type: Option<String>,
+    // Hedge order fields
+    hedge_type: Option<HedgeType>,
+    hedge_param: Option<f64>,
+
+    // Financial Advisor allocation
+    fa_group: Option<String>,
+    fa_method: Option<FaMethod>,
+
+    // Adjusted stop
+    adjusted_stop: Option<AdjustedStop>,
 }
 
 impl<'a, C> OrderBuilder<'a, C> {
@@ -145,6 +154,10 @@ impl<'a, C> OrderBuilder<'a, C> {
             auction_strategy: None,
             starting_price: None,
             hedge_type: None,
+            hedge_param: None,
+            fa_group: None,
+            fa_method: None,
+            adjusted_stop: None,
         }
     }
 
@@ -333,6 +346,58 @@ impl<'a, C> OrderBuilder<'a, C> {
         self
     }
 
+    // Pegged order types
+
+    /// Pegged to Benchmark - moves with the price of another contract
+    ///
+    /// The order starts at `starting_price` and follows `reference_contract` from
+    /// `reference_price`. Set how far it moves with `pegged_change()`.
+    pub fn pegged_to_benchmark(mut self, reference_contract: &Contract, starting_price: f64, reference_price: f64) -> Self {
+        self.order_type = Some(OrderType::PeggedToBenchmark);
+        self.reference_contract_id = Some(reference_contract.contract_id);
+        self.reference_exchange = Some(reference_contract.exchange.to_string());
+        self.starting_price = Some(starting_price);
+        self.stock_ref_price = Some(reference_price);
+        self
+    }
+
+    /// Move a pegged-to-benchmark order by `pegged_change` whenever the reference moves by `reference_change`
+    pub fn pegged_change(mut self, pegged_change: f64, reference_change: f64) -> Self {
+        self.pegged_change_amount = Some(pegged_change);
+        self.reference_change_amount = Some(reference_change);
+        self
+    }
+
+    /// Move a pegged-to-benchmark order down when the reference moves up, and vice versa
+    pub fn pegged_change_decrease(mut self) -> Self {
+        self.is_pegged_change_amount_decrease = true;
+        self
+    }
+
+    /// Cancel a pegged order once the reference or underlying trades outside `lower..=upper`
+    pub fn reference_range(mut self, lower: f64, upper: f64) -> Self {
+        self.stock_range_lower = Some(lower);
+        self.stock_range_upper = Some(upper);
+        self
+    }
+
+    /// Pegged to Stock - option order priced from the underlying's move times `delta`
+    pub fn pegged_to_stock(mut self, delta: f64, stock_reference_price: f64, starting_price: f64) -> Self {
+        self.order_type = Some(OrderType::PeggedToStock);
+        self.delta = Some(delta);
+        self.stock_ref_price = Some(stock_reference_price);
+        self.starting_price = Some(starting_price);
+        self
+    }
+
+    /// Pegged to Midpoint - tracks the NBBO midpoint by `offset`, capped at `limit_price`
+    pub fn pegged_to_midpoint(mut self, offset: f64, limit_price: impl Into<f64>) -> Self {
+        self.order_type = Some(OrderType::PeggedToMidpoint);
+        self.aux_price = Some(offset);
+        self.limit_price = Some(limit_price.into());
+        self
+    }
+
     // Time in force methods
 
     /// Set time in force for the order
@@ -420,6 +485,56 @@ impl<'a, C> OrderBuilder<'a, C> {
         self
     }
 
+    /// Allocate the order across a Financial Advisor account group.
+    ///
+    /// Replaces `account()`; the two cannot be combined.
+    pub fn allocate_to_group(mut self, group: impl Into<String>, method: FaMethod) -> Self {
+        self.fa_group = Some(group.into());
+        self.fa_method = Some(method);
+        self
+    }
+
+    /// Size the order in units of the quote currency instead of a quantity.
+    ///
+    /// Supported for FX (`CASH`) and crypto orders. Combine with a zero quantity,
+    /// e.g. `.buy(0).cash_quantity(25_000.0)`; crypto sells must use a quantity.
+    pub fn cash_quantity(mut self, amount: f64) -> Self {
+        self.cash_qty = Some(amount);
+        self
+    }
+
+    /// Mark this order as a hedge attached to the order set with `parent()`.
+    ///
+    /// `param` is the beta for [HedgeType::Beta] and the ratio for [HedgeType::Pair];
+    /// pass `None` for delta and FX hedges.
+    pub fn attach_hedge(mut self, hedge_type: HedgeType, param: Option<f64>) -> Self {
+        self.hedge_type = Some(hedge_type);
+        self.hedge_param = param;
+        self
+    }
+
+    /// Turn this order into a volatility (VOL) order.
+    ///
+    /// TWS derives the option's limit price from `volatility`, a percentage
+    /// quoted as daily or annual volatility.
+    pub fn volatility_order(mut self, volatility: f64, volatility_type: VolatilityType) -> VolatilityOrderBuilder<'a, C> {
+        self.order_type = Some(OrderType::Volatility);
+        self.volatility = Some(volatility);
+        self.volatility_type = Some(volatility_type.into());
+        VolatilityOrderBuilder::new(self)
+    }
+
+    /// Adjust this stop order once the market trades through `trigger_price`.
+    ///
+    /// Choose the adjusted order with `to_stop()`, `to_stop_limit()` or `to_trailing()`.
+    /// TWS only adjusts stops attached to a parent order, so set one with `parent()`.
+    pub fn adjust_stop_when(self, trigger_price: f64) -> AdjustedStopBuilder<'a, C> {
+        AdjustedStopBuilder {
+            parent_builder: self,
+            trigger_price,
+        }
+    }
+
     /// Do not transmit order immediately
     pub fn do_not_transmit(mut self) -> Self {
         self.transmit = false;
@@ -431,6 +546,14 @@ impl<'a, C> OrderBuilder<'a, C> {
         BracketOrderBuilder::new(self)
     }
 
+    /// Turn this limit order into a scale order.
+    ///
+    /// The order is worked in components of `initial_size`, each priced
+    /// `price_increment` away from the previous one.
+    pub fn scale(self, initial_size: i32, price_increment: f64) -> ScaleOrderBuilder<'a, C> {
+        ScaleOrderBuilder::new(self, initial_size, price_increment)
+    }
+
     // Conditional orders
 
     /// Add a condition to the order.
@@ -667,8 +790,14 @@ impl<'a, C> OrderBuilder<'a, C> {
         let quantity_raw = self.quantity.ok_or(ValidationError::MissingRequiredField("quantity"))?;
         let order_type = self.order_type.ok_or(ValidationError::MissingRequiredField("order_type"))?;
 
-        // Validate quantity
-        let quantity = Quantity::new(quantity_raw)?;
+        // Validate quantity; cash quantity orders leave the share quantity at zero
+        let total_quantity = match self.cash_qty {
+            Some(cash_qty) => {
+                validation::validate_cash_quantity(self.contract, &action, quantity_raw, cash_qty)?;
+                0.0
+            }
+            None => Quantity::new(quantity_raw)?.value(),
+        };
 
         // Validate prices based on order type
         let limit_price = if order_type.requires_limit_price() {
@@ -713,6 +842,31 @@ impl<'a, C> OrderBuilder<'a, C> {
             return Err(ValidationError::MissingRequiredField("volatility"));
         }
 
+        if let Some(hedge_type) = self.hedge_type {
+            validation::validate_hedge(self.contract, hedge_type, self.hedge_param, self.parent_id)?;
+        }
+
+        if let (Some(group), Some(method)) = (&self.fa_group, &self.fa_method) {
+            validation::validate_fa_allocation(group, method, self.account.as_deref())?;
+        }
+
+        validation::validate_pegged(
+            &order_type,
+            &PeggedInputs {
+                reference_contract_id: self.reference_contract_id,
+                starting_price: self.starting_price,
+                pegged_change_amount: self.pegged_change_amount,
+                reference_change_amount: self.reference_change_amount,
+                delta: self.delta,
+                range: (self.stock_range_lower, self.stock_range_upper),
+            },
+        )?;
+
+        if let Some(adjusted) = &self.adjusted_stop {
+            let stop_price = self.stop_price.or(self.trail_stop_price);
+            validation::validate_adjusted_stop(&action, &order_type, stop_price, adjusted, self.parent_id)?;
+        }
+
         // Validate time in force specific requirements
         if let TimeInForce::GoodTillDate { .. } = &self.time_in_force {
             if self.good_till_date.is_none() {
@@ -723,7 +877,7 @@ impl<'a, C> OrderBuilder<'a, C> {
         // Build the order
         let mut order = Order {
             action,
-            total_quantity: quantity.value(),
+            total_quantity: decimal::from_f64(total_quantity),
             order_type: order_type.as_str().to_string(),
             ..Default::default()
         };
@@ -907,9 +1061,40 @@ impl<'a, C> OrderBuilder<'a, C> {
             order.starting_price = Some(price);
         }
 
-        // Set hedge type
+        // Set hedge fields
         if let Some(hedge_type) = self.hedge_type {
-            order.hedge_type = hedge_type;
+            order.hedge_type = hedge_type.as_str().to_string();
+            order.hedge_param = self.hedge_param.map(|param| param.to_string()).unwrap_or_default();
+        }
+
+        // Set adjusted stop fields
+        if let Some(adjusted) = self.adjusted_stop {
+            order.adjusted_order_type = adjusted.adjustment.order_type().as_str().to_string();
+            order.trigger_price = Some(adjusted.trigger_price);
+            match adjusted.adjustment {
+                StopAdjustment::Stop { stop_price } => {
+                    order.adjusted_stop_price = Some(stop_price);
+                }
+                StopAdjustment::StopLimit { stop_price, limit_price } => {
+                    order.adjusted_stop_price = Some(stop_price);
+                    order.adjusted_stop_limit_price = Some(limit_price);
+                }
+                StopAdjustment::Trailing { stop_price, amount } => {
+                    order.adjusted_stop_price = Some(stop_price);
+                    let (value, unit) = amount.value_and_unit();
+                    order.adjusted_trailing_amount = Some(value);
+                    order.adjustable_trailing_unit = unit;
+                }
+            }
+        }
+
+        // Set Financial Advisor allocation
+        if let (Some(group), Some(method)) = (self.fa_group, self.fa_method) {
+            order.fa_group = group;
+            order.fa_method = method.as_str().to_string();
+            if let FaMethod::PctChange(percentage) = method {
+                order.fa_percentage = percentage.to_string();
+            }
         }
 
         Ok(order)
@@ -928,6 +1113,199 @@ fn set_conjunction(condition: &mut OrderCondition, is_conjunction: bool) {
     }
 }
 
+/// Builder step choosing what a stop order becomes once its trigger is hit
+pub struct AdjustedStopBuilder<'a, C> {
+    parent_builder: OrderBuilder<'a, C>,
+    trigger_price: f64,
+}
+
+impl<'a, C> AdjustedStopBuilder<'a, C> {
+    /// Move the stop to `stop_price`, e.g. the entry price to lock in breakeven
+    pub fn to_stop(self, stop_price: f64) -> OrderBuilder<'a, C> {
+        self.adjust(StopAdjustment::Stop { stop_price })
+    }
+
+    /// Replace the stop with a stop limit order
+    pub fn to_stop_limit(self, stop_price: f64, limit_price: f64) -> OrderBuilder<'a, C> {
+        self.adjust(StopAdjustment::StopLimit { stop_price, limit_price })
+    }
+
+    /// Replace the stop with a trailing stop starting at `stop_price`
+    pub fn to_trailing(self, stop_price: f64, amount: TrailingAmount) -> OrderBuilder<'a, C> {
+        self.adjust(StopAdjustment::Trailing { stop_price, amount })
+    }
+
+    fn adjust(mut self, adjustment: StopAdjustment) -> OrderBuilder<'a, C> {
+        self.parent_builder.adjusted_stop = Some(AdjustedStop {
+            trigger_price: self.trigger_price,
+            adjustment,
+        });
+        self.parent_builder
+    }
+}
+
+/// Builder for volatility (VOL) orders and their delta-neutral hedge
+///
+/// When a delta-neutral order type is set, TWS submits a hedge in the underlying
+/// on each fill and replies with a [PlaceOrder::DeltaNeutralValidation](crate::orders::PlaceOrder::DeltaNeutralValidation)
+/// carrying the contract, delta and price it used.
+pub struct VolatilityOrderBuilder<'a, C> {
+    pub(crate) parent_builder: OrderBuilder<'a, C>,
+    continuous_update: bool,
+    reference_price_type: Option<ReferencePriceType>,
+    delta_neutral_order_type: Option<OrderType>,
+    delta_neutral_aux_price: Option<f64>,
+    delta_neutral_contract_id: Option<i32>,
+    settling_firm: Option<String>,
+    clearing_account: Option<String>,
+    clearing_intent: Option<String>,
+    open_close: Option<OrderOpenClose>,
+    short_sale_slot: ShortSaleSlot,
+    designated_location: Option<String>,
+}
+
+impl<'a, C> VolatilityOrderBuilder<'a, C> {
+    fn new(parent_builder: OrderBuilder<'a, C>) -> Self {
+        Self {
+            parent_builder,
+            continuous_update: false,
+            reference_price_type: None,
+            delta_neutral_order_type: None,
+            delta_neutral_aux_price: None,
+            delta_neutral_contract_id: None,
+            settling_firm: None,
+            clearing_account: None,
+            clearing_intent: None,
+            open_close: None,
+            short_sale_slot: ShortSaleSlot::None,
+            designated_location: None,
+        }
+    }
+
+    /// Update the limit price as the underlying moves
+    ///
+    /// Requires reference_price() to tell TWS which underlying price to follow.
+    pub fn continuous_update(mut self) -> Self {
+        self.continuous_update = true;
+        self
+    }
+
+    /// Set the underlying price used to compute the limit price
+    pub fn reference_price(mut self, reference_price_type: ReferencePriceType) -> Self {
+        self.reference_price_type = Some(reference_price_type);
+        self
+    }
+
+    /// Hedge fills with an order of `order_type` in the underlying
+    ///
+    /// Limit and relative hedges take their limit price or offset from `aux_price`.
+    pub fn delta_neutral(mut self, order_type: OrderType, aux_price: Option<f64>) -> Self {
+        self.delta_neutral_order_type = Some(order_type);
+        self.delta_neutral_aux_price = aux_price;
+        self
+    }
+
+    /// Hedge with a specific contract instead of the option's underlying
+    pub fn delta_neutral_contract(mut self, contract_id: i32) -> Self {
+        self.delta_neutral_contract_id = Some(contract_id);
+        self
+    }
+
+    /// Set where the hedge is settled and cleared (institutions only)
+    ///
+    /// `clearing_intent` is one of `IB`, `Away` or `PTA`.
+    pub fn delta_neutral_clearing(
+        mut self,
+        settling_firm: impl Into<String>,
+        clearing_account: impl Into<String>,
+        clearing_intent: impl Into<String>,
+    ) -> Self {
+        self.settling_firm = Some(settling_firm.into());
+        self.clearing_account = Some(clearing_account.into());
+        self.clearing_intent = Some(clearing_intent.into());
+        self
+    }
+
+    /// Mark the hedge as opening or closing a position (CFD hedges clearing away)
+    pub fn delta_neutral_open_close(mut self, open_close: OrderOpenClose) -> Self {
+        self.open_close = Some(open_close);
+        self
+    }
+
+    /// Sell the hedge short from the given slot
+    ///
+    /// `designated_location` is required for [ShortSaleSlot::ThirdParty] and ignored otherwise.
+    pub fn delta_neutral_short_sale(mut self, slot: ShortSaleSlot, designated_location: Option<&str>) -> Self {
+        self.short_sale_slot = slot;
+        self.designated_location = designated_location.map(str::to_string);
+        self
+    }
+
+    /// Build the volatility order with full validation
+    pub fn build(self) -> Result<Order, ValidationError> {
+        let volatility = self
+            .parent_builder
+            .volatility
+            .ok_or(ValidationError::MissingRequiredField("volatility"))?;
+        if !volatility.is_finite() || volatility <= 0.0 {
+            return Err(ValidationError::InvalidCombination(format!(
+                "Volatility must be a positive percentage, got {}",
+                volatility
+            )));
+        }
+
+        if self.continuous_update && self.reference_price_type.is_none() {
+            return Err(ValidationError::MissingRequiredField("reference_price_type"));
+        }
+
+        validation::validate_delta_neutral(
+            self.delta_neutral_order_type.as_ref(),
+            self.delta_neutral_aux_price,
+            self.short_sale_slot,
+            self.designated_location.as_deref(),
+        )?;
+
+        let has_settlement = self.delta_neutral_contract_id.is_some()
+            || self.settling_firm.is_some()
+            || self.open_close.is_some()
+            || self.short_sale_slot != ShortSaleSlot::None;
+        if has_settlement && self.delta_neutral_order_type.is_none() {
+            return Err(ValidationError::InvalidCombination(
+                "Delta-neutral contract and settlement fields require a delta-neutral order type".to_string(),
+            ));
+        }
+
+        if let Some(intent) = &self.clearing_intent {
+            if !["IB", "Away", "PTA"].contains(&intent.as_str()) {
+                return Err(ValidationError::InvalidCombination(format!(
+                    "Clearing intent must be IB, Away or PTA, got {}",
+                    intent
+                )));
+            }
+        }
+
+        let mut order = self.parent_builder.build()?;
+
+        order.continuous_update = self.continuous_update;
+        order.reference_price_type = self.reference_price_type;
+
+        if let Some(order_type) = self.delta_neutral_order_type {
+            order.delta_neutral_order_type = order_type.as_str().to_string();
+            order.delta_neutral_aux_price = self.delta_neutral_aux_price;
+            order.delta_neutral_con_id = self.delta_neutral_contract_id.unwrap_or_default();
+            order.delta_neutral_settling_firm = self.settling_firm.unwrap_or_default();
+            order.delta_neutral_clearing_account = self.clearing_account.unwrap_or_default();
+            order.delta_neutral_clearing_intent = self.clearing_intent.unwrap_or_default();
+            order.delta_neutral_open_close = self.open_close.map(|open_close| open_close.to_string()).unwrap_or_default();
+            order.delta_neutral_short_sale = self.short_sale_slot != ShortSaleSlot::None;
+            order.delta_neutral_short_sale_slot = self.short_sale_slot.into();
+            order.delta_neutral_designated_location = self.designated_location.unwrap_or_default();
+        }
+
+        Ok(order)
+    }
+}
+
 /// Entry order type for bracket orders
 #[derive(Default)]
 enum BracketEntryType {
@@ -1042,3 +1420,150 @@ impl<'a, C> BracketOrderBuilder<'a, C> {
         Ok(vec![parent, take_profit_order, stop_loss_order])
     }
 }
+
+/// Builder for scale orders
+///
+/// Scale orders split a limit order into components placed at successively better
+/// prices. Extended attributes (price adjustment, profit taking, restoring state) are
+/// validated together in build().
+pub struct ScaleOrderBuilder<'a, C> {
+    pub(crate) parent_builder: OrderBuilder<'a, C>,
+    initial_size: i32,
+    price_increment: f64,
+    subsequent_size: Option<i32>,
+    price_adjust_value: Option<f64>,
+    price_adjust_interval: Option<i32>,
+    profit_offset: Option<f64>,
+    auto_reset: bool,
+    initial_position: Option<i32>,
+    initial_fill_qty: Option<i32>,
+    random_percent: bool,
+    table: Option<String>,
+}
+
+impl<'a, C> ScaleOrderBuilder<'a, C> {
+    fn new(parent_builder: OrderBuilder<'a, C>, initial_size: i32, price_increment: f64) -> Self {
+        Self {
+            parent_builder,
+            initial_size,
+            price_increment,
+            subsequent_size: None,
+            price_adjust_value: None,
+            price_adjust_interval: None,
+            profit_offset: None,
+            auto_reset: false,
+            initial_position: None,
+            initial_fill_qty: None,
+            random_percent: false,
+            table: None,
+        }
+    }
+
+    /// Set the size of the components following the first one
+    pub fn subsequent_size(mut self, size: i32) -> Self {
+        self.subsequent_size = Some(size);
+        self
+    }
+
+    /// Adjust the starting price by `value` every `interval_seconds`
+    pub fn price_adjust(mut self, value: f64, interval_seconds: i32) -> Self {
+        self.price_adjust_value = Some(value);
+        self.price_adjust_interval = Some(interval_seconds);
+        self
+    }
+
+    /// Place a profit taking order `offset` away from each filled component
+    ///
+    /// Requires auto_reset() so filled components are replenished.
+    pub fn profit_offset(mut self, offset: f64) -> Self {
+        self.profit_offset = Some(offset);
+        self
+    }
+
+    /// Restore the scale series once profit taking orders fill
+    pub fn auto_reset(mut self) -> Self {
+        self.auto_reset = true;
+        self
+    }
+
+    /// Resume a scale series from a known position and filled quantity
+    pub fn initial_position(mut self, position: i32, filled_quantity: i32) -> Self {
+        self.initial_position = Some(position);
+        self.initial_fill_qty = Some(filled_quantity);
+        self
+    }
+
+    /// Randomize component sizes by up to 55% to hide the order's pattern
+    pub fn random_percent(mut self) -> Self {
+        self.random_percent = true;
+        self
+    }
+
+    /// Set the scale table, as exported from TWS
+    pub fn table(mut self, table: impl Into<String>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    /// Build the scale order with full validation
+    pub fn build(self) -> Result<Order, ValidationError> {
+        if self.parent_builder.order_type != Some(OrderType::Limit) {
+            return Err(ValidationError::InvalidCombination("Scale orders must be limit orders".to_string()));
+        }
+
+        if self.parent_builder.algo_strategy.is_some() {
+            return Err(ValidationError::InvalidCombination(
+                "Scale orders cannot be combined with algo strategies".to_string(),
+            ));
+        }
+
+        let quantity = self.parent_builder.quantity.ok_or(ValidationError::MissingRequiredField("quantity"))?;
+        validation::validate_scale_components(quantity, self.initial_size, self.subsequent_size, self.price_increment)?;
+
+        match (self.price_adjust_value, self.price_adjust_interval) {
+            (Some(value), Some(interval)) if !value.is_finite() || value == 0.0 || interval <= 0 => {
+                return Err(ValidationError::InvalidCombination(format!(
+                    "Price adjustment needs a non-zero value and a positive interval, got {} every {}s",
+                    value, interval
+                )));
+            }
+            _ => {}
+        }
+
+        if let Some(offset) = self.profit_offset {
+            if !offset.is_finite() || offset <= 0.0 {
+                return Err(ValidationError::InvalidPrice(offset));
+            }
+            if !self.auto_reset {
+                return Err(ValidationError::InvalidCombination("Scale profit offset requires auto reset".to_string()));
+            }
+        }
+
+        if let (Some(position), Some(filled)) = (self.initial_position, self.initial_fill_qty) {
+            if filled < 0 || filled > position.abs() {
+                return Err(ValidationError::InvalidCombination(format!(
+                    "Initial fill quantity {} must be between 0 and the initial position {}",
+                    filled, position
+                )));
+            }
+        }
+
+        let mut order = self.parent_builder.build()?;
+
+        order.scale_init_level_size = Some(self.initial_size);
+        order.scale_subs_level_size = self.subsequent_size;
+        order.scale_price_increment = Some(self.price_increment);
+        order.scale_price_adjust_value = self.price_adjust_value;
+        order.scale_price_adjust_interval = self.price_adjust_interval;
+        order.scale_profit_offset = self.profit_offset;
+        order.scale_auto_reset = self.auto_reset;
+        order.scale_init_position = self.initial_position;
+        order.scale_init_fill_qty = self.initial_fill_qty;
+        order.scale_random_percent = self.random_percent;
+        if let Some(table) = self.table {
+            order.scale_table = table;
+        }
+
+        Ok(order)
+    }
+}
@@ -2,6 +2,7 @@ use super::*;
 use crate::contracts::{Contract, Currency, Exchange, Symbol};
 use crate::market_data::TradingHours;
 use crate::orders::Action;
+use crate::Decimal;
 
 fn create_test_contract() -> Contract {
     Contract {
@@ -26,7 +27,7 @@ fn test_stop_order() {
     assert_eq!(order.order_type, "STP");
     assert_eq!(order.aux_price, Some(95.50));
     assert_eq!(order.action, Action::Buy);
-    assert_eq!(order.total_quantity, 100.0);
+    assert_eq!(order.total_quantity, Decimal::from(100));
 }
 
 #[test]
@@ -630,9 +631,9 @@ fn test_bracket_order_large_quantity() {
         .stop_loss(45.0);
 
     let orders = bracket.build().unwrap();
-    assert_eq!(orders[0].total_quantity, 10000.0);
-    assert_eq!(orders[1].total_quantity, 10000.0);
-    assert_eq!(orders[2].total_quantity, 10000.0);
+    assert_eq!(orders[0].total_quantity, Decimal::from(10000));
+    assert_eq!(orders[1].total_quantity, Decimal::from(10000));
+    assert_eq!(orders[2].total_quantity, Decimal::from(10000));
 }
 
 #[test]
@@ -904,9 +905,9 @@ fn test_bracket_order_market_entry_quantity_propagation() {
     let orders = bracket.build().unwrap();
 
     // All orders should have the same quantity
-    assert_eq!(orders[0].total_quantity, 500.0);
-    assert_eq!(orders[1].total_quantity, 500.0);
-    assert_eq!(orders[2].total_quantity, 500.0);
+    assert_eq!(orders[0].total_quantity, Decimal::from(500));
+    assert_eq!(orders[1].total_quantity, Decimal::from(500));
+    assert_eq!(orders[2].total_quantity, Decimal::from(500));
 }
 
 #[test]
@@ -938,7 +939,7 @@ fn test_market_on_close() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "MOC");
     assert_eq!(order.action, Action::Buy);
-    assert_eq!(order.total_quantity, 100.0);
+    assert_eq!(order.total_quantity, Decimal::from(100));
     assert_eq!(order.limit_price, None);
 }
 
@@ -952,7 +953,7 @@ fn test_limit_on_close() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "LOC");
     assert_eq!(order.action, Action::Buy);
-    assert_eq!(order.total_quantity, 100.0);
+    assert_eq!(order.total_quantity, Decimal::from(100));
     assert_eq!(order.limit_price, Some(50.50));
 }
 
@@ -966,7 +967,7 @@ fn test_market_on_open() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "MKT");
     assert_eq!(order.action, Action::Buy);
-    assert_eq!(order.total_quantity, 100.0);
+    assert_eq!(order.total_quantity, Decimal::from(100));
     assert_eq!(order.tif, crate::orders::TimeInForce::OnOpen);
     assert_eq!(order.limit_price, None);
 }
@@ -981,7 +982,7 @@ fn test_limit_on_open() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "LMT");
     assert_eq!(order.action, Action::Buy);
-    assert_eq!(order.total_quantity, 100.0);
+    assert_eq!(order.total_quantity, Decimal::from(100));
     assert_eq!(order.limit_price, Some(50.50));
     assert_eq!(order.tif, crate::orders::TimeInForce::OnOpen);
 }
@@ -996,7 +997,7 @@ fn test_market_with_protection() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "MKT PRT");
     assert_eq!(order.action, Action::Buy);
-    assert_eq!(order.total_quantity, 100.0);
+    assert_eq!(order.total_quantity, Decimal::from(100));
     assert_eq!(order.limit_price, None);
     assert_eq!(order.aux_price, None);
 }
@@ -1011,7 +1012,7 @@ fn test_stop_with_protection() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "STP PRT");
     assert_eq!(order.action, Action::Sell);
-    assert_eq!(order.total_quantity, 100.0);
+    assert_eq!(order.total_quantity, Decimal::from(100));
     assert_eq!(order.aux_price, Some(95.00));
 }
 
@@ -1025,7 +1026,7 @@ fn test_market_on_close_sell() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "MOC");
     assert_eq!(order.action, Action::Sell);
-    assert_eq!(order.total_quantity, 200.0);
+    assert_eq!(order.total_quantity, Decimal::from(200));
 }
 
 #[test]
@@ -1038,7 +1039,7 @@ fn test_limit_on_close_sell() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "LOC");
     assert_eq!(order.action, Action::Sell);
-    assert_eq!(order.total_quantity, 200.0);
+    assert_eq!(order.total_quantity, Decimal::from(200));
     assert_eq!(order.limit_price, Some(100.00));
 }
 
@@ -1052,7 +1053,7 @@ fn test_stop_with_protection_buy() {
     let order = builder.build().unwrap();
     assert_eq!(order.order_type, "STP PRT");
     assert_eq!(order.action, Action::Buy);
-    assert_eq!(order.total_quantity, 50.0);
+    assert_eq!(order.total_quantity, Decimal::from(50));
     assert_eq!(order.aux_price, Some(105.00));
 }
 
@@ -1291,3 +1292,534 @@ fn test_execution_condition_no_threshold() {
         _ => panic!("Expected Execution condition"),
     }
 }
+
+fn create_forex_contract() -> Contract {
+    Contract {
+        symbol: Symbol::from("EUR"),
+        security_type: crate::contracts::SecurityType::ForexPair,
+        exchange: Exchange::from("IDEALPRO"),
+        currency: Currency::from("USD"),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_allocate_to_group() {
+    let client = MockClient;
+    let contract = create_test_contract();
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .market()
+        .allocate_to_group("Growth", FaMethod::NetLiq)
+        .build()
+        .unwrap();
+    assert_eq!(order.fa_group, "Growth");
+    assert_eq!(order.fa_method, "NetLiq");
+    assert_eq!(order.fa_percentage, "");
+
+    let order = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .market()
+        .allocate_to_group("Growth", FaMethod::PctChange(-50.0))
+        .build()
+        .unwrap();
+    assert_eq!(order.fa_method, "PctChange");
+    assert_eq!(order.fa_percentage, "-50");
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .market()
+        .account("DU1234567")
+        .allocate_to_group("Growth", FaMethod::EqualQuantity)
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))));
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .market()
+        .allocate_to_group("Growth", FaMethod::PctChange(-150.0))
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))));
+}
+
+#[test]
+fn test_cash_quantity() {
+    let client = MockClient;
+    let contract = create_forex_contract();
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(0)
+        .market()
+        .cash_quantity(25_000.0)
+        .build()
+        .unwrap();
+    assert_eq!(order.cash_qty, Some(25_000.0));
+    assert_eq!(order.total_quantity, Decimal::from(0));
+
+    let result = OrderBuilder::new(&client, &contract).buy(100).market().cash_quantity(25_000.0).build();
+    assert!(
+        matches!(result, Err(ValidationError::InvalidCombination(_))),
+        "quantity and cash quantity"
+    );
+
+    let result = OrderBuilder::new(&client, &contract).buy(0).market().cash_quantity(-5.0).build();
+    assert!(matches!(result, Err(ValidationError::InvalidQuantity(_))));
+
+    let stock = create_test_contract();
+    let result = OrderBuilder::new(&client, &stock).buy(0).market().cash_quantity(1_000.0).build();
+    assert!(
+        matches!(result, Err(ValidationError::InvalidCombination(_))),
+        "stocks are sized in shares"
+    );
+
+    let crypto = Contract {
+        security_type: crate::contracts::SecurityType::Crypto,
+        ..create_forex_contract()
+    };
+    assert!(OrderBuilder::new(&client, &crypto)
+        .buy(0)
+        .limit(60_000.0)
+        .cash_quantity(500.0)
+        .build()
+        .is_ok());
+    let result = OrderBuilder::new(&client, &crypto).sell(0).limit(60_000.0).cash_quantity(500.0).build();
+    assert!(
+        matches!(result, Err(ValidationError::InvalidCombination(_))),
+        "crypto sells use a quantity"
+    );
+}
+
+#[test]
+fn test_attach_hedge() {
+    let client = MockClient;
+    let contract = create_test_contract();
+
+    let order = OrderBuilder::new(&client, &contract)
+        .sell(50)
+        .market()
+        .parent(1001)
+        .attach_hedge(HedgeType::Beta, Some(1.25))
+        .build()
+        .unwrap();
+    assert_eq!(order.hedge_type, "B");
+    assert_eq!(order.hedge_param, "1.25");
+    assert_eq!(order.parent_id, 1001);
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .market()
+        .parent(1001)
+        .attach_hedge(HedgeType::Delta, None)
+        .build()
+        .unwrap();
+    assert_eq!(order.hedge_type, "D");
+    assert_eq!(order.hedge_param, "");
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .market()
+        .attach_hedge(HedgeType::Delta, None)
+        .build();
+    assert!(matches!(result, Err(ValidationError::MissingRequiredField("parent_id"))));
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .market()
+        .parent(1001)
+        .attach_hedge(HedgeType::Pair, None)
+        .build();
+    assert!(matches!(result, Err(ValidationError::MissingRequiredField("hedge_param"))));
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .market()
+        .parent(1001)
+        .attach_hedge(HedgeType::Delta, Some(0.5))
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))));
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .market()
+        .parent(1001)
+        .attach_hedge(HedgeType::Fx, None)
+        .build();
+    assert!(
+        matches!(result, Err(ValidationError::InvalidCombination(_))),
+        "FX hedges trade a currency pair"
+    );
+
+    let forex = create_forex_contract();
+    let order = OrderBuilder::new(&client, &forex)
+        .buy(0)
+        .market()
+        .cash_quantity(10_000.0)
+        .parent(1001)
+        .attach_hedge(HedgeType::Fx, None)
+        .build()
+        .unwrap();
+    assert_eq!(order.hedge_type, "F");
+}
+
+#[test]
+fn test_scale_order() {
+    let client = MockClient;
+    let contract = create_test_contract();
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(1000)
+        .limit(50.0)
+        .scale(200, 0.10)
+        .subsequent_size(100)
+        .build()
+        .unwrap();
+    assert!(order.is_scale_order());
+    assert_eq!(order.order_type, "LMT");
+    assert_eq!(order.scale_init_level_size, Some(200));
+    assert_eq!(order.scale_subs_level_size, Some(100));
+    assert_eq!(order.scale_price_increment, Some(0.10));
+    assert_eq!(order.scale_profit_offset, None);
+    assert!(!order.scale_auto_reset);
+
+    let order = OrderBuilder::new(&client, &contract)
+        .sell(1000)
+        .limit(50.0)
+        .scale(100, 0.05)
+        .price_adjust(0.02, 60)
+        .profit_offset(0.25)
+        .auto_reset()
+        .initial_position(-300, 300)
+        .random_percent()
+        .build()
+        .unwrap();
+    assert_eq!(order.scale_price_adjust_value, Some(0.02));
+    assert_eq!(order.scale_price_adjust_interval, Some(60));
+    assert_eq!(order.scale_profit_offset, Some(0.25));
+    assert!(order.scale_auto_reset);
+    assert_eq!(order.scale_init_position, Some(-300));
+    assert_eq!(order.scale_init_fill_qty, Some(300));
+    assert!(order.scale_random_percent);
+}
+
+#[test]
+fn test_scale_order_validation() {
+    let client = MockClient;
+    let contract = create_test_contract();
+    let scale = || OrderBuilder::new(&client, &contract).buy(1000).limit(50.0);
+
+    let result = OrderBuilder::new(&client, &contract).buy(1000).market().scale(100, 0.1).build();
+    assert!(
+        matches!(result, Err(ValidationError::InvalidCombination(_))),
+        "market orders cannot scale"
+    );
+
+    let result = scale().algo("Vwap").scale(100, 0.1).build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))), "algos cannot scale");
+
+    assert!(matches!(scale().scale(0, 0.1).build(), Err(ValidationError::InvalidQuantity(_))));
+    assert!(matches!(scale().scale(100, 0.0).build(), Err(ValidationError::InvalidPrice(_))));
+    assert!(matches!(scale().scale(2000, 0.1).build(), Err(ValidationError::InvalidCombination(_))));
+    assert!(matches!(
+        scale().scale(100, 0.1).subsequent_size(5000).build(),
+        Err(ValidationError::InvalidCombination(_))
+    ));
+    assert!(matches!(
+        scale().scale(100, 0.1).price_adjust(0.02, 0).build(),
+        Err(ValidationError::InvalidCombination(_))
+    ));
+    assert!(matches!(
+        scale().scale(100, 0.1).profit_offset(0.25).build(),
+        Err(ValidationError::InvalidCombination(_))
+    ));
+    assert!(matches!(
+        scale().scale(100, 0.1).initial_position(100, 200).build(),
+        Err(ValidationError::InvalidCombination(_))
+    ));
+}
+
+#[test]
+fn test_pegged_to_benchmark() {
+    let client = MockClient;
+    let contract = create_test_contract();
+    let reference = Contract {
+        contract_id: 265598,
+        exchange: Exchange::from("ISLAND"),
+        ..Contract::stock("AAPL").build()
+    };
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .pegged_to_benchmark(&reference, 55.0, 185.0)
+        .pegged_change(0.5, 1.0)
+        .pegged_change_decrease()
+        .reference_range(180.0, 190.0)
+        .build()
+        .unwrap();
+    assert_eq!(order.order_type, "PEG BENCH");
+    assert_eq!(order.reference_contract_id, 265598);
+    assert_eq!(order.reference_exchange, "ISLAND");
+    assert_eq!(order.starting_price, Some(55.0));
+    assert_eq!(order.stock_ref_price, Some(185.0));
+    assert_eq!(order.pegged_change_amount, Some(0.5));
+    assert_eq!(order.reference_change_amount, Some(1.0));
+    assert!(order.is_pegged_change_amount_decrease);
+    assert_eq!(order.stock_range_lower, Some(180.0));
+    assert_eq!(order.stock_range_upper, Some(190.0));
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .pegged_to_benchmark(&reference, 55.0, 185.0)
+        .build();
+    assert!(matches!(result, Err(ValidationError::MissingRequiredField("pegged_change_amount"))));
+
+    let unqualified = Contract::stock("AAPL").build();
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .pegged_to_benchmark(&unqualified, 55.0, 185.0)
+        .pegged_change(0.5, 1.0)
+        .build();
+    assert!(matches!(result, Err(ValidationError::MissingRequiredField("reference_contract_id"))));
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .pegged_to_benchmark(&reference, 55.0, 185.0)
+        .pegged_change(0.5, 1.0)
+        .reference_range(190.0, 180.0)
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))));
+}
+
+#[test]
+fn test_pegged_to_stock_and_midpoint() {
+    let client = MockClient;
+    let contract = create_test_contract();
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(10)
+        .pegged_to_stock(0.5, 185.0, 4.20)
+        .reference_range(175.0, 195.0)
+        .build()
+        .unwrap();
+    assert_eq!(order.order_type, "PEG STK");
+    assert_eq!(order.delta, Some(0.5));
+    assert_eq!(order.stock_ref_price, Some(185.0));
+    assert_eq!(order.starting_price, Some(4.20));
+    assert_eq!(order.stock_range_lower, Some(175.0));
+
+    let result = OrderBuilder::new(&client, &contract).buy(10).pegged_to_stock(1.5, 185.0, 4.20).build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))));
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .pegged_to_midpoint(0.01, 50.0)
+        .build()
+        .unwrap();
+    assert_eq!(order.order_type, "PEG MID");
+    assert_eq!(order.aux_price, Some(0.01));
+    assert_eq!(order.limit_price, Some(50.0));
+}
+
+#[test]
+fn test_adjusted_stop() {
+    let client = MockClient;
+    let contract = create_test_contract();
+
+    // Long entry at 100: once 105 trades, raise the protective stop from 95 to breakeven.
+    let order = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .stop(95.0)
+        .parent(1001)
+        .adjust_stop_when(105.0)
+        .to_stop(100.0)
+        .build()
+        .unwrap();
+    assert_eq!(order.order_type, "STP");
+    assert_eq!(order.aux_price, Some(95.0));
+    assert_eq!(order.trigger_price, Some(105.0));
+    assert_eq!(order.adjusted_order_type, "STP");
+    assert_eq!(order.adjusted_stop_price, Some(100.0));
+
+    let order = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .stop(95.0)
+        .parent(1001)
+        .adjust_stop_when(105.0)
+        .to_trailing(100.0, TrailingAmount::Percent(2.0))
+        .build()
+        .unwrap();
+    assert_eq!(order.adjusted_order_type, "TRAIL");
+    assert_eq!(order.adjusted_trailing_amount, Some(2.0));
+    assert_eq!(order.adjustable_trailing_unit, 100);
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(100)
+        .stop(105.0)
+        .parent(1001)
+        .adjust_stop_when(95.0)
+        .to_stop_limit(100.0, 100.5)
+        .build()
+        .unwrap();
+    assert_eq!(order.adjusted_order_type, "STP LMT");
+    assert_eq!(order.adjusted_stop_limit_price, Some(100.5));
+}
+
+#[test]
+fn test_adjusted_stop_validation() {
+    let client = MockClient;
+    let contract = create_test_contract();
+
+    let result = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .limit(95.0)
+        .parent(1001)
+        .adjust_stop_when(105.0)
+        .to_stop(100.0)
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))), "only stops adjust");
+
+    let result = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .stop(95.0)
+        .parent(1001)
+        .adjust_stop_when(105.0)
+        .to_stop(90.0)
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))), "stop must ratchet up");
+
+    let result = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .stop(95.0)
+        .parent(1001)
+        .adjust_stop_when(105.0)
+        .to_stop(106.0)
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))), "stop beyond trigger");
+
+    let result = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .stop(95.0)
+        .parent(1001)
+        .adjust_stop_when(105.0)
+        .to_stop_limit(100.0, 101.0)
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidLimitPrice { .. })));
+
+    let result = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .stop(95.0)
+        .parent(1001)
+        .adjust_stop_when(105.0)
+        .to_trailing(100.0, TrailingAmount::Amount(0.0))
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))));
+
+    let result = OrderBuilder::new(&client, &contract)
+        .sell(100)
+        .stop(95.0)
+        .adjust_stop_when(105.0)
+        .to_stop(100.0)
+        .build();
+    assert!(
+        matches!(result, Err(ValidationError::MissingRequiredField("parent_id"))),
+        "only attached stops adjust"
+    );
+}
+
+#[test]
+fn test_volatility_order() {
+    use crate::orders::{OrderOpenClose, ReferencePriceType, ShortSaleSlot, VolatilityType};
+
+    let client = MockClient;
+    let contract = create_test_contract();
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(5)
+        .volatility_order(25.0, VolatilityType::Annual)
+        .reference_price(ReferencePriceType::AverageOfNBBO)
+        .continuous_update()
+        .build()
+        .unwrap();
+    assert_eq!(order.order_type, "VOL");
+    assert_eq!(order.volatility, Some(25.0));
+    assert_eq!(order.volatility_type, Some(VolatilityType::Annual));
+    assert_eq!(order.reference_price_type, Some(ReferencePriceType::AverageOfNBBO));
+    assert!(order.continuous_update);
+    assert!(!order.is_delta_neutral());
+
+    let order = OrderBuilder::new(&client, &contract)
+        .buy(5)
+        .volatility_order(1.2, VolatilityType::Daily)
+        .delta_neutral(OrderType::Limit, Some(185.0))
+        .delta_neutral_contract(8314)
+        .delta_neutral_clearing("FIRM", "ACCT1", "Away")
+        .delta_neutral_open_close(OrderOpenClose::Open)
+        .delta_neutral_short_sale(ShortSaleSlot::ThirdParty, Some("LOC1"))
+        .build()
+        .unwrap();
+    assert!(order.is_delta_neutral());
+    assert_eq!(order.delta_neutral_order_type, "LMT");
+    assert_eq!(order.delta_neutral_aux_price, Some(185.0));
+    assert_eq!(order.delta_neutral_con_id, 8314);
+    assert_eq!(order.delta_neutral_settling_firm, "FIRM");
+    assert_eq!(order.delta_neutral_clearing_account, "ACCT1");
+    assert_eq!(order.delta_neutral_clearing_intent, "Away");
+    assert_eq!(order.delta_neutral_open_close, "O");
+    assert!(order.delta_neutral_short_sale);
+    assert_eq!(order.delta_neutral_short_sale_slot, 2);
+    assert_eq!(order.delta_neutral_designated_location, "LOC1");
+}
+
+#[test]
+fn test_volatility_order_validation() {
+    use crate::orders::{ShortSaleSlot, VolatilityType};
+
+    let client = MockClient;
+    let contract = create_test_contract();
+    let vol = || {
+        OrderBuilder::new(&client, &contract)
+            .buy(5)
+            .volatility_order(25.0, VolatilityType::Annual)
+    };
+
+    let result = OrderBuilder::new(&client, &contract)
+        .buy(5)
+        .volatility_order(-1.0, VolatilityType::Annual)
+        .build();
+    assert!(matches!(result, Err(ValidationError::InvalidCombination(_))));
+
+    assert!(matches!(
+        vol().continuous_update().build(),
+        Err(ValidationError::MissingRequiredField("reference_price_type"))
+    ));
+    assert!(matches!(
+        vol().delta_neutral(OrderType::Relative, None).build(),
+        Err(ValidationError::MissingRequiredField("delta_neutral_aux_price"))
+    ));
+    assert!(matches!(
+        vol().delta_neutral(OrderType::Market, Some(1.0)).build(),
+        Err(ValidationError::InvalidCombination(_))
+    ));
+    assert!(matches!(
+        vol().delta_neutral(OrderType::Volatility, None).build(),
+        Err(ValidationError::InvalidCombination(_))
+    ));
+    assert!(matches!(
+        vol().delta_neutral_contract(8314).build(),
+        Err(ValidationError::InvalidCombination(_))
+    ));
+    assert!(matches!(
+        vol()
+            .delta_neutral(OrderType::Market, None)
+            .delta_neutral_clearing("FIRM", "ACCT1", "Elsewhere")
+            .build(),
+        Err(ValidationError::InvalidCombination(_))
+    ));
+    assert!(matches!(
+        vol()
+            .delta_neutral(OrderType::Market, None)
+            .delta_neutral_short_sale(ShortSaleSlot::ThirdParty, None)
+            .build(),
+        Err(ValidationError::MissingRequiredField("delta_neutral_designated_location"))
+    ));
+}
@@ -1,4 +1,4 @@
-use super::{BracketOrderBuilder, BracketOrderIds, OrderBuilder, OrderId};
+use super::{BracketOrderBuilder, BracketOrderIds, OrderBuilder, OrderId, ScaleOrderBuilder, VolatilityOrderBuilder};
 use crate::client::sync::Client;
 use crate::contracts::Contract;
 use crate::errors::Error;
@@ -49,6 +49,42 @@ impl<'a> OrderBuilder<'a, Client> {
     }
 }
 
+impl<'a> ScaleOrderBuilder<'a, Client> {
+    /// Submit the scale order synchronously
+    /// Returns the order ID assigned to the submitted order
+    pub fn submit(self) -> Result<OrderId, Error> {
+        let client = self.parent_builder.client;
+        let contract = self.parent_builder.contract;
+        let order_id = client.next_order_id();
+        let order = self.build()?;
+        orders::blocking::submit_order(client, order_id, contract, &order)?;
+        Ok(OrderId::new(order_id))
+    }
+
+    /// Build the scale order and return it without submitting
+    pub fn build_order(self) -> Result<crate::orders::Order, Error> {
+        self.build().map_err(Into::into)
+    }
+}
+
+impl<'a> VolatilityOrderBuilder<'a, Client> {
+    /// Submit the volatility order synchronously
+    /// Returns the order ID assigned to the submitted order
+    pub fn submit(self) -> Result<OrderId, Error> {
+        let client = self.parent_builder.client;
+        let contract = self.parent_builder.contract;
+        let order_id = client.next_order_id();
+        let order = self.build()?;
+        orders::blocking::submit_order(client, order_id, contract, &order)?;
+        Ok(OrderId::new(order_id))
+    }
+
+    /// Build the volatility order and return it without submitting
+    pub fn build_order(self) -> Result<crate::orders::Order, Error> {
+        self.build().map_err(Into::into)
+    }
+}
+
 impl<'a> BracketOrderBuilder<'a, Client> {
     /// Submit bracket orders synchronously
     /// Returns BracketOrderIds containing all three order IDs
@@ -3,6 +3,7 @@ use crate::errors::Error;
 use crate::orders::builder::tests::mock_client::mock::MockOrderClient;
 use crate::orders::builder::{BracketOrderBuilder, BracketOrderIds, OrderBuilder, OrderId};
 use crate::orders::{Action, Order, OrderData, OrderState, PlaceOrder};
+use crate::Decimal;
 
 fn create_stock_contract(symbol: &str) -> Contract {
     Contract {
@@ -118,7 +119,7 @@ fn test_order_submit() {
     assert_eq!(submitted.len(), 1);
     assert_eq!(submitted[0].0, 100);
     assert_eq!(submitted[0].2.action, Action::Buy);
-    assert_eq!(submitted[0].2.total_quantity, 100.0);
+    assert_eq!(submitted[0].2.total_quantity, Decimal::from(100));
     assert_eq!(submitted[0].2.order_type, "LMT");
     assert_eq!(submitted[0].2.limit_price, Some(50.00));
 }
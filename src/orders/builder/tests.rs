@@ -7,6 +7,7 @@ mod sync_integration_tests {
     use crate::contracts::{Contract, Currency, Exchange, Symbol};
     use crate::orders::builder::OrderBuilder;
     use crate::orders::{Action, OcaType, TimeInForce};
+    use crate::Decimal;
 
     fn create_stock_contract(symbol: &str) -> Contract {
         Contract {
@@ -33,7 +34,7 @@ mod sync_integration_tests {
         // Verify orders have correct properties
         assert_eq!(orders[0].action, Action::Buy);
         assert_eq!(orders[0].order_type, "MKT");
-        assert_eq!(orders[0].total_quantity, 100.0);
+        assert_eq!(orders[0].total_quantity, Decimal::from(100));
 
         assert_eq!(orders[1].action, Action::Sell);
         assert_eq!(orders[1].order_type, "LMT");
@@ -84,6 +85,7 @@ mod async_integration_tests {
     use crate::contracts::{Contract, Currency, Exchange, Symbol};
     use crate::orders::builder::OrderBuilder;
     use crate::orders::Action;
+    use crate::Decimal;
 
     fn create_stock_contract(symbol: &str) -> Contract {
         Contract {
@@ -110,7 +112,7 @@ mod async_integration_tests {
         // Verify orders have correct properties
         assert_eq!(orders[0].action, Action::Buy);
         assert_eq!(orders[0].order_type, "MKT");
-        assert_eq!(orders[0].total_quantity, 100.0);
+        assert_eq!(orders[0].total_quantity, Decimal::from(100));
 
         assert_eq!(orders[1].action, Action::Sell);
         assert_eq!(orders[1].order_type, "LMT");
@@ -202,6 +202,125 @@ impl AuctionType {
     }
 }
 
+/// Financial Advisor allocation method for orders placed on behalf of a group
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FaMethod {
+    /// Split the order quantity equally across the group's accounts.
+    EqualQuantity,
+    /// Allocate in proportion to each account's net liquidation value.
+    NetLiq,
+    /// Allocate in proportion to each account's available equity.
+    AvailableEquity,
+    /// Change each account's existing position by the given percentage (e.g. `-50.0` halves it).
+    PctChange(f64),
+}
+
+impl FaMethod {
+    /// Return the TWS API string identifier for the allocation method.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::EqualQuantity => "EqualQuantity",
+            Self::NetLiq => "NetLiq",
+            Self::AvailableEquity => "AvailableEquity",
+            Self::PctChange(_) => "PctChange",
+        }
+    }
+}
+
+/// Hedge order types that can be attached to a parent order
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HedgeType {
+    /// Delta hedge of an option parent using the underlying.
+    Delta,
+    /// Beta hedge against an index; requires the beta as parameter.
+    Beta,
+    /// Currency hedge of a parent traded in a foreign currency.
+    Fx,
+    /// Pair trade; requires the hedge ratio as parameter.
+    Pair,
+}
+
+impl HedgeType {
+    /// Return the TWS API code for the hedge type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Delta => "D",
+            Self::Beta => "B",
+            Self::Fx => "F",
+            Self::Pair => "P",
+        }
+    }
+
+    /// Whether the hedge type requires a parameter (beta or ratio).
+    pub fn requires_param(&self) -> bool {
+        matches!(self, Self::Beta | Self::Pair)
+    }
+}
+
+/// Trailing distance of an adjusted trailing stop
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TrailingAmount {
+    /// Trail by a fixed price amount.
+    Amount(f64),
+    /// Trail by a percentage of the market price.
+    Percent(f64),
+}
+
+impl TrailingAmount {
+    /// Return the trailing value and the TWS trailing unit (0 for amounts, 100 for percentages).
+    pub fn value_and_unit(&self) -> (f64, i32) {
+        match self {
+            Self::Amount(amount) => (*amount, 0),
+            Self::Percent(percent) => (*percent, 100),
+        }
+    }
+}
+
+/// Order a stop turns into once its adjustment trigger is hit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum StopAdjustment {
+    Stop { stop_price: f64 },
+    StopLimit { stop_price: f64, limit_price: f64 },
+    Trailing { stop_price: f64, amount: TrailingAmount },
+}
+
+impl StopAdjustment {
+    pub(crate) fn order_type(&self) -> OrderType {
+        match self {
+            Self::Stop { .. } => OrderType::Stop,
+            Self::StopLimit { .. } => OrderType::StopLimit,
+            Self::Trailing { .. } => OrderType::TrailingStop,
+        }
+    }
+
+    pub(crate) fn stop_price(&self) -> f64 {
+        match self {
+            Self::Stop { stop_price } | Self::StopLimit { stop_price, .. } | Self::Trailing { stop_price, .. } => *stop_price,
+        }
+    }
+}
+
+/// Adjustment applied to a stop order when the market trades through the trigger
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AdjustedStop {
+    pub(crate) trigger_price: f64,
+    pub(crate) adjustment: StopAdjustment,
+}
+
+/// Pegged order attributes checked together at build time
+#[derive(Debug, Default)]
+pub(crate) struct PeggedInputs {
+    pub(crate) reference_contract_id: Option<i32>,
+    pub(crate) starting_price: Option<f64>,
+    pub(crate) pegged_change_amount: Option<f64>,
+    pub(crate) reference_change_amount: Option<f64>,
+    pub(crate) delta: Option<f64>,
+    pub(crate) range: (Option<f64>, Option<f64>),
+}
+
 /// Order types supported by Interactive Brokers
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1,5 +1,6 @@
-use super::types::ValidationError;
-use crate::orders::Action;
+use super::types::{AdjustedStop, FaMethod, HedgeType, OrderType, PeggedInputs, Price, StopAdjustment, TrailingAmount, ValidationError};
+use crate::contracts::{Contract, SecurityType};
+use crate::orders::{Action, ShortSaleSlot};
 
 /// Validates bracket order prices
 pub fn validate_bracket_prices(action: Option<&Action>, entry: f64, take_profit: f64, stop_loss: f64) -> Result<(), ValidationError> {
@@ -40,20 +41,300 @@ pub fn validate_bracket_prices(action: Option<&Action>, entry: f64, take_profit:
     Ok(())
 }
 
+/// Validates a cash quantity order against the contract and the share quantity
+pub fn validate_cash_quantity(contract: &Contract, action: &Action, quantity: f64, cash_qty: f64) -> Result<(), ValidationError> {
+    if !cash_qty.is_finite() || cash_qty <= 0.0 {
+        return Err(ValidationError::InvalidQuantity(cash_qty));
+    }
+
+    match contract.security_type {
+        SecurityType::ForexPair => {}
+        SecurityType::Crypto if *action == Action::Buy => {}
+        SecurityType::Crypto => {
+            return Err(ValidationError::InvalidCombination(
+                "Crypto sell orders must specify a quantity instead of a cash quantity".to_string(),
+            ));
+        }
+        _ => {
+            return Err(ValidationError::InvalidCombination(format!(
+                "Cash quantity is only supported for CASH and CRYPTO contracts, not {}",
+                contract.security_type
+            )));
+        }
+    }
+
+    if quantity != 0.0 {
+        return Err(ValidationError::InvalidCombination(
+            "Specify either a quantity or a cash quantity, not both".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates a hedge order attached to a parent
+pub fn validate_hedge(contract: &Contract, hedge_type: HedgeType, param: Option<f64>, parent_id: Option<i32>) -> Result<(), ValidationError> {
+    if parent_id.is_none() {
+        return Err(ValidationError::MissingRequiredField("parent_id"));
+    }
+
+    match param {
+        None if hedge_type.requires_param() => return Err(ValidationError::MissingRequiredField("hedge_param")),
+        Some(_) if !hedge_type.requires_param() => {
+            return Err(ValidationError::InvalidCombination(format!(
+                "{:?} hedges do not take a hedge parameter",
+                hedge_type
+            )));
+        }
+        Some(value) if !value.is_finite() || (hedge_type == HedgeType::Pair && value <= 0.0) => {
+            return Err(ValidationError::InvalidCombination(format!(
+                "Invalid {:?} hedge parameter: {}",
+                hedge_type, value
+            )));
+        }
+        _ => {}
+    }
+
+    if hedge_type == HedgeType::Fx && contract.security_type != SecurityType::ForexPair {
+        return Err(ValidationError::InvalidCombination(
+            "FX hedge orders must be placed on a CASH contract".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates a Financial Advisor group allocation
+pub fn validate_fa_allocation(group: &str, method: &FaMethod, account: Option<&str>) -> Result<(), ValidationError> {
+    if group.trim().is_empty() {
+        return Err(ValidationError::MissingRequiredField("fa_group"));
+    }
+
+    if account.is_some() {
+        return Err(ValidationError::InvalidCombination(
+            "An order is either placed for an account or allocated to an FA group, not both".to_string(),
+        ));
+    }
+
+    if let FaMethod::PctChange(percentage) = method {
+        if !percentage.is_finite() || *percentage == 0.0 || *percentage < -100.0 {
+            return Err(ValidationError::InvalidCombination(format!(
+                "PctChange percentage must be non-zero and at least -100, got {}",
+                percentage
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates the component sizes and price increment of a scale order
+pub fn validate_scale_components(
+    total_quantity: f64,
+    initial_size: i32,
+    subsequent_size: Option<i32>,
+    price_increment: f64,
+) -> Result<(), ValidationError> {
+    if initial_size <= 0 {
+        return Err(ValidationError::InvalidQuantity(initial_size as f64));
+    }
+
+    if let Some(size) = subsequent_size {
+        if size <= 0 {
+            return Err(ValidationError::InvalidQuantity(size as f64));
+        }
+        if size as f64 > total_quantity {
+            return Err(ValidationError::InvalidCombination(format!(
+                "Subsequent component size {} exceeds the order quantity {}",
+                size, total_quantity
+            )));
+        }
+    }
+
+    if initial_size as f64 > total_quantity {
+        return Err(ValidationError::InvalidCombination(format!(
+            "Initial component size {} exceeds the order quantity {}",
+            initial_size, total_quantity
+        )));
+    }
+
+    if !price_increment.is_finite() || price_increment <= 0.0 {
+        return Err(ValidationError::InvalidPrice(price_increment));
+    }
+
+    Ok(())
+}
+
+/// Validates the attributes of pegged-to-benchmark and pegged-to-stock orders
+pub(crate) fn validate_pegged(order_type: &OrderType, inputs: &PeggedInputs) -> Result<(), ValidationError> {
+    if let (Some(lower), Some(upper)) = inputs.range {
+        if lower.is_nan() || upper.is_nan() || lower >= upper {
+            return Err(ValidationError::InvalidCombination(format!(
+                "Reference range lower bound {} must be below upper bound {}",
+                lower, upper
+            )));
+        }
+    }
+
+    match order_type {
+        OrderType::PeggedToBenchmark => {
+            if inputs.reference_contract_id.unwrap_or_default() <= 0 {
+                return Err(ValidationError::MissingRequiredField("reference_contract_id"));
+            }
+            let starting_price = inputs.starting_price.ok_or(ValidationError::MissingRequiredField("starting_price"))?;
+            Price::new(starting_price)?;
+
+            let pegged_change = inputs
+                .pegged_change_amount
+                .ok_or(ValidationError::MissingRequiredField("pegged_change_amount"))?;
+            let reference_change = inputs
+                .reference_change_amount
+                .ok_or(ValidationError::MissingRequiredField("reference_change_amount"))?;
+            if !(pegged_change > 0.0 && reference_change > 0.0 && pegged_change.is_finite() && reference_change.is_finite()) {
+                return Err(ValidationError::InvalidCombination(format!(
+                    "Pegged change {} and reference change {} must be positive",
+                    pegged_change, reference_change
+                )));
+            }
+        }
+        OrderType::PeggedToStock => {
+            // Custom order_type(PeggedToStock) orders may leave these to TWS; check what was supplied.
+            if let Some(delta) = inputs.delta {
+                if !(delta > 0.0 && delta <= 1.0) {
+                    return Err(ValidationError::InvalidCombination(format!(
+                        "Pegged to stock delta must be in (0, 1], got {}",
+                        delta
+                    )));
+                }
+            }
+            if let Some(starting_price) = inputs.starting_price {
+                Price::new(starting_price)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Validates that an adjusted stop is attached to a parent and ratchets in the order's favour
+///
+/// TWS only adjusts stops attached to a parent order. A sell stop protects a long position,
+/// so the trigger lies above the stop and the adjusted stop moves up between the two. Buy
+/// stops mirror this below the market.
+pub(crate) fn validate_adjusted_stop(
+    action: &Action,
+    order_type: &OrderType,
+    stop_price: Option<f64>,
+    adjusted: &AdjustedStop,
+    parent_id: Option<i32>,
+) -> Result<(), ValidationError> {
+    if parent_id.is_none() {
+        return Err(ValidationError::MissingRequiredField("parent_id"));
+    }
+
+    if !matches!(
+        order_type,
+        OrderType::Stop | OrderType::StopLimit | OrderType::TrailingStop | OrderType::TrailingStopLimit
+    ) {
+        return Err(ValidationError::InvalidCombination(format!(
+            "Only stop orders can be adjusted, not {}",
+            order_type.as_str()
+        )));
+    }
+
+    let trigger = Price::new(adjusted.trigger_price)?.value();
+    let adjusted_stop = Price::new(adjusted.adjustment.stop_price())?.value();
+    let sell = matches!(action, Action::Sell | Action::SellShort);
+
+    if let Some(stop) = stop_price {
+        let ratchets = if sell {
+            stop < adjusted_stop && adjusted_stop < trigger
+        } else {
+            trigger < adjusted_stop && adjusted_stop < stop
+        };
+        if !ratchets {
+            return Err(ValidationError::InvalidCombination(format!(
+                "Adjusted stop {} must lie between the stop {} and the trigger {}",
+                adjusted_stop, stop, trigger
+            )));
+        }
+    }
+
+    match adjusted.adjustment {
+        StopAdjustment::StopLimit { limit_price, .. } => {
+            let limit = Price::new(limit_price)?.value();
+            if (sell && limit > adjusted_stop) || (!sell && limit < adjusted_stop) {
+                return Err(ValidationError::InvalidLimitPrice {
+                    limit,
+                    current: adjusted_stop,
+                });
+            }
+        }
+        StopAdjustment::Trailing { amount, .. } => {
+            let valid = match amount {
+                TrailingAmount::Amount(value) => value > 0.0 && value.is_finite(),
+                TrailingAmount::Percent(value) => value > 0.0 && value < 100.0,
+            };
+            if !valid {
+                return Err(ValidationError::InvalidCombination(format!("Invalid trailing amount {:?}", amount)));
+            }
+        }
+        StopAdjustment::Stop { .. } => {}
+    }
+
+    Ok(())
+}
+
+/// Validates the hedge order attached to a delta-neutral VOL order
+pub(crate) fn validate_delta_neutral(
+    order_type: Option<&OrderType>,
+    aux_price: Option<f64>,
+    short_sale_slot: ShortSaleSlot,
+    designated_location: Option<&str>,
+) -> Result<(), ValidationError> {
+    if let Some(order_type) = order_type {
+        match (order_type, aux_price) {
+            (OrderType::Market, None) => {}
+            (OrderType::Limit | OrderType::Relative, Some(price)) => {
+                Price::new(price)?;
+            }
+            (OrderType::Limit | OrderType::Relative, None) => {
+                return Err(ValidationError::MissingRequiredField("delta_neutral_aux_price"));
+            }
+            (OrderType::Market, Some(_)) => {
+                return Err(ValidationError::InvalidCombination(
+                    "Market delta-neutral orders do not take an aux price".to_string(),
+                ));
+            }
+            (other, _) => {
+                return Err(ValidationError::InvalidCombination(format!(
+                    "Unsupported delta-neutral order type {}",
+                    other.as_str()
+                )));
+            }
+        }
+    }
+
+    match (short_sale_slot, designated_location) {
+        (ShortSaleSlot::ThirdParty, None) => Err(ValidationError::MissingRequiredField("delta_neutral_designated_location")),
+        (ShortSaleSlot::None | ShortSaleSlot::Broker, Some(_)) => Err(ValidationError::InvalidCombination(
+            "A designated location is only used with third party short sales".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
 /// Validates stop price relative to current market price
 #[cfg(test)]
 pub fn validate_stop_price(action: &Action, stop_price: f64, current_price: Option<f64>) -> Result<(), ValidationError> {
     if let Some(current) = current_price {
         match action {
-            Action::Buy => {
-                if stop_price <= current {
-                    return Err(ValidationError::InvalidStopPrice { stop: stop_price, current });
-                }
+            Action::Buy if stop_price <= current => {
+                return Err(ValidationError::InvalidStopPrice { stop: stop_price, current });
             }
-            Action::Sell | Action::SellShort => {
-                if stop_price >= current {
-                    return Err(ValidationError::InvalidStopPrice { stop: stop_price, current });
-                }
+            Action::Sell | Action::SellShort if stop_price >= current => {
+                return Err(ValidationError::InvalidStopPrice { stop: stop_price, current });
             }
             _ => {}
         }
@@ -1,8 +1,8 @@
 use crate::contracts::{ComboLeg, ComboLegOpenClose, Contract, Currency, DeltaNeutralContract, Exchange, SecurityType, Symbol, TagValue};
 use crate::messages::ResponseMessage;
 use crate::orders::{
-    Action, CommissionReport, ExecutionData, Liquidity, Order, OrderAllocation, OrderComboLeg, OrderCondition, OrderData, OrderOpenClose, OrderState,
-    OrderStatus, Rule80A, SoftDollarTier, TimeInForce,
+    Action, CommissionReport, ExecutionData, Liquidity, Order, OrderAllocation, OrderBound, OrderComboLeg, OrderCondition, OrderData, OrderOpenClose,
+    OrderState, OrderStatus, Rule80A, SoftDollarTier, TimeInForce,
 };
 use crate::{server_versions, Error};
 
@@ -71,7 +71,7 @@ impl OrderDecoder {
     }
 
     fn read_total_quantity(&mut self) -> Result<(), Error> {
-        self.order.total_quantity = self.message.next_double()?;
+        self.order.total_quantity = self.message.next_decimal()?;
         Ok(())
     }
 
@@ -855,8 +855,8 @@ pub(crate) fn decode_order_status(server_version: i32, message: &mut ResponseMes
     let mut order_status = OrderStatus {
         order_id: message.next_int()?,
         status: message.next_string()?,
-        filled: message.next_double()?,
-        remaining: message.next_double()?,
+        filled: message.next_decimal()?,
+        remaining: message.next_decimal()?,
         average_fill_price: message.next_double()?,
         perm_id: message.next_int()?,
         parent_id: message.next_int()?,
@@ -903,12 +903,12 @@ pub(crate) fn decode_execution_data(server_version: i32, message: &mut ResponseM
     execution.account_number = message.next_string()?;
     execution.exchange = message.next_string()?;
     execution.side = message.next_string()?;
-    execution.shares = message.next_double()?;
+    execution.shares = message.next_decimal()?;
     execution.price = message.next_double()?;
     execution.perm_id = message.next_int()?;
     execution.client_id = message.next_int()?;
     execution.liquidation = message.next_int()?;
-    execution.cumulative_quantity = message.next_double()?;
+    execution.cumulative_quantity = message.next_decimal()?;
     execution.average_price = message.next_double()?;
     execution.order_reference = message.next_string()?;
     execution.ev_rule = message.next_string()?;
@@ -947,6 +947,28 @@ pub(crate) fn decode_commission_report(_server_version: i32, message: &mut Respo
     })
 }
 
+pub(crate) fn decode_delta_neutral_validation(_server_version: i32, message: &mut ResponseMessage) -> Result<DeltaNeutralContract, Error> {
+    message.skip(); // message type
+    message.skip(); // message version
+    message.skip(); // order id
+
+    Ok(DeltaNeutralContract {
+        contract_id: message.next_int()?,
+        delta: message.next_double()?,
+        price: message.next_double()?,
+    })
+}
+
+pub(crate) fn decode_order_bound(message: &mut ResponseMessage) -> Result<OrderBound, Error> {
+    message.skip(); // message type
+
+    Ok(OrderBound {
+        perm_id: message.next_long()?,
+        client_id: message.next_int()?,
+        order_id: message.next_int()?,
+    })
+}
+
 pub(crate) fn decode_completed_order(server_version: i32, message: ResponseMessage) -> Result<OrderData, Error> {
     let mut decoder = OrderDecoder::new(server_version, message);
 
@@ -1131,6 +1153,29 @@ fn decode_percent_change_condition(message: &mut ResponseMessage, is_conjunction
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Decimal;
+
+    #[test]
+    fn test_decode_delta_neutral_validation() {
+        let mut message = ResponseMessage::from("56\01\013\08314\00.52\0185.5\0");
+
+        let contract = decode_delta_neutral_validation(server_versions::SIZE_RULES, &mut message).unwrap();
+
+        assert_eq!(contract.contract_id, 8314);
+        assert_eq!(contract.delta, 0.52);
+        assert_eq!(contract.price, 185.5);
+    }
+
+    #[test]
+    fn test_decode_order_bound() {
+        let mut message = ResponseMessage::from("100\01376327563\00\0-12\0");
+
+        let binding = decode_order_bound(&mut message).unwrap();
+
+        assert_eq!(binding.perm_id, 1376327563);
+        assert_eq!(binding.client_id, 0);
+        assert_eq!(binding.order_id, -12);
+    }
 
     #[test]
     fn test_completed_order_parsing_issue_318() {
@@ -2064,7 +2109,7 @@ mod tests {
         assert_eq!(result.execution.order_id, 42);
         assert_eq!(result.contract.symbol.to_string(), "AAPL");
         assert_eq!(result.execution.execution_id, "0001f4e8.67890abc.01.01");
-        assert_eq!(result.execution.shares, 100.0);
+        assert_eq!(result.execution.shares, Decimal::from(100));
         assert_eq!(result.execution.price, 150.50);
 
         // Verify new fields
@@ -3,7 +3,7 @@ use time::OffsetDateTime;
 use crate::contracts::Contract;
 use crate::messages::{OutgoingMessages, RequestMessage};
 use crate::orders::{ExecutionFilter, ExerciseAction, Order, OrderCondition, COMPETE_AGAINST_BEST_OFFSET_UP_TO_MID};
-use crate::{server_versions, Error};
+use crate::{decimal, server_versions, Error};
 
 pub(crate) fn encode_place_order(server_version: i32, order_id: i32, contract: &Contract, order: &Order) -> Result<RequestMessage, Error> {
     let mut message = RequestMessage::default();
@@ -43,7 +43,7 @@ pub(crate) fn encode_place_order(server_version: i32, order_id: i32, contract: &
     if server_version >= server_versions::FRACTIONAL_POSITIONS {
         message.push_field(&order.total_quantity);
     } else {
-        message.push_field(&(order.total_quantity as i32));
+        message.push_field(&(decimal::to_f64(order.total_quantity) as i32));
     }
 
     message.push_field(&order.order_type);
@@ -699,6 +699,7 @@ fn encode_percent_change_condition(message: &mut RequestMessage, condition: &cra
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use crate::Decimal;
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -943,7 +944,7 @@ pub(crate) mod tests {
         let contract = Contract::stock("AAPL").build();
         let order = Order {
             action: crate::orders::Action::Buy,
-            total_quantity: 100.0,
+            total_quantity: Decimal::from(100),
             order_type: "LMT".to_string(),
             limit_price: Some(150.50),
             customer_account: "CUST001".to_string(),
@@ -975,7 +976,7 @@ pub(crate) mod tests {
         let contract = Contract::stock("AAPL").build();
         let order = Order {
             action: crate::orders::Action::Buy,
-            total_quantity: 100.0,
+            total_quantity: Decimal::from(100),
             order_type: "LMT".to_string(),
             customer_account: "CUST001".to_string(),
             professional_customer: true,
@@ -1001,7 +1002,7 @@ pub(crate) mod tests {
         let contract = Contract::stock("AAPL").build();
         let order = Order {
             action: crate::orders::Action::Buy,
-            total_quantity: 100.0,
+            total_quantity: Decimal::from(100),
             order_type: "LMT".to_string(),
             customer_account: "CUST001".to_string(),
             professional_customer: true,
@@ -1021,4 +1022,59 @@ pub(crate) mod tests {
         assert_eq!(field_vec[len - 3], "1", "professional_customer");
         assert_eq!(field_vec[len - 4], "CUST001", "customer_account");
     }
+
+    fn contains_fields(fields: &[&str], expected: &[&str]) -> bool {
+        fields.windows(expected.len()).any(|window| window == expected)
+    }
+
+    #[test]
+    fn test_encode_place_order_pegged_to_benchmark() {
+        use crate::orders::builder::OrderBuilder;
+
+        let contract = Contract::stock("AAPL").build();
+        let reference = Contract {
+            contract_id: 8314,
+            exchange: "ISLAND".into(),
+            ..Contract::stock("IBM").build()
+        };
+        let order = OrderBuilder::new(&(), &contract)
+            .buy(100)
+            .pegged_to_benchmark(&reference, 55.0, 185.0)
+            .pegged_change(0.5, 1.0)
+            .reference_range(180.0, 190.0)
+            .build()
+            .unwrap();
+
+        let result = encode_place_order(server_versions::PEGBEST_PEGMID_OFFSETS, 42, &contract, &order).unwrap();
+        let fields = result.encode();
+        let field_vec: Vec<&str> = fields.split('\0').collect();
+
+        assert!(contains_fields(&field_vec, &["PEG BENCH"]));
+        // starting price, stock reference price, delta, stock range lower and upper
+        assert!(contains_fields(&field_vec, &["55", "185", "", "180", "190"]));
+        // reference contract, decrease flag, pegged change, reference change, reference exchange
+        assert!(contains_fields(&field_vec, &["8314", "0", "0.5", "1", "ISLAND"]));
+    }
+
+    #[test]
+    fn test_encode_place_order_adjusted_stop() {
+        use crate::orders::builder::{OrderBuilder, TrailingAmount};
+
+        let contract = Contract::stock("AAPL").build();
+        let order = OrderBuilder::new(&(), &contract)
+            .sell(100)
+            .stop(95.0)
+            .parent(1001)
+            .adjust_stop_when(105.0)
+            .to_trailing(100.0, TrailingAmount::Amount(1.5))
+            .build()
+            .unwrap();
+
+        let result = encode_place_order(server_versions::PEGBEST_PEGMID_OFFSETS, 42, &contract, &order).unwrap();
+        let fields = result.encode();
+        let field_vec: Vec<&str> = fields.split('\0').collect();
+
+        // adjusted order type, trigger, limit offset, adjusted stop, adjusted stop limit, trailing amount, trailing unit
+        assert!(contains_fields(&field_vec, &["TRAIL", "105", "", "100", "", "1.5", "0"]));
+    }
 }
@@ -1,4 +1,4 @@
-pub(super) mod decoders;
+pub(crate) mod decoders;
 pub(crate) mod encoders;
 /// Helpers for constructing commonly used order templates.
 pub mod order_builder;
@@ -1,3 +1,4 @@
+use crate::decimal;
 use crate::orders::{
     Action, AuctionStrategy, OcaType, Order, OrderComboLeg, TagValue, TimeInForce, VolatilityType, COMPETE_AGAINST_BEST_OFFSET_UP_TO_MID,
 };
@@ -25,7 +26,7 @@ pub fn at_auction(action: Action, quantity: f64, price: f64) -> Order {
         action,
         tif: TimeInForce::Auction,
         order_type: "MTL".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(price),
         ..Order::default()
     }
@@ -38,7 +39,7 @@ pub fn discretionary(action: Action, quantity: f64, price: f64, discretionary_am
     Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(price),
         discretionary_amt: discretionary_amount,
         ..Order::default()
@@ -53,7 +54,7 @@ pub fn market_order(action: Action, quantity: f64) -> Order {
     Order {
         action,
         order_type: "MKT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         ..Order::default()
     }
 }
@@ -69,7 +70,7 @@ pub fn market_if_touched(action: Action, quantity: f64, price: f64) -> Order {
     Order {
         action,
         order_type: "MIT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         aux_price: Some(price),
         ..Order::default()
     }
@@ -81,7 +82,7 @@ pub fn market_on_close(action: Action, quantity: f64) -> Order {
     Order {
         action,
         order_type: "MOC".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         ..Order::default()
     }
 }
@@ -93,7 +94,7 @@ pub fn market_on_open(action: Action, quantity: f64) -> Order {
     Order {
         action,
         order_type: "MKT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         tif: TimeInForce::OnOpen,
         ..Order::default()
     }
@@ -107,7 +108,7 @@ pub fn midpoint_match(action: Action, quantity: f64) -> Order {
     Order {
         action,
         order_type: "MKT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         ..Order::default()
     }
 }
@@ -120,7 +121,7 @@ pub fn midprice(action: Action, quantity: f64, price_cap: Option<f64>) -> Order
     Order {
         action,
         order_type: "MIDPRICE".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: price_cap,
         ..Order::default()
     }
@@ -137,7 +138,7 @@ pub fn pegged_to_market(action: Action, quantity: f64, market_offset: f64) -> Or
     Order {
         action,
         order_type: "PEG MKT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         aux_price: Some(market_offset),
         ..Order::default()
     }
@@ -155,7 +156,7 @@ pub fn pegged_to_stock(action: Action, quantity: f64, delta: f64, stock_referenc
     Order {
         action,
         order_type: "PEG STK".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         delta: Some(delta),
         stock_ref_price: Some(stock_reference_price),
         starting_price: Some(starting_price),
@@ -177,7 +178,7 @@ pub fn relative_pegged_to_primary(action: Action, quantity: f64, price_cap: f64,
     Order {
         action,
         order_type: "REL".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(price_cap),
         aux_price: Some(offset_amount),
         ..Order::default()
@@ -193,7 +194,7 @@ pub fn sweep_to_fill(action: Action, quantity: f64, price: f64) -> Order {
     Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(price),
         sweep_to_fill: true,
         ..Order::default()
@@ -213,7 +214,7 @@ pub fn auction_limit(action: Action, quantity: f64, price: f64, auction_strategy
     Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(price),
         auction_strategy: Some(auction_strategy),
         ..Order::default()
@@ -236,7 +237,7 @@ pub fn auction_pegged_to_stock(action: Action, quantity: f64, starting_price: f6
     Order {
         action,
         order_type: "PEG STK".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         delta: Some(delta),
         starting_price: Some(starting_price),
         ..Order::default()
@@ -259,7 +260,7 @@ pub fn auction_relative(action: Action, quantity: f64, offset: f64) -> Order {
     Order {
         action,
         order_type: "REL".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         aux_price: Some(offset),
         ..Order::default()
     }
@@ -272,7 +273,7 @@ pub fn block(action: Action, quantity: f64, price: f64) -> Order {
     Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(price),
         block_order: true,
         ..Order::default()
@@ -287,7 +288,7 @@ pub fn box_top(action: Action, quantity: f64) -> Order {
     Order {
         action,
         order_type: "BOX TOP".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         ..Order::default()
     }
 }
@@ -299,7 +300,7 @@ pub fn limit_order(action: Action, quantity: f64, limit_price: f64) -> Order {
     Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         ..Order::default()
     }
@@ -326,7 +327,7 @@ pub fn limit_if_touched(action: Action, quantity: f64, limit_price: f64, trigger
     Order {
         action,
         order_type: "LIT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         aux_price: Some(trigger_price),
         ..Order::default()
@@ -340,7 +341,7 @@ pub fn limit_on_close(action: Action, quantity: f64, limit_price: f64) -> Order
     Order {
         action,
         order_type: "LOC".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         ..Order::default()
     }
@@ -353,7 +354,7 @@ pub fn limit_on_open(action: Action, quantity: f64, limit_price: f64) -> Order {
     Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         tif: TimeInForce::OnOpen,
         ..Order::default()
@@ -374,7 +375,7 @@ pub fn passive_relative(action: Action, quantity: f64, offset: f64) -> Order {
     Order {
         action,
         order_type: "PASSV REL".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         aux_price: Some(offset),
         ..Order::default()
     }
@@ -389,7 +390,7 @@ pub fn pegged_to_midpoint(action: Action, quantity: f64, offset: f64, limit_pric
     Order {
         action,
         order_type: "PEG MID".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         aux_price: Some(offset),
         limit_price: Some(limit_price),
         ..Order::default()
@@ -413,7 +414,7 @@ pub fn bracket_order(
         order_id: parent_order_id,
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         transmit: false,
         ..Order::default()
@@ -423,7 +424,7 @@ pub fn bracket_order(
         order_id: parent.order_id + 1,
         action: action.reverse(),
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(take_profit_limit_price),
         parent_id: parent_order_id,
         transmit: false,
@@ -436,7 +437,7 @@ pub fn bracket_order(
         order_type: "STP".to_owned(),
         //Stop trigger price
         aux_price: Some(stop_loss_price),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         parent_id: parent_order_id,
         //In this case, the low side order will be the last child being sent. Therefore, it needs to set this attribute to true
         //to activate all its predecessors
@@ -455,7 +456,7 @@ pub fn market_to_limit(action: Action, quantity: f64) -> Order {
     Order {
         action,
         order_type: "MTL".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         ..Order::default()
     }
 }
@@ -468,7 +469,7 @@ pub fn market_with_protection(action: Action, quantity: f64) -> Order {
     Order {
         action,
         order_type: "MKT PRT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         ..Order::default()
     }
 }
@@ -483,7 +484,7 @@ pub fn stop(action: Action, quantity: f64, stop_price: f64) -> Order {
     Order {
         action,
         order_type: "STP".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         aux_price: Some(stop_price),
         ..Order::default()
     }
@@ -497,7 +498,7 @@ pub fn stop_limit(action: Action, quantity: f64, limit_price: f64, stop_price: f
     Order {
         action,
         order_type: "STP LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         aux_price: Some(stop_price),
         ..Order::default()
@@ -514,7 +515,7 @@ pub fn stop_with_protection(action: Action, quantity: f64, stop_price: f64) -> O
     Order {
         action,
         order_type: "STP PRT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         aux_price: Some(stop_price),
         ..Order::default()
     }
@@ -530,7 +531,7 @@ pub fn trailing_stop(action: Action, quantity: f64, trailing_percent: f64, trail
     Order {
         action,
         order_type: "TRAIL".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         trailing_percent: Some(trailing_percent),
         trail_stop_price: Some(trail_stop_price),
         ..Order::default()
@@ -549,7 +550,7 @@ pub fn trailing_stop_limit(action: Action, quantity: f64, lmt_price_offset: f64,
     Order {
         action,
         order_type: "TRAIL LIMIT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         trail_stop_price: Some(trail_stop_price),
         limit_price_offset: Some(lmt_price_offset),
         aux_price: Some(trailing_amount),
@@ -566,7 +567,7 @@ pub fn combo_limit_order(action: Action, quantity: f64, limit_price: f64, non_gu
     let mut order = Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         ..Order::default()
     };
@@ -596,7 +597,7 @@ pub fn combo_market_order(action: Action, quantity: f64, non_guaranteed: bool) -
     let mut order = Order {
         action,
         order_type: "MKT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         ..Order::default()
     };
 
@@ -616,7 +617,7 @@ pub fn limit_order_for_combo_with_leg_prices(action: Action, quantity: f64, leg_
     let mut order = Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         order_combo_legs: vec![],
         ..Order::default()
     };
@@ -641,7 +642,7 @@ pub fn relative_limit_combo(action: Action, quantity: f64, limit_price: f64, non
     let mut order = Order {
         action,
         order_type: "REL + LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         ..Order::default()
     };
@@ -662,7 +663,7 @@ pub fn relative_market_combo(action: Action, quantity: f64, non_guaranteed: bool
     let mut order = Order {
         action,
         order_type: "REL + MKT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         ..Order::default()
     };
 
@@ -705,7 +706,7 @@ pub fn volatility(action: Action, quantity: f64, volatility_percent: f64, volati
     Order {
         action,
         order_type: "VOL".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         volatility: Some(volatility_percent), //Expressed in percentage (40%)
         volatility_type: Some(volatility_type),
         ..Order::default()
@@ -740,7 +741,7 @@ pub fn pegged_to_benchmark(
     Order {
         action,
         order_type: "PEG BENCH".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         starting_price: Some(starting_price),
         is_pegged_change_amount_decrease: pegged_change_amount_decrease,
         pegged_change_amount: Some(pegged_change_amount), // by ... (and likewise for price moving in opposite direction)
@@ -759,7 +760,7 @@ pub fn pegged_to_benchmark(
 /// in the opposite direction when the trigger is hit.
 pub fn attach_adjustable_to_stop(parent: &Order, attached_order_stop_price: f64, trigger_price: f64, adjusted_stop_price: f64) -> Order {
     // Attached order is a conventional STP order
-    let mut order = stop(parent.action.reverse(), parent.total_quantity, attached_order_stop_price);
+    let mut order = stop(parent.action.reverse(), decimal::to_f64(parent.total_quantity), attached_order_stop_price);
 
     order.parent_id = parent.order_id;
     order.trigger_price = Some(trigger_price); // When trigger price is penetrated
@@ -779,7 +780,7 @@ pub fn attach_adjustable_to_stop_limit(
     adjusted_stop_limit_price: f64,
 ) -> Order {
     // Attached order is a conventional STP order
-    let mut order = stop(parent.action.reverse(), parent.total_quantity, attached_order_stop_price);
+    let mut order = stop(parent.action.reverse(), decimal::to_f64(parent.total_quantity), attached_order_stop_price);
 
     order.parent_id = parent.order_id;
     order.trigger_price = Some(trigger_price); // When trigger price is penetrated
@@ -801,7 +802,7 @@ pub fn attach_adjustable_to_trail(
     trail_unit: i32,
 ) -> Order {
     // Attached order is a conventional STP order
-    let mut order = stop(parent.action.reverse(), parent.total_quantity, attached_order_stop_price);
+    let mut order = stop(parent.action.reverse(), decimal::to_f64(parent.total_quantity), attached_order_stop_price);
 
     order.parent_id = parent.order_id;
     order.trigger_price = Some(trigger_price); // When trigger price is penetrated
@@ -927,7 +928,7 @@ pub fn limit_ibkrats(action: Action, quantity: f64, limit_price: f64) -> Order {
     Order {
         action,
         order_type: "LMT".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         not_held: true,
         ..Order::default()
@@ -955,7 +956,7 @@ pub fn peg_best_up_to_mid_order(
     Order {
         action,
         order_type: "PEG BEST".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         not_held: true,
         min_trade_qty: Some(min_trade_qty),
@@ -979,7 +980,7 @@ pub fn peg_best_order(
     Order {
         action,
         order_type: "PEG BEST".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         not_held: true,
         min_trade_qty: Some(min_trade_qty),
@@ -1001,7 +1002,7 @@ pub fn peg_mid_order(
     Order {
         action,
         order_type: "PEG MID".to_owned(),
-        total_quantity: quantity,
+        total_quantity: decimal::from_f64(quantity),
         limit_price: Some(limit_price),
         not_held: true,
         min_trade_qty: Some(min_trade_qty),
@@ -1015,6 +1016,7 @@ pub fn peg_mid_order(
 mod tests {
     use crate::orders::common::order_builder::*;
     use crate::orders::{Action, COMPETE_AGAINST_BEST_OFFSET_UP_TO_MID};
+    use crate::Decimal;
 
     /// Tests for basic order types like market, limit, and stop orders
     #[cfg(test)]
@@ -1027,14 +1029,14 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MKT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, None);
             assert_eq!(order.aux_price, None);
 
             // Test sell order
             let order = market_order(Action::Sell, 200.0);
             assert_eq!(order.action, Action::Sell);
-            assert_eq!(order.total_quantity, 200.0);
+            assert_eq!(order.total_quantity, Decimal::from(200));
         }
 
         #[test]
@@ -1043,7 +1045,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.25));
 
             // Test sell order
@@ -1058,7 +1060,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Sell);
             assert_eq!(order.order_type, "STP");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(45.0)); // Stop price
             assert_eq!(order.limit_price, None);
         }
@@ -1069,7 +1071,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Sell);
             assert_eq!(order.order_type, "STP LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(45.0));
             assert_eq!(order.aux_price, Some(44.0)); // Stop trigger price
         }
@@ -1080,7 +1082,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LIT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(52.0));
             assert_eq!(order.aux_price, Some(50.0)); // Trigger price
         }
@@ -1091,7 +1093,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MIT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(50.0)); // Trigger price
         }
     }
@@ -1106,7 +1108,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MOC");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
         }
 
         #[test]
@@ -1115,7 +1117,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MKT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.tif, TimeInForce::OnOpen);
         }
 
@@ -1125,7 +1127,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LOC");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
         }
 
@@ -1135,7 +1137,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert_eq!(order.tif, TimeInForce::OnOpen);
         }
@@ -1156,7 +1158,7 @@ mod tests {
             assert_eq!(parent.order_id, 1000);
             assert_eq!(parent.action, Action::Buy);
             assert_eq!(parent.order_type, "LMT");
-            assert_eq!(parent.total_quantity, 100.0);
+            assert_eq!(parent.total_quantity, Decimal::from(100));
             assert_eq!(parent.limit_price, Some(50.0));
             assert!(!parent.transmit);
 
@@ -1165,7 +1167,7 @@ mod tests {
             assert_eq!(take_profit.order_id, 1001);
             assert_eq!(take_profit.action, Action::Sell);
             assert_eq!(take_profit.order_type, "LMT");
-            assert_eq!(take_profit.total_quantity, 100.0);
+            assert_eq!(take_profit.total_quantity, Decimal::from(100));
             assert_eq!(take_profit.limit_price, Some(55.0));
             assert_eq!(take_profit.parent_id, 1000);
             assert!(!take_profit.transmit);
@@ -1175,7 +1177,7 @@ mod tests {
             assert_eq!(stop_loss.order_id, 1002);
             assert_eq!(stop_loss.action, Action::Sell);
             assert_eq!(stop_loss.order_type, "STP");
-            assert_eq!(stop_loss.total_quantity, 100.0);
+            assert_eq!(stop_loss.total_quantity, Decimal::from(100));
             assert_eq!(stop_loss.aux_price, Some(45.0));
             assert_eq!(stop_loss.parent_id, 1000);
             assert!(stop_loss.transmit);
@@ -1205,7 +1207,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Sell);
             assert_eq!(order.order_type, "TRAIL");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.trailing_percent, Some(5.0));
             assert_eq!(order.trail_stop_price, Some(45.0));
         }
@@ -1216,7 +1218,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Sell);
             assert_eq!(order.order_type, "TRAIL LIMIT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price_offset, Some(2.0));
             assert_eq!(order.aux_price, Some(5.0)); // Trailing amount
             assert_eq!(order.trail_stop_price, Some(45.0));
@@ -1233,7 +1235,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MKT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
 
             // Check non-guaranteed params
             assert_eq!(order.smart_combo_routing_params.len(), 1);
@@ -1247,7 +1249,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
 
             // Check non-guaranteed params
@@ -1262,7 +1264,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "REL + LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
 
             // Check non-guaranteed params
@@ -1278,7 +1280,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
 
             // Check leg prices
             assert_eq!(order.order_combo_legs.len(), 2);
@@ -1302,7 +1304,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "PEG MKT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(0.05));
         }
 
@@ -1312,7 +1314,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "VOL");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.volatility, Some(0.04));
             assert_eq!(order.volatility_type, Some(VolatilityType::Daily));
         }
@@ -1323,7 +1325,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert_eq!(order.auction_strategy, Some(AuctionStrategy::Improvement));
         }
@@ -1334,7 +1336,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "REL");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(0.05));
         }
 
@@ -1344,7 +1346,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert!(order.block_order);
         }
@@ -1355,7 +1357,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "BOX TOP");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
         }
 
         #[test]
@@ -1364,7 +1366,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert!(order.sweep_to_fill);
         }
@@ -1375,7 +1377,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert_eq!(order.discretionary_amt, 0.1);
         }
@@ -1386,7 +1388,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MKT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
         }
 
         #[test]
@@ -1395,7 +1397,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MIDPRICE");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
         }
 
@@ -1405,7 +1407,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MIDPRICE");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, None);
         }
 
@@ -1427,7 +1429,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "PEG BENCH");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.starting_price, Some(50.0));
             assert!(!order.is_pegged_change_amount_decrease);
             assert_eq!(order.pegged_change_amount, Some(0.02));
@@ -1457,7 +1459,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "PEG BEST");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert!(order.not_held);
             assert_eq!(order.min_trade_qty, Some(10));
@@ -1479,7 +1481,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "PEG BEST");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert!(order.not_held);
             assert_eq!(order.min_trade_qty, Some(10));
@@ -1502,7 +1504,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "PEG MID");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert!(order.not_held);
             assert_eq!(order.min_trade_qty, Some(10));
@@ -1531,7 +1533,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert_eq!(order.manual_order_time, "20240101 10:00:00");
         }
@@ -1542,7 +1544,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MKT PRT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
         }
 
         #[test]
@@ -1551,7 +1553,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Sell);
             assert_eq!(order.order_type, "STP PRT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(45.0));
         }
 
@@ -1561,7 +1563,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert!(order.not_held);
         }
@@ -1572,7 +1574,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MKT");
-            assert_eq!(order.total_quantity, 0.0);
+            assert_eq!(order.total_quantity, Decimal::from(0));
             assert_eq!(order.parent_id, 1001);
             assert_eq!(order.hedge_type, "F");
         }
@@ -1593,7 +1595,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Sell); // Opposite of parent
             assert_eq!(order.order_type, "STP");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(45.0));
             assert_eq!(order.parent_id, parent.order_id);
             assert_eq!(order.trigger_price, Some(48.0));
@@ -1613,7 +1615,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Sell); // Opposite of parent
             assert_eq!(order.order_type, "STP");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(45.0));
             assert_eq!(order.parent_id, parent.order_id);
             assert_eq!(order.trigger_price, Some(48.0));
@@ -1635,7 +1637,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Sell); // Opposite of parent
             assert_eq!(order.order_type, "STP");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(45.0));
             assert_eq!(order.parent_id, parent.order_id);
             assert_eq!(order.trigger_price, Some(48.0));
@@ -1656,7 +1658,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "REL + MKT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
 
             // Check non-guaranteed params
             assert_eq!(order.smart_combo_routing_params.len(), 1);
@@ -1675,7 +1677,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "PEG STK");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.starting_price, Some(50.0));
             assert_eq!(order.delta, Some(0.5));
         }
@@ -1692,7 +1694,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "PEG STK");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.delta, Some(0.5));
             assert_eq!(order.stock_ref_price, Some(50.0));
             assert_eq!(order.starting_price, Some(49.0));
@@ -1709,7 +1711,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "REL");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert_eq!(order.aux_price, Some(0.01));
         }
@@ -1724,7 +1726,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "PASSV REL");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.aux_price, Some(0.01));
         }
 
@@ -1738,7 +1740,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "MTL");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert_eq!(order.tif, TimeInForce::Auction);
         }
@@ -1753,7 +1755,7 @@ mod tests {
 
             assert_eq!(order.action, Action::Buy);
             assert_eq!(order.order_type, "LMT");
-            assert_eq!(order.total_quantity, 100.0);
+            assert_eq!(order.total_quantity, Decimal::from(100));
             assert_eq!(order.limit_price, Some(50.0));
             assert!(order.what_if);
         }
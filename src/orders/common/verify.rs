@@ -1,9 +1,15 @@
+use log::warn;
+
 use crate::contracts::Contract;
 use crate::orders::Order;
 use crate::{server_versions, Error};
 
+#[cfg(test)]
+mod tests;
+
 pub(crate) trait VersionedClient {
     fn check_version(&self, version: i32, message: &str) -> Result<(), Error>;
+    fn server_version(&self) -> i32;
 }
 
 #[cfg(feature = "sync")]
@@ -11,6 +17,10 @@ impl VersionedClient for crate::client::sync::Client {
     fn check_version(&self, version: i32, message: &str) -> Result<(), Error> {
         self.check_server_version(version, message)
     }
+
+    fn server_version(&self) -> i32 {
+        self.server_version()
+    }
 }
 
 #[cfg(feature = "async")]
@@ -18,6 +28,10 @@ impl VersionedClient for crate::client::r#async::Client {
     fn check_version(&self, version: i32, message: &str) -> Result<(), Error> {
         self.check_server_version(version, message)
     }
+
+    fn server_version(&self) -> i32 {
+        self.server_version()
+    }
 }
 
 // Verifies that Order is properly formed.
@@ -51,10 +65,14 @@ pub(crate) fn verify_order(client: &impl VersionedClient, order: &Order, _order_
         client.check_version(server_versions::SSHORTX, "It does not support exempt_code parameter.")?
     }
 
-    if !order.hedge_type.is_empty() {
+    if !order.hedge_type.is_empty() || !order.hedge_param.is_empty() {
         client.check_version(server_versions::HEDGE_ORDERS, "It does not support hedge orders.")?
     }
 
+    if !order.fa_profile.is_empty() && client.server_version() >= server_versions::FA_PROFILE_DESUPPORT {
+        warn!("fa_profile is not sent to servers that no longer support FA profiles; allocate with fa_group and fa_method instead.");
+    }
+
     if order.opt_out_smart_routing {
         client.check_version(
             server_versions::OPT_OUT_SMART_ROUTING,
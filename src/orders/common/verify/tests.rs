@@ -0,0 +1,82 @@
+use super::*;
+
+struct StubClient {
+    server_version: i32,
+}
+
+impl VersionedClient for StubClient {
+    fn check_version(&self, version: i32, message: &str) -> Result<(), Error> {
+        if version <= self.server_version {
+            Ok(())
+        } else {
+            Err(Error::ServerVersion(version, self.server_version, message.into()))
+        }
+    }
+
+    fn server_version(&self) -> i32 {
+        self.server_version
+    }
+}
+
+#[test]
+fn test_hedge_orders_require_server_support() {
+    let order = Order {
+        hedge_type: "B".into(),
+        hedge_param: "1.25".into(),
+        ..Default::default()
+    };
+
+    let old = StubClient {
+        server_version: server_versions::HEDGE_ORDERS - 1,
+    };
+    assert!(matches!(
+        verify_order(&old, &order, 1),
+        Err(Error::ServerVersion(server_versions::HEDGE_ORDERS, _, _))
+    ));
+
+    let current = StubClient {
+        server_version: server_versions::HEDGE_ORDERS,
+    };
+    assert!(verify_order(&current, &order, 1).is_ok());
+}
+
+#[test]
+fn test_cash_qty_requires_server_support() {
+    let order = Order {
+        cash_qty: Some(25_000.0),
+        ..Default::default()
+    };
+
+    let old = StubClient {
+        server_version: server_versions::CASH_QTY - 1,
+    };
+    assert!(matches!(
+        verify_order(&old, &order, 1),
+        Err(Error::ServerVersion(server_versions::CASH_QTY, _, _))
+    ));
+}
+
+#[test]
+fn test_fa_profile_desupported_is_not_rejected() {
+    let order = Order {
+        fa_profile: "Growth".into(),
+        ..Default::default()
+    };
+
+    let old = StubClient {
+        server_version: server_versions::FA_PROFILE_DESUPPORT - 1,
+    };
+    assert!(verify_order(&old, &order, 1).is_ok());
+
+    let current = StubClient {
+        server_version: server_versions::FA_PROFILE_DESUPPORT,
+    };
+    assert!(verify_order(&current, &order, 1).is_ok());
+
+    let group = Order {
+        fa_group: "Growth".into(),
+        fa_method: "NetLiq".into(),
+        ..Default::default()
+    };
+    assert!(verify_order(&current, &group, 1).is_ok());
+}
@@ -0,0 +1,107 @@
+//! Typed builder for [ExecutionFilter].
+
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, UtcOffset};
+
+use super::{Action, ExecutionFilter};
+use crate::contracts::SecurityType;
+use crate::ToField;
+
+impl ExecutionFilter {
+    /// Starts building a filter. Criteria that are not set match every execution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ibapi::contracts::SecurityType;
+    /// use ibapi::orders::{Action, ExecutionFilter};
+    ///
+    /// let filter = ExecutionFilter::builder()
+    ///     .account("DU1234567")
+    ///     .security_type(SecurityType::Stock)
+    ///     .side(Action::Buy)
+    ///     .build();
+    ///
+    /// assert_eq!(filter.security_type, "STK");
+    /// assert_eq!(filter.side, "BUY");
+    /// ```
+    pub fn builder() -> ExecutionFilterBuilder {
+        ExecutionFilterBuilder::default()
+    }
+}
+
+/// Builder for [ExecutionFilter] that takes typed values instead of wire strings.
+#[derive(Debug, Default)]
+pub struct ExecutionFilterBuilder {
+    filter: ExecutionFilter,
+}
+
+impl ExecutionFilterBuilder {
+    /// Only return executions of orders placed by the API client with this id.
+    pub fn client_id(mut self, client_id: i32) -> Self {
+        self.filter.client_id = Some(client_id);
+        self
+    }
+
+    /// Only return executions allocated to this account.
+    pub fn account(mut self, account: impl Into<String>) -> Self {
+        self.filter.account_code = account.into();
+        self
+    }
+
+    /// Only return executions reported after `time`. The time is sent to TWS in UTC.
+    pub fn since(mut self, time: OffsetDateTime) -> Self {
+        let format = format_description!("[year][month][day]-[hour]:[minute]:[second]");
+        // The format only has numeric components, so formatting cannot fail.
+        self.filter.time = time.to_offset(UtcOffset::UTC).format(format).unwrap_or_default();
+        self
+    }
+
+    /// Only return executions for this symbol.
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.filter.symbol = symbol.into();
+        self
+    }
+
+    /// Only return executions for this security type.
+    pub fn security_type(mut self, security_type: SecurityType) -> Self {
+        self.filter.security_type = security_type.to_field();
+        self
+    }
+
+    /// Only return executions on this exchange.
+    pub fn exchange(mut self, exchange: impl Into<String>) -> Self {
+        self.filter.exchange = exchange.into();
+        self
+    }
+
+    /// Only return buys or sells. TWS filters on BUY or SELL only, so short and long sells match all sells.
+    pub fn side(mut self, action: Action) -> Self {
+        self.filter.side = match action {
+            Action::Buy => "BUY",
+            Action::Sell | Action::SellShort | Action::SellLong => "SELL",
+        }
+        .to_string();
+        self
+    }
+
+    /// Only return executions from the last `days` days. Requires server version `PARAMETRIZED_DAYS_OF_EXECUTIONS`.
+    pub fn last_n_days(mut self, days: i32) -> Self {
+        self.filter.last_n_days = days;
+        self
+    }
+
+    /// Adds a date to return executions for. May be called repeatedly. Requires server version `PARAMETRIZED_DAYS_OF_EXECUTIONS`.
+    pub fn on_date(mut self, date: Date) -> Self {
+        self.filter.specific_dates.push(date.to_field());
+        self
+    }
+
+    /// Returns the filter.
+    pub fn build(self) -> ExecutionFilter {
+        self.filter
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,47 @@
+use time::macros::{date, datetime};
+
+use super::*;
+use crate::orders::common::encoders::encode_executions;
+use crate::server_versions;
+
+#[test]
+fn test_builder_sets_wire_values() {
+    let filter = ExecutionFilter::builder()
+        .client_id(32)
+        .account("DU1234567")
+        .since(datetime!(2025-01-17 09:30:00 -5))
+        .symbol("AAPL")
+        .security_type(SecurityType::Option)
+        .exchange("CBOE")
+        .side(Action::SellShort)
+        .build();
+
+    assert_eq!(filter.client_id, Some(32));
+    assert_eq!(filter.account_code, "DU1234567");
+    assert_eq!(filter.time, "20250117-14:30:00", "time is converted to UTC");
+    assert_eq!(filter.symbol, "AAPL");
+    assert_eq!(filter.security_type, "OPT");
+    assert_eq!(filter.exchange, "CBOE");
+    assert_eq!(filter.side, "SELL");
+}
+
+#[test]
+fn test_default_builder_matches_default_filter() {
+    let filter = ExecutionFilter::builder().build();
+    let message = encode_executions(server_versions::PARAMETRIZED_DAYS_OF_EXECUTIONS, 9000, &filter).unwrap();
+    let expected = encode_executions(server_versions::PARAMETRIZED_DAYS_OF_EXECUTIONS, 9000, &ExecutionFilter::default()).unwrap();
+    assert_eq!(message.encode_simple(), expected.encode_simple());
+}
+
+#[test]
+fn test_builder_date_filters() {
+    let filter = ExecutionFilter::builder()
+        .last_n_days(3)
+        .on_date(date!(2025 - 01 - 16))
+        .on_date(date!(2025 - 01 - 17))
+        .side(Action::Buy)
+        .build();
+
+    let message = encode_executions(server_versions::PARAMETRIZED_DAYS_OF_EXECUTIONS, 9000, &filter).unwrap();
+    assert_eq!(message.encode_simple(), "7|3|9000|||||||BUY|3|2|20250116|20250117|");
+}
@@ -0,0 +1,98 @@
+//! Asynchronous reconciliation of an order journal
+
+use std::sync::Mutex;
+
+use log::debug;
+use tokio::sync::mpsc;
+
+use super::{BrokerSnapshot, OrderJournal, ReconciliationEvent};
+use crate::orders::{completed_orders, executions, open_orders, ExecutionFilter, Executions, Orders};
+use crate::subscriptions::Subscription;
+use crate::{Client, Error};
+
+/// Requests open orders, completed orders and executions matching `filter`, then reconciles them with `journal`.
+pub(crate) async fn reconcile(
+    client: &Client,
+    journal: &Mutex<OrderJournal>,
+    filter: ExecutionFilter,
+) -> Result<Subscription<ReconciliationEvent>, Error> {
+    // The journal is locked only once TWS has answered, so the connection can keep recording into it.
+    let snapshot = broker_snapshot(client, filter).await?;
+    let events = journal.lock()?.reconcile(&snapshot);
+
+    let (sender, receiver) = mpsc::unbounded_channel();
+    for event in events {
+        let _ = sender.send(Ok(event));
+    }
+    Ok(Subscription::new(receiver))
+}
+
+async fn broker_snapshot(client: &Client, filter: ExecutionFilter) -> Result<BrokerSnapshot, Error> {
+    let mut snapshot = BrokerSnapshot::default();
+
+    let mut subscription = open_orders(client).await?;
+    while let Some(item) = subscription.next().await {
+        match item? {
+            Orders::OrderData(order_data) => snapshot.open_orders.push(order_data),
+            Orders::OrderStatus(_) => {}
+            Orders::Notice(notice) => debug!("notice while requesting open orders: {notice}"),
+        }
+    }
+
+    let mut subscription = completed_orders(client, false).await?;
+    while let Some(item) = subscription.next().await {
+        match item? {
+            Orders::OrderData(order_data) => snapshot.completed_orders.push(order_data),
+            Orders::OrderStatus(_) => {}
+            Orders::Notice(notice) => debug!("notice while requesting completed orders: {notice}"),
+        }
+    }
+
+    let mut subscription = executions(client, filter).await?;
+    while let Some(item) = subscription.next().await {
+        match item? {
+            Executions::ExecutionData(execution) => snapshot.executions.push(execution),
+            Executions::CommissionReport(_) => {}
+            Executions::Notice(notice) => debug!("notice while requesting executions: {notice}"),
+        }
+    }
+
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex, RwLock};
+
+    use super::*;
+    use crate::contracts::Contract;
+    use crate::orders::Order;
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+
+    #[tokio::test]
+    async fn test_reconcile() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: super::super::tests::BROKER_RESPONSES.iter().map(|s| s.to_string()).collect(),
+        });
+        let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+        let mut journal = OrderJournal::in_memory();
+        journal.record_submitted(100, 7, &Contract::default(), &Order::default()).unwrap();
+
+        let mut subscription = reconcile(&client, &Mutex::new(journal), ExecutionFilter::default())
+            .await
+            .expect("reconcile failed");
+        let mut events = Vec::new();
+        while let Some(event) = subscription.next().await {
+            events.push(event.expect("unexpected error"));
+        }
+        super::super::tests::assert_stub_events(&events);
+
+        let request_messages = message_bus.request_messages();
+        assert_eq!(request_messages.len(), 3);
+        assert_eq!(request_messages[0].encode_simple(), "5|1|");
+        assert_eq!(request_messages[1].encode_simple(), "99|0|");
+    }
+}
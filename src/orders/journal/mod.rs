@@ -0,0 +1,440 @@
+//! Persistent order journal and startup reconciliation.
+//!
+//! An [OrderJournal] records every order submitted by the application, the status updates it
+//! receives and each execution keyed by its execution id. When backed by a file the journal is
+//! an append-only JSON-lines log, so the state survives a crash and is rebuilt on [OrderJournal::open].
+//!
+//! After reconnecting, the journal is reconciled against what TWS reports through
+//! `open_orders()`, `completed_orders()` and `executions()`. The resulting
+//! [ReconciliationEvent]s describe fills that were missed while offline, orders that
+//! completed or vanished, and open orders that were placed from another session.
+//!
+//! Attach a journal with [ConnectionOptions::order_journal](crate::ConnectionOptions::order_journal)
+//! and the client records every order sent with `place_order()` or `submit_order()`, along with the
+//! order status and execution messages TWS sends to the connection. Without it, record submissions
+//! with [OrderJournal::record_submitted] and their events with [OrderJournal::record_place_order]
+//! or [OrderJournal::record_update].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::sync::{Arc, Mutex};
+//!
+//! use ibapi::orders::journal::{OrderJournal, ReconciliationEvent};
+//! use ibapi::orders::ExecutionFilter;
+//! use ibapi::{Client, ConnectionOptions};
+//!
+//! # #[cfg(feature = "async")]
+//! #[tokio::main]
+//! async fn main() {
+//!     let journal = Arc::new(Mutex::new(OrderJournal::open("orders.journal").expect("failed to open journal")));
+//!     let options = ConnectionOptions::default().order_journal(journal.clone());
+//!     let client = Client::connect_with_options("127.0.0.1:4002", 100, options)
+//!         .await
+//!         .expect("connection failed");
+//!
+//!     let mut events = client
+//!         .reconcile_orders(&journal, ExecutionFilter::default())
+//!         .await
+//!         .expect("reconciliation failed");
+//!
+//!     while let Some(event) = events.next().await {
+//!         match event.expect("reconciliation failed") {
+//!             ReconciliationEvent::MissedFill(execution) => {
+//!                 println!("missed fill: {}", execution.execution.execution_id);
+//!                 journal.lock().unwrap().record_execution(&execution).expect("failed to journal execution");
+//!             }
+//!             other => println!("{other:?}"),
+//!         }
+//!     }
+//! }
+//! # #[cfg(not(feature = "async"))]
+//! # fn main() {}
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use super::common::decoders;
+use super::{ExecutionData, Order, OrderData, OrderStatus, OrderUpdate, PlaceOrder};
+use crate::contracts::Contract;
+use crate::messages::{IncomingMessages, ResponseMessage};
+use crate::transport::routing::UNSPECIFIED_REQUEST_ID;
+use crate::Error;
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
+
+#[cfg(feature = "async")]
+pub(crate) mod r#async;
+
+#[cfg(feature = "sync")]
+/// Blocking reconciliation stream returned by the synchronous client.
+pub mod blocking {
+    pub use super::sync::Reconciliation;
+}
+
+#[cfg(all(feature = "sync", not(feature = "async")))]
+pub use sync::Reconciliation;
+
+/// Order statuses after which TWS no longer reports an order as open.
+const TERMINAL_STATUSES: &[&str] = &["Filled", "Cancelled", "ApiCancelled", "Inactive"];
+
+/// A single record in the journal log.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
+pub enum JournalEntry {
+    /// An order was submitted by this application.
+    Submitted {
+        /// Id of the client that placed the order.
+        #[serde(default)]
+        client_id: i32,
+        /// Order id used when the order was placed.
+        order_id: i32,
+        /// Contract the order was placed for.
+        contract: Contract,
+        /// The order as submitted.
+        order: Order,
+    },
+    /// TWS reported a status change for an order.
+    Status(OrderStatus),
+    /// TWS reported an execution.
+    Execution(ExecutionData),
+}
+
+/// Journaled view of an order submitted by this application.
+#[derive(Clone, Debug)]
+pub struct JournaledOrder {
+    /// Id of the client that placed the order.
+    pub client_id: i32,
+    /// Order id used when the order was placed.
+    pub order_id: i32,
+    /// Contract the order was placed for.
+    pub contract: Contract,
+    /// The order as submitted.
+    pub order: Order,
+    /// Most recent status received for the order, if any.
+    pub status: Option<OrderStatus>,
+}
+
+impl JournaledOrder {
+    /// Permanent id assigned by TWS, or 0 if no status has been journaled yet.
+    pub fn perm_id(&self) -> i32 {
+        match &self.status {
+            Some(status) if status.perm_id != 0 => status.perm_id,
+            _ => self.order.perm_id,
+        }
+    }
+
+    /// Returns true while the last journaled status leaves the order working.
+    pub fn is_active(&self) -> bool {
+        match &self.status {
+            Some(status) => !TERMINAL_STATUSES.contains(&status.status.as_str()),
+            None => true,
+        }
+    }
+
+    fn matches(&self, order_data: &OrderData) -> bool {
+        let perm_id = self.perm_id();
+        if perm_id != 0 && order_data.order.perm_id != 0 {
+            return perm_id == order_data.order.perm_id;
+        }
+        // Order ids are only unique per client.
+        order_data.order_id != 0 && order_data.order.client_id == self.client_id && order_data.order_id == self.order_id
+    }
+}
+
+/// State reported by TWS that a journal is reconciled against.
+#[derive(Clone, Debug, Default)]
+pub struct BrokerSnapshot {
+    /// Orders returned by `open_orders()`.
+    pub open_orders: Vec<OrderData>,
+    /// Orders returned by `completed_orders()`.
+    pub completed_orders: Vec<OrderData>,
+    /// Executions returned by `executions()`.
+    pub executions: Vec<ExecutionData>,
+}
+
+/// Discrepancy found while reconciling a journal with TWS.
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum ReconciliationEvent {
+    /// An execution reported by TWS that was never journaled.
+    MissedFill(ExecutionData),
+    /// A journaled working order that TWS now reports as completed.
+    OrderCompleted(OrderData),
+    /// A journaled working order that TWS reports neither as open nor as completed.
+    OrderVanished(JournaledOrder),
+    /// An open order that was not submitted through this journal, e.g. placed from another session.
+    ExternalOrder(OrderData),
+}
+
+/// Record of submitted orders, status changes and executions.
+///
+/// Use [OrderJournal::open] for a journal persisted to disk or [OrderJournal::in_memory]
+/// for one that lives only as long as the process.
+#[derive(Debug, Default)]
+pub struct OrderJournal {
+    path: Option<PathBuf>,
+    file: Option<File>,
+    orders: BTreeMap<(i32, i32), JournaledOrder>,
+    executions: BTreeMap<String, ExecutionData>,
+}
+
+impl OrderJournal {
+    /// Creates a journal that is not persisted.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Opens the journal at `path`, creating the file if needed and replaying existing entries.
+    ///
+    /// A truncated final entry, as left behind by a crash during a write, is discarded.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new().read(true).append(true).create(true).open(&path)?;
+
+        let mut journal = Self::default();
+        let valid_len = journal.replay(&mut file)?;
+        if valid_len < file.metadata()?.len() {
+            warn!("discarding truncated entry at end of order journal {}", path.display());
+            file.set_len(valid_len)?;
+        }
+        file.seek(SeekFrom::End(0))?;
+
+        journal.path = Some(path);
+        journal.file = Some(file);
+        Ok(journal)
+    }
+
+    fn replay(&mut self, file: &mut File) -> Result<u64, Error> {
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        let mut valid_len = 0;
+        let mut line_number = 0;
+
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            line_number += 1;
+
+            let complete = line.ends_with('\n');
+            if line.trim().is_empty() {
+                if complete {
+                    valid_len += read as u64;
+                }
+                continue;
+            }
+
+            match serde_json::from_str::<JournalEntry>(line.trim_end()) {
+                Ok(entry) if complete => {
+                    self.apply(entry);
+                    valid_len += read as u64;
+                }
+                // Last line was cut short by a crash.
+                Ok(_) => break,
+                Err(_) if !complete => break,
+                Err(err) => return Err(Error::Parse(line_number, line.trim_end().to_string(), err.to_string())),
+            }
+        }
+
+        Ok(valid_len)
+    }
+
+    /// Path of the backing file, if the journal is persisted.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Records an order submitted by client `client_id` with `order_id`.
+    pub fn record_submitted(&mut self, client_id: i32, order_id: i32, contract: &Contract, order: &Order) -> Result<(), Error> {
+        self.record(JournalEntry::Submitted {
+            client_id,
+            order_id,
+            contract: contract.clone(),
+            order: order.clone(),
+        })
+    }
+
+    /// Records a status update. Updates for orders that were not submitted through the journal are ignored.
+    pub fn record_status(&mut self, status: &OrderStatus) -> Result<(), Error> {
+        if !self.orders.contains_key(&(status.client_id, status.order_id)) {
+            return Ok(());
+        }
+        self.record(JournalEntry::Status(status.clone()))
+    }
+
+    /// Records an execution. Executions already journaled under the same execution id are ignored.
+    pub fn record_execution(&mut self, execution: &ExecutionData) -> Result<(), Error> {
+        if self.has_execution(&execution.execution.execution_id) {
+            return Ok(());
+        }
+        self.record(JournalEntry::Execution(execution.clone()))
+    }
+
+    /// Records the status and execution events from an `order_update_stream()` item.
+    pub fn record_update(&mut self, update: &OrderUpdate) -> Result<(), Error> {
+        match update {
+            OrderUpdate::OrderStatus(status) => self.record_status(status),
+            OrderUpdate::ExecutionData(execution) => self.record_execution(execution),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the status and execution events from a `place_order()` subscription item.
+    pub fn record_place_order(&mut self, event: &PlaceOrder) -> Result<(), Error> {
+        match event {
+            PlaceOrder::OrderStatus(status) => self.record_status(status),
+            PlaceOrder::ExecutionData(execution) => self.record_execution(execution),
+            _ => Ok(()),
+        }
+    }
+
+    fn record(&mut self, entry: JournalEntry) -> Result<(), Error> {
+        if let Some(file) = &mut self.file {
+            let mut line = serde_json::to_string(&entry).map_err(std::io::Error::from)?;
+            line.push('\n');
+            file.write_all(line.as_bytes())?;
+            file.flush()?;
+        }
+        self.apply(entry);
+        Ok(())
+    }
+
+    fn apply(&mut self, entry: JournalEntry) {
+        match entry {
+            JournalEntry::Submitted {
+                client_id,
+                order_id,
+                contract,
+                order,
+            } => {
+                self.orders.insert(
+                    (client_id, order_id),
+                    JournaledOrder {
+                        client_id,
+                        order_id,
+                        contract,
+                        order,
+                        status: None,
+                    },
+                );
+            }
+            JournalEntry::Status(status) => {
+                if let Some(order) = self.orders.get_mut(&(status.client_id, status.order_id)) {
+                    order.status = Some(status);
+                }
+            }
+            JournalEntry::Execution(execution) => {
+                self.executions.insert(execution.execution.execution_id.clone(), execution);
+            }
+        }
+    }
+
+    /// Returns the journaled order placed by client `client_id` with `order_id`.
+    pub fn order(&self, client_id: i32, order_id: i32) -> Option<&JournaledOrder> {
+        self.orders.get(&(client_id, order_id))
+    }
+
+    /// Iterates over all journaled orders by client id and order id.
+    pub fn orders(&self) -> impl Iterator<Item = &JournaledOrder> {
+        self.orders.values()
+    }
+
+    /// Iterates over journaled orders that are still working.
+    pub fn active_orders(&self) -> impl Iterator<Item = &JournaledOrder> {
+        self.orders.values().filter(|order| order.is_active())
+    }
+
+    /// Iterates over all journaled executions.
+    pub fn executions(&self) -> impl Iterator<Item = &ExecutionData> {
+        self.executions.values()
+    }
+
+    /// Returns true if an execution with `execution_id` has been journaled.
+    pub fn has_execution(&self, execution_id: &str) -> bool {
+        self.executions.contains_key(execution_id)
+    }
+
+    /// Compares the journal with a snapshot of broker state.
+    ///
+    /// Events are returned in the order: missed fills, completed orders, vanished orders and
+    /// external orders. The journal itself is not modified; record missed fills with
+    /// [OrderJournal::record_execution] once they have been processed.
+    pub fn reconcile(&self, snapshot: &BrokerSnapshot) -> Vec<ReconciliationEvent> {
+        let mut events = Vec::new();
+
+        let mut seen = HashSet::new();
+        for execution in &snapshot.executions {
+            let execution_id = &execution.execution.execution_id;
+            if !self.has_execution(execution_id) && seen.insert(execution_id) {
+                events.push(ReconciliationEvent::MissedFill(execution.clone()));
+            }
+        }
+
+        let mut vanished = Vec::new();
+        for journaled in self.active_orders() {
+            if snapshot.open_orders.iter().any(|open| journaled.matches(open)) {
+                continue;
+            }
+            match snapshot.completed_orders.iter().find(|completed| journaled.matches(completed)) {
+                Some(completed) => events.push(ReconciliationEvent::OrderCompleted(completed.clone())),
+                None => vanished.push(ReconciliationEvent::OrderVanished(journaled.clone())),
+            }
+        }
+        events.extend(vanished);
+
+        for open in &snapshot.open_orders {
+            if !self.orders.values().any(|journaled| journaled.matches(open)) {
+                events.push(ReconciliationEvent::ExternalOrder(open.clone()));
+            }
+        }
+
+        events
+    }
+}
+
+/// Records an order about to be sent in the client's journal, if one is attached.
+pub(crate) fn record_submission(
+    journal: Option<&Arc<Mutex<OrderJournal>>>,
+    client_id: i32,
+    order_id: i32,
+    contract: &Contract,
+    order: &Order,
+) -> Result<(), Error> {
+    match journal {
+        Some(journal) => journal.lock()?.record_submitted(client_id, order_id, contract, order),
+        None => Ok(()),
+    }
+}
+
+/// Records order status and execution messages received on a connection with an attached journal.
+pub(crate) fn record_message(journal: &Mutex<OrderJournal>, server_version: i32, message: &ResponseMessage) {
+    let result = match message.message_type() {
+        IncomingMessages::OrderStatus => {
+            decoders::decode_order_status(server_version, &mut message.clone()).and_then(|status| journal.lock()?.record_status(&status))
+        }
+        // Executions requested with executions() are what the journal is reconciled against, so only live fills are recorded.
+        IncomingMessages::ExecutionData if message.request_id() == Some(UNSPECIFIED_REQUEST_ID) => {
+            decoders::decode_execution_data(server_version, &mut message.clone()).and_then(|execution| journal.lock()?.record_execution(&execution))
+        }
+        _ => return,
+    };
+
+    if let Err(err) = result {
+        warn!("failed to journal {:?} message: {err}", message.message_type());
+    }
+}
+
+#[cfg(test)]
+mod tests;
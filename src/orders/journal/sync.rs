@@ -0,0 +1,110 @@
+//! Synchronous reconciliation of an order journal
+
+use std::sync::Mutex;
+
+use log::debug;
+
+use super::{BrokerSnapshot, OrderJournal, ReconciliationEvent};
+use crate::client::sync::Client;
+use crate::orders::blocking::{completed_orders, executions, open_orders};
+use crate::orders::{ExecutionFilter, Executions, Orders};
+use crate::Error;
+
+/// Blocking stream of the [ReconciliationEvent]s returned by `Client::reconcile_orders()`.
+///
+/// Events are yielded in the order described by [OrderJournal::reconcile].
+#[derive(Debug)]
+pub struct Reconciliation {
+    events: std::vec::IntoIter<ReconciliationEvent>,
+}
+
+impl Iterator for Reconciliation {
+    type Item = ReconciliationEvent;
+
+    fn next(&mut self) -> Option<ReconciliationEvent> {
+        self.events.next()
+    }
+}
+
+/// Requests open orders, completed orders and executions matching `filter`, then reconciles them with `journal`.
+pub(crate) fn reconcile(client: &Client, journal: &Mutex<OrderJournal>, filter: ExecutionFilter) -> Result<Reconciliation, Error> {
+    // The journal is locked only once TWS has answered, so the connection can keep recording into it.
+    let snapshot = broker_snapshot(client, filter)?;
+    let events = journal.lock()?.reconcile(&snapshot);
+    Ok(Reconciliation { events: events.into_iter() })
+}
+
+fn broker_snapshot(client: &Client, filter: ExecutionFilter) -> Result<BrokerSnapshot, Error> {
+    let mut snapshot = BrokerSnapshot::default();
+
+    let subscription = open_orders(client)?;
+    for item in &subscription {
+        match item {
+            Orders::OrderData(order_data) => snapshot.open_orders.push(order_data),
+            Orders::OrderStatus(_) => {}
+            Orders::Notice(notice) => debug!("notice while requesting open orders: {notice}"),
+        }
+    }
+    if let Some(err) = subscription.error() {
+        return Err(err);
+    }
+
+    let subscription = completed_orders(client, false)?;
+    for item in &subscription {
+        match item {
+            Orders::OrderData(order_data) => snapshot.completed_orders.push(order_data),
+            Orders::OrderStatus(_) => {}
+            Orders::Notice(notice) => debug!("notice while requesting completed orders: {notice}"),
+        }
+    }
+    if let Some(err) = subscription.error() {
+        return Err(err);
+    }
+
+    let subscription = executions(client, filter)?;
+    for item in &subscription {
+        match item {
+            Executions::ExecutionData(execution) => snapshot.executions.push(execution),
+            Executions::CommissionReport(_) => {}
+            Executions::Notice(notice) => debug!("notice while requesting executions: {notice}"),
+        }
+    }
+    if let Some(err) = subscription.error() {
+        return Err(err);
+    }
+
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex, RwLock};
+
+    use super::*;
+    use crate::contracts::Contract;
+    use crate::orders::Order;
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+
+    #[test]
+    fn test_reconcile() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: super::super::tests::BROKER_RESPONSES.iter().map(|s| s.to_string()).collect(),
+        });
+        let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+        let mut journal = OrderJournal::in_memory();
+        journal.record_submitted(100, 7, &Contract::default(), &Order::default()).unwrap();
+
+        let events: Vec<_> = reconcile(&client, &Mutex::new(journal), ExecutionFilter::default())
+            .expect("reconcile failed")
+            .collect();
+        super::super::tests::assert_stub_events(&events);
+
+        let request_messages = message_bus.request_messages();
+        assert_eq!(request_messages.len(), 3);
+        assert_eq!(request_messages[0].encode_simple(), "5|1|");
+        assert_eq!(request_messages[1].encode_simple(), "99|0|");
+    }
+}
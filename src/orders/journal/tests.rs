@@ -0,0 +1,270 @@
+use std::io::Write;
+
+use super::*;
+use crate::orders::{order_builder, Action, Execution};
+
+/// Responses used by the client-level reconciliation tests: open order 13, its status and a fill for it.
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(super) const BROKER_RESPONSES: &[&str] = &[
+    "5|13|76792991|TSLA|STK||0|?||SMART|USD|TSLA|NMS|BUY|100|MKT|0.0|0.0|DAY||DU1234567||0||100|1376327563|0|0|0||1376327563.0/DU1234567/100||||||||||0||-1|0||||||2147483647|0|0|0||3|0|0||0|0||0|None||0||||?|0|0||0|0||||||0|0|0|2147483647|2147483647|||0||IB|0|0||0|0|PreSubmitted|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308||||||0|0|0|None|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|1.7976931348623157E308|0||||0|1|0|0|0|||0||",
+    "3|13|PreSubmitted|0|100|0|1376327563|0|0|100||0||",
+    "53|1|",
+    "11|-1|13|76792991|TSLA|STK||0.0|||ISLAND|USD|TSLA|NMS|00025b46.63f8f39c.01.01|20230224  12:04:56|DU1234567|ISLAND|BOT|100|196.52|1376327563|100|0|100|196.52|||||2||",
+    "55|1|-1|",
+];
+
+/// Checks the events produced by reconciling a journal holding only order 7 against [BROKER_RESPONSES].
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(super) fn assert_stub_events(events: &[ReconciliationEvent]) {
+    assert_eq!(events.len(), 3, "unexpected events: {events:?}");
+    assert!(
+        matches!(&events[0], ReconciliationEvent::MissedFill(execution) if execution.execution.execution_id == "00025b46.63f8f39c.01.01"),
+        "{:?}",
+        events[0]
+    );
+    assert!(
+        matches!(&events[1], ReconciliationEvent::OrderVanished(order) if order.order_id == 7),
+        "{:?}",
+        events[1]
+    );
+    assert!(
+        matches!(&events[2], ReconciliationEvent::ExternalOrder(order_data) if order_data.order_id == 13),
+        "{:?}",
+        events[2]
+    );
+}
+
+const CLIENT_ID: i32 = 100;
+
+fn execution(order_id: i32, execution_id: &str) -> ExecutionData {
+    ExecutionData {
+        execution: Execution {
+            order_id,
+            execution_id: execution_id.to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn order_data(order_id: i32, perm_id: i32) -> OrderData {
+    let mut order = order_builder::limit_order(Action::Buy, 100.0, 50.0);
+    order.order_id = order_id;
+    order.perm_id = perm_id;
+    order.client_id = CLIENT_ID;
+    OrderData {
+        order_id,
+        order,
+        ..Default::default()
+    }
+}
+
+fn status(order_id: i32, perm_id: i32, status: &str) -> OrderStatus {
+    OrderStatus {
+        order_id,
+        perm_id,
+        client_id: CLIENT_ID,
+        status: status.to_string(),
+        ..Default::default()
+    }
+}
+
+fn journal_with_orders(order_ids: &[i32]) -> OrderJournal {
+    let mut journal = OrderJournal::in_memory();
+    for order_id in order_ids {
+        let order = order_builder::limit_order(Action::Buy, 100.0, 50.0);
+        journal
+            .record_submitted(CLIENT_ID, *order_id, &Contract::stock("AAPL").build(), &order)
+            .unwrap();
+    }
+    journal
+}
+
+#[test]
+fn test_reconcile_reports_missed_fills() {
+    let mut journal = journal_with_orders(&[1]);
+    journal.record_execution(&execution(1, "0001.01")).unwrap();
+
+    let snapshot = BrokerSnapshot {
+        open_orders: vec![order_data(1, 0)],
+        executions: vec![execution(1, "0001.01"), execution(1, "0001.02"), execution(1, "0001.02")],
+        ..Default::default()
+    };
+
+    let events = journal.reconcile(&snapshot);
+    assert_eq!(events.len(), 1, "{events:?}");
+    assert!(matches!(&events[0], ReconciliationEvent::MissedFill(data) if data.execution.execution_id == "0001.02"));
+}
+
+#[test]
+fn test_reconcile_completed_and_vanished_orders() {
+    let mut journal = journal_with_orders(&[1, 2, 3]);
+    journal.record_status(&status(1, 1001, "Submitted")).unwrap();
+    journal.record_status(&status(2, 1002, "Submitted")).unwrap();
+
+    let snapshot = BrokerSnapshot {
+        open_orders: vec![order_data(1, 1001)],
+        // Completed orders are reported with a zero order id, so they must match on perm id.
+        completed_orders: vec![order_data(0, 1002)],
+        ..Default::default()
+    };
+
+    let events = journal.reconcile(&snapshot);
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert!(matches!(&events[0], ReconciliationEvent::OrderCompleted(data) if data.order.perm_id == 1002));
+    assert!(matches!(&events[1], ReconciliationEvent::OrderVanished(order) if order.order_id == 3));
+}
+
+#[test]
+fn test_reconcile_ignores_terminal_orders() {
+    let mut journal = journal_with_orders(&[1, 2]);
+    journal.record_status(&status(1, 1001, "Filled")).unwrap();
+    journal.record_status(&status(2, 1002, "Cancelled")).unwrap();
+
+    assert_eq!(journal.active_orders().count(), 0);
+    assert!(journal.reconcile(&BrokerSnapshot::default()).is_empty());
+}
+
+#[test]
+fn test_reconcile_reports_external_orders() {
+    let mut journal = journal_with_orders(&[1]);
+    journal.record_status(&status(1, 1001, "Submitted")).unwrap();
+
+    let snapshot = BrokerSnapshot {
+        open_orders: vec![order_data(1, 1001), order_data(1, 2001), order_data(5, 0)],
+        ..Default::default()
+    };
+
+    let events = journal.reconcile(&snapshot);
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert!(matches!(&events[0], ReconciliationEvent::ExternalOrder(data) if data.order.perm_id == 2001));
+    assert!(matches!(&events[1], ReconciliationEvent::ExternalOrder(data) if data.order_id == 5));
+}
+
+#[test]
+fn test_reconcile_matches_order_ids_per_client() {
+    let journal = journal_with_orders(&[1]);
+
+    let mut other_client = order_data(1, 0);
+    other_client.order.client_id = CLIENT_ID + 1;
+    let snapshot = BrokerSnapshot {
+        open_orders: vec![other_client],
+        ..Default::default()
+    };
+
+    let events = journal.reconcile(&snapshot);
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert!(matches!(&events[0], ReconciliationEvent::OrderVanished(order) if order.order_id == 1));
+    assert!(matches!(&events[1], ReconciliationEvent::ExternalOrder(data) if data.order.client_id == CLIENT_ID + 1));
+}
+
+#[test]
+fn test_record_ignores_unknown_and_duplicate_events() {
+    let mut journal = journal_with_orders(&[1]);
+
+    journal.record_update(&OrderUpdate::OrderStatus(status(9, 0, "Submitted"))).unwrap();
+    assert!(journal.order(CLIENT_ID, 9).is_none());
+
+    let mut other_client = status(1, 2001, "Filled");
+    other_client.client_id = CLIENT_ID + 1;
+    journal.record_status(&other_client).unwrap();
+    assert!(journal.order(CLIENT_ID, 1).unwrap().status.is_none());
+
+    journal.record_place_order(&PlaceOrder::OrderStatus(status(1, 1001, "Filled"))).unwrap();
+    assert_eq!(journal.order(CLIENT_ID, 1).unwrap().perm_id(), 1001);
+    assert!(!journal.order(CLIENT_ID, 1).unwrap().is_active());
+
+    journal.record_execution(&execution(1, "0001.01")).unwrap();
+    journal.record_update(&OrderUpdate::ExecutionData(execution(1, "0001.01"))).unwrap();
+    assert_eq!(journal.executions().count(), 1);
+    assert!(journal.has_execution("0001.01"));
+}
+
+#[test]
+fn test_journal_replays_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.journal");
+
+    {
+        let mut journal = OrderJournal::open(&path).unwrap();
+        assert_eq!(journal.path(), Some(path.as_path()));
+        journal
+            .record_submitted(
+                CLIENT_ID,
+                1,
+                &Contract::stock("AAPL").build(),
+                &order_builder::market_order(Action::Sell, 10.0),
+            )
+            .unwrap();
+        journal.record_status(&status(1, 1001, "Submitted")).unwrap();
+        journal.record_execution(&execution(1, "0001.01")).unwrap();
+    }
+
+    let mut journal = OrderJournal::open(&path).unwrap();
+    let order = journal.order(CLIENT_ID, 1).expect("order not replayed");
+    assert_eq!(order.contract.symbol.to_string(), "AAPL");
+    assert_eq!(order.order.action, Action::Sell);
+    assert_eq!(order.order.order_type, "MKT");
+    assert_eq!(order.perm_id(), 1001);
+    assert!(journal.has_execution("0001.01"));
+
+    journal.record_submitted(CLIENT_ID, 2, &Contract::default(), &Order::default()).unwrap();
+    drop(journal);
+
+    let journal = OrderJournal::open(&path).unwrap();
+    assert_eq!(journal.orders().count(), 2);
+}
+
+#[test]
+fn test_journal_discards_truncated_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.journal");
+
+    {
+        let mut journal = OrderJournal::open(&path).unwrap();
+        journal.record_submitted(CLIENT_ID, 1, &Contract::default(), &Order::default()).unwrap();
+    }
+    let complete_len = std::fs::metadata(&path).unwrap().len();
+
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(br#"{"type":"status","order_id":1,"sta"#).unwrap();
+    drop(file);
+
+    let mut journal = OrderJournal::open(&path).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), complete_len);
+    assert!(journal.order(CLIENT_ID, 1).unwrap().status.is_none());
+
+    journal.record_status(&status(1, 1001, "Submitted")).unwrap();
+    drop(journal);
+
+    let journal = OrderJournal::open(&path).unwrap();
+    assert_eq!(journal.order(CLIENT_ID, 1).unwrap().perm_id(), 1001);
+}
+
+#[test]
+fn test_journal_rejects_corrupt_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("orders.journal");
+    std::fs::write(&path, "not json\n").unwrap();
+
+    let result = OrderJournal::open(&path);
+    assert!(matches!(result, Err(Error::Parse(1, _, _))), "{result:?}");
+}
+
+#[test]
+fn test_record_message_skips_requested_executions() {
+    let journal = Mutex::new(journal_with_orders(&[13]));
+    let server_version = crate::server_versions::SIZE_RULES;
+
+    let status = ResponseMessage::from_simple("3|13|Submitted|0|100|0|1376327563|0|0|100||0||");
+    record_message(&journal, server_version, &status);
+    let requested = ResponseMessage::from_simple("11|9000|13|76792991|TSLA|STK||0.0|||ISLAND|USD|TSLA|NMS|0001.01|20230224  12:04:56|DU1234567|ISLAND|BOT|100|196.52|1376327563|100|0|100|196.52|||||2||");
+    record_message(&journal, server_version, &requested);
+    let live = ResponseMessage::from_simple("11|-1|13|76792991|TSLA|STK||0.0|||ISLAND|USD|TSLA|NMS|0001.02|20230224  12:04:56|DU1234567|ISLAND|BOT|100|196.52|1376327563|100|0|100|196.52|||||2||");
+    record_message(&journal, server_version, &live);
+
+    let journal = journal.lock().unwrap();
+    assert_eq!(journal.order(CLIENT_ID, 13).unwrap().perm_id(), 1376327563);
+    assert!(!journal.has_execution("0001.01"));
+    assert!(journal.has_execution("0001.02"));
+}
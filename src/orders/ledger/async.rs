@@ -0,0 +1,40 @@
+//! Asynchronous fill requests
+
+use super::Fill;
+use crate::orders::{executions, ExecutionFilter};
+use crate::{Client, Error};
+
+/// Requests executions matching `filter` and joins them with their commission reports.
+pub(crate) async fn fills(client: &Client, filter: ExecutionFilter) -> Result<Vec<Fill>, Error> {
+    let mut subscription = executions(client, filter).await?;
+    let mut items = Vec::new();
+    while let Some(item) = subscription.next().await {
+        items.push(item?);
+    }
+    Ok(Fill::join(items))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use super::*;
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+
+    #[tokio::test]
+    async fn test_fills() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: super::super::tests::EXECUTION_RESPONSES.iter().map(|s| s.to_string()).collect(),
+        });
+        let client = Client::stubbed(message_bus.clone(), server_versions::SIZE_RULES);
+
+        let filter = ExecutionFilter::builder().account("DU1234567").build();
+        let fills = fills(&client, filter).await.expect("request failed");
+        super::super::tests::assert_stub_fills(&fills);
+
+        let request_messages = message_bus.request_messages();
+        assert_eq!(request_messages.len(), 1);
+    }
+}
@@ -0,0 +1,537 @@
+//! Fills joined with their commissions, and tax lot accounting over them.
+//!
+//! TWS reports executions and commissions as separate messages linked by execution id.
+//! [Fill::join] pairs them up, and a [Ledger] applies the resulting fills to FIFO or LIFO
+//! tax lots per account and contract. Fills, open lots and closed lots can be exported as
+//! CSV or JSON for end-of-day reconciliation.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ibapi::orders::ledger::{ExportFormat, Ledger, LotMethod};
+//! use ibapi::orders::ExecutionFilter;
+//! use ibapi::Client;
+//!
+//! # #[cfg(feature = "async")]
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+//!
+//!     let filter = ExecutionFilter::builder().account("DU1234567").build();
+//!     let fills = client.fills(filter).await.expect("request failed");
+//!
+//!     let mut ledger = Ledger::new(LotMethod::Fifo);
+//!     ledger.apply_all(&fills).expect("unexpected fill");
+//!
+//!     println!("realized P&L: {:.2}", ledger.realized_pnl());
+//!     ledger.export_closed_lots(std::io::stdout(), ExportFormat::Csv).expect("export failed");
+//! }
+//! # #[cfg(not(feature = "async"))]
+//! # fn main() {}
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::{CommissionReport, Execution, ExecutionData, Executions};
+use crate::contracts::Contract;
+use crate::decimal::to_f64;
+use crate::{Decimal, Error};
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
+
+#[cfg(feature = "async")]
+pub(crate) mod r#async;
+
+/// An execution joined with its commission report.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Fill {
+    /// Contract that was executed.
+    pub contract: Contract,
+    /// Execution details.
+    pub execution: Execution,
+    /// Commission charged, or `None` if no commission report was received.
+    pub commission: Option<f64>,
+    /// Currency the commission is reported in.
+    pub commission_currency: String,
+    /// Realized P&L reported by TWS for closing executions.
+    pub realized_pnl: Option<f64>,
+    /// Income return reported for bond executions.
+    pub yields: Option<f64>,
+}
+
+impl Fill {
+    /// Creates a fill for an execution without commission details.
+    pub fn new(execution: ExecutionData) -> Self {
+        Fill {
+            contract: execution.contract,
+            execution: execution.execution,
+            ..Default::default()
+        }
+    }
+
+    /// Joins execution and commission messages, as returned by `executions()`, by execution id.
+    ///
+    /// Fills are returned in the order their executions were received, whether the commission
+    /// report came before or after the execution. A corrected execution, whose id differs from an
+    /// earlier one only after the final period, replaces the execution it corrects.
+    pub fn join(items: impl IntoIterator<Item = Executions>) -> Vec<Fill> {
+        let mut fills: Vec<Fill> = Vec::new();
+        let mut by_base_id: HashMap<String, usize> = HashMap::new();
+        let mut reports: HashMap<String, CommissionReport> = HashMap::new();
+
+        for item in items {
+            match item {
+                Executions::ExecutionData(execution) => {
+                    let mut fill = Fill::new(execution);
+                    if let Some(report) = reports.remove(&fill.execution.execution_id) {
+                        fill.set_commission(&report);
+                    }
+                    let base_id = correction_base(&fill.execution.execution_id).to_string();
+                    match by_base_id.get(&base_id) {
+                        Some(&index) => fills[index] = fill,
+                        None => {
+                            by_base_id.insert(base_id, fills.len());
+                            fills.push(fill);
+                        }
+                    }
+                }
+                Executions::CommissionReport(report) => match fills.iter_mut().find(|fill| fill.execution.execution_id == report.execution_id) {
+                    Some(fill) => fill.set_commission(&report),
+                    None => {
+                        reports.insert(report.execution_id.clone(), report);
+                    }
+                },
+                Executions::Notice(_) => {}
+            }
+        }
+
+        fills
+    }
+
+    fn set_commission(&mut self, report: &CommissionReport) {
+        self.commission = Some(report.commission);
+        self.commission_currency = report.currency.clone();
+        self.realized_pnl = report.realized_pnl;
+        self.yields = report.yields;
+    }
+
+    /// Returns true for executions that bought (`BOT`).
+    pub fn is_buy(&self) -> bool {
+        self.execution.side == "BOT"
+    }
+
+    /// Returns true for executions that sold (`SLD`).
+    pub fn is_sell(&self) -> bool {
+        self.execution.side == "SLD"
+    }
+}
+
+/// Execution id with the correction suffix (the digits after the final period) removed.
+fn correction_base(execution_id: &str) -> &str {
+    execution_id.rsplit_once('.').map_or(execution_id, |(base, _)| base)
+}
+
+/// Order in which open lots are consumed by closing fills.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LotMethod {
+    /// First in, first out: the oldest lot is closed first.
+    #[default]
+    Fifo,
+    /// Last in, first out: the most recent lot is closed first.
+    Lifo,
+}
+
+/// An open position opened by a single fill.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Lot {
+    /// Account holding the lot.
+    pub account: String,
+    /// Contract id of the instrument.
+    pub contract_id: i32,
+    /// Symbol of the instrument.
+    pub symbol: String,
+    /// Execution that opened the lot.
+    pub execution_id: String,
+    /// Time of the opening execution, as reported by TWS.
+    pub time: String,
+    /// Remaining quantity: positive for long lots, negative for short lots.
+    pub quantity: Decimal,
+    /// Opening price per unit.
+    pub price: f64,
+    /// Share of the opening commission attributed to the remaining quantity.
+    pub commission: f64,
+}
+
+/// The part of a lot closed by a fill.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClosedLot {
+    /// Account that held the lot.
+    pub account: String,
+    /// Contract id of the instrument.
+    pub contract_id: i32,
+    /// Symbol of the instrument.
+    pub symbol: String,
+    /// Quantity closed: positive when a long lot was sold, negative when a short lot was bought back.
+    pub quantity: Decimal,
+    /// Execution that opened the lot.
+    pub open_execution_id: String,
+    /// Time of the opening execution.
+    pub opened: String,
+    /// Opening price per unit.
+    pub open_price: f64,
+    /// Execution that closed the lot.
+    pub close_execution_id: String,
+    /// Time of the closing execution.
+    pub closed: String,
+    /// Closing price per unit.
+    pub close_price: f64,
+    /// Opening and closing commissions attributed to the closed quantity.
+    pub commission: f64,
+    /// Realized profit or loss after commissions.
+    pub realized_pnl: f64,
+}
+
+/// Format used when exporting fills and lots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma separated values with a header row.
+    Csv,
+    /// A pretty printed JSON array.
+    Json,
+}
+
+#[derive(Debug)]
+struct Position {
+    multiplier: f64,
+    lots: VecDeque<Lot>,
+}
+
+/// Tax lots built from fills, per account and contract.
+///
+/// Fills must be applied in the order they were executed. A fill is applied once; fills with an
+/// execution id already seen are ignored.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    method: LotMethod,
+    fills: Vec<Fill>,
+    execution_ids: HashSet<String>,
+    positions: BTreeMap<(String, i32), Position>,
+    closed: Vec<ClosedLot>,
+}
+
+impl Ledger {
+    /// Creates an empty ledger that closes lots using `method`.
+    pub fn new(method: LotMethod) -> Self {
+        Ledger {
+            method,
+            ..Default::default()
+        }
+    }
+
+    /// Lot method used by the ledger.
+    pub fn method(&self) -> LotMethod {
+        self.method
+    }
+
+    /// Applies a fill, closing opposing lots and opening a lot for any remaining quantity.
+    ///
+    /// Returns `Ok(false)` if a fill with the same execution id was already applied, and an
+    /// error if the execution side is neither `BOT` nor `SLD`.
+    pub fn apply(&mut self, fill: &Fill) -> Result<bool, Error> {
+        let execution = &fill.execution;
+        if !fill.is_buy() && !fill.is_sell() {
+            return Err(Error::InvalidArgument(format!(
+                "execution {} has unknown side {:?}",
+                execution.execution_id, execution.side
+            )));
+        }
+        if !self.execution_ids.insert(execution.execution_id.clone()) {
+            return Ok(false);
+        }
+        self.fills.push(fill.clone());
+
+        let zero = Decimal::default();
+        let shares = execution.shares.abs();
+        if shares == zero {
+            return Ok(true);
+        }
+        let commission = fill.commission.unwrap_or_default();
+
+        let key = (execution.account_number.clone(), fill.contract.contract_id);
+        let position = self.positions.entry(key).or_insert_with(|| Position {
+            multiplier: fill
+                .contract
+                .multiplier
+                .parse()
+                .ok()
+                .filter(|multiplier: &f64| *multiplier > 0.0)
+                .unwrap_or(1.0),
+            lots: VecDeque::new(),
+        });
+
+        let mut remaining = shares;
+        while remaining > zero {
+            let closes = match position.lots.front() {
+                Some(lot) => (lot.quantity > zero) != fill.is_buy(),
+                None => false,
+            };
+            if !closes {
+                break;
+            }
+
+            let lot = match self.method {
+                LotMethod::Fifo => position.lots.front_mut(),
+                LotMethod::Lifo => position.lots.back_mut(),
+            }
+            .expect("position has lots");
+
+            let lot_size = lot.quantity.abs();
+            let matched = remaining.min(lot_size);
+            let long = lot.quantity > zero;
+
+            let open_commission = lot.commission * to_f64(matched) / to_f64(lot_size);
+            let close_commission = commission * to_f64(matched) / to_f64(shares);
+            let direction = if long { 1.0 } else { -1.0 };
+            let gross = (execution.price - lot.price) * to_f64(matched) * position.multiplier * direction;
+
+            self.closed.push(ClosedLot {
+                account: lot.account.clone(),
+                contract_id: lot.contract_id,
+                symbol: lot.symbol.clone(),
+                quantity: if long { matched } else { -matched },
+                open_execution_id: lot.execution_id.clone(),
+                opened: lot.time.clone(),
+                open_price: lot.price,
+                close_execution_id: execution.execution_id.clone(),
+                closed: execution.time.clone(),
+                close_price: execution.price,
+                commission: open_commission + close_commission,
+                realized_pnl: gross - open_commission - close_commission,
+            });
+
+            lot.commission -= open_commission;
+            lot.quantity = if long { lot_size - matched } else { matched - lot_size };
+            remaining -= matched;
+
+            if lot.quantity == zero {
+                match self.method {
+                    LotMethod::Fifo => position.lots.pop_front(),
+                    LotMethod::Lifo => position.lots.pop_back(),
+                };
+            }
+        }
+
+        if remaining > zero {
+            position.lots.push_back(Lot {
+                account: execution.account_number.clone(),
+                contract_id: fill.contract.contract_id,
+                symbol: fill.contract.symbol.to_string(),
+                execution_id: execution.execution_id.clone(),
+                time: execution.time.clone(),
+                quantity: if fill.is_buy() { remaining } else { -remaining },
+                price: execution.price,
+                commission: commission * to_f64(remaining) / to_f64(shares),
+            });
+        }
+
+        Ok(true)
+    }
+
+    /// Applies fills in order, stopping at the first fill that cannot be applied.
+    pub fn apply_all<'a>(&mut self, fills: impl IntoIterator<Item = &'a Fill>) -> Result<(), Error> {
+        for fill in fills {
+            self.apply(fill)?;
+        }
+        Ok(())
+    }
+
+    /// Fills applied to the ledger, in the order they were applied.
+    pub fn fills(&self) -> &[Fill] {
+        &self.fills
+    }
+
+    /// Open lots across all accounts and contracts, oldest first within each position.
+    pub fn open_lots(&self) -> impl Iterator<Item = &Lot> {
+        self.positions.values().flat_map(|position| position.lots.iter())
+    }
+
+    /// Open lots held in `account` for `contract_id`, oldest first.
+    pub fn lots(&self, account: &str, contract_id: i32) -> impl Iterator<Item = &Lot> {
+        self.positions
+            .get(&(account.to_string(), contract_id))
+            .into_iter()
+            .flat_map(|position| position.lots.iter())
+    }
+
+    /// Net open quantity held in `account` for `contract_id`.
+    pub fn position(&self, account: &str, contract_id: i32) -> Decimal {
+        self.lots(account, contract_id)
+            .fold(Decimal::default(), |total, lot| total + lot.quantity)
+    }
+
+    /// Lots closed so far, in the order they were closed.
+    pub fn closed_lots(&self) -> &[ClosedLot] {
+        &self.closed
+    }
+
+    /// Total realized P&L of the closed lots, after commissions.
+    pub fn realized_pnl(&self) -> f64 {
+        self.closed.iter().map(|lot| lot.realized_pnl).sum()
+    }
+
+    /// Writes the applied fills to `writer`.
+    pub fn export_fills(&self, writer: impl Write, format: ExportFormat) -> Result<(), Error> {
+        match format {
+            ExportFormat::Json => write_json(writer, &self.fills),
+            ExportFormat::Csv => write_csv(
+                writer,
+                &[
+                    "execution_id",
+                    "time",
+                    "account",
+                    "contract_id",
+                    "symbol",
+                    "security_type",
+                    "exchange",
+                    "side",
+                    "shares",
+                    "price",
+                    "order_id",
+                    "perm_id",
+                    "commission",
+                    "commission_currency",
+                    "realized_pnl",
+                    "yield",
+                ],
+                self.fills.iter().map(|fill| {
+                    vec![
+                        fill.execution.execution_id.clone(),
+                        fill.execution.time.clone(),
+                        fill.execution.account_number.clone(),
+                        fill.contract.contract_id.to_string(),
+                        fill.contract.symbol.to_string(),
+                        fill.contract.security_type.to_string(),
+                        fill.execution.exchange.clone(),
+                        fill.execution.side.clone(),
+                        fill.execution.shares.to_string(),
+                        fill.execution.price.to_string(),
+                        fill.execution.order_id.to_string(),
+                        fill.execution.perm_id.to_string(),
+                        optional(fill.commission),
+                        fill.commission_currency.clone(),
+                        optional(fill.realized_pnl),
+                        optional(fill.yields),
+                    ]
+                }),
+            ),
+        }
+    }
+
+    /// Writes the open lots to `writer`.
+    pub fn export_open_lots(&self, writer: impl Write, format: ExportFormat) -> Result<(), Error> {
+        match format {
+            ExportFormat::Json => write_json(writer, &self.open_lots().collect::<Vec<_>>()),
+            ExportFormat::Csv => write_csv(
+                writer,
+                &[
+                    "account",
+                    "contract_id",
+                    "symbol",
+                    "execution_id",
+                    "time",
+                    "quantity",
+                    "price",
+                    "commission",
+                ],
+                self.open_lots().map(|lot| {
+                    vec![
+                        lot.account.clone(),
+                        lot.contract_id.to_string(),
+                        lot.symbol.clone(),
+                        lot.execution_id.clone(),
+                        lot.time.clone(),
+                        lot.quantity.to_string(),
+                        lot.price.to_string(),
+                        lot.commission.to_string(),
+                    ]
+                }),
+            ),
+        }
+    }
+
+    /// Writes the closed lots to `writer`.
+    pub fn export_closed_lots(&self, writer: impl Write, format: ExportFormat) -> Result<(), Error> {
+        match format {
+            ExportFormat::Json => write_json(writer, &self.closed),
+            ExportFormat::Csv => write_csv(
+                writer,
+                &[
+                    "account",
+                    "contract_id",
+                    "symbol",
+                    "quantity",
+                    "open_execution_id",
+                    "opened",
+                    "open_price",
+                    "close_execution_id",
+                    "closed",
+                    "close_price",
+                    "commission",
+                    "realized_pnl",
+                ],
+                self.closed.iter().map(|lot| {
+                    vec![
+                        lot.account.clone(),
+                        lot.contract_id.to_string(),
+                        lot.symbol.clone(),
+                        lot.quantity.to_string(),
+                        lot.open_execution_id.clone(),
+                        lot.opened.clone(),
+                        lot.open_price.to_string(),
+                        lot.close_execution_id.clone(),
+                        lot.closed.clone(),
+                        lot.close_price.to_string(),
+                        lot.commission.to_string(),
+                        lot.realized_pnl.to_string(),
+                    ]
+                }),
+            ),
+        }
+    }
+}
+
+fn optional(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn write_json<T: Serialize + ?Sized>(mut writer: impl Write, value: &T) -> Result<(), Error> {
+    serde_json::to_writer_pretty(&mut writer, value).map_err(std::io::Error::from)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+fn write_csv(mut writer: impl Write, header: &[&str], rows: impl Iterator<Item = Vec<String>>) -> Result<(), Error> {
+    writeln!(writer, "{}", header.join(","))?;
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Quotes a field containing a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests;
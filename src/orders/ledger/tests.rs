@@ -0,0 +1,278 @@
+use super::*;
+use crate::contracts::SecurityType;
+
+/// Responses used by the client-level fill tests: a buy and a partial sell of TSLA with their commissions.
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(super) const EXECUTION_RESPONSES: &[&str] = &[
+    "11|-1|13|76792991|TSLA|STK||0.0|||ISLAND|USD|TSLA|NMS|00025b46.63f8f39c.01.01|20230224  12:04:56|DU1234567|ISLAND|BOT|100|196.52|1376327563|100|0|100|196.52|||||2||",
+    "59|1|00025b46.63f8f39c.01.01|1.0|USD|1.7976931348623157E308|1.7976931348623157E308||",
+    "11|-1|14|76792991|TSLA|STK||0.0|||ISLAND|USD|TSLA|NMS|00025b46.63f8f39d.01.01|20230224  14:10:02|DU1234567|ISLAND|SLD|40|200.00|1376327564|40|0|40|200.00|||||2||",
+    "59|1|00025b46.63f8f39d.01.01|0.4|USD|138.8|1.7976931348623157E308||",
+    "55|1|-1|",
+];
+
+/// Checks the fills joined from [EXECUTION_RESPONSES].
+#[cfg(any(feature = "sync", feature = "async"))]
+pub(super) fn assert_stub_fills(fills: &[Fill]) {
+    assert_eq!(fills.len(), 2, "unexpected fills: {fills:?}");
+
+    assert_eq!(fills[0].execution.execution_id, "00025b46.63f8f39c.01.01");
+    assert!(fills[0].is_buy());
+    assert_eq!(fills[0].commission, Some(1.0));
+    assert_eq!(fills[0].commission_currency, "USD");
+    assert_eq!(fills[0].realized_pnl, None);
+
+    assert_eq!(fills[1].execution.execution_id, "00025b46.63f8f39d.01.01");
+    assert!(fills[1].is_sell());
+    assert_eq!(fills[1].commission, Some(0.4));
+    assert_eq!(fills[1].realized_pnl, Some(138.8));
+}
+
+fn fill(account: &str, contract_id: i32, execution_id: &str, side: &str, shares: i32, price: f64, commission: Option<f64>) -> Fill {
+    Fill {
+        contract: Contract {
+            contract_id,
+            symbol: "AAPL".into(),
+            security_type: SecurityType::Stock,
+            ..Default::default()
+        },
+        execution: Execution {
+            execution_id: execution_id.to_string(),
+            account_number: account.to_string(),
+            side: side.to_string(),
+            shares: Decimal::from(shares),
+            price,
+            time: format!("20250117 10:00:{:02}", shares % 60),
+            ..Default::default()
+        },
+        commission,
+        ..Default::default()
+    }
+}
+
+fn report(execution_id: &str, commission: f64, realized_pnl: Option<f64>) -> CommissionReport {
+    CommissionReport {
+        execution_id: execution_id.to_string(),
+        commission,
+        currency: "USD".to_string(),
+        realized_pnl,
+        ..Default::default()
+    }
+}
+
+fn execution_data(execution_id: &str, shares: i32) -> ExecutionData {
+    let fill = fill("DU1", 1, execution_id, "BOT", shares, 10.0, None);
+    ExecutionData {
+        contract: fill.contract,
+        execution: fill.execution,
+        ..Default::default()
+    }
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "expected {expected}, got {actual}");
+}
+
+#[test]
+fn test_join_pairs_commissions_in_any_order() {
+    let fills = Fill::join(vec![
+        Executions::CommissionReport(report("b.01", 2.0, Some(15.0))),
+        Executions::ExecutionData(execution_data("a.01", 100)),
+        Executions::ExecutionData(execution_data("b.01", 50)),
+        Executions::CommissionReport(report("a.01", 1.0, None)),
+        Executions::ExecutionData(execution_data("c.01", 25)),
+    ]);
+
+    let ids: Vec<&str> = fills.iter().map(|fill| fill.execution.execution_id.as_str()).collect();
+    assert_eq!(ids, vec!["a.01", "b.01", "c.01"]);
+    assert_eq!(fills[0].commission, Some(1.0));
+    assert_eq!(fills[1].commission, Some(2.0));
+    assert_eq!(fills[1].realized_pnl, Some(15.0));
+    assert_eq!(fills[2].commission, None, "no commission report received");
+}
+
+#[test]
+fn test_join_replaces_corrected_executions() {
+    let fills = Fill::join(vec![
+        Executions::ExecutionData(execution_data("0001f4e8.57427bbb.01.01", 100)),
+        Executions::ExecutionData(execution_data("0001f4e8.57427bbc.01.01", 10)),
+        Executions::ExecutionData(execution_data("0001f4e8.57427bbb.01.02", 90)),
+        Executions::CommissionReport(report("0001f4e8.57427bbb.01.02", 0.9, None)),
+    ]);
+
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[0].execution.execution_id, "0001f4e8.57427bbb.01.02");
+    assert_eq!(fills[0].execution.shares, Decimal::from(90));
+    assert_eq!(fills[0].commission, Some(0.9));
+}
+
+#[test]
+fn test_fifo_lots() {
+    let mut ledger = Ledger::new(LotMethod::Fifo);
+    ledger
+        .apply_all(&[
+            fill("DU1", 1, "e1", "BOT", 100, 10.0, Some(1.0)),
+            fill("DU1", 1, "e2", "BOT", 100, 12.0, Some(1.0)),
+            fill("DU1", 1, "e3", "SLD", 150, 15.0, Some(1.5)),
+        ])
+        .unwrap();
+
+    let closed = ledger.closed_lots();
+    assert_eq!(closed.len(), 2);
+    assert_eq!(closed[0].open_execution_id, "e1");
+    assert_eq!(closed[0].quantity, Decimal::from(100));
+    assert_close(closed[0].realized_pnl, 500.0 - 1.0 - 1.0);
+    assert_eq!(closed[1].open_execution_id, "e2");
+    assert_eq!(closed[1].quantity, Decimal::from(50));
+    assert_close(closed[1].realized_pnl, 150.0 - 0.5 - 0.5);
+    assert_close(ledger.realized_pnl(), 647.0);
+
+    let open: Vec<&Lot> = ledger.lots("DU1", 1).collect();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].execution_id, "e2");
+    assert_eq!(open[0].quantity, Decimal::from(50));
+    assert_close(open[0].commission, 0.5);
+    assert_eq!(ledger.position("DU1", 1), Decimal::from(50));
+}
+
+#[test]
+fn test_lifo_lots() {
+    let mut ledger = Ledger::new(LotMethod::Lifo);
+    ledger
+        .apply_all(&[
+            fill("DU1", 1, "e1", "BOT", 100, 10.0, Some(1.0)),
+            fill("DU1", 1, "e2", "BOT", 100, 12.0, Some(1.0)),
+            fill("DU1", 1, "e3", "SLD", 150, 15.0, Some(1.5)),
+        ])
+        .unwrap();
+
+    let closed = ledger.closed_lots();
+    assert_eq!(closed[0].open_execution_id, "e2");
+    assert_close(closed[0].realized_pnl, 300.0 - 1.0 - 1.0);
+    assert_eq!(closed[1].open_execution_id, "e1");
+    assert_close(closed[1].realized_pnl, 250.0 - 0.5 - 0.5);
+
+    let open: Vec<&Lot> = ledger.open_lots().collect();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].execution_id, "e1");
+    assert_eq!(open[0].quantity, Decimal::from(50));
+}
+
+#[test]
+fn test_short_lots_and_reversal() {
+    let mut option = fill("DU1", 7, "e1", "SLD", 10, 2.0, None);
+    option.contract.multiplier = "100".to_string();
+    let mut cover = fill("DU1", 7, "e2", "BOT", 15, 1.5, None);
+    cover.contract.multiplier = "100".to_string();
+
+    let mut ledger = Ledger::default();
+    ledger.apply(&option).unwrap();
+    assert_eq!(ledger.position("DU1", 7), Decimal::from(-10));
+
+    ledger.apply(&cover).unwrap();
+    let closed = ledger.closed_lots();
+    assert_eq!(closed.len(), 1);
+    assert_eq!(closed[0].quantity, Decimal::from(-10), "a short lot was bought back");
+    assert_close(closed[0].realized_pnl, 0.5 * 10.0 * 100.0);
+
+    assert_eq!(ledger.position("DU1", 7), Decimal::from(5), "excess quantity opens a long lot");
+    let open: Vec<&Lot> = ledger.lots("DU1", 7).collect();
+    assert_eq!(open[0].execution_id, "e2");
+    assert_eq!(open[0].price, 1.5);
+}
+
+#[test]
+fn test_lots_are_kept_per_account_and_contract() {
+    let mut ledger = Ledger::default();
+    ledger
+        .apply_all(&[
+            fill("DU1", 1, "e1", "BOT", 100, 10.0, None),
+            fill("DU2", 1, "e2", "SLD", 100, 11.0, None),
+            fill("DU1", 2, "e3", "SLD", 100, 12.0, None),
+        ])
+        .unwrap();
+
+    assert!(ledger.closed_lots().is_empty());
+    assert_eq!(ledger.position("DU1", 1), Decimal::from(100));
+    assert_eq!(ledger.position("DU2", 1), Decimal::from(-100));
+    assert_eq!(ledger.position("DU1", 2), Decimal::from(-100));
+    assert_eq!(ledger.position("DU2", 2), Decimal::default());
+    assert_eq!(ledger.open_lots().count(), 3);
+}
+
+#[test]
+fn test_apply_ignores_duplicates_and_rejects_unknown_sides() {
+    let mut ledger = Ledger::default();
+    let buy = fill("DU1", 1, "e1", "BOT", 100, 10.0, None);
+
+    assert!(ledger.apply(&buy).unwrap());
+    assert!(!ledger.apply(&buy).unwrap(), "duplicate execution id");
+    assert_eq!(ledger.fills().len(), 1);
+    assert_eq!(ledger.position("DU1", 1), Decimal::from(100));
+
+    let unknown = fill("DU1", 1, "e2", "BUY", 100, 10.0, None);
+    assert!(matches!(ledger.apply(&unknown), Err(Error::InvalidArgument(_))));
+    assert_eq!(ledger.fills().len(), 1);
+}
+
+#[test]
+fn test_export_csv() {
+    let mut ledger = Ledger::default();
+    let mut buy = fill("DU1", 1, "e1", "BOT", 100, 10.0, Some(1.0));
+    buy.commission_currency = "USD".to_string();
+    buy.execution.exchange = "ISLAND, NMS".to_string();
+    ledger.apply_all(&[buy, fill("DU1", 1, "e2", "SLD", 40, 11.0, None)]).unwrap();
+
+    let mut output = Vec::new();
+    ledger.export_fills(&mut output, ExportFormat::Csv).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("execution_id,time,account,contract_id,symbol"));
+    assert!(lines[1].contains(",\"ISLAND, NMS\",BOT,"), "{}", lines[1]);
+    assert!(lines[1].ends_with(",1,USD,,"), "{}", lines[1]);
+
+    let mut output = Vec::new();
+    ledger.export_open_lots(&mut output, ExportFormat::Csv).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "account,contract_id,symbol,execution_id,time,quantity,price,commission");
+    assert_eq!(lines[1], "DU1,1,AAPL,e1,20250117 10:00:40,60,10,0.6");
+
+    let mut output = Vec::new();
+    ledger.export_closed_lots(&mut output, ExportFormat::Csv).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.lines().count(), 2);
+}
+
+#[test]
+fn test_export_json() {
+    let mut ledger = Ledger::default();
+    ledger
+        .apply_all(&[fill("DU1", 1, "e1", "BOT", 100, 10.0, None), fill("DU1", 1, "e2", "SLD", 40, 11.0, None)])
+        .unwrap();
+
+    let mut output = Vec::new();
+    ledger.export_closed_lots(&mut output, ExportFormat::Json).unwrap();
+    let closed: Vec<ClosedLot> = serde_json::from_slice(&output).unwrap();
+    assert_eq!(closed, ledger.closed_lots());
+
+    let mut output = Vec::new();
+    ledger.export_open_lots(&mut output, ExportFormat::Json).unwrap();
+    let open: Vec<Lot> = serde_json::from_slice(&output).unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].quantity, Decimal::from(60));
+
+    let mut output = Vec::new();
+    ledger.export_fills(&mut output, ExportFormat::Json).unwrap();
+    let fills: Vec<Fill> = serde_json::from_slice(&output).unwrap();
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[1].execution.execution_id, "e2");
+}
+
+#[test]
+fn test_csv_field_quoting() {
+    assert_eq!(csv_field("plain"), "plain");
+    assert_eq!(csv_field("a,b"), "\"a,b\"");
+    assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+}
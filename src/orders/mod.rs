@@ -39,12 +39,24 @@ pub mod builder;
 /// Order condition types for conditional orders.
 pub mod conditions;
 
+/// Persistent order journal and startup reconciliation.
+pub mod journal;
+
+/// Fills joined with commissions, tax lot accounting and export.
+pub mod ledger;
+
+mod bindings;
+mod execution_filter;
+
 /// Convenience re-export for low-level order builder helpers.
 pub use common::order_builder;
 
 // Re-export builder types
 pub use builder::{BracketOrderBuilder, BracketOrderIds, OrderBuilder, OrderId};
 
+pub use bindings::OrderBindings;
+pub use execution_filter::ExecutionFilterBuilder;
+
 // Re-export condition types and builders
 pub use conditions::{
     ExecutionCondition, ExecutionConditionBuilder, MarginCondition, MarginConditionBuilder, PercentChangeCondition, PercentChangeConditionBuilder,
@@ -56,8 +68,8 @@ use std::fmt::Debug;
 
 use serde::{Deserialize, Serialize};
 
-use crate::contracts::Contract;
-use crate::{encode_option_field, ToField};
+use crate::contracts::{Contract, DeltaNeutralContract};
+use crate::{encode_option_field, Decimal, ToField};
 
 // Public types - always available regardless of feature flags
 
@@ -88,7 +100,7 @@ pub struct Order {
     /// SLONG is available in specially-configured institutional accounts to indicate that long position not yet delivered is being sold.
     pub action: Action,
     /// The number of positions being bought/sold.
-    pub total_quantity: f64,
+    pub total_quantity: Decimal,
     /// The order's type.
     pub order_type: String,
     /// The LIMIT price.
@@ -484,7 +496,7 @@ impl Default for Order {
             client_id: 0,
             perm_id: 0,
             action: Action::Buy,
-            total_quantity: 0.0,
+            total_quantity: Decimal::default(),
             order_type: "".to_owned(),
             limit_price: None,
             aux_price: None,
@@ -1313,7 +1325,7 @@ impl OrderOpenClose {
 
 /// Represents the commission generated by an execution.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct CommissionReport {
     /// the execution's id this commission belongs to.
     pub execution_id: String,
@@ -1331,7 +1343,7 @@ pub struct CommissionReport {
 
 /// Liquidity types for executions.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum Liquidity {
     /// No liquidity information.
     #[default]
@@ -1357,7 +1369,7 @@ impl From<i32> for Liquidity {
 
 /// Describes an order's execution.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Execution {
     /// The API client's order Id. May not be unique to an account.
     pub order_id: i32,
@@ -1377,7 +1389,7 @@ pub struct Execution {
     /// BOT for bought, SLD for sold
     pub side: String,
     /// The number of shares filled.
-    pub shares: f64,
+    pub shares: Decimal,
     /// The order's execution price excluding commissions.
     pub price: f64,
     /// The TWS order identifier. The PermId can be 0 for trades originating outside IB.
@@ -1386,7 +1398,7 @@ pub struct Execution {
     pub liquidation: i32,
     /// Cumulative quantity.
     // Used in regular trades, combo trades and legs of the combo.
-    pub cumulative_quantity: f64,
+    pub cumulative_quantity: Decimal,
     /// Average price.
     /// Used in regular trades, combo trades and legs of the combo. Does not include commissions.
     pub average_price: f64,
@@ -1410,7 +1422,7 @@ pub struct Execution {
 
 /// Contains execution information including the request ID, contract, and execution details.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ExecutionData {
     /// The request ID associated with this execution.
     pub request_id: i32,
@@ -1421,9 +1433,12 @@ pub struct ExecutionData {
 }
 
 /// Responses from placing an order.
+///
+/// New kinds of responses may be added, so matches need a wildcard arm.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug)]
 #[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
 pub enum PlaceOrder {
     /// Order status update.
     OrderStatus(OrderStatus),
@@ -1433,15 +1448,20 @@ pub enum PlaceOrder {
     ExecutionData(ExecutionData),
     /// Commission report.
     CommissionReport(CommissionReport),
+    /// Underlying contract, delta and price TWS validated for a delta-neutral VOL order.
+    DeltaNeutralValidation(DeltaNeutralContract),
     /// Notice or error message.
     Message(crate::messages::Notice),
 }
 
 /// Updates received when monitoring order activity.
 /// This enum is used by `order_update_stream` to deliver real-time order updates.
+///
+/// New kinds of updates may be added, so matches need a wildcard arm.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug)]
 #[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
 pub enum OrderUpdate {
     /// Order status update.
     OrderStatus(OrderStatus),
@@ -1451,10 +1471,27 @@ pub enum OrderUpdate {
     ExecutionData(ExecutionData),
     /// Commission report.
     CommissionReport(CommissionReport),
+    /// Order placed in TWS was bound to an API order id.
+    OrderBound(OrderBound),
     /// Notice or error message.
     Message(crate::messages::Notice),
 }
 
+/// Binding of an order to the API, sent when an order placed in TWS is bound with `auto_open_orders(true)`.
+///
+/// The permanent id identifies the order across sessions, while the order id is the one that
+/// `cancel_order` and `place_order` use for the bound order.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct OrderBound {
+    /// Permanent id assigned by TWS.
+    pub perm_id: i64,
+    /// Id of the API client the order is bound to.
+    pub client_id: i32,
+    /// API order id assigned to the order.
+    pub order_id: i32,
+}
+
 /// Contains all relevant information on the current status of the order execution-wise (i.e. amount filled and pending, filling price, etc.).
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
@@ -1473,9 +1510,9 @@ pub struct OrderStatus {
     /// * Inactive - indicates that the order was received by the system but is no longer active because it was rejected or canceled.
     pub status: String,
     /// Number of filled positions.
-    pub filled: f64,
+    pub filled: Decimal,
     /// The remnant positions.
-    pub remaining: f64,
+    pub remaining: Decimal,
     /// Average filling price.
     pub average_fill_price: f64,
     /// The order's permId used by the TWS to identify orders.
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use super::common::{decoders, encoders, verify};
+use super::journal;
 use super::{CancelOrder, ExecutionFilter, Executions, ExerciseAction, ExerciseOptions, OrderUpdate, Orders, PlaceOrder};
 use crate::client::blocking::Subscription;
 use crate::contracts::Contract;
@@ -25,6 +26,10 @@ impl StreamDecoder<PlaceOrder> for PlaceOrder {
                 context.server_version,
                 message,
             )?)),
+            IncomingMessages::DeltaNeutralValidation => Ok(PlaceOrder::DeltaNeutralValidation(decoders::decode_delta_neutral_validation(
+                context.server_version,
+                message,
+            )?)),
             IncomingMessages::Error => Ok(PlaceOrder::Message(Notice::from(message))),
             _ => Err(Error::UnexpectedResponse(message.clone())),
         }
@@ -47,6 +52,7 @@ impl StreamDecoder<OrderUpdate> for OrderUpdate {
                 context.server_version,
                 message,
             )?)),
+            IncomingMessages::OrderBound => Ok(OrderUpdate::OrderBound(decoders::decode_order_bound(message)?)),
             IncomingMessages::Error => Ok(OrderUpdate::Message(Notice::from(message))),
             _ => Err(Error::UnexpectedResponse(message.clone())),
         }
@@ -145,6 +151,7 @@ pub(crate) fn submit_order(client: &Client, order_id: i32, contract: &Contract,
     verify::verify_order_contract(client, contract, order_id)?;
 
     let request = encoders::encode_place_order(client.server_version, order_id, contract, order)?;
+    journal::record_submission(client.order_journal(), client.client_id(), order_id, contract, order)?;
     client.send_message(request)?;
 
     Ok(())
@@ -171,6 +178,7 @@ pub(crate) fn place_order(client: &Client, order_id: i32, contract: &Contract, o
     verify::verify_order_contract(client, contract, order_id)?;
 
     let request = encoders::encode_place_order(client.server_version, order_id, contract, order)?;
+    journal::record_submission(client.order_journal(), client.client_id(), order_id, contract, order)?;
     let subscription = client.send_order(order_id, request)?;
 
     Ok(Subscription::new(Arc::clone(&client.message_bus), subscription, client.decoder_context()))
@@ -372,11 +380,12 @@ pub(crate) fn exercise_options(
 
 #[cfg(test)]
 mod tests {
+    use crate::Decimal;
     use std::sync::{Arc, RwLock};
 
     use crate::contracts::{ComboLeg, Contract, Currency, Exchange, SecurityType, Symbol};
     use crate::orders::conditions::TriggerMethod;
-    use crate::orders::{Action, Liquidity, OcaType, OrderOrigin, ShortSaleSlot, TimeInForce};
+    use crate::orders::{Action, Liquidity, OcaType, Order, OrderOrigin, ShortSaleSlot, TimeInForce, VolatilityType};
     use crate::stubs::MessageBusStub;
 
     use super::*;
@@ -444,7 +453,7 @@ mod tests {
 
             assert_eq!(order.order_id, 13, "order.order_id");
             assert_eq!(order.action, Action::Buy, "order.action");
-            assert_eq!(order.total_quantity, 100.0, "order.total_quantity");
+            assert_eq!(order.total_quantity, Decimal::from(100), "order.total_quantity");
             assert_eq!(order.order_type, "MKT", "order.order_type");
             assert_eq!(order.limit_price, Some(0.0), "order.limit_price");
             assert_eq!(order.aux_price, Some(0.0), "order.aux_price");
@@ -569,8 +578,8 @@ mod tests {
         if let Some(PlaceOrder::OrderStatus(order_status)) = notifications.next() {
             assert_eq!(order_status.order_id, 13, "order_status.order_id");
             assert_eq!(order_status.status, "PreSubmitted", "order_status.status");
-            assert_eq!(order_status.filled, 0.0, "order_status.filled");
-            assert_eq!(order_status.remaining, 100.0, "order_status.remaining");
+            assert_eq!(order_status.filled, Decimal::from(0), "order_status.filled");
+            assert_eq!(order_status.remaining, Decimal::from(100), "order_status.remaining");
             assert_eq!(order_status.average_fill_price, 0.0, "order_status.average_fill_price");
             assert_eq!(order_status.perm_id, 1376327563, "order_status.perm_id");
             assert_eq!(order_status.parent_id, 0, "order_status.parent_id");
@@ -607,12 +616,12 @@ mod tests {
             assert_eq!(execution.account_number, "DU1234567", "execution.account_number");
             assert_eq!(execution.exchange, "ISLAND", "execution.exchange");
             assert_eq!(execution.side, "BOT", "execution.side");
-            assert_eq!(execution.shares, 100.0, "execution.shares");
+            assert_eq!(execution.shares, Decimal::from(100), "execution.shares");
             assert_eq!(execution.price, 196.52, "execution.price");
             assert_eq!(execution.perm_id, 1376327563, "execution.perm_id");
             assert_eq!(execution.client_id, 100, "execution.client_id");
             assert_eq!(execution.liquidation, 0, "execution.liquidation");
-            assert_eq!(execution.cumulative_quantity, 100.0, "execution.cumulative_quantity");
+            assert_eq!(execution.cumulative_quantity, Decimal::from(100), "execution.cumulative_quantity");
             assert_eq!(execution.average_price, 196.52, "execution.average_price");
             assert_eq!(execution.order_reference, "", "execution.order_reference");
             assert_eq!(execution.ev_rule, "", "execution.ev_rule");
@@ -635,8 +644,8 @@ mod tests {
         if let Some(PlaceOrder::OrderStatus(order_status)) = notifications.next() {
             assert_eq!(order_status.order_id, 13, "order_status.order_id");
             assert_eq!(order_status.status, "Filled", "order_status.status");
-            assert_eq!(order_status.filled, 100.0, "order_status.filled");
-            assert_eq!(order_status.remaining, 0.0, "order_status.remaining");
+            assert_eq!(order_status.filled, Decimal::from(100), "order_status.filled");
+            assert_eq!(order_status.remaining, Decimal::from(0), "order_status.remaining");
             assert_eq!(order_status.average_fill_price, 196.52, "order_status.average_fill_price");
             assert_eq!(order_status.last_fill_price, 196.52, "order_status.last_fill_price");
         } else {
@@ -668,6 +677,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn place_order_delta_neutral_validation() {
+        let message_bus = Arc::new(MessageBusStub::with_responses(vec![
+            "56|1|13|8314|0.52|185.5|".to_owned(),
+            "3|13|PreSubmitted|0|1|0|1376327563|0|0|100||0||".to_owned(),
+        ]));
+
+        let client = Client::stubbed(message_bus, server_versions::SIZE_RULES);
+
+        let contract = Contract::option("IBM", "20250620", 185.0, "C");
+        let order = Order {
+            action: Action::Buy,
+            total_quantity: Decimal::from(1),
+            order_type: "VOL".to_owned(),
+            volatility: Some(25.0),
+            volatility_type: Some(VolatilityType::Annual),
+            delta_neutral_order_type: "MKT".to_owned(),
+            ..Order::default()
+        };
+
+        let notifications = client.place_order(13, &contract, &order).expect("failed to place order");
+
+        match notifications.next() {
+            Some(PlaceOrder::DeltaNeutralValidation(validated)) => {
+                assert_eq!(validated.contract_id, 8314);
+                assert_eq!(validated.delta, 0.52);
+                assert_eq!(validated.price, 185.5);
+            }
+            other => panic!("expected delta neutral validation, got {other:?}"),
+        }
+        assert!(matches!(notifications.next(), Some(PlaceOrder::OrderStatus(_))));
+    }
+
     #[test]
     fn cancel_order() {
         let message_bus = Arc::new(MessageBusStub {
@@ -694,8 +736,8 @@ mod tests {
         if let Some(CancelOrder::OrderStatus(order_status)) = results.next() {
             assert_eq!(order_status.order_id, 41, "order_status.order_id");
             assert_eq!(order_status.status, "Cancelled", "order_status.status");
-            assert_eq!(order_status.filled, 0.0, "order_status.filled");
-            assert_eq!(order_status.remaining, 100.0, "order_status.remaining");
+            assert_eq!(order_status.filled, Decimal::from(0), "order_status.filled");
+            assert_eq!(order_status.remaining, Decimal::from(100), "order_status.remaining");
             assert_eq!(order_status.average_fill_price, 0.0, "order_status.average_fill_price");
             assert_eq!(order_status.perm_id, 71270927, "order_status.perm_id");
             assert_eq!(order_status.parent_id, 0, "order_status.parent_id");
@@ -828,7 +870,7 @@ mod tests {
             assert_eq!(contract.local_symbol, "AAPL", "contract.local_symbol");
             assert_eq!(contract.trading_class, "NMS", "contract.trading_class");
             assert_eq!(order.action, Action::Buy, "order.action");
-            assert_eq!(order.total_quantity, 100.0, "order.total_quantity");
+            assert_eq!(order.total_quantity, Decimal::from(100), "order.total_quantity");
             assert_eq!(order.order_type, "MKT", "order.order_type");
             assert_eq!(order.limit_price, Some(0.0), "order.limit_price");
             assert_eq!(order.aux_price, Some(0.0), "order.aux_price");
@@ -1130,6 +1172,7 @@ mod tests {
                 "3|13|PreSubmitted|0|100|0|1376327563|0|0|100||0||".to_owned(),
                 "11|-1|13|76792991|TSLA|STK||0.0|||ISLAND|USD|TSLA|NMS|00025b46.63f8f39c.01.01|20230224  12:04:56|DU1234567|ISLAND|BOT|100|196.52|1376327563|100|0|100|196.52|||||2||".to_owned(),
                 "59|1|00025b46.63f8f39c.01.01|1.0|USD|1.7976931348623157E308|1.7976931348623157E308|||".to_owned(),
+                "100|1376327563|0|13|".to_owned(),
             ]
         });
 
@@ -1145,7 +1188,7 @@ mod tests {
             assert_eq!(open_order.order_id, 13, "open_order.order_id");
             assert_eq!(open_order.contract.symbol, Symbol::from("TSLA"), "contract.symbol");
             assert_eq!(open_order.order.action, Action::Buy, "order.action");
-            assert_eq!(open_order.order.total_quantity, 100.0, "order.total_quantity");
+            assert_eq!(open_order.order.total_quantity, Decimal::from(100), "order.total_quantity");
             assert_eq!(open_order.order_state.status, "PreSubmitted", "order_state.status");
         } else {
             assert!(false, "expected open order notification");
@@ -1155,8 +1198,8 @@ mod tests {
         if let Some(OrderUpdate::OrderStatus(status)) = notifications.next() {
             assert_eq!(status.order_id, 13, "order_status.order_id");
             assert_eq!(status.status, "PreSubmitted", "order_status.status");
-            assert_eq!(status.filled, 0.0, "order_status.filled");
-            assert_eq!(status.remaining, 100.0, "order_status.remaining");
+            assert_eq!(status.filled, Decimal::from(0), "order_status.filled");
+            assert_eq!(status.remaining, Decimal::from(100), "order_status.remaining");
         } else {
             assert!(false, "expected order status notification");
         }
@@ -1164,7 +1207,7 @@ mod tests {
         // Third event: ExecutionData
         if let Some(OrderUpdate::ExecutionData(exec_data)) = notifications.next() {
             assert_eq!(exec_data.execution.order_id, 13, "execution.order_id");
-            assert_eq!(exec_data.execution.shares, 100.0, "execution.shares");
+            assert_eq!(exec_data.execution.shares, Decimal::from(100), "execution.shares");
             assert_eq!(exec_data.execution.price, 196.52, "execution.price");
             assert_eq!(exec_data.execution.side, "BOT", "execution.side");
         } else {
@@ -1179,6 +1222,15 @@ mod tests {
         } else {
             assert!(false, "expected commission report notification");
         }
+
+        // Fifth event: OrderBound
+        if let Some(OrderUpdate::OrderBound(binding)) = notifications.next() {
+            assert_eq!(binding.perm_id, 1376327563, "binding.perm_id");
+            assert_eq!(binding.client_id, 0, "binding.client_id");
+            assert_eq!(binding.order_id, 13, "binding.order_id");
+        } else {
+            assert!(false, "expected order bound notification");
+        }
     }
 
     #[test]
@@ -8,6 +8,11 @@ use serde::{Deserialize, Serialize};
 
 // Common implementation modules
 mod common;
+pub mod monitor;
+mod query;
+
+pub use monitor::{ScanEvent, ScanQuote, ScanTracker, ScanUpdate, MAX_ACTIVE_SCANNERS};
+pub use query::{Instrument, LocationCode, ScanCode, ScannerQuery, StockType};
 
 // Feature-specific implementations
 #[cfg(feature = "sync")]
@@ -110,9 +115,15 @@ pub struct ScannerData {
 #[cfg(feature = "sync")]
 /// Blocking scanner APIs layered on top of the synchronous client.
 pub mod blocking {
+    pub use super::monitor::sync::{ScanMonitor, ScanMonitorGroup};
     pub(crate) use super::sync::{scanner_parameters, scanner_subscription};
 }
 
+#[cfg(feature = "async")]
+pub use monitor::r#async::{ScanMonitor, ScanMonitorGroup};
+#[cfg(all(feature = "sync", not(feature = "async")))]
+pub use monitor::sync::{ScanMonitor, ScanMonitorGroup};
+
 #[cfg(all(feature = "sync", not(feature = "async")))]
 #[allow(unused_imports)]
 pub(crate) use sync::{scanner_parameters, scanner_subscription};
@@ -0,0 +1,351 @@
+//! Asynchronous scan monitors.
+
+use std::time::Duration;
+
+use futures::future::{join_all, select_all};
+use log::warn;
+
+use super::{check_capacity, ScanEvent, ScanQuote, ScanTracker, ScanUpdate, DEFAULT_SNAPSHOT_TIMEOUT};
+use crate::market_data::realtime::TickTypes;
+use crate::orders::TagValue;
+use crate::scanner::{ScannerData, ScannerSubscription};
+use crate::subscriptions::Subscription;
+use crate::{Client, Error};
+
+/// Scanner subscription that reports changes between refreshes instead of complete result lists.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::scanner::{ScanEvent, ScanMonitor, ScannerSubscription};
+/// use ibapi::Client;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+///
+///     let scan = ScannerSubscription {
+///         number_of_rows: 20,
+///         instrument: Some("STK".to_string()),
+///         location_code: Some("STK.US.MAJOR".to_string()),
+///         scan_code: Some("TOP_PERC_GAIN".to_string()),
+///         ..Default::default()
+///     };
+///     let subscription = client.scanner_subscription(&scan, &vec![]).await.expect("scanner request failed");
+///
+///     let mut monitor = ScanMonitor::new(subscription).with_market_data(&client);
+///     while let Some(Ok(events)) = monitor.next().await {
+///         for event in events {
+///             if let ScanEvent::Entered { rank, contract, quote } = event {
+///                 println!("+{rank} {} {quote:?}", contract.symbol);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub struct ScanMonitor<'a> {
+    subscription: Subscription<Vec<ScannerData>>,
+    tracker: ScanTracker,
+    market_data: Option<&'a Client>,
+    snapshot_timeout: Duration,
+}
+
+impl<'a> ScanMonitor<'a> {
+    /// Wraps a subscription returned by `Client::scanner_subscription`.
+    pub fn new(subscription: Subscription<Vec<ScannerData>>) -> Self {
+        Self {
+            subscription,
+            tracker: ScanTracker::new(),
+            market_data: None,
+            snapshot_timeout: DEFAULT_SNAPSHOT_TIMEOUT,
+        }
+    }
+
+    /// Starts a scanner subscription and wraps it.
+    pub async fn start(client: &Client, subscription: &ScannerSubscription, filter: &Vec<TagValue>) -> Result<Self, Error> {
+        Ok(Self::new(client.scanner_subscription(subscription, filter).await?))
+    }
+
+    /// Snapshots the quote of each entrant. Each snapshot uses a market data line while it is pending.
+    pub fn with_market_data(mut self, client: &'a Client) -> Self {
+        self.market_data = Some(client);
+        self
+    }
+
+    /// How long to wait for the entrants' snapshots. Default: 15 seconds.
+    pub fn snapshot_timeout(mut self, timeout: Duration) -> Self {
+        self.snapshot_timeout = timeout;
+        self
+    }
+
+    /// Waits for the next refresh and returns its changes. Returns `None` when the subscription ends.
+    pub async fn next(&mut self) -> Option<Result<Vec<ScanEvent>, Error>> {
+        let results = self.subscription.next().await?;
+        Some(self.process(results).await)
+    }
+
+    /// Results as of the latest refresh.
+    pub fn tracker(&self) -> &ScanTracker {
+        &self.tracker
+    }
+
+    /// Cancels the scanner subscription.
+    pub async fn cancel(&self) {
+        self.subscription.cancel().await;
+    }
+
+    async fn process(&mut self, results: Result<Vec<ScannerData>, Error>) -> Result<Vec<ScanEvent>, Error> {
+        let mut events = self.tracker.update(&results?);
+        if let Some(client) = self.market_data {
+            enrich(client, &mut events, self.snapshot_timeout).await;
+        }
+        Ok(events)
+    }
+}
+
+async fn enrich(client: &Client, events: &mut [ScanEvent], timeout: Duration) {
+    join_all(events.iter_mut().map(|event| async move {
+        let ScanEvent::Entered { contract, quote, .. } = event else {
+            return;
+        };
+
+        let mut subscription = match client.market_data(contract).snapshot().subscribe().await {
+            Ok(subscription) => subscription,
+            Err(error) => {
+                warn!("market data snapshot failed for {}: {error}", contract.symbol);
+                return;
+            }
+        };
+
+        // The quote is only set once the snapshot completes, so a timeout leaves it as None.
+        let collect = async {
+            let mut snapshot = ScanQuote::default();
+            while let Some(Ok(tick)) = subscription.next().await {
+                if matches!(tick, TickTypes::SnapshotEnd) {
+                    return Some(snapshot);
+                }
+                snapshot.apply(&tick);
+            }
+            None
+        };
+        match tokio::time::timeout(timeout, collect).await {
+            Ok(Some(snapshot)) => *quote = Some(snapshot),
+            _ => warn!("market data snapshot timed out for {}", contract.symbol),
+        }
+    }))
+    .await;
+}
+
+/// Several named scan monitors polled together.
+///
+/// TWS rejects scanner subscriptions beyond [MAX_ACTIVE_SCANNERS](super::MAX_ACTIVE_SCANNERS), so
+/// [add](Self::add) refuses to start more. The limit applies per TWS session; scanners started
+/// outside the group count against it as well.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::scanner::{ScanMonitorGroup, ScannerSubscription};
+/// use ibapi::Client;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::connect("127.0.0.1:4002", 100).await.expect("connection failed");
+///
+///     let mut group = ScanMonitorGroup::new(&client).with_market_data();
+///     for scan_code in ["TOP_PERC_GAIN", "TOP_PERC_LOSE"] {
+///         let scan = ScannerSubscription {
+///             number_of_rows: 10,
+///             instrument: Some("STK".to_string()),
+///             location_code: Some("STK.US.MAJOR".to_string()),
+///             scan_code: Some(scan_code.to_string()),
+///             ..Default::default()
+///         };
+///         group.add(scan_code, &scan, &vec![]).await.expect("scanner request failed");
+///     }
+///
+///     while let Some(update) = group.next().await {
+///         println!("{}: {:?}", update.name, update.events);
+///     }
+/// }
+/// ```
+pub struct ScanMonitorGroup<'a> {
+    client: &'a Client,
+    monitors: Vec<(String, ScanMonitor<'a>)>,
+    market_data: bool,
+    snapshot_timeout: Duration,
+}
+
+impl<'a> ScanMonitorGroup<'a> {
+    /// Creates an empty group whose scanners are started on `client`.
+    pub fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            monitors: Vec::new(),
+            market_data: false,
+            snapshot_timeout: DEFAULT_SNAPSHOT_TIMEOUT,
+        }
+    }
+
+    /// Snapshots the quote of each entrant of scanners added afterwards.
+    pub fn with_market_data(mut self) -> Self {
+        self.market_data = true;
+        self
+    }
+
+    /// How long to wait for the entrants' snapshots. Default: 15 seconds.
+    pub fn snapshot_timeout(mut self, timeout: Duration) -> Self {
+        self.snapshot_timeout = timeout;
+        self
+    }
+
+    /// Starts a scanner under `name`.
+    ///
+    /// Fails with [Error::InvalidArgument] if the name is taken or [MAX_ACTIVE_SCANNERS](super::MAX_ACTIVE_SCANNERS)
+    /// scanners are already running.
+    pub async fn add(&mut self, name: &str, subscription: &ScannerSubscription, filter: &Vec<TagValue>) -> Result<(), Error> {
+        check_capacity(self.names(), self.monitors.len(), name)?;
+
+        let mut monitor = ScanMonitor::start(self.client, subscription, filter)
+            .await?
+            .snapshot_timeout(self.snapshot_timeout);
+        if self.market_data {
+            monitor = monitor.with_market_data(self.client);
+        }
+        self.monitors.push((name.to_string(), monitor));
+        Ok(())
+    }
+
+    /// Cancels and removes the scanner added under `name`.
+    pub async fn remove(&mut self, name: &str) -> bool {
+        match self.monitors.iter().position(|(existing, _)| existing == name) {
+            Some(index) => {
+                let (_, monitor) = self.monitors.remove(index);
+                monitor.cancel().await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Names of the running scanners.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.monitors.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Number of running scanners.
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    /// Returns true if no scanner is running.
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+
+    /// Monitor of the scanner added under `name`.
+    pub fn monitor(&self, name: &str) -> Option<&ScanMonitor<'a>> {
+        self.monitors.iter().find(|(existing, _)| existing == name).map(|(_, monitor)| monitor)
+    }
+
+    /// Waits until one of the scanners refreshes. Returns `None` once no scanner is left.
+    ///
+    /// A scanner that fails or ends is removed from the group; failures are reported with their error.
+    pub async fn next(&mut self) -> Option<ScanUpdate> {
+        loop {
+            if self.monitors.is_empty() {
+                return None;
+            }
+
+            // Only the subscriptions are raced: receiving is cancel safe, while processing a refresh
+            // (which may wait for snapshots) must not be interrupted by another scanner.
+            let (results, index) = {
+                let receivers = self.monitors.iter_mut().map(|(_, monitor)| Box::pin(monitor.subscription.next()));
+                let (results, index, _) = select_all(receivers).await;
+                (results, index)
+            };
+
+            let Some(results) = results else {
+                self.monitors.remove(index);
+                continue;
+            };
+
+            let (name, monitor) = &mut self.monitors[index];
+            let name = name.clone();
+            let events = monitor.process(results).await;
+            if events.is_err() {
+                self.monitors.remove(index);
+            }
+            return Some(ScanUpdate { name, events });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+    use std::sync::{Arc, RwLock};
+
+    const REFRESHES: [&str; 2] = [
+        "20|3|9000|2|0|265598|AAPL|STK||0||SMART|USD|AAPL|NMS|NMS|||||1|4815747|NVDA|STK||0||SMART|USD|NVDA|NMS|NMS|||||",
+        "20|3|9000|2|0|4815747|NVDA|STK||0||SMART|USD|NVDA|NMS|NMS|||||1|272093|MSFT|STK||0||SMART|USD|MSFT|NMS|NMS|||||",
+    ];
+
+    fn scan() -> ScannerSubscription {
+        ScannerSubscription {
+            number_of_rows: 2,
+            instrument: Some("STK".to_string()),
+            location_code: Some("STK.US.MAJOR".to_string()),
+            scan_code: Some("TOP_PERC_GAIN".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_monitor() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: REFRESHES.iter().map(|message| message.to_string()).collect(),
+        });
+        let client = Client::stubbed(message_bus, server_versions::SCANNER_GENERIC_OPTS);
+
+        let mut monitor = ScanMonitor::start(&client, &scan(), &vec![]).await.expect("scanner request failed");
+
+        let events = monitor.next().await.expect("first refresh").expect("first refresh failed");
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], ScanEvent::Entered { rank: 0, contract, quote: None } if contract.contract_id == 265598));
+
+        let events = monitor.next().await.expect("second refresh").expect("second refresh failed");
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], ScanEvent::RankChanged { from: 1, to: 0, .. }));
+        assert!(matches!(&events[1], ScanEvent::Entered { rank: 1, contract, .. } if contract.contract_id == 272093));
+        assert!(matches!(&events[2], ScanEvent::Exited { rank: 0, contract } if contract.contract_id == 265598));
+    }
+
+    #[tokio::test]
+    async fn test_scan_monitor_group() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: REFRESHES.iter().map(|message| message.to_string()).collect(),
+        });
+        let client = Client::stubbed(message_bus, server_versions::SCANNER_GENERIC_OPTS);
+
+        let mut group = ScanMonitorGroup::new(&client);
+        group.add("gainers", &scan(), &vec![]).await.expect("scanner request failed");
+        assert!(matches!(group.add("gainers", &scan(), &vec![]).await, Err(Error::InvalidArgument(_))));
+
+        let update = group.next().await.expect("first refresh");
+        assert_eq!(update.name, "gainers");
+        assert_eq!(update.events.expect("first refresh failed").len(), 2);
+
+        let update = group.next().await.expect("second refresh");
+        assert_eq!(update.events.expect("second refresh failed").len(), 3);
+        assert_eq!(group.monitor("gainers").map(|monitor| monitor.tracker().len()), Some(2));
+
+        assert!(group.remove("gainers").await);
+        assert!(group.is_empty());
+        assert!(group.next().await.is_none());
+    }
+}
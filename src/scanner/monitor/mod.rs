@@ -0,0 +1,246 @@
+//! Change tracking on top of scanner subscriptions.
+//!
+//! A scanner subscription delivers the complete result list on every refresh. [ScanTracker]
+//! compares consecutive lists by contract id and reports what changed as [ScanEvent]s. The
+//! `ScanMonitor` types wrap a subscription with a tracker and can snapshot the quotes of new
+//! entrants; `ScanMonitorGroup` runs several monitors side by side while keeping below TWS's
+//! limit of [MAX_ACTIVE_SCANNERS] concurrent scanner subscriptions.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::ScannerData;
+use crate::contracts::tick_types::TickType;
+use crate::contracts::Contract;
+use crate::market_data::realtime::TickTypes;
+use crate::Error;
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
+
+#[cfg(feature = "async")]
+pub(crate) mod r#async;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of scanner subscriptions TWS allows to be active at the same time.
+pub const MAX_ACTIVE_SCANNERS: usize = 10;
+
+// Snapshots normally complete within 11 seconds.
+const DEFAULT_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Change between two consecutive scanner refreshes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanEvent {
+    /// The contract appeared in the results.
+    Entered {
+        /// Rank in the latest results.
+        rank: i32,
+        /// The contract as reported by the scanner.
+        contract: Contract,
+        /// Market data snapshot, when the monitor was asked to enrich entrants and the snapshot completed in time.
+        quote: Option<ScanQuote>,
+    },
+    /// The contract is no longer in the results.
+    Exited {
+        /// Rank in the previous results.
+        rank: i32,
+        /// The contract as last reported by the scanner.
+        contract: Contract,
+    },
+    /// The contract moved within the results.
+    RankChanged {
+        /// The contract as reported by the scanner.
+        contract: Contract,
+        /// Rank in the previous results.
+        from: i32,
+        /// Rank in the latest results.
+        to: i32,
+    },
+}
+
+impl ScanEvent {
+    /// The contract the event refers to.
+    pub fn contract(&self) -> &Contract {
+        match self {
+            ScanEvent::Entered { contract, .. } | ScanEvent::Exited { contract, .. } | ScanEvent::RankChanged { contract, .. } => contract,
+        }
+    }
+}
+
+/// Market data snapshot of a contract entering the scanner results.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ScanQuote {
+    /// Bid price.
+    pub bid: Option<f64>,
+    /// Ask price.
+    pub ask: Option<f64>,
+    /// Last traded price.
+    pub last: Option<f64>,
+    /// Previous close.
+    pub close: Option<f64>,
+}
+
+impl ScanQuote {
+    // Folds a snapshot tick into the quote.
+    pub(crate) fn apply(&mut self, tick: &TickTypes) {
+        let (tick_type, price) = match tick {
+            TickTypes::Price(price) => (&price.tick_type, price.price),
+            TickTypes::PriceSize(price) => (&price.price_tick_type, price.price),
+            _ => return,
+        };
+
+        // TWS sends -1 when there is no quote.
+        let price = (price > 0.0).then_some(price);
+        match tick_type {
+            TickType::Bid | TickType::DelayedBid => self.bid = price,
+            TickType::Ask | TickType::DelayedAsk => self.ask = price,
+            TickType::Last | TickType::DelayedLast => self.last = price,
+            TickType::Close | TickType::DelayedClose => self.close = price,
+            _ => {}
+        }
+    }
+}
+
+/// Events produced by one scanner of a `ScanMonitorGroup`.
+#[derive(Debug)]
+pub struct ScanUpdate {
+    /// Name the scanner was added with.
+    pub name: String,
+    /// Changes from the latest refresh, or the error that ended the scanner.
+    pub events: Result<Vec<ScanEvent>, Error>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Ranked {
+    rank: i32,
+    contract: Contract,
+}
+
+/// Latest scanner results keyed by contract id, used to turn refreshes into [ScanEvent]s.
+///
+/// # Examples
+///
+/// ```
+/// use ibapi::scanner::{ScanEvent, ScanTracker, ScannerData};
+///
+/// fn result(rank: i32, contract_id: i32) -> ScannerData {
+///     let mut data = ScannerData { rank, ..Default::default() };
+///     data.contract_details.contract.contract_id = contract_id;
+///     data
+/// }
+///
+/// let mut tracker = ScanTracker::new();
+/// tracker.update(&[result(0, 265598), result(1, 4815747)]);
+///
+/// let events = tracker.update(&[result(0, 4815747), result(1, 76792991)]);
+/// assert!(matches!(events[0], ScanEvent::RankChanged { from: 1, to: 0, .. }));
+/// assert!(matches!(events[1], ScanEvent::Entered { rank: 1, .. }));
+/// assert!(matches!(events[2], ScanEvent::Exited { rank: 0, .. }));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ScanTracker {
+    entries: HashMap<i32, Ranked>,
+}
+
+impl ScanTracker {
+    /// Creates a tracker without previous results.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the tracked results with `results` and returns the changes.
+    ///
+    /// Contracts listed more than once keep their best rank. Entries without a contract id cannot be
+    /// matched across refreshes and are ignored. Entrants and rank changes are reported in rank order,
+    /// followed by the contracts that left, in their previous rank order.
+    pub fn update(&mut self, results: &[ScannerData]) -> Vec<ScanEvent> {
+        let mut latest: HashMap<i32, Ranked> = HashMap::with_capacity(results.len());
+        for result in results {
+            let contract = &result.contract_details.contract;
+            if contract.contract_id == 0 {
+                continue;
+            }
+            if !matches!(latest.get(&contract.contract_id), Some(ranked) if ranked.rank <= result.rank) {
+                latest.insert(
+                    contract.contract_id,
+                    Ranked {
+                        rank: result.rank,
+                        contract: contract.clone(),
+                    },
+                );
+            }
+        }
+
+        let mut current: Vec<&Ranked> = latest.values().collect();
+        current.sort_by_key(|ranked| ranked.rank);
+
+        let mut events = Vec::new();
+        for ranked in current {
+            match self.entries.get(&ranked.contract.contract_id) {
+                None => events.push(ScanEvent::Entered {
+                    rank: ranked.rank,
+                    contract: ranked.contract.clone(),
+                    quote: None,
+                }),
+                Some(previous) if previous.rank != ranked.rank => events.push(ScanEvent::RankChanged {
+                    contract: ranked.contract.clone(),
+                    from: previous.rank,
+                    to: ranked.rank,
+                }),
+                Some(_) => {}
+            }
+        }
+
+        let mut exited: Vec<Ranked> = self
+            .entries
+            .drain()
+            .filter(|(id, _)| !latest.contains_key(id))
+            .map(|(_, ranked)| ranked)
+            .collect();
+        exited.sort_by_key(|ranked| ranked.rank);
+        events.extend(exited.into_iter().map(|ranked| ScanEvent::Exited {
+            rank: ranked.rank,
+            contract: ranked.contract,
+        }));
+
+        self.entries = latest;
+        events
+    }
+
+    /// Rank of `contract_id` in the latest results.
+    pub fn rank(&self, contract_id: i32) -> Option<i32> {
+        self.entries.get(&contract_id).map(|ranked| ranked.rank)
+    }
+
+    /// Latest results in rank order.
+    pub fn ranking(&self) -> Vec<(i32, &Contract)> {
+        let mut ranking: Vec<(i32, &Contract)> = self.entries.values().map(|ranked| (ranked.rank, &ranked.contract)).collect();
+        ranking.sort_by_key(|(rank, _)| *rank);
+        ranking
+    }
+
+    /// Number of contracts in the latest results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true before the first refresh or when the latest results were empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+// Errors when another scanner would exceed the limit or reuse a name.
+fn check_capacity<'n>(mut names: impl Iterator<Item = &'n str>, active: usize, name: &str) -> Result<(), Error> {
+    if names.any(|existing| existing == name) {
+        return Err(Error::InvalidArgument(format!("a scanner named {name:?} is already running")));
+    }
+    if active >= MAX_ACTIVE_SCANNERS {
+        return Err(Error::InvalidArgument(format!(
+            "cannot start scanner {name:?}: at most {MAX_ACTIVE_SCANNERS} scanners may be active"
+        )));
+    }
+    Ok(())
+}
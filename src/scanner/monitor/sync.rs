@@ -0,0 +1,435 @@
+//! Blocking scan monitors.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::warn;
+
+use super::{check_capacity, ScanEvent, ScanQuote, ScanTracker, ScanUpdate, DEFAULT_SNAPSHOT_TIMEOUT};
+use crate::client::sync::Client;
+use crate::market_data::realtime::TickTypes;
+use crate::orders::TagValue;
+use crate::scanner::{ScannerData, ScannerSubscription};
+use crate::subscriptions::sync::Subscription;
+use crate::Error;
+
+// Pause between polling rounds of a group when no scanner had results.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Scanner subscription that reports changes between refreshes instead of complete result lists.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::client::blocking::Client;
+/// use ibapi::scanner::blocking::ScanMonitor;
+/// use ibapi::scanner::{ScanEvent, ScannerSubscription};
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let scan = ScannerSubscription {
+///     number_of_rows: 20,
+///     instrument: Some("STK".to_string()),
+///     location_code: Some("STK.US.MAJOR".to_string()),
+///     scan_code: Some("TOP_PERC_GAIN".to_string()),
+///     ..Default::default()
+/// };
+/// let subscription = client.scanner_subscription(&scan, &vec![]).expect("scanner request failed");
+///
+/// let mut monitor = ScanMonitor::new(subscription).with_market_data(&client);
+/// while let Some(events) = monitor.next() {
+///     for event in events {
+///         match event {
+///             ScanEvent::Entered { rank, contract, quote } => println!("+{rank} {} {quote:?}", contract.symbol),
+///             ScanEvent::Exited { contract, .. } => println!("-{}", contract.symbol),
+///             ScanEvent::RankChanged { contract, from, to } => println!("{} {from} -> {to}", contract.symbol),
+///         }
+///     }
+/// }
+/// ```
+pub struct ScanMonitor<'a> {
+    subscription: Subscription<Vec<ScannerData>>,
+    tracker: ScanTracker,
+    market_data: Option<&'a Client>,
+    snapshot_timeout: Duration,
+}
+
+impl<'a> ScanMonitor<'a> {
+    /// Wraps a subscription returned by `Client::scanner_subscription`.
+    pub fn new(subscription: Subscription<Vec<ScannerData>>) -> Self {
+        Self {
+            subscription,
+            tracker: ScanTracker::new(),
+            market_data: None,
+            snapshot_timeout: DEFAULT_SNAPSHOT_TIMEOUT,
+        }
+    }
+
+    /// Starts a scanner subscription and wraps it.
+    pub fn start(client: &Client, subscription: &ScannerSubscription, filter: &Vec<TagValue>) -> Result<Self, Error> {
+        Ok(Self::new(client.scanner_subscription(subscription, filter)?))
+    }
+
+    /// Snapshots the quote of each entrant. Each snapshot uses a market data line while it is pending.
+    pub fn with_market_data(mut self, client: &'a Client) -> Self {
+        self.market_data = Some(client);
+        self
+    }
+
+    /// How long to wait for the entrants' snapshots. Default: 15 seconds.
+    pub fn snapshot_timeout(mut self, timeout: Duration) -> Self {
+        self.snapshot_timeout = timeout;
+        self
+    }
+
+    /// Returns the changes of a refresh that already arrived, without blocking.
+    pub fn try_next(&mut self) -> Option<Vec<ScanEvent>> {
+        let results = self.subscription.try_next()?;
+        Some(self.process(&results))
+    }
+
+    /// Waits up to `timeout` for the next refresh.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<Vec<ScanEvent>> {
+        let results = self.subscription.next_timeout(timeout)?;
+        Some(self.process(&results))
+    }
+
+    /// Error that ended the subscription, if any.
+    pub fn error(&self) -> Option<Error> {
+        self.subscription.error()
+    }
+
+    /// Results as of the latest refresh.
+    pub fn tracker(&self) -> &ScanTracker {
+        &self.tracker
+    }
+
+    /// Cancels the scanner subscription.
+    pub fn cancel(&self) {
+        self.subscription.cancel();
+    }
+
+    fn process(&mut self, results: &[ScannerData]) -> Vec<ScanEvent> {
+        let mut events = self.tracker.update(results);
+        if let Some(client) = self.market_data {
+            enrich(client, &mut events, self.snapshot_timeout);
+        }
+        events
+    }
+}
+
+/// Blocks until the next refresh and yields its changes.
+///
+/// Ends with the subscription; check [error](ScanMonitor::error) for the cause.
+impl Iterator for ScanMonitor<'_> {
+    type Item = Vec<ScanEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let results = self.subscription.next()?;
+        Some(self.process(&results))
+    }
+}
+
+// Requests every snapshot before reading any, so they are served in parallel.
+fn enrich(client: &Client, events: &mut [ScanEvent], timeout: Duration) {
+    let mut pending = Vec::new();
+    for event in events.iter_mut() {
+        let ScanEvent::Entered { contract, quote, .. } = event else {
+            continue;
+        };
+        match client.market_data(contract).snapshot().subscribe() {
+            Ok(subscription) => pending.push((&contract.symbol, quote, subscription)),
+            Err(error) => warn!("market data snapshot failed for {}: {error}", contract.symbol),
+        }
+    }
+
+    // The quote is only set once the snapshot completes, so a timeout leaves it as None.
+    let deadline = Instant::now() + timeout;
+    for (symbol, quote, subscription) in pending {
+        let mut snapshot = ScanQuote::default();
+        loop {
+            match subscription.next_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Some(TickTypes::SnapshotEnd) => {
+                    *quote = Some(snapshot);
+                    break;
+                }
+                Some(tick) => snapshot.apply(&tick),
+                None => {
+                    warn!("market data snapshot timed out for {symbol}");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Several named scan monitors polled together.
+///
+/// TWS rejects scanner subscriptions beyond [MAX_ACTIVE_SCANNERS](super::MAX_ACTIVE_SCANNERS), so
+/// [add](Self::add) refuses to start more. The limit applies per TWS session; scanners started
+/// outside the group count against it as well.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::client::blocking::Client;
+/// use ibapi::scanner::blocking::ScanMonitorGroup;
+/// use ibapi::scanner::ScannerSubscription;
+///
+/// let client = Client::connect("127.0.0.1:4002", 100).expect("connection failed");
+///
+/// let mut group = ScanMonitorGroup::new(&client);
+/// for scan_code in ["TOP_PERC_GAIN", "TOP_PERC_LOSE", "HOT_BY_VOLUME"] {
+///     let scan = ScannerSubscription {
+///         number_of_rows: 10,
+///         instrument: Some("STK".to_string()),
+///         location_code: Some("STK.US.MAJOR".to_string()),
+///         scan_code: Some(scan_code.to_string()),
+///         ..Default::default()
+///     };
+///     group.add(scan_code, &scan, &vec![]).expect("scanner request failed");
+/// }
+///
+/// while let Some(update) = group.next() {
+///     println!("{}: {:?}", update.name, update.events);
+/// }
+/// ```
+pub struct ScanMonitorGroup<'a> {
+    client: &'a Client,
+    monitors: Vec<(String, ScanMonitor<'a>)>,
+    market_data: bool,
+    snapshot_timeout: Duration,
+    // Index of the monitor polled first in the next round, so busy scanners cannot starve the others.
+    cursor: usize,
+}
+
+impl<'a> ScanMonitorGroup<'a> {
+    /// Creates an empty group whose scanners are started on `client`.
+    pub fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            monitors: Vec::new(),
+            market_data: false,
+            snapshot_timeout: DEFAULT_SNAPSHOT_TIMEOUT,
+            cursor: 0,
+        }
+    }
+
+    /// Snapshots the quote of each entrant of scanners added afterwards.
+    pub fn with_market_data(mut self) -> Self {
+        self.market_data = true;
+        self
+    }
+
+    /// How long to wait for the entrants' snapshots. Default: 15 seconds.
+    pub fn snapshot_timeout(mut self, timeout: Duration) -> Self {
+        self.snapshot_timeout = timeout;
+        self
+    }
+
+    /// Starts a scanner under `name`.
+    ///
+    /// Fails with [Error::InvalidArgument] if the name is taken or [MAX_ACTIVE_SCANNERS](super::MAX_ACTIVE_SCANNERS)
+    /// scanners are already running.
+    pub fn add(&mut self, name: &str, subscription: &ScannerSubscription, filter: &Vec<TagValue>) -> Result<(), Error> {
+        check_capacity(self.names(), self.monitors.len(), name)?;
+
+        let mut monitor = ScanMonitor::start(self.client, subscription, filter)?.snapshot_timeout(self.snapshot_timeout);
+        if self.market_data {
+            monitor = monitor.with_market_data(self.client);
+        }
+        self.monitors.push((name.to_string(), monitor));
+        Ok(())
+    }
+
+    /// Cancels and removes the scanner added under `name`.
+    pub fn remove(&mut self, name: &str) -> bool {
+        match self.monitors.iter().position(|(existing, _)| existing == name) {
+            Some(index) => {
+                let (_, monitor) = self.monitors.remove(index);
+                monitor.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Names of the running scanners.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.monitors.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Number of running scanners.
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    /// Returns true if no scanner is running.
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+
+    /// Monitor of the scanner added under `name`.
+    pub fn monitor(&self, name: &str) -> Option<&ScanMonitor<'a>> {
+        self.monitors.iter().find(|(existing, _)| existing == name).map(|(_, monitor)| monitor)
+    }
+
+    /// Waits up to `timeout` for one of the scanners to refresh.
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<ScanUpdate> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(update) = self.poll() {
+                return Some(update);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if self.monitors.is_empty() || remaining.is_zero() {
+                return None;
+            }
+            thread::sleep(remaining.min(POLL_INTERVAL));
+        }
+    }
+
+    // Checks every monitor once, starting after the one that produced the previous update.
+    fn poll(&mut self) -> Option<ScanUpdate> {
+        for offset in 0..self.monitors.len() {
+            let index = (self.cursor + offset) % self.monitors.len();
+            let (name, monitor) = &mut self.monitors[index];
+
+            if let Some(events) = monitor.try_next() {
+                let update = ScanUpdate {
+                    name: name.clone(),
+                    events: Ok(events),
+                };
+                self.cursor = index + 1;
+                return Some(update);
+            }
+
+            if let Some(error) = monitor.error() {
+                let (name, _) = self.monitors.remove(index);
+                self.cursor = index;
+                return Some(ScanUpdate { name, events: Err(error) });
+            }
+        }
+        None
+    }
+}
+
+/// Blocks until one of the scanners refreshes. Ends once no scanner is left.
+///
+/// A scanner that fails is removed from the group and reported with its error.
+impl Iterator for ScanMonitorGroup<'_> {
+    type Item = ScanUpdate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.monitors.is_empty() {
+                return None;
+            }
+            if let Some(update) = self.poll() {
+                return Some(update);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::Contract;
+    use crate::scanner::MAX_ACTIVE_SCANNERS;
+    use crate::server_versions;
+    use crate::stubs::MessageBusStub;
+    use std::sync::{Arc, RwLock};
+
+    const REFRESHES: [&str; 2] = [
+        "20|3|9000|2|0|265598|AAPL|STK||0||SMART|USD|AAPL|NMS|NMS|||||1|4815747|NVDA|STK||0||SMART|USD|NVDA|NMS|NMS|||||",
+        "20|3|9000|2|0|4815747|NVDA|STK||0||SMART|USD|NVDA|NMS|NMS|||||1|272093|MSFT|STK||0||SMART|USD|MSFT|NMS|NMS|||||",
+    ];
+
+    fn scan() -> ScannerSubscription {
+        ScannerSubscription {
+            number_of_rows: 2,
+            instrument: Some("STK".to_string()),
+            location_code: Some("STK.US.MAJOR".to_string()),
+            scan_code: Some("TOP_PERC_GAIN".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scan_monitor() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: REFRESHES.iter().map(|message| message.to_string()).collect(),
+        });
+        let client = Client::stubbed(message_bus, server_versions::SCANNER_GENERIC_OPTS);
+
+        let mut monitor = ScanMonitor::start(&client, &scan(), &vec![]).expect("scanner request failed");
+
+        let events = monitor.next().expect("first refresh");
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], ScanEvent::Entered { rank: 0, contract, quote: None } if contract.contract_id == 265598));
+        assert!(matches!(&events[1], ScanEvent::Entered { rank: 1, contract, .. } if contract.contract_id == 4815747));
+
+        let events = monitor.next().expect("second refresh");
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], ScanEvent::RankChanged { from: 1, to: 0, .. }));
+        assert!(matches!(&events[1], ScanEvent::Entered { rank: 1, contract, .. } if contract.contract_id == 272093));
+        assert!(matches!(&events[2], ScanEvent::Exited { rank: 0, contract } if contract.contract_id == 265598));
+        assert_eq!(monitor.tracker().len(), 2);
+    }
+
+    fn enriched_quote(responses: &[&str]) -> Option<ScanQuote> {
+        let message_bus = Arc::new(MessageBusStub::with_responses(
+            responses.iter().map(|message| message.to_string()).collect(),
+        ));
+        let client = Client::stubbed(message_bus, server_versions::SCANNER_GENERIC_OPTS);
+
+        let mut events = vec![ScanEvent::Entered {
+            rank: 0,
+            contract: Contract::stock("AAPL").build(),
+            quote: None,
+        }];
+        enrich(&client, &mut events, Duration::from_millis(50));
+
+        match events.remove(0) {
+            ScanEvent::Entered { quote, .. } => quote,
+            event => panic!("unexpected event: {event:?}"),
+        }
+    }
+
+    #[test]
+    fn test_enrich_sets_quote_on_snapshot_end() {
+        let quote = enriched_quote(&["1|6|9000|1|185.50|100|3|", "57|1|9000|"]).expect("completed snapshot");
+        assert_eq!(quote.bid, Some(185.50));
+        assert_eq!(quote.ask, None);
+    }
+
+    #[test]
+    fn test_enrich_leaves_quote_unset_on_timeout() {
+        assert_eq!(enriched_quote(&["1|6|9000|1|185.50|100|3|"]), None);
+    }
+
+    #[test]
+    fn test_scan_monitor_group_limit() {
+        let message_bus = Arc::new(MessageBusStub {
+            request_messages: RwLock::new(vec![]),
+            response_messages: vec![],
+        });
+        let client = Client::stubbed(message_bus, server_versions::SCANNER_GENERIC_OPTS);
+
+        let mut group = ScanMonitorGroup::new(&client);
+        for i in 0..MAX_ACTIVE_SCANNERS {
+            group.add(&format!("scan-{i}"), &scan(), &vec![]).expect("scanner request failed");
+        }
+        assert_eq!(group.len(), MAX_ACTIVE_SCANNERS);
+        assert!(matches!(group.add("one-more", &scan(), &vec![]), Err(Error::InvalidArgument(_))));
+
+        assert!(group.remove("scan-3"));
+        assert!(!group.remove("scan-3"));
+        group.add("one-more", &scan(), &vec![]).expect("scanner request failed");
+        assert!(group.monitor("one-more").is_some());
+        assert!(matches!(group.add("one-more", &scan(), &vec![]), Err(Error::InvalidArgument(_))));
+    }
+}
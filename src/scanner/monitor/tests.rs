@@ -0,0 +1,116 @@
+use super::*;
+use crate::contracts::Symbol;
+use crate::market_data::realtime::{TickPrice, TickSize};
+
+fn result(rank: i32, contract_id: i32, symbol: &str) -> ScannerData {
+    let mut data = ScannerData { rank, ..Default::default() };
+    data.contract_details.contract.contract_id = contract_id;
+    data.contract_details.contract.symbol = Symbol::from(symbol);
+    data
+}
+
+fn describe(events: &[ScanEvent]) -> Vec<String> {
+    events
+        .iter()
+        .map(|event| match event {
+            ScanEvent::Entered { rank, contract, .. } => format!("+{} @{rank}", contract.symbol),
+            ScanEvent::Exited { rank, contract } => format!("-{} @{rank}", contract.symbol),
+            ScanEvent::RankChanged { contract, from, to } => format!("{} {from}->{to}", contract.symbol),
+        })
+        .collect()
+}
+
+#[test]
+fn test_first_refresh_reports_entrants_in_rank_order() {
+    let mut tracker = ScanTracker::new();
+    assert!(tracker.is_empty());
+
+    let events = tracker.update(&[result(1, 2, "NVDA"), result(0, 1, "AAPL")]);
+
+    assert_eq!(describe(&events), vec!["+AAPL @0", "+NVDA @1"]);
+    assert_eq!(tracker.len(), 2);
+    assert_eq!(tracker.rank(2), Some(1));
+    let ranking: Vec<String> = tracker.ranking().iter().map(|(_, contract)| contract.symbol.to_string()).collect();
+    assert_eq!(ranking, vec!["AAPL", "NVDA"]);
+}
+
+#[test]
+fn test_refresh_reports_changes() {
+    let mut tracker = ScanTracker::new();
+    tracker.update(&[result(0, 1, "AAPL"), result(1, 2, "NVDA"), result(2, 3, "TSLA"), result(3, 4, "AMD")]);
+
+    let events = tracker.update(&[result(0, 2, "NVDA"), result(1, 1, "AAPL"), result(2, 5, "MSFT"), result(3, 4, "AMD")]);
+
+    assert_eq!(describe(&events), vec!["NVDA 1->0", "AAPL 0->1", "+MSFT @2", "-TSLA @2"]);
+    assert_eq!(tracker.rank(3), None);
+    assert_eq!(tracker.rank(5), Some(2));
+
+    let events = tracker.update(&[result(0, 2, "NVDA"), result(1, 1, "AAPL"), result(2, 5, "MSFT"), result(3, 4, "AMD")]);
+    assert!(events.is_empty(), "unchanged results produce no events: {events:?}");
+
+    let events = tracker.update(&[]);
+    assert_eq!(describe(&events), vec!["-NVDA @0", "-AAPL @1", "-MSFT @2", "-AMD @3"]);
+    assert!(tracker.is_empty());
+}
+
+#[test]
+fn test_duplicates_and_unknown_contracts() {
+    let mut tracker = ScanTracker::new();
+
+    let events = tracker.update(&[result(0, 1, "AAPL"), result(1, 0, "EFP"), result(2, 1, "AAPL"), result(3, 2, "NVDA")]);
+
+    assert_eq!(describe(&events), vec!["+AAPL @0", "+NVDA @3"]);
+    assert_eq!(tracker.rank(1), Some(0), "duplicates keep their best rank");
+    assert_eq!(events[0].contract().contract_id, 1);
+}
+
+#[test]
+fn test_quote_applies_snapshot_ticks() {
+    let mut quote = ScanQuote::default();
+
+    for (tick_type, price) in [
+        (TickType::Bid, 101.5),
+        (TickType::Ask, 101.75),
+        (TickType::DelayedLast, 101.6),
+        (TickType::Close, 99.0),
+        (TickType::High, 103.0),
+    ] {
+        quote.apply(&TickTypes::Price(TickPrice {
+            tick_type,
+            price,
+            ..Default::default()
+        }));
+    }
+    quote.apply(&TickTypes::Size(TickSize::default()));
+
+    assert_eq!(
+        quote,
+        ScanQuote {
+            bid: Some(101.5),
+            ask: Some(101.75),
+            last: Some(101.6),
+            close: Some(99.0),
+        }
+    );
+
+    quote.apply(&TickTypes::Price(TickPrice {
+        tick_type: TickType::Bid,
+        price: -1.0,
+        ..Default::default()
+    }));
+    assert_eq!(quote.bid, None, "TWS reports missing quotes as -1");
+}
+
+#[test]
+fn test_check_capacity() {
+    let names = ["gainers", "losers"];
+    assert!(check_capacity(names.into_iter(), names.len(), "volume").is_ok());
+    assert!(matches!(
+        check_capacity(names.into_iter(), names.len(), "losers"),
+        Err(Error::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        check_capacity(std::iter::empty(), MAX_ACTIVE_SCANNERS, "volume"),
+        Err(Error::InvalidArgument(_))
+    ));
+}
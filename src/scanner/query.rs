@@ -0,0 +1,479 @@
+//! Typed builder for scanner subscriptions and their generic filters.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::ScannerSubscription;
+use crate::orders::TagValue;
+
+/// Instrument type a scan runs over.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Instrument {
+    /// US stocks (`STK`).
+    Stock,
+    /// US ETFs (`ETF.EQ.US`).
+    Etf,
+    /// European stocks (`STOCK.EU`).
+    StockEu,
+    /// Hong Kong stocks (`STOCK.HK`).
+    StockHk,
+    /// US futures (`FUT.US`).
+    Future,
+    /// US indexes (`IND.US`).
+    Index,
+    /// Bonds (`BOND`).
+    Bond,
+    /// Any other instrument code listed by `scanner_parameters`.
+    Other(String),
+}
+
+impl Instrument {
+    /// Maps an instrument code to an instrument.
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "STK" => Instrument::Stock,
+            "ETF.EQ.US" => Instrument::Etf,
+            "STOCK.EU" => Instrument::StockEu,
+            "STOCK.HK" => Instrument::StockHk,
+            "FUT.US" => Instrument::Future,
+            "IND.US" => Instrument::Index,
+            "BOND" => Instrument::Bond,
+            _ => Instrument::Other(code.to_string()),
+        }
+    }
+
+    /// The code sent to TWS.
+    pub fn code(&self) -> &str {
+        match self {
+            Instrument::Stock => "STK",
+            Instrument::Etf => "ETF.EQ.US",
+            Instrument::StockEu => "STOCK.EU",
+            Instrument::StockHk => "STOCK.HK",
+            Instrument::Future => "FUT.US",
+            Instrument::Index => "IND.US",
+            Instrument::Bond => "BOND",
+            Instrument::Other(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for Instrument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Market or exchange a scan is restricted to.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LocationCode {
+    /// All US stocks (`STK.US`).
+    StockUs,
+    /// US stocks listed on major exchanges (`STK.US.MAJOR`).
+    StockUsMajor,
+    /// US OTC and pink sheet stocks (`STK.US.MINOR`).
+    StockUsMinor,
+    /// NASDAQ (`STK.NASDAQ`).
+    Nasdaq,
+    /// NYSE (`STK.NYSE`).
+    Nyse,
+    /// NYSE Arca (`STK.ARCA`).
+    Arca,
+    /// NYSE American (`STK.AMEX`).
+    Amex,
+    /// European stocks (`STK.EU`).
+    StockEu,
+    /// Hong Kong Stock Exchange (`STK.HK.SEHK`).
+    Sehk,
+    /// US futures (`FUT.US`).
+    FutureUs,
+    /// US indexes (`IND.US`).
+    IndexUs,
+    /// Any other location code listed by `scanner_parameters`.
+    Other(String),
+}
+
+impl LocationCode {
+    /// Maps a location code to a location.
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "STK.US" => LocationCode::StockUs,
+            "STK.US.MAJOR" => LocationCode::StockUsMajor,
+            "STK.US.MINOR" => LocationCode::StockUsMinor,
+            "STK.NASDAQ" => LocationCode::Nasdaq,
+            "STK.NYSE" => LocationCode::Nyse,
+            "STK.ARCA" => LocationCode::Arca,
+            "STK.AMEX" => LocationCode::Amex,
+            "STK.EU" => LocationCode::StockEu,
+            "STK.HK.SEHK" => LocationCode::Sehk,
+            "FUT.US" => LocationCode::FutureUs,
+            "IND.US" => LocationCode::IndexUs,
+            _ => LocationCode::Other(code.to_string()),
+        }
+    }
+
+    /// The code sent to TWS.
+    pub fn code(&self) -> &str {
+        match self {
+            LocationCode::StockUs => "STK.US",
+            LocationCode::StockUsMajor => "STK.US.MAJOR",
+            LocationCode::StockUsMinor => "STK.US.MINOR",
+            LocationCode::Nasdaq => "STK.NASDAQ",
+            LocationCode::Nyse => "STK.NYSE",
+            LocationCode::Arca => "STK.ARCA",
+            LocationCode::Amex => "STK.AMEX",
+            LocationCode::StockEu => "STK.EU",
+            LocationCode::Sehk => "STK.HK.SEHK",
+            LocationCode::FutureUs => "FUT.US",
+            LocationCode::IndexUs => "IND.US",
+            LocationCode::Other(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for LocationCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Sort order of a scan, as named in the TWS Market Scanner.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScanCode {
+    /// Top % gainers (`TOP_PERC_GAIN`).
+    TopPercentGain,
+    /// Top % losers (`TOP_PERC_LOSE`).
+    TopPercentLose,
+    /// Most active by volume (`MOST_ACTIVE`).
+    MostActive,
+    /// Most active by dollar volume (`MOST_ACTIVE_USD`).
+    MostActiveUsd,
+    /// Hot contracts by volume (`HOT_BY_VOLUME`).
+    HotByVolume,
+    /// Hot contracts by price (`HOT_BY_PRICE`).
+    HotByPrice,
+    /// Top trade count (`TOP_TRADE_COUNT`).
+    TopTradeCount,
+    /// Top % gainers since the open (`TOP_OPEN_PERC_GAIN`).
+    TopOpenPercentGain,
+    /// Top % losers since the open (`TOP_OPEN_PERC_LOSE`).
+    TopOpenPercentLose,
+    /// Highest option implied volatility (`HIGH_OPT_IMP_VOLAT`).
+    HighOptionImpliedVolatility,
+    /// Lowest option implied volatility (`LOW_OPT_IMP_VOLAT`).
+    LowOptionImpliedVolatility,
+    /// Top option implied volatility % gainers (`TOP_OPT_IMP_VOLAT_GAIN`).
+    TopOptionImpliedVolatilityGain,
+    /// Closest to the 52 week high (`HIGH_VS_52W_HL`).
+    High52Week,
+    /// Closest to the 52 week low (`LOW_VS_52W_HL`).
+    Low52Week,
+    /// Highest dividend yield (`HIGH_DIVIDEND_YIELD_IB`).
+    HighDividendYield,
+    /// Any other scan code listed by `scanner_parameters`.
+    Other(String),
+}
+
+impl ScanCode {
+    /// Maps a scan code to a scan.
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "TOP_PERC_GAIN" => ScanCode::TopPercentGain,
+            "TOP_PERC_LOSE" => ScanCode::TopPercentLose,
+            "MOST_ACTIVE" => ScanCode::MostActive,
+            "MOST_ACTIVE_USD" => ScanCode::MostActiveUsd,
+            "HOT_BY_VOLUME" => ScanCode::HotByVolume,
+            "HOT_BY_PRICE" => ScanCode::HotByPrice,
+            "TOP_TRADE_COUNT" => ScanCode::TopTradeCount,
+            "TOP_OPEN_PERC_GAIN" => ScanCode::TopOpenPercentGain,
+            "TOP_OPEN_PERC_LOSE" => ScanCode::TopOpenPercentLose,
+            "HIGH_OPT_IMP_VOLAT" => ScanCode::HighOptionImpliedVolatility,
+            "LOW_OPT_IMP_VOLAT" => ScanCode::LowOptionImpliedVolatility,
+            "TOP_OPT_IMP_VOLAT_GAIN" => ScanCode::TopOptionImpliedVolatilityGain,
+            "HIGH_VS_52W_HL" => ScanCode::High52Week,
+            "LOW_VS_52W_HL" => ScanCode::Low52Week,
+            "HIGH_DIVIDEND_YIELD_IB" => ScanCode::HighDividendYield,
+            _ => ScanCode::Other(code.to_string()),
+        }
+    }
+
+    /// The code sent to TWS.
+    pub fn code(&self) -> &str {
+        match self {
+            ScanCode::TopPercentGain => "TOP_PERC_GAIN",
+            ScanCode::TopPercentLose => "TOP_PERC_LOSE",
+            ScanCode::MostActive => "MOST_ACTIVE",
+            ScanCode::MostActiveUsd => "MOST_ACTIVE_USD",
+            ScanCode::HotByVolume => "HOT_BY_VOLUME",
+            ScanCode::HotByPrice => "HOT_BY_PRICE",
+            ScanCode::TopTradeCount => "TOP_TRADE_COUNT",
+            ScanCode::TopOpenPercentGain => "TOP_OPEN_PERC_GAIN",
+            ScanCode::TopOpenPercentLose => "TOP_OPEN_PERC_LOSE",
+            ScanCode::HighOptionImpliedVolatility => "HIGH_OPT_IMP_VOLAT",
+            ScanCode::LowOptionImpliedVolatility => "LOW_OPT_IMP_VOLAT",
+            ScanCode::TopOptionImpliedVolatilityGain => "TOP_OPT_IMP_VOLAT_GAIN",
+            ScanCode::High52Week => "HIGH_VS_52W_HL",
+            ScanCode::Low52Week => "LOW_VS_52W_HL",
+            ScanCode::HighDividendYield => "HIGH_DIVIDEND_YIELD_IB",
+            ScanCode::Other(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for ScanCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Kind of stock a scan is restricted to.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StockType {
+    /// Common stock of corporations (`CORP`).
+    Corporation,
+    /// American depositary receipts (`ADR`).
+    Adr,
+    /// Exchange traded funds (`ETF`).
+    Etf,
+    /// Real estate investment trusts (`REIT`).
+    Reit,
+    /// Closed end funds (`CEF`).
+    ClosedEndFund,
+    /// Any other stock type.
+    Other(String),
+}
+
+impl StockType {
+    /// The code sent to TWS.
+    pub fn code(&self) -> &str {
+        match self {
+            StockType::Corporation => "CORP",
+            StockType::Adr => "ADR",
+            StockType::Etf => "ETF",
+            StockType::Reit => "REIT",
+            StockType::ClosedEndFund => "CEF",
+            StockType::Other(code) => code,
+        }
+    }
+}
+
+/// Builder for a [ScannerSubscription] and the generic filters sent along with it.
+///
+/// The typed filter methods emit the filter tags TWS expects; [filter_tag](Self::filter_tag) adds
+/// any other filter listed by `scanner_parameters`. Setting a filter twice keeps the last value.
+///
+/// # Examples
+///
+/// ```
+/// use ibapi::scanner::{Instrument, LocationCode, ScanCode, ScannerQuery};
+///
+/// let (subscription, filter) = ScannerQuery::new(Instrument::Stock, LocationCode::StockUsMajor, ScanCode::TopPercentGain)
+///     .rows(25)
+///     .price_above(5.0)
+///     .volume_above(1_000_000)
+///     .market_cap_above(2_000.0)
+///     .build();
+///
+/// assert_eq!(subscription.scan_code.as_deref(), Some("TOP_PERC_GAIN"));
+/// assert_eq!(filter[0].tag, "priceAbove");
+/// assert_eq!(filter[2].tag, "marketCapAbove1e6");
+///
+/// // client.scanner_subscription(&subscription, &filter)
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScannerQuery {
+    subscription: ScannerSubscription,
+    filter: Vec<TagValue>,
+}
+
+impl ScannerQuery {
+    /// Creates a query for `scan_code` over `instrument` at `location`.
+    pub fn new(instrument: Instrument, location: LocationCode, scan_code: ScanCode) -> Self {
+        Self {
+            subscription: ScannerSubscription {
+                instrument: Some(instrument.code().to_string()),
+                location_code: Some(location.code().to_string()),
+                scan_code: Some(scan_code.code().to_string()),
+                ..Default::default()
+            },
+            filter: Vec::new(),
+        }
+    }
+
+    /// Number of results to return. TWS returns at most 50.
+    pub fn rows(mut self, rows: i32) -> Self {
+        self.subscription.number_of_rows = rows;
+        self
+    }
+
+    /// Restricts the scan to one kind of stock.
+    pub fn stock_type(mut self, stock_type: StockType) -> Self {
+        self.subscription.stock_type_filter = Some(stock_type.code().to_string());
+        self
+    }
+
+    /// Excludes contracts priced below `price`.
+    pub fn price_above(self, price: f64) -> Self {
+        self.filter_tag("priceAbove", price)
+    }
+
+    /// Excludes contracts priced above `price`.
+    pub fn price_below(self, price: f64) -> Self {
+        self.filter_tag("priceBelow", price)
+    }
+
+    /// Excludes contracts that traded fewer than `volume` shares today.
+    pub fn volume_above(self, volume: i64) -> Self {
+        self.filter_tag("volumeAbove", volume)
+    }
+
+    /// Excludes contracts with a market cap below `millions` of USD.
+    pub fn market_cap_above(self, millions: f64) -> Self {
+        self.filter_tag("marketCapAbove1e6", millions)
+    }
+
+    /// Excludes contracts with a market cap above `millions` of USD.
+    pub fn market_cap_below(self, millions: f64) -> Self {
+        self.filter_tag("marketCapBelow1e6", millions)
+    }
+
+    /// Excludes contracts whose option implied volatility is below `percent`.
+    pub fn implied_volatility_above(self, percent: f64) -> Self {
+        self.filter_tag("impVolatAbove", percent)
+    }
+
+    /// Excludes contracts whose option implied volatility is above `percent`.
+    pub fn implied_volatility_below(self, percent: f64) -> Self {
+        self.filter_tag("impVolatBelow", percent)
+    }
+
+    /// Excludes contracts whose change since the previous close is below `percent`.
+    pub fn change_percent_above(self, percent: f64) -> Self {
+        self.filter_tag("changePercAbove", percent)
+    }
+
+    /// Excludes contracts whose change since the previous close is above `percent`.
+    pub fn change_percent_below(self, percent: f64) -> Self {
+        self.filter_tag("changePercBelow", percent)
+    }
+
+    /// Sets a filter that has no typed method, using its tag from `scanner_parameters`.
+    pub fn filter_tag(mut self, tag: &str, value: impl ToString) -> Self {
+        let value = value.to_string();
+        match self.filter.iter_mut().find(|existing| existing.tag == tag) {
+            Some(existing) => existing.value = value,
+            None => self.filter.push(TagValue { tag: tag.to_string(), value }),
+        }
+        self
+    }
+
+    /// The subscription built so far.
+    pub fn subscription(&self) -> &ScannerSubscription {
+        &self.subscription
+    }
+
+    /// The generic filters built so far.
+    pub fn filters(&self) -> &[TagValue] {
+        &self.filter
+    }
+
+    /// Returns the arguments for `Client::scanner_subscription`.
+    pub fn build(self) -> (ScannerSubscription, Vec<TagValue>) {
+        (self.subscription, self.filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(filter: &[TagValue]) -> Vec<(&str, &str)> {
+        filter.iter().map(|tag| (tag.tag.as_str(), tag.value.as_str())).collect()
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        for code in ["STK", "ETF.EQ.US", "STOCK.EU", "STOCK.HK", "FUT.US", "IND.US", "BOND", "NATCOMB"] {
+            assert_eq!(Instrument::from_code(code).code(), code);
+        }
+        for code in ["STK.US", "STK.US.MAJOR", "STK.NASDAQ", "STK.HK.SEHK", "FUT.US", "STK.NASDAQ.SCM"] {
+            assert_eq!(LocationCode::from_code(code).to_string(), code);
+        }
+        for code in [
+            "TOP_PERC_GAIN",
+            "HOT_BY_VOLUME",
+            "HIGH_OPT_IMP_VOLAT",
+            "HIGH_VS_52W_HL",
+            "SCAN_socialSentimentScore_DESC",
+        ] {
+            assert_eq!(ScanCode::from_code(code).code(), code);
+        }
+
+        assert_eq!(ScanCode::from_code("HOT_BY_VOLUME"), ScanCode::HotByVolume);
+        assert_eq!(Instrument::from_code("NATCOMB"), Instrument::Other("NATCOMB".to_string()));
+    }
+
+    #[test]
+    fn test_build() {
+        let (subscription, filter) = ScannerQuery::new(Instrument::Stock, LocationCode::StockUsMajor, ScanCode::HotByVolume)
+            .rows(10)
+            .stock_type(StockType::Corporation)
+            .price_above(5.0)
+            .price_below(250.5)
+            .volume_above(500_000)
+            .market_cap_above(1_000.0)
+            .market_cap_below(50_000.0)
+            .implied_volatility_above(30.0)
+            .implied_volatility_below(80.0)
+            .change_percent_above(2.5)
+            .change_percent_below(20.0)
+            .build();
+
+        assert_eq!(
+            subscription,
+            ScannerSubscription {
+                number_of_rows: 10,
+                instrument: Some("STK".to_string()),
+                location_code: Some("STK.US.MAJOR".to_string()),
+                scan_code: Some("HOT_BY_VOLUME".to_string()),
+                stock_type_filter: Some("CORP".to_string()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            tags(&filter),
+            vec![
+                ("priceAbove", "5"),
+                ("priceBelow", "250.5"),
+                ("volumeAbove", "500000"),
+                ("marketCapAbove1e6", "1000"),
+                ("marketCapBelow1e6", "50000"),
+                ("impVolatAbove", "30"),
+                ("impVolatBelow", "80"),
+                ("changePercAbove", "2.5"),
+                ("changePercBelow", "20"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_tag_replaces_previous_value() {
+        let query = ScannerQuery::new(
+            Instrument::Other("STOCK.NA".to_string()),
+            LocationCode::Other("STK.NA.CANADA".to_string()),
+            ScanCode::Other("SCAN_socialSentimentScore_DESC".to_string()),
+        )
+        .price_above(1.0)
+        .filter_tag("avgVolumeAbove", 100_000)
+        .price_above(2.0);
+
+        assert_eq!(query.subscription().instrument.as_deref(), Some("STOCK.NA"));
+        assert_eq!(query.subscription().location_code.as_deref(), Some("STK.NA.CANADA"));
+        assert_eq!(tags(query.filters()), vec![("priceAbove", "2"), ("avgVolumeAbove", "100000")]);
+        assert_eq!(query.subscription().number_of_rows, -1);
+    }
+}
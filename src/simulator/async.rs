@@ -0,0 +1,202 @@
+//! Asynchronous message bus backed by the simulated broker
+
+use async_trait::async_trait;
+use tokio::sync::broadcast;
+
+use super::{Outlet, SimulatedBroker};
+use crate::messages::{OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::transport::{AsyncInternalSubscription, AsyncMessageBus};
+use crate::Error;
+
+// Matches the capacity of the TCP message bus channels.
+const CHANNEL_CAPACITY: usize = 1024;
+
+impl Outlet for broadcast::Sender<ResponseMessage> {
+    fn deliver(&self, message: &ResponseMessage) -> bool {
+        // Sending fails only when every receiver has been dropped.
+        self.send(message.clone()).is_ok()
+    }
+}
+
+/// Routes requests from an async client to a [SimulatedBroker].
+pub(crate) struct AsyncSimulatedMessageBus {
+    broker: SimulatedBroker,
+}
+
+impl AsyncSimulatedMessageBus {
+    pub(crate) fn new(broker: SimulatedBroker) -> Self {
+        Self { broker }
+    }
+
+    fn send(&self, message: &RequestMessage) -> Result<(), Error> {
+        let mut state = self.broker.lock();
+        let replies = state.engine.handle(message)?;
+        state.dispatch(replies);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsyncMessageBus for AsyncSimulatedMessageBus {
+    async fn send_request(&self, request_id: i32, message: RequestMessage) -> Result<AsyncInternalSubscription, Error> {
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+
+        let mut state = self.broker.lock();
+        let replies = state.engine.handle(&message)?;
+        state.async_routes.requests.insert(request_id, sender);
+        state.dispatch(replies);
+
+        Ok(AsyncInternalSubscription::new(receiver))
+    }
+
+    async fn send_order_request(&self, order_id: i32, message: RequestMessage) -> Result<AsyncInternalSubscription, Error> {
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+
+        let mut state = self.broker.lock();
+        let replies = state.engine.handle(&message)?;
+        state.async_routes.orders.insert(order_id, sender);
+        state.dispatch(replies);
+
+        Ok(AsyncInternalSubscription::new(receiver))
+    }
+
+    async fn send_shared_request(&self, message_type: OutgoingMessages, message: RequestMessage) -> Result<AsyncInternalSubscription, Error> {
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+
+        let mut state = self.broker.lock();
+        let replies = state.engine.handle(&message)?;
+        state.async_routes.shared.entry(message_type).or_default().push(sender);
+        state.dispatch(replies);
+
+        Ok(AsyncInternalSubscription::new(receiver))
+    }
+
+    async fn send_message(&self, message: RequestMessage) -> Result<(), Error> {
+        self.send(&message)
+    }
+
+    async fn cancel_subscription(&self, request_id: i32, message: RequestMessage) -> Result<(), Error> {
+        self.send(&message)?;
+
+        let mut state = self.broker.lock();
+        if let Some(sender) = state.async_routes.requests.remove(&request_id) {
+            let _ = sender.send(ResponseMessage::from("Cancelled"));
+        }
+
+        Ok(())
+    }
+
+    async fn cancel_order_subscription(&self, order_id: i32, message: RequestMessage) -> Result<(), Error> {
+        self.send(&message)?;
+
+        let mut state = self.broker.lock();
+        if let Some(sender) = state.async_routes.orders.remove(&order_id) {
+            let _ = sender.send(ResponseMessage::from("Cancelled"));
+        }
+
+        Ok(())
+    }
+
+    async fn create_order_update_subscription(&self) -> Result<AsyncInternalSubscription, Error> {
+        let mut state = self.broker.lock();
+
+        // Subscriptions hand their receiver off, so a dropped stream shows up as a sender without receivers.
+        if state
+            .async_routes
+            .order_updates
+            .as_ref()
+            .is_some_and(|sender| sender.receiver_count() > 0)
+        {
+            return Err(Error::AlreadySubscribed);
+        }
+
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        state.async_routes.order_updates = Some(sender);
+
+        Ok(AsyncInternalSubscription::new(receiver))
+    }
+
+    async fn ensure_shutdown(&self) {}
+
+    fn request_shutdown_sync(&self) {}
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accounts::PositionUpdate;
+    use crate::client::r#async::Client;
+    use crate::contracts::Contract;
+    use crate::orders::{order_builder, Action, PlaceOrder};
+    use crate::simulator::SimulatedBroker;
+    use crate::Decimal;
+
+    use super::super::tests::bar;
+
+    #[tokio::test]
+    async fn test_bracket_order_fills_take_profit() {
+        let broker = SimulatedBroker::default();
+        let client = Client::simulated(&broker);
+        let contract = Contract::stock("AAPL").build();
+
+        let parent_id = client.next_order_id();
+        let orders = order_builder::bracket_order(parent_id, Action::Buy, 100.0, 100.0, 105.0, 95.0);
+
+        let mut parent = client
+            .place_order(orders[0].order_id, &contract, &orders[0])
+            .await
+            .expect("parent failed");
+        let mut take_profit = client
+            .place_order(orders[1].order_id, &contract, &orders[1])
+            .await
+            .expect("take profit failed");
+        let mut stop_loss = client
+            .place_order(orders[2].order_id, &contract, &orders[2])
+            .await
+            .expect("stop loss failed");
+
+        broker.on_bar(&contract, &bar(100.5, 101.0, 99.5, 100.0));
+
+        let mut parent_filled = false;
+        while let Some(Ok(event)) = parent.next().await {
+            if let PlaceOrder::ExecutionData(execution) = event {
+                assert_eq!(execution.execution.price, 100.0);
+                parent_filled = true;
+                break;
+            }
+        }
+        assert!(parent_filled);
+
+        let mut positions = client.positions().await.expect("positions failed");
+
+        broker.on_bar(&contract, &bar(102.0, 106.0, 101.5, 105.5));
+
+        let mut take_profit_filled = false;
+        while let Some(Ok(event)) = take_profit.next().await {
+            if let PlaceOrder::ExecutionData(execution) = event {
+                assert_eq!(execution.execution.price, 105.0);
+                take_profit_filled = true;
+                break;
+            }
+        }
+        assert!(take_profit_filled);
+
+        let mut stop_loss_cancelled = false;
+        while let Some(Ok(event)) = stop_loss.next().await {
+            if let PlaceOrder::OrderStatus(status) = event {
+                if status.status == "Cancelled" {
+                    stop_loss_cancelled = true;
+                    break;
+                }
+            }
+        }
+        assert!(stop_loss_cancelled);
+
+        assert!(matches!(positions.next().await, Some(Ok(PositionUpdate::Position(position))) if position.position == Decimal::from(100)));
+        assert!(matches!(positions.next().await, Some(Ok(PositionUpdate::PositionEnd))));
+        assert_eq!(broker.position(&contract), Decimal::from(0));
+    }
+}
@@ -0,0 +1,460 @@
+//! Encoders for the responses produced by the simulated broker
+//!
+//! Messages are written in the layout the decoders expect at [SERVER_VERSION](super::SERVER_VERSION),
+//! so they take the same path through the client as responses from a real Gateway.
+
+use crate::accounts::Position;
+use crate::contracts::Contract;
+use crate::messages::{IncomingMessages, ResponseMessage, INFINITY_STR};
+use crate::orders::{CommissionReport, ExecutionData, Order, OrderState, OrderStatus};
+use crate::ToField;
+
+use super::SERVER_VERSION;
+
+/// Accumulates the fields of a response message.
+struct MessageBuilder {
+    fields: Vec<String>,
+}
+
+impl MessageBuilder {
+    fn new(message_type: IncomingMessages) -> Self {
+        Self {
+            fields: vec![(message_type as i32).to_string()],
+        }
+    }
+
+    fn push<T: ToField>(&mut self, value: &T) -> &mut Self {
+        self.fields.push(value.to_field());
+        self
+    }
+
+    fn build(self) -> ResponseMessage {
+        ResponseMessage {
+            i: 0,
+            fields: self.fields,
+            server_version: SERVER_VERSION,
+        }
+    }
+}
+
+// Unbounded offsets are sent as "Infinity" rather than Rust's "inf".
+fn optional_price(value: Option<f64>) -> String {
+    match value {
+        Some(value) if value.is_infinite() => INFINITY_STR.to_string(),
+        _ => value.to_field(),
+    }
+}
+
+pub(super) fn encode_open_order(order_id: i32, contract: &Contract, order: &Order, order_state: &OrderState) -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::OpenOrder);
+
+    message.push(&order_id);
+
+    message
+        .push(&contract.contract_id)
+        .push(&contract.symbol)
+        .push(&contract.security_type)
+        .push(&contract.last_trade_date_or_contract_month)
+        .push(&contract.strike)
+        .push(&contract.right)
+        .push(&contract.multiplier)
+        .push(&contract.exchange)
+        .push(&contract.currency)
+        .push(&contract.local_symbol)
+        .push(&contract.trading_class);
+
+    message
+        .push(&order.action)
+        .push(&order.total_quantity)
+        .push(&order.order_type)
+        .push(&order.limit_price)
+        .push(&order.aux_price)
+        .push(&order.tif)
+        .push(&order.oca_group)
+        .push(&order.account)
+        .push(&order.open_close)
+        .push(&order.origin)
+        .push(&order.order_ref)
+        .push(&order.client_id)
+        .push(&order.perm_id)
+        .push(&order.outside_rth)
+        .push(&order.hidden)
+        .push(&order.discretionary_amt)
+        .push(&order.good_after_time)
+        .push(&"") // deprecated shares allocation
+        .push(&order.fa_group)
+        .push(&order.fa_method)
+        .push(&order.fa_percentage)
+        .push(&order.model_code)
+        .push(&order.good_till_date)
+        .push(&order.rule_80_a)
+        .push(&order.percent_offset)
+        .push(&order.settling_firm)
+        .push(&order.short_sale_slot)
+        .push(&order.designated_location)
+        .push(&order.exempt_code)
+        .push(&order.auction_strategy)
+        .push(&order.starting_price)
+        .push(&order.stock_ref_price)
+        .push(&order.delta)
+        .push(&order.stock_range_lower)
+        .push(&order.stock_range_upper)
+        .push(&order.display_size)
+        .push(&order.block_order)
+        .push(&order.sweep_to_fill)
+        .push(&order.all_or_none)
+        .push(&order.min_qty)
+        .push(&order.oca_type)
+        .push(&"") // e-trade only
+        .push(&"") // firm quote only
+        .push(&"") // NBBO price cap
+        .push(&order.parent_id)
+        .push(&order.trigger_method);
+
+    message
+        .push(&order.volatility)
+        .push(&order.volatility_type)
+        .push(&order.delta_neutral_order_type)
+        .push(&order.delta_neutral_aux_price);
+    if order.is_delta_neutral() {
+        message
+            .push(&order.delta_neutral_con_id)
+            .push(&order.delta_neutral_settling_firm)
+            .push(&order.delta_neutral_clearing_account)
+            .push(&order.delta_neutral_clearing_intent)
+            .push(&order.delta_neutral_open_close)
+            .push(&order.delta_neutral_short_sale)
+            .push(&order.delta_neutral_short_sale_slot)
+            .push(&order.delta_neutral_designated_location);
+    }
+    message.push(&order.continuous_update).push(&order.reference_price_type);
+
+    message
+        .push(&order.trail_stop_price)
+        .push(&order.trailing_percent)
+        .push(&order.basis_points)
+        .push(&order.basis_points_type);
+
+    message.push(&contract.combo_legs_description).push(&contract.combo_legs.len());
+    for combo_leg in &contract.combo_legs {
+        message
+            .push(&combo_leg.contract_id)
+            .push(&combo_leg.ratio)
+            .push(&combo_leg.action)
+            .push(&combo_leg.exchange)
+            .push(&combo_leg.open_close)
+            .push(&combo_leg.short_sale_slot)
+            .push(&combo_leg.designated_location)
+            .push(&combo_leg.exempt_code);
+    }
+    message.push(&order.order_combo_legs.len());
+    for combo_leg in &order.order_combo_legs {
+        message.push(&combo_leg.price);
+    }
+    message.push(&order.smart_combo_routing_params.len());
+    for tag_value in &order.smart_combo_routing_params {
+        message.push(&tag_value.tag).push(&tag_value.value);
+    }
+
+    message
+        .push(&order.scale_init_level_size)
+        .push(&order.scale_subs_level_size)
+        .push(&order.scale_price_increment);
+    if order.scale_price_increment.is_some_and(|increment| increment > 0.0) {
+        message
+            .push(&order.scale_price_adjust_value)
+            .push(&order.scale_price_adjust_interval)
+            .push(&order.scale_profit_offset)
+            .push(&order.scale_auto_reset)
+            .push(&order.scale_init_position)
+            .push(&order.scale_init_fill_qty)
+            .push(&order.scale_random_percent);
+    }
+
+    message.push(&order.hedge_type);
+    if !order.hedge_type.is_empty() {
+        message.push(&order.hedge_param);
+    }
+
+    message
+        .push(&order.opt_out_smart_routing)
+        .push(&order.clearing_account)
+        .push(&order.clearing_intent)
+        .push(&order.not_held);
+
+    if let Some(delta_neutral_contract) = &contract.delta_neutral_contract {
+        message
+            .push(&true)
+            .push(&delta_neutral_contract.contract_id)
+            .push(&delta_neutral_contract.delta)
+            .push(&delta_neutral_contract.price);
+    } else {
+        message.push(&false);
+    }
+
+    message.push(&order.algo_strategy);
+    if !order.algo_strategy.is_empty() {
+        message.push(&order.algo_params.len());
+        for tag_value in &order.algo_params {
+            message.push(&tag_value.tag).push(&tag_value.value);
+        }
+    }
+
+    message.push(&order.solicited);
+
+    encode_order_state(&mut message, order, order_state);
+
+    message.push(&order.randomize_size).push(&order.randomize_price);
+
+    if order.order_type == "PEG BENCH" {
+        message
+            .push(&order.reference_contract_id)
+            .push(&order.is_pegged_change_amount_decrease)
+            .push(&order.pegged_change_amount)
+            .push(&order.reference_change_amount)
+            .push(&order.reference_exchange);
+    }
+
+    // The simulator does not evaluate order conditions, so none are reported back.
+    message.push(&0);
+
+    message
+        .push(&order.adjusted_order_type)
+        .push(&order.trigger_price)
+        .push(&order.trail_stop_price)
+        .push(&order.limit_price_offset)
+        .push(&order.adjusted_stop_price)
+        .push(&order.adjusted_stop_limit_price)
+        .push(&order.adjusted_trailing_amount)
+        .push(&order.adjustable_trailing_unit);
+
+    message
+        .push(&order.soft_dollar_tier.name)
+        .push(&order.soft_dollar_tier.value)
+        .push(&order.soft_dollar_tier.display_name);
+
+    message
+        .push(&order.cash_qty)
+        .push(&order.dont_use_auto_price_for_hedge)
+        .push(&order.is_oms_container)
+        .push(&order.discretionary_up_to_limit_price)
+        .push(&order.use_price_mgmt_algo)
+        .push(&order.duration)
+        .push(&order.post_to_ats)
+        .push(&order.auto_cancel_parent)
+        .push(&order.min_trade_qty)
+        .push(&order.min_compete_size)
+        .push(&optional_price(order.compete_against_best_offset))
+        .push(&order.mid_offset_at_whole)
+        .push(&order.mid_offset_at_half)
+        .push(&order.customer_account)
+        .push(&order.professional_customer)
+        .push(&order.bond_accrued_interest)
+        .push(&order.include_overnight)
+        .push(&order.ext_operator)
+        .push(&order.manual_order_indicator)
+        .push(&order.submitter)
+        .push(&order.imbalance_only);
+
+    message.build()
+}
+
+fn encode_order_state(message: &mut MessageBuilder, order: &Order, order_state: &OrderState) {
+    message
+        .push(&order.what_if)
+        .push(&order_state.status)
+        .push(&order_state.initial_margin_before)
+        .push(&order_state.maintenance_margin_before)
+        .push(&order_state.equity_with_loan_before)
+        .push(&order_state.initial_margin_change)
+        .push(&order_state.maintenance_margin_change)
+        .push(&order_state.equity_with_loan_change)
+        .push(&order_state.initial_margin_after)
+        .push(&order_state.maintenance_margin_after)
+        .push(&order_state.equity_with_loan_after)
+        .push(&order_state.commission)
+        .push(&order_state.minimum_commission)
+        .push(&order_state.maximum_commission)
+        .push(&order_state.commission_currency)
+        .push(&order_state.margin_currency)
+        .push(&order_state.initial_margin_before_outside_rth)
+        .push(&order_state.maintenance_margin_before_outside_rth)
+        .push(&order_state.equity_with_loan_before_outside_rth)
+        .push(&order_state.initial_margin_change_outside_rth)
+        .push(&order_state.maintenance_margin_change_outside_rth)
+        .push(&order_state.equity_with_loan_change_outside_rth)
+        .push(&order_state.initial_margin_after_outside_rth)
+        .push(&order_state.maintenance_margin_after_outside_rth)
+        .push(&order_state.equity_with_loan_after_outside_rth)
+        .push(&order_state.suggested_size)
+        .push(&order_state.reject_reason)
+        .push(&order_state.order_allocations.len());
+
+    for allocation in &order_state.order_allocations {
+        message
+            .push(&allocation.account)
+            .push(&allocation.position)
+            .push(&allocation.position_desired)
+            .push(&allocation.position_after)
+            .push(&allocation.desired_alloc_qty)
+            .push(&allocation.allowed_alloc_qty)
+            .push(&allocation.is_monetary);
+    }
+
+    message.push(&order_state.warning_text);
+}
+
+pub(super) fn encode_order_status(order_status: &OrderStatus) -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::OrderStatus);
+
+    message
+        .push(&order_status.order_id)
+        .push(&order_status.status)
+        .push(&order_status.filled)
+        .push(&order_status.remaining)
+        .push(&order_status.average_fill_price)
+        .push(&order_status.perm_id)
+        .push(&order_status.parent_id)
+        .push(&order_status.last_fill_price)
+        .push(&order_status.client_id)
+        .push(&order_status.why_held)
+        .push(&order_status.market_cap_price);
+
+    message.build()
+}
+
+pub(super) fn encode_execution_data(execution_data: &ExecutionData) -> ResponseMessage {
+    let contract = &execution_data.contract;
+    let execution = &execution_data.execution;
+
+    let mut message = MessageBuilder::new(IncomingMessages::ExecutionData);
+
+    message
+        .push(&execution_data.request_id)
+        .push(&execution.order_id)
+        .push(&contract.contract_id)
+        .push(&contract.symbol)
+        .push(&contract.security_type)
+        .push(&contract.last_trade_date_or_contract_month)
+        .push(&contract.strike)
+        .push(&contract.right)
+        .push(&contract.multiplier)
+        .push(&contract.exchange)
+        .push(&contract.currency)
+        .push(&contract.local_symbol)
+        .push(&contract.trading_class)
+        .push(&execution.execution_id)
+        .push(&execution.time)
+        .push(&execution.account_number)
+        .push(&execution.exchange)
+        .push(&execution.side)
+        .push(&execution.shares)
+        .push(&execution.price)
+        .push(&execution.perm_id)
+        .push(&execution.client_id)
+        .push(&execution.liquidation)
+        .push(&execution.cumulative_quantity)
+        .push(&execution.average_price)
+        .push(&execution.order_reference)
+        .push(&execution.ev_rule)
+        .push(&execution.ev_multiplier)
+        .push(&execution.model_code)
+        .push(&(execution.last_liquidity.clone() as i32))
+        .push(&execution.pending_price_revision)
+        .push(&execution.submitter);
+
+    message.build()
+}
+
+pub(super) fn encode_commission_report(commission_report: &CommissionReport) -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::CommissionsReport);
+
+    message
+        .push(&1) // message version
+        .push(&commission_report.execution_id)
+        .push(&commission_report.commission)
+        .push(&commission_report.currency)
+        .push(&commission_report.realized_pnl)
+        .push(&commission_report.yields)
+        .push(&commission_report.yield_redemption_date);
+
+    message.build()
+}
+
+pub(super) fn encode_position(position: &Position) -> ResponseMessage {
+    let contract = &position.contract;
+
+    let mut message = MessageBuilder::new(IncomingMessages::Position);
+
+    message
+        .push(&3) // message version
+        .push(&position.account)
+        .push(&contract.contract_id)
+        .push(&contract.symbol)
+        .push(&contract.security_type)
+        .push(&contract.last_trade_date_or_contract_month)
+        .push(&contract.strike)
+        .push(&contract.right)
+        .push(&contract.multiplier)
+        .push(&contract.exchange)
+        .push(&contract.currency)
+        .push(&contract.local_symbol)
+        .push(&contract.trading_class)
+        .push(&position.position)
+        .push(&position.average_cost);
+
+    message.build()
+}
+
+pub(super) fn encode_position_end() -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::PositionEnd);
+    message.push(&1); // message version
+    message.build()
+}
+
+pub(super) fn encode_open_order_end() -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::OpenOrderEnd);
+    message.push(&1); // message version
+    message.build()
+}
+
+pub(super) fn encode_execution_data_end(request_id: i32) -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::ExecutionDataEnd);
+    message.push(&1).push(&request_id); // message version, request id
+    message.build()
+}
+
+pub(super) fn encode_completed_orders_end() -> ResponseMessage {
+    MessageBuilder::new(IncomingMessages::CompletedOrdersEnd).build()
+}
+
+pub(super) fn encode_next_valid_id(order_id: i32) -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::NextValidId);
+    message.push(&1).push(&order_id); // message version, order id
+    message.build()
+}
+
+pub(super) fn encode_managed_accounts(account: &str) -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::ManagedAccounts);
+    message.push(&1).push(&account); // message version, accounts
+    message.build()
+}
+
+pub(super) fn encode_current_time(timestamp: i64) -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::CurrentTime);
+    message.push(&1).push(&timestamp.to_string()); // message version, seconds since epoch
+    message.build()
+}
+
+pub(super) fn encode_error(id: i32, code: i32, text: &str, timestamp_millis: i64) -> ResponseMessage {
+    let mut message = MessageBuilder::new(IncomingMessages::Error);
+
+    message
+        .push(&id)
+        .push(&code)
+        .push(&text)
+        .push(&"") // advanced order reject json
+        .push(&timestamp_millis.to_string());
+
+    message.build()
+}
@@ -0,0 +1,837 @@
+//! Order matching and account bookkeeping for the simulated broker
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use log::warn;
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+use super::{encoders, SimulatorOptions, SERVER_VERSION};
+use crate::accounts::Position;
+use crate::contracts::{Contract, Currency, Exchange, SecurityType, Symbol};
+use crate::messages::{OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::orders::{Action, CommissionReport, Execution, ExecutionData, Liquidity, Order, OrderOpenClose, OrderState, OrderStatus, TimeInForce};
+use crate::{decimal, Decimal, Error, ToField};
+
+const SUPPORTED_ORDER_TYPES: &[&str] = &["MKT", "LMT", "STP", "STP LMT"];
+
+// First permanent id handed out; keeps simulated ids visually distinct from order ids.
+const FIRST_PERM_ID: i32 = 1_000_000;
+
+/// Where a simulated response is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Route {
+    /// Subscription created with a request id.
+    Request(i32),
+    /// Subscription created for an order id. Also copied to the order update stream.
+    Order(i32),
+    /// Subscriptions sharing a channel by request type.
+    Shared(OutgoingMessages),
+}
+
+/// A response produced by the engine, with its destination.
+#[derive(Debug, Clone)]
+pub(crate) struct Reply {
+    pub(crate) route: Route,
+    pub(crate) message: ResponseMessage,
+}
+
+impl Reply {
+    fn new(route: Route, message: ResponseMessage) -> Self {
+        Self { route, message }
+    }
+}
+
+/// Price movement of one instrument over an interval. A tick is an interval where all four prices are equal.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PriceEvent {
+    pub(crate) time: OffsetDateTime,
+    pub(crate) open: f64,
+    pub(crate) high: f64,
+    pub(crate) low: f64,
+    pub(crate) close: f64,
+}
+
+impl PriceEvent {
+    pub(crate) fn tick(time: OffsetDateTime, price: f64) -> Self {
+        Self {
+            time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        }
+    }
+}
+
+/// Identifies an instrument independently of routing details such as exchange or contract id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct ContractKey {
+    symbol: String,
+    security_type: String,
+    expiry: String,
+    strike: u64,
+    right: String,
+}
+
+impl From<&Contract> for ContractKey {
+    fn from(contract: &Contract) -> Self {
+        Self {
+            symbol: contract.symbol.to_field(),
+            security_type: contract.security_type.to_field(),
+            expiry: contract.last_trade_date_or_contract_month.clone(),
+            strike: contract.strike.to_bits(),
+            right: contract.right.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// Received without `transmit`; waits for a transmitting order from the same group.
+    Staged,
+    /// Child order waiting for its parent to fill.
+    Held,
+    /// Eligible to fill on the next price event.
+    Working,
+    Filled,
+    Cancelled,
+}
+
+impl Stage {
+    fn status(self) -> &'static str {
+        match self {
+            Stage::Staged => "Inactive",
+            Stage::Held => "PreSubmitted",
+            Stage::Working => "Submitted",
+            Stage::Filled => "Filled",
+            Stage::Cancelled => "Cancelled",
+        }
+    }
+
+    fn is_open(self) -> bool {
+        matches!(self, Stage::Held | Stage::Working)
+    }
+
+    fn is_cancellable(self) -> bool {
+        self.is_open() || self == Stage::Staged
+    }
+}
+
+#[derive(Debug)]
+struct SimulatedOrder {
+    contract: Contract,
+    order: Order,
+    stage: Stage,
+    filled: Decimal,
+    average_fill_price: f64,
+    last_fill_price: f64,
+    commission: Option<f64>,
+    // Set once a stop limit order has been triggered and rests as a limit order.
+    triggered: bool,
+}
+
+impl SimulatedOrder {
+    fn is_buy(&self) -> bool {
+        self.order.action == Action::Buy
+    }
+
+    fn remaining(&self) -> Decimal {
+        self.order.total_quantity - self.filled
+    }
+
+    /// Returns the price this order fills at during `event`, if it fills at all.
+    fn fill_price(&mut self, event: &PriceEvent) -> Option<f64> {
+        let buy = self.is_buy();
+
+        match self.order.order_type.as_str() {
+            "MKT" => Some(event.open),
+            "LMT" => limit_fill(buy, self.order.limit_price?, event),
+            "STP" => stop_trigger(buy, self.order.aux_price?, event),
+            "STP LMT" => {
+                let limit = self.order.limit_price?;
+                if self.triggered {
+                    return limit_fill(buy, limit, event);
+                }
+
+                let trigger = stop_trigger(buy, self.order.aux_price?, event)?;
+                self.triggered = true;
+
+                // Prices before the trigger cannot be reached, so only a marketable limit fills right away.
+                let marketable = if buy { trigger <= limit } else { trigger >= limit };
+                marketable.then_some(trigger)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn limit_fill(buy: bool, limit: f64, event: &PriceEvent) -> Option<f64> {
+    if buy {
+        if event.open <= limit {
+            Some(event.open)
+        } else {
+            (event.low <= limit).then_some(limit)
+        }
+    } else if event.open >= limit {
+        Some(event.open)
+    } else {
+        (event.high >= limit).then_some(limit)
+    }
+}
+
+fn stop_trigger(buy: bool, stop: f64, event: &PriceEvent) -> Option<f64> {
+    if buy {
+        if event.open >= stop {
+            Some(event.open)
+        } else {
+            (event.high >= stop).then_some(stop)
+        }
+    } else if event.open <= stop {
+        Some(event.open)
+    } else {
+        (event.low <= stop).then_some(stop)
+    }
+}
+
+#[derive(Debug)]
+struct Holding {
+    contract: Contract,
+    position: Decimal,
+    // Cost per unit including multiplier and commissions, as reported by TWS.
+    average_cost: f64,
+}
+
+/// Simulated broker state: orders, executions and positions for a single account.
+#[derive(Debug)]
+pub(crate) struct Engine {
+    options: SimulatorOptions,
+    next_order_id: i32,
+    next_perm_id: i32,
+    execution_count: i32,
+    time: OffsetDateTime,
+    orders: BTreeMap<i32, SimulatedOrder>,
+    last_prices: HashMap<ContractKey, f64>,
+    positions: BTreeMap<ContractKey, Holding>,
+    executions: Vec<(ExecutionData, CommissionReport)>,
+}
+
+impl Engine {
+    pub(crate) fn new(options: SimulatorOptions) -> Self {
+        Self {
+            options,
+            next_order_id: 1,
+            next_perm_id: FIRST_PERM_ID,
+            execution_count: 0,
+            time: OffsetDateTime::now_utc(),
+            orders: BTreeMap::new(),
+            last_prices: HashMap::new(),
+            positions: BTreeMap::new(),
+            executions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn options(&self) -> &SimulatorOptions {
+        &self.options
+    }
+
+    pub(crate) fn next_order_id(&self) -> i32 {
+        self.next_order_id
+    }
+
+    pub(crate) fn time(&self) -> OffsetDateTime {
+        self.time
+    }
+
+    pub(crate) fn position(&self, contract: &Contract) -> Decimal {
+        self.positions
+            .get(&ContractKey::from(contract))
+            .map_or(Decimal::default(), |holding| holding.position)
+    }
+
+    /// Processes a request sent by the client and returns the responses it produces.
+    pub(crate) fn handle(&mut self, request: &RequestMessage) -> Result<Vec<Reply>, Error> {
+        let message_type = OutgoingMessages::from_str(&request[0])?;
+        let mut message = ResponseMessage {
+            i: 1,
+            fields: request.fields.clone(),
+            server_version: SERVER_VERSION,
+        };
+
+        match message_type {
+            OutgoingMessages::PlaceOrder => self.place_order(&mut message),
+            OutgoingMessages::CancelOrder => self.cancel_order(&mut message),
+            OutgoingMessages::RequestGlobalCancel => Ok(self.global_cancel()),
+            OutgoingMessages::RequestIds => Ok(vec![Reply::new(
+                Route::Shared(message_type),
+                encoders::encode_next_valid_id(self.next_order_id),
+            )]),
+            OutgoingMessages::RequestOpenOrders | OutgoingMessages::RequestAllOpenOrders | OutgoingMessages::RequestAutoOpenOrders => {
+                Ok(self.open_orders(message_type))
+            }
+            // Completed orders are not retained across sessions, so the list is always empty.
+            OutgoingMessages::RequestCompletedOrders => Ok(vec![Reply::new(Route::Shared(message_type), encoders::encode_completed_orders_end())]),
+            OutgoingMessages::RequestExecutions => self.executions(&mut message),
+            OutgoingMessages::RequestPositions => Ok(self.positions()),
+            OutgoingMessages::RequestManagedAccounts => Ok(vec![Reply::new(
+                Route::Shared(message_type),
+                encoders::encode_managed_accounts(&self.options.account),
+            )]),
+            OutgoingMessages::RequestCurrentTime => Ok(vec![Reply::new(
+                Route::Shared(message_type),
+                encoders::encode_current_time(self.time.unix_timestamp()),
+            )]),
+            OutgoingMessages::CancelPositions | OutgoingMessages::RequestMarketDataType => Ok(Vec::new()),
+            _ => {
+                warn!("simulated broker does not support {message_type:?} requests");
+                Err(Error::NotImplemented)
+            }
+        }
+    }
+
+    /// Advances the clock to `event` and fills the working orders on `contract` it reaches.
+    pub(crate) fn on_price(&mut self, contract: &Contract, event: PriceEvent) -> Vec<Reply> {
+        let key = ContractKey::from(contract);
+
+        self.time = event.time;
+        self.last_prices.insert(key.clone(), event.close);
+
+        let order_ids: Vec<i32> = self
+            .orders
+            .iter()
+            .filter(|(_, order)| order.stage == Stage::Working && ContractKey::from(&order.contract) == key)
+            .map(|(order_id, _)| *order_id)
+            .collect();
+
+        self.match_orders(&order_ids, &event)
+    }
+
+    fn match_orders(&mut self, order_ids: &[i32], event: &PriceEvent) -> Vec<Reply> {
+        let mut replies = Vec::new();
+
+        for order_id in order_ids {
+            // An earlier fill in this event may have cancelled the order through its OCA group.
+            let fill_price = match self.orders.get_mut(order_id) {
+                Some(order) if order.stage == Stage::Working => order.fill_price(event),
+                _ => None,
+            };
+
+            if let Some(price) = fill_price {
+                replies.extend(self.fill(*order_id, price, event.time));
+            }
+        }
+
+        replies
+    }
+
+    fn place_order(&mut self, message: &mut ResponseMessage) -> Result<Vec<Reply>, Error> {
+        let (order_id, contract, mut order) = decode_place_order(message)?;
+        self.next_order_id = self.next_order_id.max(order_id + 1);
+
+        if let Some(reason) = unsupported_reason(&contract, &order) {
+            return Ok(vec![self.error(order_id, 201, &format!("Order rejected - reason:{reason}"))]);
+        }
+
+        order.order_id = order_id;
+        order.client_id = self.options.client_id;
+        if order.account.is_empty() {
+            order.account = self.options.account.clone();
+        }
+
+        if let Some(existing) = self.orders.get_mut(&order_id) {
+            if !existing.stage.is_cancellable() {
+                return Ok(vec![self.error(order_id, 104, "Can't modify a filled order.")]);
+            }
+
+            order.perm_id = existing.order.perm_id;
+            existing.contract = contract;
+            existing.order = order;
+            existing.triggered = false;
+        } else {
+            order.perm_id = self.next_perm_id;
+            self.next_perm_id += 1;
+
+            self.orders.insert(
+                order_id,
+                SimulatedOrder {
+                    contract,
+                    order,
+                    stage: Stage::Staged,
+                    filled: Decimal::default(),
+                    average_fill_price: 0.0,
+                    last_fill_price: 0.0,
+                    commission: None,
+                    triggered: false,
+                },
+            );
+        }
+
+        let order = &self.orders[&order_id];
+        if !order.order.transmit {
+            // Staged orders are not reported until their group is transmitted.
+            return Ok(if order.stage.is_open() {
+                self.order_events(order_id)
+            } else {
+                Vec::new()
+            });
+        }
+
+        // A transmitting order releases its parent and any staged siblings.
+        let root_id = if order.order.parent_id != 0 { order.order.parent_id } else { order_id };
+        let group: Vec<i32> = self
+            .orders
+            .iter()
+            .filter(|(id, order)| **id == root_id || order.order.parent_id == root_id)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut replies = Vec::new();
+        let mut activated = Vec::new();
+        for id in group {
+            if id != order_id && self.orders[&id].stage != Stage::Staged {
+                continue;
+            }
+
+            let parent_stage = match self.orders[&id].order.parent_id {
+                0 => None,
+                parent_id => self.orders.get(&parent_id).map(|parent| parent.stage),
+            };
+            let stage = match parent_stage {
+                Some(Stage::Filled) | None => Stage::Working,
+                Some(Stage::Cancelled) => Stage::Cancelled,
+                Some(_) => Stage::Held,
+            };
+
+            // A modified live order keeps its current stage.
+            let order = self.orders.get_mut(&id).expect("order in group");
+            if order.stage == Stage::Staged {
+                order.stage = stage;
+            }
+            replies.extend(self.order_events(id));
+
+            if self.orders[&id].stage == Stage::Working {
+                activated.push(id);
+            }
+        }
+
+        // Working orders are checked against the last known price, so market orders fill immediately.
+        for id in activated {
+            if let Some(price) = self.last_prices.get(&ContractKey::from(&self.orders[&id].contract)).copied() {
+                replies.extend(self.match_orders(&[id], &PriceEvent::tick(self.time, price)));
+            }
+        }
+
+        Ok(replies)
+    }
+
+    fn cancel_order(&mut self, message: &mut ResponseMessage) -> Result<Vec<Reply>, Error> {
+        let order_id = message.next_int()?;
+
+        match self.orders.get(&order_id) {
+            None => Ok(vec![self.error(
+                order_id,
+                10147,
+                &format!("OrderId {order_id} that needs to be cancelled is not found."),
+            )]),
+            Some(order) if !order.stage.is_cancellable() => {
+                let text = format!(
+                    "Cancel attempted when order is not in a cancellable state. Order permId ={}",
+                    order.order.perm_id
+                );
+                Ok(vec![self.error(order_id, 161, &text)])
+            }
+            Some(_) => Ok(self.cancel(order_id, true)),
+        }
+    }
+
+    fn global_cancel(&mut self) -> Vec<Reply> {
+        let order_ids: Vec<i32> = self
+            .orders
+            .iter()
+            .filter(|(_, order)| order.stage.is_cancellable())
+            .map(|(order_id, _)| *order_id)
+            .collect();
+
+        let mut replies = Vec::new();
+        for order_id in order_ids {
+            if self.orders[&order_id].stage.is_cancellable() {
+                replies.extend(self.cancel(order_id, true));
+            }
+        }
+        replies
+    }
+
+    // Cancels an order together with its unfilled children. `requested` adds the notice TWS sends for explicit cancels.
+    fn cancel(&mut self, order_id: i32, requested: bool) -> Vec<Reply> {
+        let mut replies = Vec::new();
+
+        if let Some(order) = self.orders.get_mut(&order_id) {
+            order.stage = Stage::Cancelled;
+            replies.push(Reply::new(
+                Route::Order(order_id),
+                encoders::encode_order_status(&self.order_status(order_id)),
+            ));
+        }
+        if requested {
+            replies.push(self.error(order_id, 202, "Order Canceled - reason:"));
+        }
+
+        let children: Vec<i32> = self
+            .orders
+            .iter()
+            .filter(|(_, order)| order.order.parent_id == order_id && order.stage.is_cancellable())
+            .map(|(child_id, _)| *child_id)
+            .collect();
+        for child_id in children {
+            replies.extend(self.cancel(child_id, false));
+        }
+
+        replies
+    }
+
+    fn fill(&mut self, order_id: i32, price: f64, time: OffsetDateTime) -> Vec<Reply> {
+        self.execution_count += 1;
+
+        let options = &self.options;
+        let order = self.orders.get_mut(&order_id).expect("filled order exists");
+        let quantity = order.remaining();
+        let commission = (decimal::to_f64(quantity) * options.commission_per_share).max(options.minimum_commission);
+
+        order.filled += quantity;
+        order.average_fill_price = price;
+        order.last_fill_price = price;
+        order.commission = Some(order.commission.unwrap_or_default() + commission);
+        order.stage = Stage::Filled;
+
+        let liquidity = match order.order.order_type.as_str() {
+            "MKT" | "STP" => Liquidity::RemovedLiquidity,
+            _ => Liquidity::AddedLiquidity,
+        };
+        let contract = order.contract.clone();
+        let execution_id = format!("{:08x}.{:08x}.01.01", order.order.perm_id, self.execution_count);
+
+        let execution_data = ExecutionData {
+            request_id: -1,
+            contract: contract.clone(),
+            execution: Execution {
+                order_id,
+                client_id: order.order.client_id,
+                execution_id: execution_id.clone(),
+                time: format_execution_time(time),
+                account_number: order.order.account.clone(),
+                exchange: contract.exchange.to_field(),
+                side: if order.is_buy() { "BOT" } else { "SLD" }.to_string(),
+                shares: quantity,
+                price,
+                perm_id: order.order.perm_id,
+                cumulative_quantity: order.filled,
+                average_price: order.average_fill_price,
+                order_reference: order.order.order_ref.clone(),
+                last_liquidity: liquidity,
+                ..Default::default()
+            },
+        };
+
+        let signed_quantity = if order.is_buy() { quantity } else { -quantity };
+        let account = order.order.account.clone();
+        let oca_group = order.order.oca_group.clone();
+        let parent_id = order.order.parent_id;
+
+        let realized_pnl = self.update_position(&contract, signed_quantity, price, commission);
+        let commission_report = CommissionReport {
+            execution_id,
+            commission,
+            currency: if contract.currency.to_field().is_empty() {
+                "USD".to_string()
+            } else {
+                contract.currency.to_field()
+            },
+            realized_pnl,
+            yields: None,
+            yield_redemption_date: String::new(),
+        };
+
+        let mut replies = vec![
+            Reply::new(Route::Order(order_id), encoders::encode_execution_data(&execution_data)),
+            Reply::new(Route::Order(order_id), self.open_order_message(order_id)),
+            Reply::new(Route::Order(order_id), encoders::encode_order_status(&self.order_status(order_id))),
+            Reply::new(Route::Order(order_id), encoders::encode_commission_report(&commission_report)),
+            Reply::new(
+                Route::Shared(OutgoingMessages::RequestPositions),
+                encoders::encode_position(&self.position_message(&account, &ContractKey::from(&contract))),
+            ),
+        ];
+        self.executions.push((execution_data, commission_report));
+
+        // Children of the filled order start working; they are first evaluated on the next price event.
+        let children: Vec<i32> = self
+            .orders
+            .iter()
+            .filter(|(_, child)| child.order.parent_id == order_id && child.stage == Stage::Held)
+            .map(|(child_id, _)| *child_id)
+            .collect();
+        for child_id in children {
+            self.orders.get_mut(&child_id).expect("child exists").stage = Stage::Working;
+            replies.extend(self.order_events(child_id));
+        }
+
+        // Orders sharing an OCA group, and the other children of the same parent, are cancelled.
+        let siblings: Vec<i32> = self
+            .orders
+            .iter()
+            .filter(|(id, sibling)| {
+                **id != order_id
+                    && sibling.stage.is_open()
+                    && ((!oca_group.is_empty() && sibling.order.oca_group == oca_group) || (parent_id != 0 && sibling.order.parent_id == parent_id))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        for sibling_id in siblings {
+            replies.extend(self.cancel(sibling_id, false));
+        }
+
+        replies
+    }
+
+    // Applies a fill to the position and returns the realized profit and loss for any closed quantity.
+    // Quantities are tracked exactly; costs and profit and loss are computed in f64 as TWS reports them.
+    fn update_position(&mut self, contract: &Contract, quantity: Decimal, price: f64, commission: f64) -> Option<f64> {
+        let multiplier = contract.multiplier.parse::<f64>().unwrap_or(1.0);
+        let cost = price * multiplier;
+        let zero = Decimal::default();
+
+        let holding = self.positions.entry(ContractKey::from(contract)).or_insert_with(|| Holding {
+            contract: contract.clone(),
+            position: zero,
+            average_cost: 0.0,
+        });
+
+        if holding.position == zero || holding.position.is_sign_negative() == quantity.is_sign_negative() {
+            let total_cost = decimal::to_f64(holding.position.abs()) * holding.average_cost + decimal::to_f64(quantity.abs()) * cost + commission;
+            holding.position += quantity;
+            holding.average_cost = total_cost / decimal::to_f64(holding.position.abs());
+            return None;
+        }
+
+        let closing = quantity.abs().min(holding.position.abs());
+        let opening = quantity.abs() - closing;
+        let closing_commission = commission * decimal::to_f64(closing) / decimal::to_f64(quantity.abs());
+        let direction = if holding.position.is_sign_negative() { -1.0 } else { 1.0 };
+        let realized_pnl = (cost - holding.average_cost) * decimal::to_f64(closing) * direction - closing_commission;
+
+        holding.position += quantity;
+        if opening > zero {
+            holding.average_cost = (decimal::to_f64(opening) * cost + (commission - closing_commission)) / decimal::to_f64(opening);
+        } else if holding.position == zero {
+            holding.average_cost = 0.0;
+        }
+
+        Some(realized_pnl)
+    }
+
+    fn open_orders(&self, message_type: OutgoingMessages) -> Vec<Reply> {
+        let mut replies = Vec::new();
+
+        for (order_id, order) in &self.orders {
+            if order.stage.is_open() {
+                replies.push(Reply::new(Route::Shared(message_type), self.open_order_message(*order_id)));
+                replies.push(Reply::new(
+                    Route::Shared(message_type),
+                    encoders::encode_order_status(&self.order_status(*order_id)),
+                ));
+            }
+        }
+        replies.push(Reply::new(Route::Shared(message_type), encoders::encode_open_order_end()));
+
+        replies
+    }
+
+    fn executions(&self, message: &mut ResponseMessage) -> Result<Vec<Reply>, Error> {
+        message.skip(); // message version
+        let request_id = message.next_int()?;
+        let client_id = message.next_optional_int()?;
+        let account = message.next_string()?;
+        message.skip(); // time, not applied
+        let symbol = message.next_string()?;
+        let security_type = message.next_string()?;
+        let exchange = message.next_string()?;
+        let side = message.next_string()?;
+
+        let side = match side.as_str() {
+            "BUY" => "BOT",
+            "SELL" => "SLD",
+            other => other,
+        };
+        let matches = |filter: &str, value: &str| filter.is_empty() || filter.eq_ignore_ascii_case(value);
+
+        let mut replies = Vec::new();
+        for (execution_data, commission_report) in &self.executions {
+            let execution = &execution_data.execution;
+            let contract = &execution_data.contract;
+
+            if client_id.is_some_and(|client_id| client_id != execution.client_id)
+                || !matches(&account, &execution.account_number)
+                || !matches(&symbol, &contract.symbol.to_field())
+                || !matches(&security_type, &contract.security_type.to_field())
+                || !matches(&exchange, &execution.exchange)
+                || !matches(side, &execution.side)
+            {
+                continue;
+            }
+
+            let execution_data = ExecutionData {
+                request_id,
+                ..execution_data.clone()
+            };
+            replies.push(Reply::new(Route::Request(request_id), encoders::encode_execution_data(&execution_data)));
+            replies.push(Reply::new(
+                Route::Request(request_id),
+                encoders::encode_commission_report(commission_report),
+            ));
+        }
+        replies.push(Reply::new(Route::Request(request_id), encoders::encode_execution_data_end(request_id)));
+
+        Ok(replies)
+    }
+
+    fn positions(&self) -> Vec<Reply> {
+        let route = Route::Shared(OutgoingMessages::RequestPositions);
+
+        let mut replies: Vec<Reply> = self
+            .positions
+            .keys()
+            .map(|key| Reply::new(route, encoders::encode_position(&self.position_message(&self.options.account, key))))
+            .collect();
+        replies.push(Reply::new(route, encoders::encode_position_end()));
+
+        replies
+    }
+
+    fn position_message(&self, account: &str, key: &ContractKey) -> Position {
+        let holding = &self.positions[key];
+        Position {
+            account: account.to_string(),
+            contract: holding.contract.clone(),
+            position: holding.position,
+            average_cost: holding.average_cost,
+        }
+    }
+
+    // OpenOrder followed by OrderStatus, as TWS reports every state change.
+    fn order_events(&self, order_id: i32) -> Vec<Reply> {
+        vec![
+            Reply::new(Route::Order(order_id), self.open_order_message(order_id)),
+            Reply::new(Route::Order(order_id), encoders::encode_order_status(&self.order_status(order_id))),
+        ]
+    }
+
+    fn open_order_message(&self, order_id: i32) -> ResponseMessage {
+        let order = &self.orders[&order_id];
+        let order_state = OrderState {
+            status: order.stage.status().to_string(),
+            commission: order.commission,
+            commission_currency: order.commission.map(|_| order.contract.currency.to_field()).unwrap_or_default(),
+            ..Default::default()
+        };
+        encoders::encode_open_order(order_id, &order.contract, &order.order, &order_state)
+    }
+
+    fn order_status(&self, order_id: i32) -> OrderStatus {
+        let order = &self.orders[&order_id];
+        OrderStatus {
+            order_id,
+            status: order.stage.status().to_string(),
+            filled: order.filled,
+            remaining: if order.stage == Stage::Cancelled {
+                Decimal::default()
+            } else {
+                order.remaining()
+            },
+            average_fill_price: order.average_fill_price,
+            perm_id: order.order.perm_id,
+            parent_id: order.order.parent_id,
+            last_fill_price: order.last_fill_price,
+            client_id: order.order.client_id,
+            why_held: String::new(),
+            market_cap_price: 0.0,
+        }
+    }
+
+    fn error(&self, id: i32, code: i32, text: &str) -> Reply {
+        let timestamp_millis = (self.time.unix_timestamp_nanos() / 1_000_000) as i64;
+        Reply::new(Route::Order(id), encoders::encode_error(id, code, text, timestamp_millis))
+    }
+}
+
+// Reads the leading order fields of a PlaceOrder request. Later attributes do not affect simulated fills.
+fn decode_place_order(message: &mut ResponseMessage) -> Result<(i32, Contract, Order), Error> {
+    let order_id = message.next_int()?;
+
+    let contract = Contract {
+        contract_id: message.next_int()?,
+        symbol: Symbol::from(message.next_string()?),
+        security_type: SecurityType::from(&message.next_string()?),
+        last_trade_date_or_contract_month: message.next_string()?,
+        strike: message.next_double()?,
+        right: message.next_string()?,
+        multiplier: message.next_string()?,
+        exchange: Exchange::from(message.next_string()?),
+        primary_exchange: Exchange::from(message.next_string()?),
+        currency: Currency::from(message.next_string()?),
+        local_symbol: message.next_string()?,
+        trading_class: message.next_string()?,
+        security_id_type: message.next_string()?,
+        security_id: message.next_string()?,
+        ..Default::default()
+    };
+
+    let order = Order {
+        action: Action::from(&message.next_string()?),
+        total_quantity: message.next_decimal()?,
+        order_type: message.next_string()?,
+        limit_price: message.next_optional_double()?,
+        aux_price: message.next_optional_double()?,
+        tif: TimeInForce::from(message.next_string()?),
+        oca_group: message.next_string()?,
+        account: message.next_string()?,
+        open_close: OrderOpenClose::from(&message.next_string()?),
+        origin: message.next_int()?.into(),
+        order_ref: message.next_string()?,
+        transmit: message.next_bool()?,
+        parent_id: message.next_int()?,
+        block_order: message.next_bool()?,
+        sweep_to_fill: message.next_bool()?,
+        display_size: message.next_optional_int()?,
+        trigger_method: message.next_int()?.into(),
+        outside_rth: message.next_bool()?,
+        hidden: message.next_bool()?,
+        ..Default::default()
+    };
+
+    Ok((order_id, contract, order))
+}
+
+fn unsupported_reason(contract: &Contract, order: &Order) -> Option<String> {
+    if contract.is_bag() {
+        return Some("combination orders are not supported by the simulator".to_string());
+    }
+    if !SUPPORTED_ORDER_TYPES.contains(&order.order_type.as_str()) {
+        return Some(format!("order type {} is not supported by the simulator", order.order_type));
+    }
+    if order.total_quantity <= Decimal::default() {
+        return Some("order quantity must be positive".to_string());
+    }
+    if order.order_type.contains("LMT") && order.limit_price.is_none() {
+        return Some("limit price is required".to_string());
+    }
+    if order.order_type.starts_with("STP") && order.aux_price.is_none() {
+        return Some("stop price is required".to_string());
+    }
+    None
+}
+
+fn format_execution_time(time: OffsetDateTime) -> String {
+    let format = format_description!("[year][month][day]  [hour]:[minute]:[second]");
+    time.format(format).unwrap_or_default()
+}
@@ -0,0 +1,288 @@
+//! Offline broker simulator for testing strategies without a Gateway.
+//!
+//! A [SimulatedBroker] stands in for TWS behind a normal [Client](crate::Client). Orders placed
+//! through the client are matched against prices you feed the broker, either historical
+//! [Bar]s or individual ticks, and the client receives the same `OpenOrder`, `OrderStatus`,
+//! `ExecutionData`, `CommissionReport` and position messages a live account would produce.
+//!
+//! # Matching rules
+//!
+//! * Market, limit, stop and stop limit orders are supported. Other order types are rejected with error 201.
+//! * Orders fill in full. Prices gapping through a limit or stop fill at the bar's open.
+//! * An order placed while a price is known is checked against that price right away, so market orders fill immediately.
+//! * Orders sent with `transmit` unset are held until an order from the same parent group is transmitted.
+//!   Children then wait for the parent to fill and are checked from the next price event on.
+//! * When an order fills, orders sharing its OCA group and the other children of its parent are cancelled.
+//! * Commissions are charged per share with a minimum, see [SimulatorOptions::commission].
+//!
+//! # Examples
+//!
+//! ```
+//! use ibapi::contracts::Contract;
+//! use ibapi::orders::PlaceOrder;
+//! use ibapi::simulator::SimulatedBroker;
+//! use ibapi::Client;
+//! use time::macros::datetime;
+//!
+//! # #[cfg(feature = "async")]
+//! #[tokio::main]
+//! async fn main() {
+//!     let broker = SimulatedBroker::default();
+//!     let client = Client::simulated(&broker);
+//!
+//!     let contract = Contract::stock("AAPL").build();
+//!     let order = ibapi::orders::order_builder::limit_order(ibapi::orders::Action::Buy, 100.0, 185.0);
+//!     let order_id = client.next_order_id();
+//!     let mut events = client.place_order(order_id, &contract, &order).await.expect("order failed");
+//!
+//!     broker.on_tick(&contract, datetime!(2024-01-02 14:30 UTC), 184.5);
+//!
+//!     while let Some(Ok(event)) = events.next().await {
+//!         if let PlaceOrder::ExecutionData(execution) = event {
+//!             println!("filled {} at {}", execution.execution.shares, execution.execution.price);
+//!             break;
+//!         }
+//!     }
+//! }
+//! # #[cfg(not(feature = "async"))]
+//! # fn main() {}
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use time::OffsetDateTime;
+
+use crate::contracts::Contract;
+use crate::market_data::historical::Bar;
+use crate::messages::OutgoingMessages;
+use crate::{server_versions, Decimal};
+
+use engine::{Engine, PriceEvent, Reply, Route};
+
+mod encoders;
+mod engine;
+
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
+
+#[cfg(feature = "async")]
+pub(crate) mod r#async;
+
+#[cfg(test)]
+mod tests;
+
+/// Server version the simulated broker reports to its clients.
+pub(crate) const SERVER_VERSION: i32 = server_versions::PARAMETRIZED_DAYS_OF_EXECUTIONS;
+
+/// Account and commission settings for a [SimulatedBroker].
+///
+/// ```
+/// use ibapi::simulator::SimulatorOptions;
+///
+/// let options = SimulatorOptions::default()
+///     .account("DU7654321")
+///     .commission(0.01, 2.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SimulatorOptions {
+    pub(crate) account: String,
+    pub(crate) client_id: i32,
+    pub(crate) commission_per_share: f64,
+    pub(crate) minimum_commission: f64,
+}
+
+impl Default for SimulatorOptions {
+    fn default() -> Self {
+        Self {
+            account: "DU1234567".to_string(),
+            client_id: 100,
+            commission_per_share: 0.005,
+            minimum_commission: 1.0,
+        }
+    }
+}
+
+impl SimulatorOptions {
+    /// Account reported as managed and assigned to orders that do not name one.
+    /// Default: `DU1234567`.
+    pub fn account(mut self, account: impl Into<String>) -> Self {
+        self.account = account.into();
+        self
+    }
+
+    /// Client id of simulated clients. Default: `100`.
+    pub fn client_id(mut self, client_id: i32) -> Self {
+        self.client_id = client_id;
+        self
+    }
+
+    /// Commission charged per share or contract filled, and the minimum charged per execution.
+    /// Default: `0.005` per share with a `1.0` minimum.
+    pub fn commission(mut self, per_share: f64, minimum: f64) -> Self {
+        self.commission_per_share = per_share;
+        self.minimum_commission = minimum;
+        self
+    }
+}
+
+/// In-process broker that fills orders against a supplied price feed.
+///
+/// Cloning is cheap; clones share the same account. Any number of clients can be created
+/// with `Client::simulated` and will see each other's orders, fills and positions.
+#[derive(Clone)]
+pub struct SimulatedBroker {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for SimulatedBroker {
+    fn default() -> Self {
+        Self::new(SimulatorOptions::default())
+    }
+}
+
+impl std::fmt::Debug for SimulatedBroker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimulatedBroker").field("engine", &self.lock().engine).finish()
+    }
+}
+
+impl SimulatedBroker {
+    /// Creates a broker with an empty account.
+    pub fn new(options: SimulatorOptions) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                engine: Engine::new(options),
+                #[cfg(feature = "sync")]
+                sync_routes: Routes::default(),
+                #[cfg(feature = "async")]
+                async_routes: Routes::default(),
+            })),
+        }
+    }
+
+    /// Advances the simulation by one bar of `contract`, filling the working orders its range reaches.
+    pub fn on_bar(&self, contract: &Contract, bar: &Bar) {
+        self.on_price(
+            contract,
+            PriceEvent {
+                time: bar.date,
+                open: bar.open,
+                high: bar.high,
+                low: bar.low,
+                close: bar.close,
+            },
+        );
+    }
+
+    /// Advances the simulation to a trade of `contract` at `price`.
+    pub fn on_tick(&self, contract: &Contract, time: OffsetDateTime, price: f64) {
+        self.on_price(contract, PriceEvent::tick(time, price));
+    }
+
+    /// Current position in `contract`. Positive values are long.
+    pub fn position(&self, contract: &Contract) -> Decimal {
+        self.lock().engine.position(contract)
+    }
+
+    /// Time of the last price event, or the creation time if no prices were fed yet.
+    pub fn time(&self) -> OffsetDateTime {
+        self.lock().engine.time()
+    }
+
+    fn on_price(&self, contract: &Contract, event: PriceEvent) {
+        let mut state = self.lock();
+        let replies = state.engine.on_price(contract, event);
+        state.dispatch(replies);
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, State> {
+        // Delivery never panics while the lock is held, so poisoning is not expected.
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // Metadata a client built on this broker starts with.
+    pub(crate) fn connection_metadata(&self) -> crate::connection::ConnectionMetadata {
+        let state = self.lock();
+        let options = state.engine.options();
+
+        crate::connection::ConnectionMetadata {
+            next_order_id: state.engine.next_order_id(),
+            client_id: options.client_id,
+            server_version: SERVER_VERSION,
+            managed_accounts: options.account.clone(),
+            connection_time: Some(state.engine.time()),
+            time_zone: None,
+        }
+    }
+}
+
+/// Engine and subscriber channels, guarded by a single lock so replies are delivered in the order produced.
+pub(crate) struct State {
+    pub(crate) engine: Engine,
+    #[cfg(feature = "sync")]
+    pub(crate) sync_routes: Routes<crossbeam::channel::Sender<crate::transport::Response>>,
+    #[cfg(feature = "async")]
+    pub(crate) async_routes: Routes<tokio::sync::broadcast::Sender<crate::messages::ResponseMessage>>,
+}
+
+impl State {
+    pub(crate) fn dispatch(&mut self, replies: Vec<Reply>) {
+        #[cfg(feature = "sync")]
+        self.sync_routes.dispatch(&replies);
+        #[cfg(feature = "async")]
+        self.async_routes.dispatch(&replies);
+    }
+}
+
+/// Channel end that delivers simulated responses to a subscriber.
+pub(crate) trait Outlet {
+    /// Delivers `message`, returning `false` once the subscriber has gone away.
+    fn deliver(&self, message: &crate::messages::ResponseMessage) -> bool;
+}
+
+/// Subscriber channels by the route responses are addressed to.
+pub(crate) struct Routes<S> {
+    pub(crate) requests: HashMap<i32, S>,
+    pub(crate) orders: HashMap<i32, S>,
+    pub(crate) shared: HashMap<OutgoingMessages, Vec<S>>,
+    pub(crate) order_updates: Option<S>,
+}
+
+impl<S> Default for Routes<S> {
+    fn default() -> Self {
+        Self {
+            requests: HashMap::new(),
+            orders: HashMap::new(),
+            shared: HashMap::new(),
+            order_updates: None,
+        }
+    }
+}
+
+impl<S: Outlet> Routes<S> {
+    fn dispatch(&mut self, replies: &[Reply]) {
+        for reply in replies {
+            match reply.route {
+                Route::Request(request_id) => {
+                    if self.requests.get(&request_id).is_some_and(|outlet| !outlet.deliver(&reply.message)) {
+                        self.requests.remove(&request_id);
+                    }
+                }
+                Route::Order(order_id) => {
+                    if self.orders.get(&order_id).is_some_and(|outlet| !outlet.deliver(&reply.message)) {
+                        self.orders.remove(&order_id);
+                    }
+                    if self.order_updates.as_ref().is_some_and(|outlet| !outlet.deliver(&reply.message)) {
+                        self.order_updates = None;
+                    }
+                }
+                Route::Shared(message_type) => {
+                    if let Some(outlets) = self.shared.get_mut(&message_type) {
+                        outlets.retain(|outlet| outlet.deliver(&reply.message));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Synchronous message bus backed by the simulated broker
+
+use std::sync::Arc;
+
+use crossbeam::channel::{self, Receiver, Sender};
+
+use super::{Outlet, Routes, SimulatedBroker};
+use crate::messages::{OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::transport::{InternalSubscription, MessageBus, Response, Signal, SubscriptionBuilder};
+use crate::Error;
+
+impl Outlet for Sender<Response> {
+    fn deliver(&self, message: &ResponseMessage) -> bool {
+        self.send(Ok(message.clone())).is_ok()
+    }
+}
+
+/// Routes requests from a blocking client to a [SimulatedBroker].
+pub(crate) struct SimulatedMessageBus {
+    broker: SimulatedBroker,
+    signals_send: Sender<Signal>,
+    signals_recv: Receiver<Signal>,
+}
+
+impl SimulatedMessageBus {
+    pub(crate) fn new(broker: SimulatedBroker) -> Self {
+        let (signals_send, signals_recv) = channel::unbounded();
+        Self {
+            broker,
+            signals_send,
+            signals_recv,
+        }
+    }
+
+    // Removes channels whose subscriptions were dropped.
+    fn clean_up(&self, routes: &mut Routes<Sender<Response>>) {
+        for signal in self.signals_recv.try_iter() {
+            match signal {
+                Signal::Request(request_id) => {
+                    routes.requests.remove(&request_id);
+                }
+                Signal::Order(order_id) => {
+                    routes.orders.remove(&order_id);
+                }
+                Signal::OrderUpdateStream => {
+                    routes.order_updates = None;
+                }
+            }
+        }
+    }
+
+    fn send(&self, message: &RequestMessage) -> Result<(), Error> {
+        let mut state = self.broker.lock();
+        let replies = state.engine.handle(message)?;
+        state.dispatch(replies);
+        Ok(())
+    }
+}
+
+impl MessageBus for SimulatedMessageBus {
+    fn send_request(&self, request_id: i32, packet: &RequestMessage) -> Result<InternalSubscription, Error> {
+        let (sender, receiver) = channel::unbounded();
+
+        let mut state = self.broker.lock();
+        self.clean_up(&mut state.sync_routes);
+        let replies = state.engine.handle(packet)?;
+        state.sync_routes.requests.insert(request_id, sender.clone());
+        state.dispatch(replies);
+
+        Ok(SubscriptionBuilder::new()
+            .receiver(receiver)
+            .sender(sender)
+            .signaler(self.signals_send.clone())
+            .request_id(request_id)
+            .build())
+    }
+
+    fn cancel_subscription(&self, request_id: i32, packet: &RequestMessage) -> Result<(), Error> {
+        self.send(packet)?;
+
+        let mut state = self.broker.lock();
+        if let Some(sender) = state.sync_routes.requests.remove(&request_id) {
+            let _ = sender.send(Err(Error::Cancelled));
+        }
+
+        Ok(())
+    }
+
+    fn send_shared_request(&self, message_type: OutgoingMessages, packet: &RequestMessage) -> Result<InternalSubscription, Error> {
+        let (sender, receiver) = channel::unbounded();
+
+        let mut state = self.broker.lock();
+        let replies = state.engine.handle(packet)?;
+        state.sync_routes.shared.entry(message_type).or_default().push(sender);
+        state.dispatch(replies);
+
+        Ok(SubscriptionBuilder::new()
+            .shared_receiver(Arc::new(receiver))
+            .message_type(message_type)
+            .build())
+    }
+
+    fn cancel_shared_subscription(&self, _message_type: OutgoingMessages, packet: &RequestMessage) -> Result<(), Error> {
+        self.send(packet)
+    }
+
+    fn send_order_request(&self, order_id: i32, packet: &RequestMessage) -> Result<InternalSubscription, Error> {
+        let (sender, receiver) = channel::unbounded();
+
+        let mut state = self.broker.lock();
+        self.clean_up(&mut state.sync_routes);
+        let replies = state.engine.handle(packet)?;
+        state.sync_routes.orders.insert(order_id, sender.clone());
+        state.dispatch(replies);
+
+        Ok(SubscriptionBuilder::new()
+            .receiver(receiver)
+            .sender(sender)
+            .signaler(self.signals_send.clone())
+            .order_id(order_id)
+            .build())
+    }
+
+    fn send_message(&self, packet: &RequestMessage) -> Result<(), Error> {
+        self.send(packet)
+    }
+
+    fn create_order_update_subscription(&self) -> Result<InternalSubscription, Error> {
+        let mut state = self.broker.lock();
+        self.clean_up(&mut state.sync_routes);
+
+        if state.sync_routes.order_updates.is_some() {
+            return Err(Error::AlreadySubscribed);
+        }
+
+        let (sender, receiver) = channel::unbounded();
+        state.sync_routes.order_updates = Some(sender);
+
+        Ok(SubscriptionBuilder::new().receiver(receiver).signaler(self.signals_send.clone()).build())
+    }
+
+    fn cancel_order_subscription(&self, order_id: i32, packet: &RequestMessage) -> Result<(), Error> {
+        self.send(packet)?;
+
+        let mut state = self.broker.lock();
+        if let Some(sender) = state.sync_routes.orders.remove(&order_id) {
+            let _ = sender.send(Err(Error::Cancelled));
+        }
+
+        Ok(())
+    }
+
+    fn ensure_shutdown(&self) {}
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::SimulatorOptions;
+    use crate::client::sync::Client;
+    use crate::contracts::Contract;
+    use crate::orders::{order_builder, Action, ExecutionFilter, Executions, OrderUpdate, Orders, PlaceOrder};
+    use crate::simulator::SimulatedBroker;
+    use crate::Decimal;
+
+    use super::super::tests::{bar, TIME};
+
+    #[test]
+    fn test_place_order_fills_on_bar() {
+        let broker = SimulatedBroker::new(SimulatorOptions::default().account("DU7654321"));
+        let client = Client::simulated(&broker);
+        let contract = Contract::stock("AAPL").build();
+
+        let order_id = client.next_order_id();
+        let subscription = client
+            .place_order(order_id, &contract, &order_builder::limit_order(Action::Buy, 100.0, 99.0))
+            .expect("place order failed");
+
+        assert!(matches!(subscription.try_next(), Some(PlaceOrder::OpenOrder(order_data)) if order_data.order_state.status == "Submitted"));
+        assert!(matches!(subscription.try_next(), Some(PlaceOrder::OrderStatus(status)) if status.status == "Submitted"));
+        assert!(subscription.try_next().is_none());
+
+        broker.on_bar(&contract, &bar(100.0, 101.0, 98.0, 100.5));
+
+        let Some(PlaceOrder::ExecutionData(execution)) = subscription.try_next() else {
+            panic!("expected execution");
+        };
+        assert_eq!(execution.execution.price, 99.0);
+        assert_eq!(execution.execution.side, "BOT");
+        assert_eq!(execution.execution.account_number, "DU7654321");
+        assert!(matches!(subscription.try_next(), Some(PlaceOrder::OpenOrder(order_data)) if order_data.order_state.status == "Filled"));
+        assert!(
+            matches!(subscription.try_next(), Some(PlaceOrder::OrderStatus(status)) if status.status == "Filled" && status.remaining == Decimal::default())
+        );
+        assert!(
+            matches!(subscription.try_next(), Some(PlaceOrder::CommissionReport(report)) if report.execution_id == execution.execution.execution_id)
+        );
+
+        assert_eq!(broker.position(&contract), Decimal::from(100));
+
+        let executions = client.executions(ExecutionFilter::default()).expect("executions failed");
+        let items: Vec<Executions> = executions.iter().collect();
+        assert_eq!(items.len(), 2, "{items:?}");
+        assert!(matches!(&items[0], Executions::ExecutionData(data) if data.execution.execution_id == execution.execution.execution_id));
+    }
+
+    #[test]
+    fn test_order_update_stream_and_open_orders() {
+        let broker = SimulatedBroker::default();
+        let client = Client::simulated(&broker);
+        let contract = Contract::stock("MSFT").build();
+
+        let updates = client.order_update_stream().expect("order update stream failed");
+        assert!(matches!(client.order_update_stream(), Err(crate::Error::AlreadySubscribed)));
+
+        let order_id = client.next_order_id();
+        client
+            .submit_order(order_id, &contract, &order_builder::stop(Action::Sell, 10.0, 95.0))
+            .expect("submit failed");
+
+        let open_orders: Vec<Orders> = client.open_orders().expect("open orders failed").iter().collect();
+        assert_eq!(open_orders.len(), 2, "{open_orders:?}");
+        assert!(matches!(&open_orders[0], Orders::OrderData(order_data) if order_data.order_id == order_id));
+
+        broker.on_tick(&contract, TIME, 94.0);
+
+        let events: Vec<OrderUpdate> = std::iter::from_fn(|| updates.next_timeout(Duration::from_millis(100))).collect();
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, OrderUpdate::ExecutionData(data) if data.execution.price == 94.0)),
+            "{events:?}"
+        );
+        assert_eq!(broker.position(&contract), Decimal::from(-10));
+
+        drop(updates);
+        client.order_update_stream().expect("stream should be available after drop");
+    }
+
+    #[test]
+    fn test_cancel_order() {
+        let broker = SimulatedBroker::default();
+        let client = Client::simulated(&broker);
+        let contract = Contract::stock("AAPL").build();
+
+        let order_id = client.next_order_id();
+        client
+            .submit_order(order_id, &contract, &order_builder::limit_order(Action::Buy, 100.0, 90.0))
+            .expect("submit failed");
+
+        let subscription = client.cancel_order(order_id, "").expect("cancel failed");
+        let events: Vec<_> = std::iter::from_fn(|| subscription.try_next()).collect();
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, crate::orders::CancelOrder::OrderStatus(status) if status.status == "Cancelled")),
+            "{events:?}"
+        );
+
+        broker.on_tick(&contract, TIME, 80.0);
+        assert_eq!(broker.position(&contract), Decimal::from(0));
+    }
+}
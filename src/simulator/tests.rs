@@ -0,0 +1,359 @@
+use time::macros::datetime;
+use time::OffsetDateTime;
+
+use super::engine::{Engine, PriceEvent, Reply, Route};
+use super::{encoders, SimulatorOptions, SERVER_VERSION};
+use crate::accounts::{Position, PositionUpdate};
+use crate::contracts::Contract;
+use crate::market_data::historical::Bar;
+use crate::messages::{IncomingMessages, OutgoingMessages, RequestMessage};
+use crate::orders::common::decoders::{decode_commission_report, decode_execution_data, decode_open_order, decode_order_status};
+use crate::orders::common::encoders::{encode_cancel_order, encode_place_order};
+use crate::orders::{order_builder, Action, CommissionReport, Execution, ExecutionData, Order, OrderState, OrderStatus};
+use crate::subscriptions::{DecoderContext, StreamDecoder};
+use crate::Decimal;
+
+pub(super) const TIME: OffsetDateTime = datetime!(2024-01-02 14:30 UTC);
+
+pub(super) fn bar(open: f64, high: f64, low: f64, close: f64) -> Bar {
+    Bar {
+        date: TIME,
+        open,
+        high,
+        low,
+        close,
+        volume: Decimal::from(1000),
+        wap: (high + low) / 2.0,
+        count: 10,
+    }
+}
+
+fn place(engine: &mut Engine, order_id: i32, contract: &Contract, order: &Order) -> Vec<Reply> {
+    let request = encode_place_order(SERVER_VERSION, order_id, contract, order).expect("encode place order");
+    engine.handle(&request).expect("place order")
+}
+
+fn cancel(engine: &mut Engine, order_id: i32) -> Vec<Reply> {
+    let request = encode_cancel_order(SERVER_VERSION, order_id, "").expect("encode cancel order");
+    engine.handle(&request).expect("cancel order")
+}
+
+fn price(engine: &mut Engine, contract: &Contract, open: f64, high: f64, low: f64, close: f64) -> Vec<Reply> {
+    engine.on_price(
+        contract,
+        PriceEvent {
+            time: TIME,
+            open,
+            high,
+            low,
+            close,
+        },
+    )
+}
+
+fn executions(replies: &[Reply]) -> Vec<ExecutionData> {
+    replies
+        .iter()
+        .filter(|reply| reply.message.message_type() == IncomingMessages::ExecutionData)
+        .map(|reply| decode_execution_data(SERVER_VERSION, &mut reply.message.clone()).expect("decode execution"))
+        .collect()
+}
+
+fn statuses(replies: &[Reply]) -> Vec<OrderStatus> {
+    replies
+        .iter()
+        .filter(|reply| reply.message.message_type() == IncomingMessages::OrderStatus)
+        .map(|reply| decode_order_status(SERVER_VERSION, &mut reply.message.clone()).expect("decode order status"))
+        .collect()
+}
+
+fn error_codes(replies: &[Reply]) -> Vec<i32> {
+    replies
+        .iter()
+        .filter(|reply| reply.message.message_type() == IncomingMessages::Error)
+        .map(|reply| reply.message.error_code())
+        .collect()
+}
+
+#[test]
+fn test_encode_open_order_round_trip() {
+    let contract = Contract::stock("AAPL").build();
+    let mut order = order_builder::stop_limit(Action::Sell, 25.0, 99.5, 100.0);
+    order.order_ref = "exit".to_string();
+    order.oca_group = "group-1".to_string();
+    order.account = "DU1234567".to_string();
+    order.perm_id = 1_000_001;
+    let order_state = OrderState {
+        status: "Submitted".to_string(),
+        ..Default::default()
+    };
+
+    let message = encoders::encode_open_order(7, &contract, &order, &order_state);
+    let order_data = decode_open_order(SERVER_VERSION, message).expect("decode open order");
+
+    assert_eq!(order_data.order_id, 7);
+    assert_eq!(order_data.contract.symbol, contract.symbol);
+    assert_eq!(order_data.order.action, Action::Sell);
+    assert_eq!(order_data.order.order_type, "STP LMT");
+    assert_eq!(order_data.order.total_quantity, Decimal::from(25));
+    assert_eq!(order_data.order.limit_price, Some(99.5));
+    assert_eq!(order_data.order.aux_price, Some(100.0));
+    assert_eq!(order_data.order.order_ref, "exit");
+    assert_eq!(order_data.order.oca_group, "group-1");
+    assert_eq!(order_data.order.perm_id, 1_000_001);
+    assert_eq!(order_data.order_state.status, "Submitted");
+}
+
+#[test]
+fn test_encode_order_status_round_trip() {
+    let status = OrderStatus {
+        order_id: 3,
+        status: "Filled".to_string(),
+        filled: Decimal::from(10),
+        remaining: Decimal::from(0),
+        average_fill_price: 101.25,
+        perm_id: 1_000_000,
+        parent_id: 2,
+        last_fill_price: 101.25,
+        client_id: 100,
+        why_held: String::new(),
+        market_cap_price: 0.0,
+    };
+
+    let decoded = decode_order_status(SERVER_VERSION, &mut encoders::encode_order_status(&status)).expect("decode order status");
+
+    assert_eq!(decoded.order_id, 3);
+    assert_eq!(decoded.status, "Filled");
+    assert_eq!(decoded.filled, Decimal::from(10));
+    assert_eq!(decoded.average_fill_price, 101.25);
+    assert_eq!(decoded.parent_id, 2);
+    assert_eq!(decoded.client_id, 100);
+}
+
+#[test]
+fn test_encode_execution_round_trip() {
+    let execution_data = ExecutionData {
+        request_id: 9000,
+        contract: Contract::stock("MSFT").build(),
+        execution: Execution {
+            order_id: 4,
+            client_id: 100,
+            execution_id: "000f4240.00000001.01.01".to_string(),
+            time: "20240102  14:30:00".to_string(),
+            account_number: "DU1234567".to_string(),
+            side: "SLD".to_string(),
+            shares: Decimal::from(5),
+            price: 402.5,
+            perm_id: 1_000_000,
+            cumulative_quantity: Decimal::from(5),
+            average_price: 402.5,
+            ..Default::default()
+        },
+    };
+
+    let decoded = decode_execution_data(SERVER_VERSION, &mut encoders::encode_execution_data(&execution_data)).expect("decode execution");
+
+    assert_eq!(decoded.request_id, 9000);
+    assert_eq!(decoded.contract.symbol, execution_data.contract.symbol);
+    assert_eq!(decoded.execution.execution_id, "000f4240.00000001.01.01");
+    assert_eq!(decoded.execution.side, "SLD");
+    assert_eq!(decoded.execution.shares, Decimal::from(5));
+    assert_eq!(decoded.execution.price, 402.5);
+
+    let report = CommissionReport {
+        execution_id: "000f4240.00000001.01.01".to_string(),
+        commission: 1.0,
+        currency: "USD".to_string(),
+        realized_pnl: Some(12.5),
+        yields: None,
+        yield_redemption_date: String::new(),
+    };
+    let decoded = decode_commission_report(SERVER_VERSION, &mut encoders::encode_commission_report(&report)).expect("decode commission report");
+
+    assert_eq!(decoded.execution_id, report.execution_id);
+    assert_eq!(decoded.commission, 1.0);
+    assert_eq!(decoded.realized_pnl, Some(12.5));
+    assert_eq!(decoded.yields, None);
+}
+
+#[test]
+fn test_encode_position_round_trip() {
+    let position = Position {
+        account: "DU1234567".to_string(),
+        contract: Contract::stock("AAPL").build(),
+        position: Decimal::from(-30),
+        average_cost: 187.2,
+    };
+
+    let context = DecoderContext::new(SERVER_VERSION, None);
+    let Ok(PositionUpdate::Position(decoded)) = PositionUpdate::decode(&context, &mut encoders::encode_position(&position)) else {
+        panic!("expected position");
+    };
+
+    assert_eq!(decoded.account, "DU1234567");
+    assert_eq!(decoded.contract.symbol, position.contract.symbol);
+    assert_eq!(decoded.position, Decimal::from(-30));
+    assert_eq!(decoded.average_cost, 187.2);
+}
+
+#[test]
+fn test_limit_order_gap_fills_at_open() {
+    let mut engine = Engine::new(SimulatorOptions::default());
+    let contract = Contract::stock("AAPL").build();
+
+    place(&mut engine, 1, &contract, &order_builder::limit_order(Action::Buy, 10.0, 100.0));
+
+    assert!(executions(&price(&mut engine, &contract, 101.0, 102.0, 100.5, 101.5)).is_empty());
+
+    let fills = executions(&price(&mut engine, &contract, 98.0, 99.0, 97.0, 98.5));
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].execution.price, 98.0, "gap below the limit fills at the open");
+    assert_eq!(engine.position(&contract), Decimal::from(10));
+}
+
+#[test]
+fn test_stop_order_triggers_at_stop_or_open() {
+    let mut engine = Engine::new(SimulatorOptions::default());
+    let contract = Contract::stock("AAPL").build();
+
+    place(&mut engine, 1, &contract, &order_builder::stop(Action::Sell, 10.0, 95.0));
+    place(&mut engine, 2, &contract, &order_builder::stop(Action::Sell, 10.0, 90.0));
+
+    let fills = executions(&price(&mut engine, &contract, 96.0, 96.5, 94.0, 94.5));
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].execution.order_id, 1);
+    assert_eq!(fills[0].execution.price, 95.0);
+
+    let fills = executions(&price(&mut engine, &contract, 88.0, 89.0, 87.0, 88.5));
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].execution.order_id, 2);
+    assert_eq!(fills[0].execution.price, 88.0, "gap through the stop fills at the open");
+}
+
+#[test]
+fn test_market_order_fills_at_last_price() {
+    let mut engine = Engine::new(SimulatorOptions::default());
+    let contract = Contract::stock("AAPL").build();
+
+    price(&mut engine, &contract, 100.0, 101.0, 99.0, 100.5);
+    let replies = place(&mut engine, 1, &contract, &order_builder::market_order(Action::Buy, 100.0));
+
+    let fills = executions(&replies);
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].execution.price, 100.5);
+    assert!(replies
+        .iter()
+        .any(|reply| reply.route == Route::Shared(OutgoingMessages::RequestPositions)));
+}
+
+#[test]
+fn test_bracket_children_wait_for_parent() {
+    let mut engine = Engine::new(SimulatorOptions::default());
+    let contract = Contract::stock("AAPL").build();
+    let orders = order_builder::bracket_order(10, Action::Buy, 100.0, 100.0, 110.0, 95.0);
+
+    assert!(
+        place(&mut engine, 10, &contract, &orders[0]).is_empty(),
+        "untransmitted orders stay silent"
+    );
+    assert!(place(&mut engine, 11, &contract, &orders[1]).is_empty());
+    let replies = place(&mut engine, 12, &contract, &orders[2]);
+    let submitted = statuses(&replies);
+    assert_eq!(submitted.len(), 3);
+    assert!(submitted.iter().any(|status| status.order_id == 10 && status.status == "Submitted"));
+    assert!(submitted.iter().any(|status| status.order_id == 11 && status.status == "PreSubmitted"));
+
+    // The stop is reachable in the parent's bar, but children only start working after the fill.
+    let fills = executions(&price(&mut engine, &contract, 99.0, 99.5, 94.0, 96.0));
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].execution.order_id, 10);
+
+    let replies = price(&mut engine, &contract, 96.0, 97.0, 94.0, 94.5);
+    let fills = executions(&replies);
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].execution.order_id, 12);
+    assert!(statuses(&replies)
+        .iter()
+        .any(|status| status.order_id == 11 && status.status == "Cancelled"));
+    assert_eq!(engine.position(&contract), Decimal::default());
+}
+
+#[test]
+fn test_oca_group_cancels_remaining_orders() {
+    let mut engine = Engine::new(SimulatorOptions::default());
+    let contract = Contract::stock("AAPL").build();
+
+    for (order_id, limit_price) in [(1, 99.0), (2, 98.0)] {
+        let mut order = order_builder::limit_order(Action::Buy, 10.0, limit_price);
+        order.oca_group = "entry".to_string();
+        place(&mut engine, order_id, &contract, &order);
+    }
+
+    let replies = price(&mut engine, &contract, 100.0, 100.0, 97.0, 97.5);
+
+    assert_eq!(executions(&replies).len(), 1);
+    assert!(statuses(&replies)
+        .iter()
+        .any(|status| status.order_id == 2 && status.status == "Cancelled"));
+    assert_eq!(
+        error_codes(&replies),
+        Vec::<i32>::new(),
+        "OCA cancels are not reported as requested cancels"
+    );
+}
+
+#[test]
+fn test_cancel_order() {
+    let mut engine = Engine::new(SimulatorOptions::default());
+    let contract = Contract::stock("AAPL").build();
+
+    assert_eq!(error_codes(&cancel(&mut engine, 5)), vec![10147]);
+
+    place(&mut engine, 5, &contract, &order_builder::limit_order(Action::Buy, 10.0, 90.0));
+    let replies = cancel(&mut engine, 5);
+    assert_eq!(statuses(&replies)[0].status, "Cancelled");
+    assert_eq!(error_codes(&replies), vec![202]);
+    assert_eq!(error_codes(&cancel(&mut engine, 5)), vec![161]);
+
+    assert!(executions(&price(&mut engine, &contract, 85.0, 86.0, 84.0, 85.0)).is_empty());
+}
+
+#[test]
+fn test_unsupported_order_rejected() {
+    let mut engine = Engine::new(SimulatorOptions::default());
+    let contract = Contract::stock("AAPL").build();
+
+    let replies = place(&mut engine, 1, &contract, &order_builder::trailing_stop(Action::Sell, 10.0, 1.0, 95.0));
+    assert_eq!(error_codes(&replies), vec![201]);
+}
+
+#[test]
+fn test_realized_pnl_includes_commissions() {
+    let mut engine = Engine::new(SimulatorOptions::default().commission(0.01, 1.0));
+    let contract = Contract::stock("AAPL").build();
+
+    price(&mut engine, &contract, 100.0, 100.0, 100.0, 100.0);
+    place(&mut engine, 1, &contract, &order_builder::market_order(Action::Buy, 200.0));
+    price(&mut engine, &contract, 110.0, 110.0, 110.0, 110.0);
+    let replies = place(&mut engine, 2, &contract, &order_builder::market_order(Action::Sell, 200.0));
+
+    let report = replies
+        .iter()
+        .find(|reply| reply.message.message_type() == IncomingMessages::CommissionsReport)
+        .map(|reply| decode_commission_report(SERVER_VERSION, &mut reply.message.clone()).expect("decode commission report"))
+        .expect("commission report");
+
+    // Average cost carries the 2.00 entry commission; the 2.00 exit commission comes off the result.
+    assert_eq!(report.commission, 2.0);
+    let realized_pnl = report.realized_pnl.expect("realized pnl");
+    assert!((realized_pnl - 1996.0).abs() < 1e-9, "realized pnl {realized_pnl}");
+    assert_eq!(engine.position(&contract), Decimal::default());
+}
+
+#[test]
+fn test_unsupported_request() {
+    let mut engine = Engine::new(SimulatorOptions::default());
+    let request = RequestMessage::from_simple("1|11|9000|0|AAPL|STK|");
+
+    assert!(matches!(engine.handle(&request), Err(crate::Error::NotImplemented)));
+}
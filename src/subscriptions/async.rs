@@ -222,7 +222,12 @@ impl<T> Subscription<T> {
                                 log::trace!("skipping unexpected message on shared channel");
                                 continue;
                             }
-                            ProcessingResult::Error(err) => return Some(Err(err)),
+                            ProcessingResult::Error(err) => {
+                                if let Some(message_bus) = &self.message_bus {
+                                    message_bus.record_decode_error(message.message_type());
+                                }
+                                return Some(Err(err));
+                            }
                         }
                     }
                     Some(Err(e)) => return Some(Err(e)),
@@ -303,6 +308,7 @@ mod tests {
     use crate::market_data::realtime::Bar;
     use crate::messages::OutgoingMessages;
     use crate::stubs::MessageBusStub;
+    use crate::Decimal;
     use std::sync::RwLock;
     use time::OffsetDateTime;
     use tokio::sync::{broadcast, mpsc};
@@ -327,7 +333,7 @@ mod tests {
                     high: 101.0,
                     low: 100.0,
                     close: 100.25,
-                    volume: 1000.0,
+                    volume: Decimal::from(1000),
                     wap: 100.2,
                     count: 5,
                 };
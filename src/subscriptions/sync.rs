@@ -187,6 +187,7 @@ impl<T: StreamDecoder<T>> Subscription<T> {
                 }
                 ProcessingResult::Error(err) => {
                     error!("error decoding message: {err}");
+                    self.message_bus.record_decode_error(message.message_type());
                     let mut error = self.error.lock().unwrap();
                     *error = Some(err);
                     NextAction::Return(None)
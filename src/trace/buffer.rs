@@ -0,0 +1,552 @@
+//! Per-client ring buffer of recent interactions with TWS.
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::messages::parser_registry::{parse_generic_message, MessageParserRegistry, ParsedField};
+use crate::messages::shared_channel_configuration::CHANNEL_MAPPINGS;
+use crate::messages::{IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::transport::instrumentation::RequestKey;
+use crate::Error;
+
+/// Responses kept per interaction. Older responses of long running subscriptions are dropped.
+pub const RESPONSES_PER_INTERACTION: usize = 256;
+
+#[derive(Debug)]
+struct Recorded {
+    raw: String,
+    at: OffsetDateTime,
+}
+
+impl Recorded {
+    fn new(raw: String) -> Self {
+        Self {
+            raw,
+            at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: Option<RequestKey>,
+    request_type: Option<OutgoingMessages>,
+    // None for messages TWS sent without a matching request
+    request: Option<Recorded>,
+    // false until the request was written to the socket
+    sent: bool,
+    responses: VecDeque<Recorded>,
+    omitted_responses: usize,
+}
+
+impl Entry {
+    fn request(key: Option<RequestKey>, message: &RequestMessage, sent: bool) -> Self {
+        Self {
+            key,
+            request_type: message.fields.first().and_then(|field| OutgoingMessages::from_str(field).ok()),
+            request: Some(Recorded::new(message.encode())),
+            sent,
+            responses: VecDeque::new(),
+            omitted_responses: 0,
+        }
+    }
+
+    fn unsolicited() -> Self {
+        Self {
+            key: None,
+            request_type: None,
+            request: None,
+            sent: true,
+            responses: VecDeque::new(),
+            omitted_responses: 0,
+        }
+    }
+
+    fn push_response(&mut self, raw: String) {
+        if self.responses.len() == RESPONSES_PER_INTERACTION {
+            self.responses.pop_front();
+            self.omitted_responses += 1;
+        }
+        self.responses.push_back(Recorded::new(raw));
+    }
+
+    fn expects_shared(&self, message_type: IncomingMessages) -> bool {
+        self.request_type.is_some_and(|request_type| {
+            CHANNEL_MAPPINGS
+                .iter()
+                .any(|mapping| mapping.request == request_type && mapping.responses.contains(&message_type))
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    capacity: usize,
+    server_version: i32,
+    entries: VecDeque<Entry>,
+}
+
+impl Inner {
+    fn push(&mut self, entry: Entry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+}
+
+/// Bounded buffer of the last interactions between a client and TWS.
+///
+/// An interaction is a request with the responses routed to it by request id, order id or, for shared
+/// channels, message type. Messages TWS sends on its own, such as farm status notices, are grouped into
+/// interactions without a request. Enable it with [ConnectionOptions::trace_buffer](crate::ConnectionOptions::trace_buffer)
+/// and read it from `Client::trace_buffer()`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ibapi::client::blocking::Client;
+/// use ibapi::messages::IncomingMessages;
+/// use ibapi::ConnectionOptions;
+///
+/// let options = ConnectionOptions::default().trace_buffer(100);
+/// let client = Client::connect_with_options("127.0.0.1:4002", 100, options).expect("connection failed");
+///
+/// // ... requests that fail ...
+///
+/// let trace = client.trace_buffer().expect("trace buffer not available");
+/// let failed = trace.for_response_type(IncomingMessages::Error);
+/// std::fs::write("support.json", trace.export_interactions(failed).to_json().unwrap()).unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TraceBuffer {
+    // Checked before locking, so a disabled buffer costs nothing on the message path.
+    enabled: Arc<AtomicBool>,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl TraceBuffer {
+    /// Creates a buffer keeping the last `capacity` interactions. A capacity of 0 records nothing.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(capacity > 0)),
+            inner: Arc::new(Mutex::new(Inner {
+                capacity,
+                ..Default::default()
+            })),
+        }
+    }
+
+    /// Maximum number of interactions kept.
+    pub fn capacity(&self) -> usize {
+        self.inner.lock().unwrap().capacity
+    }
+
+    /// Returns true if the buffer records interactions.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Number of interactions currently held.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    /// Returns true if no interaction was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all recorded interactions.
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().entries.clear();
+    }
+
+    /// Returns the recorded interactions, oldest first, decoded into named fields.
+    pub fn interactions(&self) -> Vec<TracedInteraction> {
+        self.collect(|_| true)
+    }
+
+    /// Returns the interactions for requests or orders with id `request_id`.
+    pub fn for_request_id(&self, request_id: i32) -> Vec<TracedInteraction> {
+        self.collect(|entry| matches!(entry.key, Some(RequestKey::Request(id) | RequestKey::Order(id)) if id == request_id))
+    }
+
+    /// Returns the interactions started by a request of type `message_type`.
+    pub fn for_message_type(&self, message_type: OutgoingMessages) -> Vec<TracedInteraction> {
+        self.collect(|entry| entry.request_type == Some(message_type))
+    }
+
+    /// Returns the interactions with at least one response of type `message_type`.
+    pub fn for_response_type(&self, message_type: IncomingMessages) -> Vec<TracedInteraction> {
+        self.collect(|entry| entry.responses.iter().any(|response| response_type(&response.raw) == message_type))
+    }
+
+    /// Exports all recorded interactions.
+    pub fn export(&self) -> TraceExport {
+        self.export_interactions(self.interactions())
+    }
+
+    /// Exports `interactions`, e.g. the result of [for_request_id](Self::for_request_id).
+    pub fn export_interactions(&self, interactions: Vec<TracedInteraction>) -> TraceExport {
+        TraceExport {
+            header: TraceHeader {
+                server_version: self.inner.lock().unwrap().server_version,
+                recorded_at: OffsetDateTime::now_utc(),
+            },
+            interactions,
+        }
+    }
+
+    fn collect(&self, predicate: impl Fn(&Entry) -> bool) -> Vec<TracedInteraction> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .entries
+            .iter()
+            .filter(|entry| predicate(entry))
+            .map(TracedInteraction::from)
+            .collect()
+    }
+
+    /// Records a request tracked under `key`. Called before the request is written.
+    pub(crate) fn record_request_started(&self, key: RequestKey, message: &RequestMessage) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        inner.push(Entry::request(Some(key), message, false));
+    }
+
+    /// Records a request written to the socket.
+    pub(crate) fn record_request(&self, message: &RequestMessage) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        let raw = message.encode();
+        let started = inner
+            .entries
+            .iter_mut()
+            .rev()
+            .find(|entry| !entry.sent && entry.request.as_ref().is_some_and(|request| request.raw == raw));
+        match started {
+            Some(entry) => entry.sent = true,
+            None => inner.push(Entry::request(None, message, true)),
+        }
+    }
+
+    /// Records a response and attaches it to the interaction it belongs to.
+    pub(crate) fn record_response(&self, message: &ResponseMessage) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.server_version = message.server_version;
+
+        let message_type = message.message_type();
+        // Errors carry the id of the failed request or order in a version dependent field.
+        let (request_id, order_id) = if message_type == IncomingMessages::Error {
+            let id = message.error_request_id();
+            (Some(id), Some(id))
+        } else {
+            (message.request_id(), message.order_id())
+        };
+        let keys = [request_id.map(RequestKey::Request), order_id.map(RequestKey::Order)];
+
+        let by_id = inner
+            .entries
+            .iter()
+            .rposition(|entry| entry.key.is_some_and(|key| keys.contains(&Some(key))));
+        let by_type = || inner.entries.iter().rposition(|entry| entry.expects_shared(message_type));
+        // Consecutive unsolicited messages share one interaction.
+        let unsolicited = || {
+            inner
+                .entries
+                .back()
+                .filter(|entry| entry.request.is_none())
+                .map(|_| inner.entries.len() - 1)
+        };
+
+        let index = match by_id.or_else(by_type).or_else(unsolicited) {
+            Some(index) => index,
+            None => {
+                inner.push(Entry::unsolicited());
+                inner.entries.len() - 1
+            }
+        };
+        inner.entries[index].push_response(message.encode());
+    }
+}
+
+/// A request and its responses, decoded into named fields.
+#[derive(Clone, Debug, Serialize)]
+pub struct TracedInteraction {
+    /// Message type of the request, or "unsolicited" for messages without a request.
+    pub name: String,
+    /// Request id the responses were routed by.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<i32>,
+    /// Order id the responses were routed by.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<i32>,
+    /// The request. None for messages TWS sent without a request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<TracedMessage>,
+    /// The most recent responses, oldest first.
+    pub responses: Vec<TracedMessage>,
+    /// Number of earlier responses dropped to stay within [RESPONSES_PER_INTERACTION].
+    #[serde(skip_serializing_if = "is_zero")]
+    pub omitted_responses: usize,
+}
+
+impl From<&Entry> for TracedInteraction {
+    fn from(entry: &Entry) -> Self {
+        let (request_id, order_id) = match entry.key {
+            Some(RequestKey::Request(id)) => (Some(id), None),
+            Some(RequestKey::Order(id)) => (None, Some(id)),
+            None => (None, None),
+        };
+
+        TracedInteraction {
+            name: match (entry.request_type, &entry.request) {
+                (Some(request_type), _) => format!("{request_type:?}"),
+                (None, Some(_)) => "unknown".to_string(),
+                (None, None) => "unsolicited".to_string(),
+            },
+            request_id,
+            order_id,
+            request: entry.request.as_ref().map(|request| TracedMessage {
+                raw: request.raw.clone(),
+                at: request.at,
+                fields: decode_request(&request.raw),
+            }),
+            responses: entry
+                .responses
+                .iter()
+                .map(|response| TracedMessage {
+                    raw: response.raw.clone(),
+                    at: response.at,
+                    fields: decode_response(&response.raw),
+                })
+                .collect(),
+            omitted_responses: entry.omitted_responses,
+        }
+    }
+}
+
+/// A message as sent or received, with its fields named by the [parser registry](crate::messages::parser_registry).
+#[derive(Clone, Debug, Serialize)]
+pub struct TracedMessage {
+    /// The message in wire format, fields terminated by NUL.
+    pub raw: String,
+    /// When the message was sent or received.
+    pub at: OffsetDateTime,
+    /// Decoded fields. Fields without a known name are called `field_<index>`.
+    pub fields: Vec<ParsedField>,
+}
+
+/// Header of an exported trace.
+#[derive(Clone, Debug, Serialize)]
+pub struct TraceHeader {
+    /// Server version of the connection.
+    pub server_version: i32,
+    /// When the trace was exported.
+    pub recorded_at: OffsetDateTime,
+}
+
+/// Interactions exported in the layout of `tws_interactions.yaml`.
+#[derive(Clone, Debug, Serialize)]
+pub struct TraceExport {
+    /// Connection details.
+    pub header: TraceHeader,
+    /// Exported interactions, oldest first.
+    pub interactions: Vec<TracedInteraction>,
+}
+
+impl TraceExport {
+    /// Serializes the trace as YAML.
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String, Error> {
+        serde_yaml::to_string(self).map_err(|e| Error::Simple(format!("error exporting trace: {e}")))
+    }
+
+    /// Serializes the trace as pretty printed JSON.
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::Simple(format!("error exporting trace: {e}")))
+    }
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+fn registry() -> &'static MessageParserRegistry {
+    static REGISTRY: OnceLock<MessageParserRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(MessageParserRegistry::new)
+}
+
+fn parts(raw: &str) -> Vec<&str> {
+    raw.strip_suffix('\0').unwrap_or(raw).split('\0').collect()
+}
+
+fn response_type(raw: &str) -> IncomingMessages {
+    let message_id = raw.split('\0').next().and_then(|field| field.parse().ok()).unwrap_or(-1);
+    IncomingMessages::from(message_id)
+}
+
+fn decode_request(raw: &str) -> Vec<ParsedField> {
+    let parts = parts(raw);
+    match parts.first().map(|field| OutgoingMessages::from_str(field)) {
+        Some(Ok(message_type)) => registry().parse_request(message_type, &parts),
+        _ => parse_generic_message(&parts),
+    }
+}
+
+fn decode_response(raw: &str) -> Vec<ParsedField> {
+    registry().parse_response(response_type(raw), &parts(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(fields: &str) -> RequestMessage {
+        RequestMessage::from_simple(fields)
+    }
+
+    fn response(fields: &str) -> ResponseMessage {
+        ResponseMessage::from_simple(fields).with_server_version(173)
+    }
+
+    fn field<'a>(message: &'a TracedMessage, name: &str) -> &'a str {
+        &message.fields.iter().find(|field| field.name == name).expect(name).value
+    }
+
+    #[test]
+    fn test_routes_responses_by_request_id() {
+        let buffer = TraceBuffer::new(10);
+        let summary = request("62|1|9000|All|NetLiquidation|");
+
+        buffer.record_request_started(RequestKey::Request(9000), &summary);
+        buffer.record_request(&summary);
+        buffer.record_response(&response("63|1|9000|DU1234567|NetLiquidation|1000.00|USD|"));
+        buffer.record_response(&response("64|1|9000|"));
+
+        let interactions = buffer.interactions();
+        assert_eq!(interactions.len(), 1, "written request is not recorded twice");
+
+        let interaction = &interactions[0];
+        assert_eq!(interaction.name, "RequestAccountSummary");
+        assert_eq!(interaction.request_id, Some(9000));
+        assert_eq!(interaction.responses.len(), 2);
+
+        let request = interaction.request.as_ref().unwrap();
+        assert_eq!(request.raw, "62\01\09000\0All\0NetLiquidation\0");
+        assert_eq!(field(request, "tags"), "NetLiquidation");
+        assert_eq!(field(&interaction.responses[0], "tag"), "NetLiquidation");
+    }
+
+    #[test]
+    fn test_routes_errors_orders_and_shared_responses() {
+        let buffer = TraceBuffer::new(10);
+
+        buffer.record_request_started(RequestKey::Order(5), &request("3|5|"));
+        buffer.record_request_started(RequestKey::Request(9001), &request("9|8|9001|"));
+        buffer.record_request(&request("49|1|"));
+
+        buffer.record_response(&response("3|5|Filled|100|0|201.94|"));
+        buffer.record_response(&response("4|2|9001|200|No security definition has been found||"));
+        buffer.record_response(&response("49|1|1752606307|"));
+        buffer.record_response(&response("4|2|-1|2104|Market data farm connection is OK:usfarm||"));
+        buffer.record_response(&response("4|2|-1|2106|HMDS data farm connection is OK:ushmds||"));
+
+        let interactions = buffer.interactions();
+        let names: Vec<&str> = interactions.iter().map(|interaction| interaction.name.as_str()).collect();
+        assert_eq!(names, vec!["PlaceOrder", "RequestContractData", "RequestCurrentTime", "unsolicited"]);
+        assert_eq!(interactions[0].order_id, Some(5));
+        assert_eq!(interactions[1].responses.len(), 1, "error routed to the failed request");
+        assert_eq!(interactions[2].responses.len(), 1, "shared response routed by message type");
+        assert!(interactions[3].request.is_none());
+        assert_eq!(interactions[3].responses.len(), 2, "consecutive notices are grouped");
+    }
+
+    #[test]
+    fn test_filters() {
+        let buffer = TraceBuffer::new(10);
+
+        buffer.record_request_started(RequestKey::Request(1), &request("9|8|1|"));
+        buffer.record_request_started(RequestKey::Request(2), &request("9|8|2|"));
+        buffer.record_request_started(RequestKey::Order(1), &request("3|1|"));
+        buffer.record_response(&response("4|2|2|200|No security definition has been found||"));
+
+        assert_eq!(buffer.for_request_id(1).len(), 2, "request and order ids both match");
+        assert_eq!(buffer.for_message_type(OutgoingMessages::RequestContractData).len(), 2);
+        assert_eq!(buffer.for_message_type(OutgoingMessages::RequestMarketData).len(), 0);
+
+        let failed = buffer.for_response_type(IncomingMessages::Error);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].request_id, Some(2));
+    }
+
+    #[test]
+    fn test_bounded() {
+        let buffer = TraceBuffer::new(2);
+
+        for request_id in 1..=3 {
+            buffer.record_request_started(RequestKey::Request(request_id), &request(&format!("1|11|{request_id}|")));
+        }
+        let request_ids: Vec<Option<i32>> = buffer.interactions().iter().map(|interaction| interaction.request_id).collect();
+        assert_eq!(request_ids, vec![Some(2), Some(3)], "oldest interaction evicted");
+
+        for _ in 0..RESPONSES_PER_INTERACTION + 2 {
+            buffer.record_response(&response("1|6|3|1|185.50|100|3|"));
+        }
+        let interaction = buffer.for_request_id(3).remove(0);
+        assert_eq!(interaction.responses.len(), RESPONSES_PER_INTERACTION);
+        assert_eq!(interaction.omitted_responses, 2);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_disabled() {
+        let buffer = TraceBuffer::default();
+
+        buffer.record_request(&request("49|1|"));
+        buffer.record_response(&response("49|1|1752606307|"));
+
+        assert!(!buffer.is_enabled());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_export() {
+        let buffer = TraceBuffer::new(10);
+        buffer.record_request(&request("49|1|"));
+        buffer.record_response(&response("49|1|1752606307|"));
+
+        let export = buffer.export();
+        assert_eq!(export.header.server_version, 173);
+
+        #[cfg(feature = "yaml")]
+        {
+            let yaml = export.to_yaml().unwrap();
+            assert!(yaml.contains("server_version: 173"), "{yaml}");
+            assert!(yaml.contains("name: RequestCurrentTime"), "{yaml}");
+            assert!(yaml.contains("name: timestamp"), "{yaml}");
+            assert!(!yaml.contains("request_id"), "absent ids are omitted: {yaml}");
+        }
+
+        let json: serde_json::Value = serde_json::from_str(&export.to_json().unwrap()).unwrap();
+        assert_eq!(json["interactions"][0]["request"]["raw"], "49\u{0}1\u{0}");
+        assert_eq!(json["interactions"][0]["responses"][0]["fields"][2]["value"], "1752606307");
+    }
+}
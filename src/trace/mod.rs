@@ -2,8 +2,12 @@
 //!
 //! This module provides functionality to capture and retrieve server interactions
 //! globally across the application. It supports both sync and async modes.
+//!
+//! For a history scoped to a single client, enable a [TraceBuffer] with
+//! [ConnectionOptions::trace_buffer](crate::ConnectionOptions::trace_buffer).
 
 // Common types and storage
+mod buffer;
 mod common;
 
 // Feature-specific implementations
@@ -14,6 +18,7 @@ mod sync;
 mod r#async;
 
 // Public types - always available regardless of feature flags
+pub use buffer::{TraceBuffer, TraceExport, TraceHeader, TracedInteraction, TracedMessage, RESPONSES_PER_INTERACTION};
 pub use common::Interaction;
 
 // Re-export API functions based on active feature
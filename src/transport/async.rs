@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 use std::mem;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use log::{debug, error, info, warn};
@@ -25,9 +25,16 @@ pub enum CleanupSignal {
 }
 
 use crate::connection::r#async::AsyncConnection;
+use crate::connection::{ConnectionEvent, ConnectionEventHub, Heartbeat};
 use crate::messages::{shared_channel_configuration, IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::orders::common::decoders::decode_order_bound;
+use crate::orders::journal;
+use crate::orders::OrderBindings;
+use crate::trace::TraceBuffer;
 use crate::Error;
 
+use super::common::HeartbeatProbes;
+use super::instrumentation::RequestKey;
 use super::routing::{determine_routing, is_warning_error, RoutingDecision, UNSPECIFIED_REQUEST_ID};
 
 /// Asynchronous message bus trait
@@ -64,6 +71,24 @@ pub trait AsyncMessageBus: Send + Sync {
     /// Returns true if the client is currently connected to TWS/IB Gateway
     fn is_connected(&self) -> bool;
 
+    /// Returns the connection event hub. Buses without a TWS connection have none.
+    fn connection_events(&self) -> Option<&ConnectionEventHub> {
+        None
+    }
+
+    /// Returns the trace buffer of the connection. Buses without a TWS connection have none.
+    fn trace_buffer(&self) -> Option<&TraceBuffer> {
+        None
+    }
+
+    /// Returns the index of orders bound to API clients. Buses without a TWS connection have none.
+    fn order_bindings(&self) -> Option<&OrderBindings> {
+        None
+    }
+
+    /// Reports a message that a subscription failed to decode.
+    fn record_decode_error(&self, _message_type: IncomingMessages) {}
+
     #[cfg(test)]
     fn request_messages(&self) -> Vec<RequestMessage> {
         vec![]
@@ -173,6 +198,8 @@ pub struct AsyncTcpMessageBus {
     execution_channels: Arc<RwLock<HashMap<String, BroadcastSender>>>,
     /// Optional channel for order update stream
     order_update_stream: Arc<RwLock<Option<BroadcastSender>>>,
+    /// Orders bound to API clients, by permanent id
+    order_bindings: OrderBindings,
     /// Channel for cleanup signals
     cleanup_sender: mpsc::UnboundedSender<CleanupSignal>,
     /// Handle to the message processing task
@@ -182,6 +209,12 @@ pub struct AsyncTcpMessageBus {
     /// Notification to wake the message loop on shutdown
     shutdown_notify: Arc<Notify>,
     connected: Arc<AtomicBool>,
+    /// Notification to the message loop that the heartbeat went unanswered
+    heartbeat_stalled: Arc<Notify>,
+    /// Why the heartbeat closed the connection
+    disconnect_reason: Mutex<Option<String>>,
+    /// Liveness of the connection and heartbeat replies to keep from shared channels
+    heartbeat_probes: HeartbeatProbes,
 }
 
 impl Drop for AsyncTcpMessageBus {
@@ -220,17 +253,22 @@ impl AsyncTcpMessageBus {
             order_channels: Arc::new(RwLock::new(HashMap::new())),
             execution_channels: Arc::new(RwLock::new(HashMap::new())),
             order_update_stream: Arc::new(RwLock::new(None)),
+            order_bindings: OrderBindings::new(),
             cleanup_sender,
             process_task: Arc::new(RwLock::new(None)),
             shutdown_requested: Arc::new(AtomicBool::new(false)),
             shutdown_notify: Arc::new(Notify::new()),
             connected: Arc::new(AtomicBool::new(true)),
+            heartbeat_stalled: Arc::new(Notify::new()),
+            disconnect_reason: Mutex::new(None),
+            heartbeat_probes: HeartbeatProbes::new(),
         };
 
         // Start cleanup task
         let request_channels = message_bus.request_channels.clone();
         let order_channels = message_bus.order_channels.clone();
         let order_update_stream = message_bus.order_update_stream.clone();
+        let connection = message_bus.connection.clone();
 
         task::spawn(async move {
             let mut receiver = cleanup_receiver;
@@ -239,11 +277,13 @@ impl AsyncTcpMessageBus {
                     CleanupSignal::Request(request_id) => {
                         let mut channels = request_channels.write().await;
                         channels.remove(&request_id);
+                        connection.instrumentation.request_ended(RequestKey::Request(request_id));
                         debug!("Cleaned up request channel for ID: {request_id}");
                     }
                     CleanupSignal::Order(order_id) => {
                         let mut channels = order_channels.write().await;
                         channels.remove(&order_id);
+                        connection.instrumentation.request_ended(RequestKey::Order(order_id));
                         debug!("Cleaned up order channel for ID: {order_id}");
                     }
                     CleanupSignal::Shared(message_type) => {
@@ -267,6 +307,7 @@ impl AsyncTcpMessageBus {
     pub fn process_messages(self: Arc<Self>, _server_version: i32, _reconnect_delay: Duration) -> Result<(), Error> {
         let message_bus = self.clone();
         let shutdown_notify = self.shutdown_notify.clone();
+        let heartbeat_stalled = self.heartbeat_stalled.clone();
 
         let handle = task::spawn(async move {
             loop {
@@ -278,6 +319,12 @@ impl AsyncTcpMessageBus {
                         debug!("Shutdown notification received, stopping message processing");
                         break;
                     }
+                    // The connection is replaced, so abandoning a partial read is safe here.
+                    _ = heartbeat_stalled.notified() => {
+                        if message_bus.handle_connection_loss(&Error::ConnectionReset).await.is_err() {
+                            break;
+                        }
+                    }
                     result = message_bus.read_and_route_message() => {
                         use crate::client::error_handler::{is_connection_error, is_timeout_error};
 
@@ -292,19 +339,8 @@ impl AsyncTcpMessageBus {
                             }
                             Err(ref err) if is_connection_error(err) => {
                                 error!("Connection error detected, attempting to reconnect: {err:?}");
-                                message_bus.connected.store(false, Ordering::Relaxed);
-
-                                match message_bus.connection.reconnect().await {
-                                    Ok(_) => {
-                                        info!("Successfully reconnected to TWS/Gateway");
-                                        message_bus.connected.store(true, Ordering::Relaxed);
-                                        message_bus.reset_channels().await;
-                                    }
-                                    Err(e) => {
-                                        error!("Failed to reconnect to TWS/Gateway: {e:?}");
-                                        message_bus.request_shutdown().await;
-                                        break;
-                                    }
+                                if message_bus.handle_connection_loss(err).await.is_err() {
+                                    break;
                                 }
                                 continue;
                             }
@@ -333,9 +369,97 @@ impl AsyncTcpMessageBus {
         Ok(())
     }
 
+    /// Reconnect after the connection was lost, shutting down if all attempts fail
+    async fn handle_connection_loss(&self, err: &Error) -> Result<(), Error> {
+        self.connected.store(false, Ordering::Relaxed);
+
+        let reason = self.disconnect_reason.lock().unwrap().take().unwrap_or_else(|| err.to_string());
+        self.connection.events.publish(ConnectionEvent::Disconnected { error: reason });
+
+        if let Err(e) = self.connection.reconnect().await {
+            error!("Failed to reconnect to TWS/Gateway: {e:?}");
+            self.connection.instrumentation.reconnect(false);
+            self.request_shutdown().await;
+            self.connection.events.publish(ConnectionEvent::GaveUp);
+            return Err(Error::ConnectionFailed);
+        }
+
+        info!("Successfully reconnected to TWS/Gateway");
+        self.connection.instrumentation.reconnect(true);
+        self.reset_channels().await;
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Start a task that requests the server time every `heartbeat.interval` and
+    /// reconnects when no message arrives within `heartbeat.timeout`
+    pub fn start_heartbeat(self: &Arc<Self>, heartbeat: Heartbeat) {
+        let message_bus = self.clone();
+        let shutdown_notify = self.shutdown_notify.clone();
+
+        task::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_notify.notified() => break,
+                    _ = tokio::time::sleep(heartbeat.interval) => {}
+                }
+
+                if message_bus.shutdown_requested.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                // Reconnection is in progress
+                if !message_bus.is_connected() {
+                    continue;
+                }
+
+                message_bus.heartbeat(heartbeat.timeout).await;
+            }
+            debug!("heartbeat task exiting");
+        });
+    }
+
+    async fn heartbeat(&self, timeout: Duration) {
+        const POLL: Duration = Duration::from_millis(10);
+
+        let (probe, sent) = self.heartbeat_probes.probe();
+        if let Err(e) = self.connection.write_message(&probe).await {
+            self.heartbeat_probes.cancel();
+            warn!("error sending heartbeat: {e}");
+            return;
+        }
+
+        // Any message counts as a reply; the probe's own reply is kept from server_time() requests.
+        let reply = async {
+            while !self.heartbeat_probes.received_since(sent) {
+                tokio::time::sleep(POLL).await;
+            }
+        };
+
+        match tokio::time::timeout(timeout, reply).await {
+            Ok(()) => {}
+            Err(_) if self.shutdown_requested.load(Ordering::Relaxed) => {}
+            Err(_) => {
+                let reason = format!("no reply to heartbeat within {timeout:?}");
+                error!("{reason}, reconnecting");
+                *self.disconnect_reason.lock().unwrap() = Some(reason);
+                self.heartbeat_stalled.notify_one();
+            }
+        }
+    }
+
     /// Read a message and route it to the appropriate channel
     async fn read_and_route_message(&self) -> Result<(), Error> {
         let message = self.connection.read_message().await?;
+        self.connection.instrumentation.response_received(&message);
+
+        if let Some(journal) = &self.connection.options.order_journal {
+            journal::record_message(journal, self.connection.server_version(), &message);
+        }
+
+        if self.heartbeat_probes.received(&message) {
+            return Ok(());
+        }
 
         // Use common routing logic
         match determine_routing(&message) {
@@ -386,6 +510,9 @@ impl AsyncTcpMessageBus {
             let mut channels = self.execution_channels.write().await;
             channels.clear();
         }
+
+        self.connection.instrumentation.clear();
+        self.heartbeat_probes.reset();
     }
 
     /// Notify all waiting subscriptions about shutdown
@@ -423,6 +550,8 @@ impl AsyncTcpMessageBus {
             let mut order_update_stream = self.order_update_stream.write().await;
             *order_update_stream = None;
         }
+
+        self.connection.instrumentation.clear();
     }
 
     /// Route error message using routing decision
@@ -479,12 +608,20 @@ impl AsyncTcpMessageBus {
 
     /// Route message to order-specific channel
     async fn route_to_order_channel(&self, order_id: i32, message: ResponseMessage) -> Result<(), Error> {
-        // Send to order update stream if it exists
-        let routed = self.send_order_update(&message).await;
         let message_type = message.message_type();
 
+        // Send to order update stream if it exists; delta-neutral validations only concern the placing request
+        let routed = message_type != IncomingMessages::DeltaNeutralValidation && self.send_order_update(&message).await;
+
         // Special handling for different order message types
         match message_type {
+            IncomingMessages::OrderBound => {
+                // Bindings are indexed whether or not anyone listens to the order update stream.
+                match decode_order_bound(&mut message.clone()) {
+                    Ok(binding) => self.order_bindings.record(binding),
+                    Err(e) => warn!("error decoding order binding {message:?}: {e}"),
+                }
+            }
             IncomingMessages::ExecutionData => {
                 let order_id = message.order_id();
                 let request_id = message.request_id();
@@ -669,6 +806,7 @@ impl AsyncMessageBus for AsyncTcpMessageBus {
             let mut channels = self.request_channels.write().await;
             channels.insert(request_id, sender);
         }
+        self.connection.instrumentation.request_started(RequestKey::Request(request_id), &message);
 
         // Now send the request - any response will find the channel
         self.connection.write_message(&message).await?;
@@ -689,6 +827,7 @@ impl AsyncMessageBus for AsyncTcpMessageBus {
             let mut channels = self.order_channels.write().await;
             channels.insert(order_id, sender);
         }
+        self.connection.instrumentation.request_started(RequestKey::Order(order_id), &message);
 
         self.connection.write_message(&message).await?;
 
@@ -741,6 +880,7 @@ impl AsyncMessageBus for AsyncTcpMessageBus {
         // Remove channel
         let mut channels = self.request_channels.write().await;
         channels.remove(&request_id);
+        self.connection.instrumentation.request_ended(RequestKey::Request(request_id));
 
         Ok(())
     }
@@ -757,6 +897,7 @@ impl AsyncMessageBus for AsyncTcpMessageBus {
         // Remove channel
         let mut channels = self.order_channels.write().await;
         channels.remove(&order_id);
+        self.connection.instrumentation.request_ended(RequestKey::Order(order_id));
 
         Ok(())
     }
@@ -810,4 +951,20 @@ impl AsyncMessageBus for AsyncTcpMessageBus {
     fn is_connected(&self) -> bool {
         self.connected.load(Ordering::Relaxed) && !self.shutdown_requested.load(Ordering::Relaxed)
     }
+
+    fn connection_events(&self) -> Option<&ConnectionEventHub> {
+        Some(&self.connection.events)
+    }
+
+    fn trace_buffer(&self) -> Option<&TraceBuffer> {
+        Some(self.connection.instrumentation.trace())
+    }
+
+    fn order_bindings(&self) -> Option<&OrderBindings> {
+        Some(&self.order_bindings)
+    }
+
+    fn record_decode_error(&self, message_type: IncomingMessages) {
+        self.connection.instrumentation.decode_error(message_type);
+    }
 }
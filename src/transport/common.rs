@@ -1,6 +1,10 @@
 //! Common utilities shared between sync and async transport implementations
 
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::messages::{IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage};
 
 /// Maximum number of reconnection attempts
 pub(crate) const MAX_RECONNECT_ATTEMPTS: i32 = 20;
@@ -34,6 +38,68 @@ impl FibonacciBackoff {
     }
 }
 
+/// Liveness tracking for the connection heartbeat.
+///
+/// Any inbound message shows that the connection is alive. Probes are server time requests whose
+/// replies are consumed here, so they never reach the shared channel of `server_time()` requests
+/// made by the application.
+#[derive(Debug)]
+pub(crate) struct HeartbeatProbes {
+    // Probes sent on the current connection whose reply has not arrived yet.
+    pending: AtomicUsize,
+    last_received: Mutex<Instant>,
+}
+
+impl HeartbeatProbes {
+    pub(crate) fn new() -> Self {
+        HeartbeatProbes {
+            pending: AtomicUsize::new(0),
+            last_received: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Encodes a probe and counts it as pending. Call [HeartbeatProbes::cancel] if it could not be sent.
+    pub(crate) fn probe(&self) -> (RequestMessage, Instant) {
+        let mut message = RequestMessage::new();
+        message.push_field(&OutgoingMessages::RequestCurrentTime);
+        message.push_field(&1);
+
+        // Counted before sending so a fast reply cannot reach the dispatcher first.
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        (message, Instant::now())
+    }
+
+    /// Forgets a probe that failed to send.
+    pub(crate) fn cancel(&self) {
+        let _ = self
+            .pending
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| pending.checked_sub(1));
+    }
+
+    /// Records an inbound message. Returns true if it answered a probe and must not be routed.
+    pub(crate) fn received(&self, message: &ResponseMessage) -> bool {
+        *self.last_received.lock().unwrap() = Instant::now();
+
+        // Replies are not tagged, so the first pending server time replies are taken. The application
+        // still gets one reply per request, each sent after its request.
+        message.message_type() == IncomingMessages::CurrentTime
+            && self
+                .pending
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |pending| pending.checked_sub(1))
+                .is_ok()
+    }
+
+    /// Returns true if any message arrived at or after `since`.
+    pub(crate) fn received_since(&self, since: Instant) -> bool {
+        *self.last_received.lock().unwrap() >= since
+    }
+
+    /// Forgets probes sent on a connection that has been replaced.
+    pub(crate) fn reset(&self) {
+        self.pending.store(0, Ordering::SeqCst);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,4 +116,28 @@ mod tests {
         assert_eq!(backoff.next_delay(), Duration::from_secs(10)); // capped at max
         assert_eq!(backoff.next_delay(), Duration::from_secs(10)); // stays at max
     }
+
+    #[test]
+    fn test_heartbeat_probes_consume_probe_replies() {
+        let probes = HeartbeatProbes::new();
+        let reply = ResponseMessage::from_simple("49|1|1700000000|");
+        let other = ResponseMessage::from_simple("9|1|1|");
+
+        let (message, sent) = probes.probe();
+        assert_eq!(message.encode_simple(), "49|1|");
+        assert!(!probes.received_since(sent + Duration::from_secs(1)));
+
+        assert!(!probes.received(&other), "other messages are routed");
+        assert!(probes.received_since(sent), "any message shows liveness");
+        assert!(probes.received(&reply), "probe reply is consumed");
+        assert!(!probes.received(&reply), "later replies belong to the application");
+
+        probes.probe();
+        probes.reset();
+        assert!(!probes.received(&reply), "probes do not survive a reconnect");
+
+        probes.probe();
+        probes.cancel();
+        assert!(!probes.received(&reply), "cancelled probe expects no reply");
+    }
 }
@@ -0,0 +1,331 @@
+//! Metrics, `tracing` spans and the trace buffer for messages flowing through a connection.
+//!
+//! Requests with a dedicated response channel (keyed by request id or order id) are tracked from
+//! the time they are sent until their channel is released. The first response reports the request
+//! latency; with the `tracing` feature each request also gets a span.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::connection::ConnectionOptions;
+use crate::messages::{IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::metrics::Metrics;
+use crate::trace::TraceBuffer;
+
+/// Identifies a request with a dedicated response channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum RequestKey {
+    Request(i32),
+    Order(i32),
+}
+
+#[derive(Debug)]
+struct InFlight {
+    message_type: Option<OutgoingMessages>,
+    sent: Instant,
+    responded: bool,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+#[derive(Default)]
+pub(crate) struct Instrumentation {
+    metrics: Option<Arc<dyn Metrics>>,
+    trace: TraceBuffer,
+    in_flight: Mutex<HashMap<RequestKey, InFlight>>,
+}
+
+impl Instrumentation {
+    pub(crate) fn new(options: &ConnectionOptions) -> Self {
+        Self {
+            metrics: options.metrics.clone(),
+            trace: TraceBuffer::new(options.trace_capacity),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn trace(&self) -> &TraceBuffer {
+        &self.trace
+    }
+
+    // Without a metrics sink or the tracing feature there is nothing to report per request.
+    fn tracks_requests(&self) -> bool {
+        cfg!(feature = "tracing") || self.metrics.is_some()
+    }
+
+    pub(crate) fn message_sent(&self, message: &RequestMessage) {
+        self.trace.record_request(message);
+        if let (Some(metrics), Some(message_type)) = (&self.metrics, message_type(message)) {
+            metrics.message_sent(message_type);
+        }
+    }
+
+    pub(crate) fn message_received(&self, message: &ResponseMessage) {
+        self.trace.record_response(message);
+        if let Some(metrics) = &self.metrics {
+            metrics.message_received(message.message_type());
+        }
+    }
+
+    pub(crate) fn decode_error(&self, message_type: IncomingMessages) {
+        if let Some(metrics) = &self.metrics {
+            metrics.decode_error(message_type);
+        }
+    }
+
+    pub(crate) fn reconnect(&self, succeeded: bool) {
+        if let Some(metrics) = &self.metrics {
+            metrics.reconnect(succeeded);
+        }
+    }
+
+    pub(crate) fn request_started(&self, key: RequestKey, message: &RequestMessage) {
+        self.trace.record_request_started(key, message);
+        if !self.tracks_requests() {
+            return;
+        }
+
+        let message_type = message_type(message);
+
+        #[cfg(feature = "tracing")]
+        let span = {
+            let (request_id, order_id) = match key {
+                RequestKey::Request(id) => (Some(id), None),
+                RequestKey::Order(id) => (None, Some(id)),
+            };
+            let span = tracing::info_span!("ibapi.request", message_type = ?message_type, request_id, order_id);
+            span.in_scope(|| tracing::debug!("request sent"));
+            span
+        };
+
+        let in_flight = InFlight {
+            message_type,
+            sent: Instant::now(),
+            responded: false,
+            #[cfg(feature = "tracing")]
+            span,
+        };
+
+        let mut requests = self.in_flight.lock().unwrap();
+        requests.insert(key, in_flight);
+        self.report_active(requests.len());
+    }
+
+    /// Reports latency when `message` is the first response to a tracked request.
+    pub(crate) fn response_received(&self, message: &ResponseMessage) {
+        if !self.tracks_requests() {
+            return;
+        }
+
+        let mut requests = self.in_flight.lock().unwrap();
+        if requests.is_empty() {
+            return;
+        }
+
+        // Errors carry the id of the failed request or order in a version dependent field.
+        let (request_id, order_id) = if message.message_type() == IncomingMessages::Error {
+            let id = message.error_request_id();
+            (Some(id), Some(id))
+        } else {
+            (message.request_id(), message.order_id())
+        };
+        let keys = [request_id.map(RequestKey::Request), order_id.map(RequestKey::Order)];
+        let key = keys.into_iter().flatten().find(|key| requests.contains_key(key));
+        let Some(request) = key.and_then(|key| requests.get_mut(&key)) else {
+            return;
+        };
+
+        if request.responded {
+            return;
+        }
+        request.responded = true;
+
+        let latency = request.sent.elapsed();
+
+        #[cfg(feature = "tracing")]
+        request.span.in_scope(|| {
+            tracing::debug!(response_type = ?message.message_type(), latency_us = latency.as_micros() as u64, "first response");
+        });
+
+        if let (Some(metrics), Some(message_type)) = (&self.metrics, request.message_type) {
+            metrics.request_latency(message_type, latency);
+        }
+    }
+
+    /// Stops tracking a request whose response channel was released.
+    pub(crate) fn request_ended(&self, key: RequestKey) {
+        if !self.tracks_requests() {
+            return;
+        }
+
+        let mut requests = self.in_flight.lock().unwrap();
+        if let Some(_request) = requests.remove(&key) {
+            #[cfg(feature = "tracing")]
+            _request.span.in_scope(|| tracing::debug!("request ended"));
+            self.report_active(requests.len());
+        }
+    }
+
+    /// Stops tracking all requests, e.g. after the connection was reset.
+    pub(crate) fn clear(&self) {
+        if !self.tracks_requests() {
+            return;
+        }
+
+        let mut requests = self.in_flight.lock().unwrap();
+        if requests.is_empty() {
+            return;
+        }
+
+        #[cfg(feature = "tracing")]
+        for request in requests.values() {
+            request.span.in_scope(|| tracing::debug!("request ended by connection reset"));
+        }
+        requests.clear();
+        self.report_active(0);
+    }
+
+    fn report_active(&self, count: usize) {
+        if let Some(metrics) = &self.metrics {
+            metrics.active_subscriptions(count);
+        }
+    }
+}
+
+impl fmt::Debug for Instrumentation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instrumentation")
+            .field("metrics", &self.metrics.is_some())
+            .field("trace_capacity", &self.trace.capacity())
+            .field("in_flight", &self.in_flight.lock().map(|requests| requests.len()).unwrap_or_default())
+            .finish()
+    }
+}
+
+fn message_type(message: &RequestMessage) -> Option<OutgoingMessages> {
+    message.fields.first().and_then(|field| OutgoingMessages::from_str(field).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        sent: Mutex<Vec<OutgoingMessages>>,
+        received: Mutex<Vec<IncomingMessages>>,
+        decode_errors: Mutex<Vec<IncomingMessages>>,
+        reconnects: Mutex<Vec<bool>>,
+        active: Mutex<Vec<usize>>,
+        latencies: Mutex<Vec<(OutgoingMessages, Duration)>>,
+    }
+
+    impl Metrics for Recorder {
+        fn message_sent(&self, message_type: OutgoingMessages) {
+            self.sent.lock().unwrap().push(message_type);
+        }
+
+        fn message_received(&self, message_type: IncomingMessages) {
+            self.received.lock().unwrap().push(message_type);
+        }
+
+        fn decode_error(&self, message_type: IncomingMessages) {
+            self.decode_errors.lock().unwrap().push(message_type);
+        }
+
+        fn reconnect(&self, succeeded: bool) {
+            self.reconnects.lock().unwrap().push(succeeded);
+        }
+
+        fn active_subscriptions(&self, count: usize) {
+            self.active.lock().unwrap().push(count);
+        }
+
+        fn request_latency(&self, message_type: OutgoingMessages, latency: Duration) {
+            self.latencies.lock().unwrap().push((message_type, latency));
+        }
+    }
+
+    fn instrumented() -> (Arc<Recorder>, Instrumentation) {
+        let recorder = Arc::new(Recorder::default());
+        let instrumentation = Instrumentation::new(&ConnectionOptions::default().metrics(recorder.clone()));
+        (recorder, instrumentation)
+    }
+
+    #[test]
+    fn test_counts_messages_by_type() {
+        let (recorder, instrumentation) = instrumented();
+
+        instrumentation.message_sent(&RequestMessage::from_simple("9|8|9000|"));
+        instrumentation.message_sent(&RequestMessage::from_simple("not a message type|"));
+        instrumentation.message_received(&ResponseMessage::from_simple("10|9000|AAPL|"));
+        instrumentation.decode_error(IncomingMessages::ContractData);
+        instrumentation.reconnect(true);
+
+        assert_eq!(*recorder.sent.lock().unwrap(), vec![OutgoingMessages::RequestContractData]);
+        assert_eq!(*recorder.received.lock().unwrap(), vec![IncomingMessages::ContractData]);
+        assert_eq!(*recorder.decode_errors.lock().unwrap(), vec![IncomingMessages::ContractData]);
+        assert_eq!(*recorder.reconnects.lock().unwrap(), vec![true]);
+    }
+
+    #[test]
+    fn test_latency_recorded_on_first_response() {
+        let (recorder, instrumentation) = instrumented();
+
+        instrumentation.request_started(RequestKey::Request(9000), &RequestMessage::from_simple("9|8|9000|"));
+        instrumentation.response_received(&ResponseMessage::from_simple("10|9000|AAPL|"));
+        instrumentation.response_received(&ResponseMessage::from_simple("52|1|9000|"));
+        instrumentation.request_ended(RequestKey::Request(9000));
+
+        let latencies = recorder.latencies.lock().unwrap();
+        assert_eq!(latencies.len(), 1, "only the first response is timed");
+        assert_eq!(latencies[0].0, OutgoingMessages::RequestContractData);
+        assert_eq!(*recorder.active.lock().unwrap(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_latency_matches_orders_and_errors() {
+        let (recorder, instrumentation) = instrumented();
+
+        instrumentation.request_started(RequestKey::Order(5), &RequestMessage::from_simple("3|5|"));
+        instrumentation.request_started(RequestKey::Request(9001), &RequestMessage::from_simple("9|8|9001|"));
+
+        // responses for untracked requests are ignored
+        instrumentation.response_received(&ResponseMessage::from_simple("10|42|AAPL|"));
+        instrumentation.response_received(&ResponseMessage::from_simple("3|5|Filled|100|0|201.94|"));
+        instrumentation.response_received(&ResponseMessage::from_simple("4|2|9001|200|No security definition has been found|"));
+
+        let latencies: Vec<OutgoingMessages> = recorder.latencies.lock().unwrap().iter().map(|(message_type, _)| *message_type).collect();
+        assert_eq!(latencies, vec![OutgoingMessages::PlaceOrder, OutgoingMessages::RequestContractData]);
+    }
+
+    #[test]
+    fn test_clear_ends_all_requests() {
+        let (recorder, instrumentation) = instrumented();
+
+        instrumentation.request_started(RequestKey::Request(1), &RequestMessage::from_simple("1|11|1|"));
+        instrumentation.request_started(RequestKey::Order(2), &RequestMessage::from_simple("3|2|"));
+        instrumentation.clear();
+        instrumentation.request_ended(RequestKey::Request(1));
+
+        assert_eq!(*recorder.active.lock().unwrap(), vec![1, 2, 0]);
+        assert!(format!("{instrumentation:?}").contains("in_flight: 0"));
+    }
+
+    #[test]
+    fn test_without_metrics() {
+        let instrumentation = Instrumentation::default();
+
+        instrumentation.message_sent(&RequestMessage::from_simple("9|8|9000|"));
+        instrumentation.request_started(RequestKey::Request(9000), &RequestMessage::from_simple("9|8|9000|"));
+        instrumentation.response_received(&ResponseMessage::from_simple("10|9000|AAPL|"));
+        instrumentation.request_ended(RequestKey::Request(9000));
+
+        assert!(format!("{instrumentation:?}").contains("metrics: false"));
+    }
+}
@@ -2,20 +2,27 @@
 
 // Common utilities
 pub(crate) mod common;
+pub(crate) mod instrumentation;
 
 #[cfg(feature = "sync")]
 use std::sync::Arc;
 #[cfg(feature = "sync")]
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "sync")]
-use crossbeam::channel::{Receiver, Sender};
+use crossbeam::channel::{Receiver, RecvTimeoutError, Sender};
 
 use crate::errors::Error;
 use crate::messages::{RequestMessage, ResponseMessage};
 
 #[cfg(feature = "sync")]
-use crate::messages::OutgoingMessages;
+use crate::connection::ConnectionEventHub;
+#[cfg(feature = "sync")]
+use crate::messages::{IncomingMessages, OutgoingMessages};
+#[cfg(feature = "sync")]
+use crate::orders::OrderBindings;
+#[cfg(feature = "sync")]
+use crate::trace::TraceBuffer;
 
 #[cfg(feature = "sync")]
 pub mod sync;
@@ -69,6 +76,24 @@ pub(crate) trait MessageBus: Send + Sync {
     /// Returns true if the client is currently connected to TWS/IB Gateway
     fn is_connected(&self) -> bool;
 
+    /// Returns the connection event hub. Buses without a TWS connection have none.
+    fn connection_events(&self) -> Option<&ConnectionEventHub> {
+        None
+    }
+
+    /// Returns the trace buffer of the connection. Buses without a TWS connection have none.
+    fn trace_buffer(&self) -> Option<&TraceBuffer> {
+        None
+    }
+
+    /// Returns the index of orders bound to API clients. Buses without a TWS connection have none.
+    fn order_bindings(&self) -> Option<&OrderBindings> {
+        None
+    }
+
+    /// Reports a message that a subscription failed to decode.
+    fn record_decode_error(&self, _message_type: IncomingMessages) {}
+
     // Testing interface. Tracks requests sent messages when Bus is stubbed.
     #[cfg(test)]
     fn request_messages(&self) -> Vec<RequestMessage> {
@@ -124,6 +149,23 @@ impl InternalSubscription {
         }
     }
 
+    // Waits for next message until the deadline, if any. Unlike next_timeout, an expired deadline yields Error::Timeout.
+    pub(crate) fn next_until(&self, deadline: Option<Instant>) -> Option<Response> {
+        let Some(deadline) = deadline else {
+            return self.next();
+        };
+        let receiver = match (&self.receiver, &self.shared_receiver) {
+            (Some(receiver), _) => receiver,
+            (None, Some(receiver)) => receiver.as_ref(),
+            (None, None) => return None,
+        };
+        match receiver.recv_deadline(deadline) {
+            Ok(response) => Some(response),
+            Err(RecvTimeoutError::Timeout) => Some(Err(Error::Timeout)),
+            Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
     pub(crate) fn cancel(&self) {
         if let Some(sender) = &self.sender {
             if let Err(e) = sender.send(Err(Error::Cancelled)) {
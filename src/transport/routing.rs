@@ -21,6 +21,15 @@ pub enum RoutingDecision {
 
 /// Determine how to route an incoming message
 pub fn determine_routing(message: &ResponseMessage) -> RoutingDecision {
+    let decision = route(message);
+
+    #[cfg(feature = "tracing")]
+    tracing::trace!(target: "ibapi::routing", message_type = ?message.message_type(), decision = ?decision, "routing message");
+
+    decision
+}
+
+fn route(message: &ResponseMessage) -> RoutingDecision {
     let message_type = message.message_type();
 
     if message_type == IncomingMessages::Shutdown {
@@ -44,7 +53,9 @@ pub fn determine_routing(message: &ResponseMessage) -> RoutingDecision {
         | IncomingMessages::CompletedOrdersEnd
         | IncomingMessages::ExecutionData
         | IncomingMessages::ExecutionDataEnd
-        | IncomingMessages::CommissionsReport => {
+        | IncomingMessages::CommissionsReport
+        | IncomingMessages::DeltaNeutralValidation
+        | IncomingMessages::OrderBound => {
             // For order messages that have an order ID, route by order ID
             // Otherwise, it will be handled by process_orders which checks other routing options
             if let Some(order_id) = message.order_id() {
@@ -190,5 +201,21 @@ mod tests {
             RoutingDecision::ByOrderId(id) => assert_eq!(id, -1),
             routing => panic!("Expected ByOrderId(-1) routing, got {routing:?}"),
         }
+
+        // Test DeltaNeutralValidation with order ID at position 2
+        let message_str = "56\01\013\08314\00.52\0185.5\0"; // DeltaNeutralValidation
+        let message = ResponseMessage::from(message_str);
+        match determine_routing(&message) {
+            RoutingDecision::ByOrderId(id) => assert_eq!(id, 13),
+            routing => panic!("Expected ByOrderId routing, got {routing:?}"),
+        }
+
+        // Test OrderBound with order ID after the perm id and client id
+        let message_str = "100\01376327563\00\0-12\0"; // OrderBound
+        let message = ResponseMessage::from(message_str);
+        match determine_routing(&message) {
+            RoutingDecision::ByOrderId(id) => assert_eq!(id, -12),
+            routing => panic!("Expected ByOrderId routing, got {routing:?}"),
+        }
     }
 }
@@ -9,17 +9,24 @@ use std::ops::RangeInclusive;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use byteorder::{BigEndian, ReadBytesExt};
 use crossbeam::channel::{self, Receiver, Sender};
 use log::{debug, error, info, warn};
 
 use crate::connection::sync::Connection;
+use crate::connection::{ConnectionEvent, ConnectionEventHub, Heartbeat};
 
+use super::common::HeartbeatProbes;
+use super::instrumentation::RequestKey;
 use super::routing::{determine_routing, is_warning_error, RoutingDecision, UNSPECIFIED_REQUEST_ID};
 use super::{InternalSubscription, MessageBus, Response, Signal, SubscriptionBuilder};
 use crate::messages::{shared_channel_configuration, IncomingMessages, OutgoingMessages, RequestMessage, ResponseMessage};
+use crate::orders::common::decoders::decode_order_bound;
+use crate::orders::journal;
+use crate::orders::OrderBindings;
+use crate::trace::TraceBuffer;
 use crate::{server_versions, Error};
 
 // pub(crate) const MIN_SERVER_VERSION: i32 = 100;
@@ -123,7 +130,10 @@ pub struct TcpMessageBus<S: Stream> {
     signals_recv: Receiver<Signal>,
     shutdown_requested: AtomicBool,
     order_update_stream: Mutex<Option<Sender<Response>>>,
+    order_bindings: OrderBindings,
     connected: AtomicBool,
+    disconnect_reason: Mutex<Option<String>>,
+    heartbeat_probes: HeartbeatProbes,
 }
 
 impl<S: Stream> TcpMessageBus<S> {
@@ -141,7 +151,10 @@ impl<S: Stream> TcpMessageBus<S> {
             signals_recv,
             shutdown_requested: AtomicBool::new(false),
             order_update_stream: Mutex::new(None),
+            order_bindings: OrderBindings::new(),
             connected: AtomicBool::new(true),
+            disconnect_reason: Mutex::new(None),
+            heartbeat_probes: HeartbeatProbes::new(),
         })
     }
 
@@ -159,6 +172,7 @@ impl<S: Stream> TcpMessageBus<S> {
         self.requests.clear();
         self.orders.clear();
         self.executions.clear();
+        self.connection.instrumentation.clear();
 
         self.connected.store(false, Ordering::Relaxed);
         self.shutdown_requested.store(true, Ordering::Relaxed);
@@ -174,17 +188,21 @@ impl<S: Stream> TcpMessageBus<S> {
         self.requests.clear();
         self.orders.clear();
         self.executions.clear();
+        self.connection.instrumentation.clear();
+        self.heartbeat_probes.reset();
 
         self.connected.store(false, Ordering::Relaxed);
     }
 
     fn clean_request(&self, request_id: i32) {
         self.requests.remove(&request_id);
+        self.connection.instrumentation.request_ended(RequestKey::Request(request_id));
         debug!("released request_id {}, requests.len()={}", request_id, self.requests.len());
     }
 
     fn clean_order(&self, order_id: i32) {
         self.orders.remove(&order_id);
+        self.connection.instrumentation.request_ended(RequestKey::Order(order_id));
         debug!("released order_id {}, orders.len()={}", order_id, self.orders.len());
     }
 
@@ -225,18 +243,7 @@ impl<S: Stream> TcpMessageBus<S> {
             }
             Err(ref err) if is_connection_error(err) => {
                 error!("error reading next message (will attempt reconnect): {err:?}");
-                self.connected.store(false, Ordering::Relaxed);
-
-                if let Err(reconnect_err) = self.connection.reconnect() {
-                    error!("failed to reconnect to TWS/Gateway: {reconnect_err:?}");
-                    self.request_shutdown();
-                    return Err(Error::ConnectionFailed);
-                }
-
-                info!("successfully reconnected to TWS/Gateway");
-                self.connected.store(true, Ordering::Relaxed);
-                self.reset();
-                Ok(())
+                self.handle_connection_loss(err)
             }
             Err(err) => {
                 error!("error reading next message (shutting down): {err:?}");
@@ -246,6 +253,91 @@ impl<S: Stream> TcpMessageBus<S> {
         }
     }
 
+    fn handle_connection_loss(&self, err: &Error) -> Result<(), Error> {
+        self.connected.store(false, Ordering::Relaxed);
+
+        // A connection closed by the heartbeat surfaces as a read error; report why it was closed.
+        let reason = self.disconnect_reason.lock().unwrap().take().unwrap_or_else(|| err.to_string());
+        self.connection.events.publish(ConnectionEvent::Disconnected { error: reason });
+
+        if let Err(reconnect_err) = self.connection.reconnect() {
+            error!("failed to reconnect to TWS/Gateway: {reconnect_err:?}");
+            self.connection.instrumentation.reconnect(false);
+            self.request_shutdown();
+            self.connection.events.publish(ConnectionEvent::GaveUp);
+            return Err(Error::ConnectionFailed);
+        }
+
+        info!("successfully reconnected to TWS/Gateway");
+        self.connection.instrumentation.reconnect(true);
+        self.reset();
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    // Requests the server time and closes the connection if no message arrives within `timeout`.
+    // Closing the socket makes the dispatcher thread fail its next read and reconnect.
+    fn heartbeat(&self, timeout: Duration) {
+        const POLL: Duration = Duration::from_millis(10);
+
+        let (probe, sent) = self.heartbeat_probes.probe();
+        if let Err(e) = self.connection.write_message(&probe) {
+            self.heartbeat_probes.cancel();
+            warn!("error sending heartbeat: {e}");
+            return;
+        }
+
+        // Any message counts as a reply; the probe's own reply is kept from server_time() requests.
+        let deadline = sent + timeout;
+        while !self.heartbeat_probes.received_since(sent) {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            thread::sleep(POLL.min(deadline - now));
+        }
+
+        if self.heartbeat_probes.received_since(sent) || self.is_shutting_down() {
+            return;
+        }
+
+        let reason = format!("no reply to heartbeat within {timeout:?}");
+        error!("{reason}, closing connection");
+        *self.disconnect_reason.lock().unwrap() = Some(reason);
+        self.connection.socket.disconnect();
+    }
+
+    // Heartbeat thread periodically checks that TWS still answers requests.
+    fn start_heartbeat_thread(self: &Arc<Self>, heartbeat: Heartbeat) -> JoinHandle<()> {
+        const SLICE: Duration = Duration::from_millis(100);
+
+        let message_bus = Arc::clone(self);
+        thread::spawn(move || loop {
+            let mut waited = Duration::ZERO;
+            while waited < heartbeat.interval {
+                if message_bus.is_shutting_down() {
+                    debug!("heartbeat thread exiting");
+                    return;
+                }
+                let slice = SLICE.min(heartbeat.interval - waited);
+                thread::sleep(slice);
+                waited += slice;
+            }
+
+            // Reconnection is in progress
+            if !message_bus.is_connected() {
+                continue;
+            }
+
+            message_bus.heartbeat(heartbeat.timeout);
+        })
+    }
+
+    pub(crate) fn start_heartbeat(self: &Arc<Self>, heartbeat: Heartbeat) {
+        let handle = self.start_heartbeat_thread(heartbeat);
+        self.add_join_handle(handle);
+    }
+
     // Dispatcher thread reads messages from TWS and dispatches them to
     // appropriate channel.
     fn start_dispatcher_thread(self: &Arc<Self>, server_version: i32) -> JoinHandle<()> {
@@ -266,6 +358,16 @@ impl<S: Stream> TcpMessageBus<S> {
     }
 
     fn dispatch_message(&self, server_version: i32, message: ResponseMessage) {
+        self.connection.instrumentation.response_received(&message);
+
+        if let Some(journal) = &self.connection.order_journal {
+            journal::record_message(journal, server_version, &message);
+        }
+
+        if self.heartbeat_probes.received(&message) {
+            return;
+        }
+
         // Use common routing logic
         match determine_routing(&message) {
             RoutingDecision::Error { request_id, error_code } => {
@@ -390,6 +492,25 @@ impl<S: Stream> TcpMessageBus<S> {
             IncomingMessages::CompletedOrder | IncomingMessages::OpenOrderEnd | IncomingMessages::CompletedOrdersEnd => {
                 self.shared_channels.send_message(message.message_type(), &message);
             }
+            IncomingMessages::DeltaNeutralValidation => {
+                // Only the place_order subscription that submitted the VOL order expects this reply.
+                match message.order_id() {
+                    Some(order_id) if self.orders.contains(&order_id) => {
+                        if let Err(e) = self.orders.send(&order_id, Ok(message)) {
+                            warn!("error routing message for order_id({order_id}): {e}");
+                        }
+                    }
+                    _ => warn!("could not route message {message:?}"),
+                }
+            }
+            IncomingMessages::OrderBound => {
+                // Bindings are indexed whether or not anyone listens to the order update stream.
+                match decode_order_bound(&mut message.clone()) {
+                    Ok(binding) => self.order_bindings.record(binding),
+                    Err(e) => warn!("error decoding order binding {message:?}: {e}"),
+                }
+                self.send_order_update(&message);
+            }
             IncomingMessages::CommissionsReport => {
                 let sent_to_update_stream = self.send_order_update(&message);
                 let exec_id = message.execution_id();
@@ -491,6 +612,7 @@ impl<S: Stream> MessageBus for TcpMessageBus<S> {
         let sender_copy = sender.clone();
 
         self.requests.insert(request_id, sender);
+        self.connection.instrumentation.request_started(RequestKey::Request(request_id), message);
 
         self.connection.write_message(message)?;
 
@@ -512,6 +634,7 @@ impl<S: Stream> MessageBus for TcpMessageBus<S> {
         }
 
         self.requests.remove(&request_id);
+        self.connection.instrumentation.request_ended(RequestKey::Request(request_id));
 
         Ok(())
     }
@@ -522,6 +645,7 @@ impl<S: Stream> MessageBus for TcpMessageBus<S> {
 
         self.orders.insert(order_id, sender);
         debug!("Registered order subscription for order_id={}", order_id);
+        self.connection.instrumentation.request_started(RequestKey::Order(order_id), message);
 
         self.connection.write_message(message)?;
 
@@ -564,6 +688,7 @@ impl<S: Stream> MessageBus for TcpMessageBus<S> {
         }
 
         self.orders.remove(&request_id);
+        self.connection.instrumentation.request_ended(RequestKey::Order(request_id));
 
         Ok(())
     }
@@ -595,6 +720,22 @@ impl<S: Stream> MessageBus for TcpMessageBus<S> {
     fn is_connected(&self) -> bool {
         self.connected.load(Ordering::Relaxed) && !self.is_shutting_down()
     }
+
+    fn connection_events(&self) -> Option<&ConnectionEventHub> {
+        Some(&self.connection.events)
+    }
+
+    fn trace_buffer(&self) -> Option<&TraceBuffer> {
+        Some(self.connection.instrumentation.trace())
+    }
+
+    fn order_bindings(&self) -> Option<&OrderBindings> {
+        Some(&self.order_bindings)
+    }
+
+    fn record_decode_error(&self, message_type: IncomingMessages) {
+        self.connection.instrumentation.decode_error(message_type);
+    }
 }
 
 fn error_event(server_version: i32, mut packet: ResponseMessage) -> Result<(), Error> {
@@ -759,11 +900,23 @@ impl Reconnect for TcpSocket {
     fn sleep(&self, duration: std::time::Duration) {
         thread::sleep(duration)
     }
+    fn disconnect(&self) {
+        match self.writer.lock() {
+            Ok(writer) => {
+                if let Err(e) = writer.shutdown(std::net::Shutdown::Both) {
+                    warn!("error closing socket: {e}");
+                }
+            }
+            Err(e) => warn!("error closing socket: {e}"),
+        }
+    }
 }
 
 pub(crate) trait Reconnect {
     fn reconnect(&self) -> Result<(), Error>;
     fn sleep(&self, duration: std::time::Duration);
+    // Closes the socket so blocked and future reads fail.
+    fn disconnect(&self);
 }
 
 pub(crate) trait Stream: Io + Reconnect + Sync + Send + 'static + std::fmt::Debug {}
@@ -806,13 +959,17 @@ pub(crate) trait Io {
 mod tests {
     use super::*;
     use crate::connection::sync::Connection;
+    use crate::connection::ConnectionOptions;
     use crate::tests::assert_send_and_sync;
     use crate::transport::common::MAX_RECONNECT_ATTEMPTS;
+    use crate::transport::instrumentation::Instrumentation;
 
     // Additional imports for connection tests
     use crate::client::sync::Client;
+    use crate::connection::sync::ConnectionEvents;
     use crate::contracts::Contract;
     use crate::messages::{encode_length, OutgoingMessages, RequestMessage};
+    use crate::metrics::Metrics;
     use crate::orders::common::encoders::encode_place_order;
     use crate::orders::{order_builder, Action};
     use log::{debug, trace};
@@ -911,6 +1068,9 @@ mod tests {
         // Accessed from read thread
         // Mutated by reader thread & writer threads
         read_call_count: AtomicUsize,
+
+        // Set by disconnect(), fails all further reads
+        disconnected: AtomicBool,
     }
 
     impl MockSocket {
@@ -923,6 +1083,7 @@ mod tests {
                 write_call_count: AtomicUsize::new(0),
                 responses_len: AtomicUsize::new(0),
                 read_call_count: AtomicUsize::new(0),
+                disconnected: AtomicBool::new(false),
             }
         }
     }
@@ -939,6 +1100,9 @@ mod tests {
             Err(mock_socket_error(ErrorKind::ConnectionRefused))
         }
         fn sleep(&self, _duration: std::time::Duration) {}
+        fn disconnect(&self) {
+            self.disconnected.store(true, Ordering::SeqCst);
+        }
     }
 
     impl Stream for MockSocket {}
@@ -947,6 +1111,10 @@ mod tests {
         fn read_message(&self) -> Result<Vec<u8>, Error> {
             trace!("===== mock read =====");
 
+            if self.disconnected.load(Ordering::SeqCst) {
+                return Err(mock_socket_error(ErrorKind::ConnectionReset));
+            }
+
             if self.keep_alive.load(Ordering::SeqCst) {
                 return Err(mock_socket_error(ErrorKind::WouldBlock));
             }
@@ -1107,6 +1275,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_bus_reports_metrics() -> Result<(), Error> {
+        #[derive(Default)]
+        struct Counters {
+            sent: AtomicUsize,
+            received: AtomicUsize,
+            latencies: AtomicUsize,
+            active: Mutex<Vec<usize>>,
+        }
+
+        impl Metrics for Counters {
+            fn message_sent(&self, _message_type: OutgoingMessages) {
+                self.sent.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn message_received(&self, _message_type: IncomingMessages) {
+                self.received.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn active_subscriptions(&self, count: usize) {
+                self.active.lock().unwrap().push(count);
+            }
+
+            fn request_latency(&self, message_type: OutgoingMessages, _latency: std::time::Duration) {
+                assert_eq!(message_type, OutgoingMessages::RequestContractData);
+                self.latencies.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let contract = Contract::stock("AAPL").build();
+        let request = encode_request_contract_data(173, 9000, &contract)?;
+
+        let events = vec![
+            Exchange::simple("v100..200", &["173|20250415 19:38:30 British Summer Time|"]),
+            Exchange::simple("71|2|28||", &["15|1|DU1234567|", "9|1|5|"]),
+            Exchange::request(request.clone(), &["52|1|9000|"]),
+        ];
+
+        let counters = Arc::new(Counters::default());
+        let mut connection = Connection::connect(MockSocket::new(events, 0), 28)?;
+        connection.instrumentation = Instrumentation::new(&ConnectionOptions::default().metrics(counters.clone()));
+        let server_version = connection.server_version();
+        let bus = Arc::new(TcpMessageBus::new(connection)?);
+
+        let _subscription = bus.send_request(9000, &request)?;
+        bus.dispatch(server_version)?;
+        bus.clean_request(9000);
+
+        assert_eq!(counters.sent.load(Ordering::SeqCst), 1);
+        assert_eq!(counters.received.load(Ordering::SeqCst), 1);
+        assert_eq!(counters.latencies.load(Ordering::SeqCst), 1);
+        assert_eq!(*counters.active.lock().unwrap(), vec![1, 0]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_connection_establish_connection() -> Result<(), Error> {
         let events = vec![
@@ -1168,6 +1392,113 @@ mod tests {
         connection.reconnect()
     }
 
+    #[test]
+    fn test_reconnect_events() -> Result<(), Error> {
+        let events = vec![
+            Exchange::simple("v100..200", &["173|20250323 22:21:01 Greenwich Mean Time|"]),
+            Exchange::simple("71|2|28||", &["15|1|DU1234567|", "9|1|1|", "\0"]), // RESTART
+            Exchange::simple("v100..200", &["173|20250323 22:21:01 Greenwich Mean Time|"]),
+            Exchange::simple("71|2|28||", &["15|1|DU1234567|", "9|1|1|"]),
+        ];
+        let socket = MockSocket::new(events, 2);
+
+        let connection = Connection::connect(socket, 28)?;
+        let events = ConnectionEvents::subscribe(&connection.events);
+
+        // simulated dispatcher thread read to trigger disconnection
+        let _ = connection.read_message();
+        connection.reconnect()?;
+
+        let received: Vec<ConnectionEvent> = std::iter::from_fn(|| events.try_next()).collect();
+        assert_eq!(received.len(), 5, "{received:?}");
+        assert!(matches!(
+            received[0],
+            ConnectionEvent::Connected {
+                server_version: 173,
+                time: Some(_)
+            }
+        ));
+        for (i, event) in received[1..4].iter().enumerate() {
+            assert!(
+                matches!(event, ConnectionEvent::Reconnecting { attempt, .. } if *attempt == i as i32 + 1),
+                "{event:?}"
+            );
+        }
+        assert_eq!(received[4], ConnectionEvent::Reconnected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_heartbeat_stall_gives_up() -> Result<(), Error> {
+        let events = vec![
+            Exchange::simple("v100..200", &["173|20250323 22:21:01 Greenwich Mean Time|"]),
+            Exchange::simple("71|2|28||", &["15|1|DU1234567|", "9|1|1|"]),
+            Exchange::simple("49|1|", &[]), // heartbeat without reply
+        ];
+        let socket = MockSocket::new(events, MAX_RECONNECT_ATTEMPTS as usize + 1);
+
+        let connection = Connection::connect(socket, 28)?;
+        let server_version = connection.server_version();
+        let bus = Arc::new(TcpMessageBus::new(connection)?);
+        let events = ConnectionEvents::subscribe(bus.connection_events().unwrap());
+
+        bus.heartbeat(Duration::from_millis(10));
+
+        assert!(matches!(bus.dispatch(server_version), Err(Error::ConnectionFailed)));
+        assert!(!bus.is_connected());
+
+        let received: Vec<ConnectionEvent> = std::iter::from_fn(|| events.try_next()).collect();
+        assert_eq!(received.len(), MAX_RECONNECT_ATTEMPTS as usize + 3, "{received:?}");
+        assert!(matches!(received[0], ConnectionEvent::Connected { .. }));
+        assert_eq!(
+            received[1],
+            ConnectionEvent::Disconnected {
+                error: "no reply to heartbeat within 10ms".to_string()
+            }
+        );
+        assert!(received[2..received.len() - 1]
+            .iter()
+            .all(|event| matches!(event, ConnectionEvent::Reconnecting { .. })));
+        assert_eq!(received.last(), Some(&ConnectionEvent::GaveUp));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_heartbeat_keeps_server_time_replies() -> Result<(), Error> {
+        let events = vec![
+            Exchange::simple("v100..200", &["173|20250323 22:21:01 Greenwich Mean Time|"]),
+            Exchange::simple("71|2|28||", &["15|1|DU1234567|", "9|1|1|"]),
+            Exchange::simple("49|1|", &["49|1|1700000000|"]), // server_time()
+            Exchange::simple("49|1|", &["49|1|1700000001|"]), // heartbeat
+        ];
+        let socket = MockSocket::new(events, 0);
+
+        let connection = Connection::connect(socket, 28)?;
+        let server_version = connection.server_version();
+        let bus = Arc::new(TcpMessageBus::new(connection)?);
+
+        let subscription = bus.send_shared_request(OutgoingMessages::RequestCurrentTime, &RequestMessage::from_simple("49|1|"))?;
+        bus.dispatch(server_version)?;
+
+        // The pending server time reply must not be taken by the heartbeat.
+        thread::scope(|scope| {
+            scope.spawn(|| bus.heartbeat(Duration::from_secs(1)));
+            bus.dispatch(server_version)
+        })?;
+        assert!(bus.is_connected(), "reply counts as liveness");
+
+        let reply = subscription.next_timeout(Duration::from_millis(100)).expect("server time reply")?;
+        assert_eq!(reply.encode_simple(), "49|1|1700000000|");
+        assert!(
+            subscription.next_timeout(Duration::from_millis(20)).is_none(),
+            "heartbeat reply is not delivered to server_time()"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_client_reconnect() -> Result<(), Error> {
         let events = vec![
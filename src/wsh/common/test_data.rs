@@ -18,6 +18,20 @@ pub mod json_responses {
     pub const EVENT_DATA_EARNINGS: &str = r#"{"event":"earnings","date":"2024-01-15"}"#;
     pub const EVENT_DATA_DIVIDEND: &str = r#"{"event":"dividend","date":"2024-02-01"}"#;
     pub const EVENT_DATA_NO_FILTERS: &str = r#"{"events":[{"type":"earnings"}]}"#;
+
+    /// Event list as returned by TWS for a watchlist filter, with the fields WSH adds beyond the typed model.
+    pub const EVENT_DATA_WSH: &str = r#"{"validated":true,"data":[
+        {"event_key":"4f2d0c1e","event_type":"wshe_ed","index_date":"20240201","index_date_type":"earnings_date","conid":265598,
+         "data":{"earnings_date":"20240201","time_of_day":"AMC","earnings_date_status":"Confirmed","fiscal_period":"Q1","fiscal_year":2024}},
+        {"event_key":"4f2d0c1f","event_type":"wshe_div","index_date":"20240209","index_date_type":"ex_date","conid":265598,
+         "data":{"ex_date":"20240209","record_date":"20240212","pay_date":"20240215","amount":"0.24","currency":"USD","date_status":"Confirmed"}},
+        {"event_key":"4f2d0c20","event_type":"wshe_ss","index_date":"20240610","conid":4815747,
+         "data":{"split_date":"20240610","split_to":10,"split_from":1,"date_status":"Unconfirmed"}},
+        {"event_key":"4f2d0c21","event_type":"wshe_conf","index_date":"20240305","conid":76792991,
+         "data":{"name":"Tech Summit","start_date":"20240305","end_date":"20240307","date_status":"Projected"}},
+        {"event_key":"4f2d0c22","event_type":"wshe_option","index_date":"20240315","conid":265598},
+        {"event_key":"4f2d0c23","event_type":"wshe_bod","index_date":"20240401","conid":265598,"data":{"date_status":"Confirmed"}}
+    ]}"#;
 }
 
 /// Sample request IDs for testing
@@ -0,0 +1,638 @@
+//! Typed Wall Street Horizon events decoded from the JSON payloads of [WshEventData] and [WshMetadata].
+
+use std::fmt;
+
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::macros::format_description;
+use time::Date;
+
+use super::{WshEventData, WshMetadata};
+use crate::Error;
+
+/// Category of a Wall Street Horizon event, identified by its WSH tag (e.g. `wshe_ed`).
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WshEventType {
+    /// Earnings dates (`wshe_ed`).
+    Earnings,
+    /// Dividends (`wshe_div`).
+    Dividend,
+    /// Stock splits (`wshe_ss`).
+    Split,
+    /// Investor conferences (`wshe_conf`).
+    Conference,
+    /// Option expirations (`wshe_option`).
+    OptionExpiry,
+    /// Any other tag listed in the [WshMetadata].
+    Other(String),
+}
+
+impl WshEventType {
+    /// Maps a WSH tag or a plain name such as `earnings` to an event type.
+    pub fn from_tag(tag: &str) -> Self {
+        match tag.to_ascii_lowercase().as_str() {
+            "wshe_ed" | "earnings" | "earnings_date" => WshEventType::Earnings,
+            "wshe_div" | "dividend" | "dividends" => WshEventType::Dividend,
+            "wshe_ss" | "split" | "stock_split" => WshEventType::Split,
+            "wshe_conf" | "conference" | "investor_conference" => WshEventType::Conference,
+            "wshe_option" | "option_expiry" | "option_expiration" => WshEventType::OptionExpiry,
+            _ => WshEventType::Other(tag.to_string()),
+        }
+    }
+
+    /// The WSH tag used in filters and event payloads.
+    pub fn tag(&self) -> &str {
+        match self {
+            WshEventType::Earnings => "wshe_ed",
+            WshEventType::Dividend => "wshe_div",
+            WshEventType::Split => "wshe_ss",
+            WshEventType::Conference => "wshe_conf",
+            WshEventType::OptionExpiry => "wshe_option",
+            WshEventType::Other(tag) => tag,
+        }
+    }
+}
+
+impl fmt::Display for WshEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.tag())
+    }
+}
+
+/// Event type offered by the WSH calendar, as listed in [WshMetadata].
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WshEventTypeInfo {
+    /// WSH tag, e.g. `wshe_ed`.
+    pub tag: String,
+    /// Display name, when provided.
+    pub name: Option<String>,
+}
+
+impl WshEventTypeInfo {
+    /// The typed event type for this tag.
+    pub fn event_type(&self) -> WshEventType {
+        WshEventType::from_tag(&self.tag)
+    }
+}
+
+/// How firmly WSH has established the date of an event.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WshConfidence {
+    /// Confirmed by the company.
+    Confirmed,
+    /// Announced but not confirmed.
+    Unconfirmed,
+    /// Projected by WSH from historical patterns.
+    Projected,
+    /// The payload did not state a confidence.
+    #[default]
+    Unknown,
+}
+
+impl WshConfidence {
+    // WSH reports date status as `Confirmed`, `Unconfirmed` or `Projected`.
+    fn from_status(status: Option<&str>) -> Self {
+        match status.map(str::to_ascii_lowercase).as_deref() {
+            Some("confirmed") => WshConfidence::Confirmed,
+            Some("unconfirmed") => WshConfidence::Unconfirmed,
+            Some("projected") => WshConfidence::Projected,
+            _ => WshConfidence::Unknown,
+        }
+    }
+}
+
+/// Earnings announcement.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarningsEvent {
+    /// Announcement date.
+    pub date: Option<Date>,
+    /// Time of day of the announcement, e.g. `BMO` (before market open) or `AMC` (after market close).
+    pub time_of_day: Option<String>,
+    /// How firmly the date is established.
+    pub confidence: WshConfidence,
+    /// Contracts the event applies to.
+    pub contract_ids: Vec<i32>,
+}
+
+/// Cash dividend.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DividendEvent {
+    /// Ex-dividend date.
+    pub ex_date: Option<Date>,
+    /// Record date.
+    pub record_date: Option<Date>,
+    /// Payment date.
+    pub payment_date: Option<Date>,
+    /// Dividend per share.
+    pub amount: Option<f64>,
+    /// Currency of the amount.
+    pub currency: Option<String>,
+    /// How firmly the date is established.
+    pub confidence: WshConfidence,
+    /// Contracts the event applies to.
+    pub contract_ids: Vec<i32>,
+}
+
+/// Stock split.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplitEvent {
+    /// Date the split takes effect.
+    pub date: Option<Date>,
+    /// Split ratio as published, e.g. `4:1`.
+    pub ratio: Option<String>,
+    /// How firmly the date is established.
+    pub confidence: WshConfidence,
+    /// Contracts the event applies to.
+    pub contract_ids: Vec<i32>,
+}
+
+/// Investor or industry conference.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConferenceEvent {
+    /// Conference name.
+    pub name: Option<String>,
+    /// First day of the conference.
+    pub start_date: Option<Date>,
+    /// Last day of the conference.
+    pub end_date: Option<Date>,
+    /// How firmly the date is established.
+    pub confidence: WshConfidence,
+    /// Contracts the event applies to.
+    pub contract_ids: Vec<i32>,
+}
+
+/// Option expiration.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptionExpiryEvent {
+    /// Expiration date.
+    pub date: Option<Date>,
+    /// How firmly the date is established.
+    pub confidence: WshConfidence,
+    /// Contracts the event applies to.
+    pub contract_ids: Vec<i32>,
+}
+
+/// Event of a type without a typed model.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtherEvent {
+    /// WSH tag of the event.
+    pub event_type: String,
+    /// Date of the event.
+    pub date: Option<Date>,
+    /// How firmly the date is established.
+    pub confidence: WshConfidence,
+    /// Contracts the event applies to.
+    pub contract_ids: Vec<i32>,
+    /// The event as received.
+    pub data_json: String,
+}
+
+/// A Wall Street Horizon calendar event.
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WshEvent {
+    /// Earnings announcement.
+    Earnings(EarningsEvent),
+    /// Cash dividend.
+    Dividend(DividendEvent),
+    /// Stock split.
+    Split(SplitEvent),
+    /// Investor or industry conference.
+    Conference(ConferenceEvent),
+    /// Option expiration.
+    OptionExpiry(OptionExpiryEvent),
+    /// Event without a typed model.
+    Other(OtherEvent),
+}
+
+impl WshEvent {
+    /// Type of the event.
+    pub fn event_type(&self) -> WshEventType {
+        match self {
+            WshEvent::Earnings(_) => WshEventType::Earnings,
+            WshEvent::Dividend(_) => WshEventType::Dividend,
+            WshEvent::Split(_) => WshEventType::Split,
+            WshEvent::Conference(_) => WshEventType::Conference,
+            WshEvent::OptionExpiry(_) => WshEventType::OptionExpiry,
+            WshEvent::Other(event) => WshEventType::Other(event.event_type.clone()),
+        }
+    }
+
+    /// Primary date of the event: the ex-date for dividends and the start date for conferences.
+    pub fn date(&self) -> Option<Date> {
+        match self {
+            WshEvent::Earnings(event) => event.date,
+            WshEvent::Dividend(event) => event.ex_date,
+            WshEvent::Split(event) => event.date,
+            WshEvent::Conference(event) => event.start_date,
+            WshEvent::OptionExpiry(event) => event.date,
+            WshEvent::Other(event) => event.date,
+        }
+    }
+
+    /// How firmly the event date is established.
+    pub fn confidence(&self) -> WshConfidence {
+        match self {
+            WshEvent::Earnings(event) => event.confidence,
+            WshEvent::Dividend(event) => event.confidence,
+            WshEvent::Split(event) => event.confidence,
+            WshEvent::Conference(event) => event.confidence,
+            WshEvent::OptionExpiry(event) => event.confidence,
+            WshEvent::Other(event) => event.confidence,
+        }
+    }
+
+    /// Contracts the event applies to.
+    pub fn contract_ids(&self) -> &[i32] {
+        match self {
+            WshEvent::Earnings(event) => &event.contract_ids,
+            WshEvent::Dividend(event) => &event.contract_ids,
+            WshEvent::Split(event) => &event.contract_ids,
+            WshEvent::Conference(event) => &event.contract_ids,
+            WshEvent::OptionExpiry(event) => &event.contract_ids,
+            WshEvent::Other(event) => &event.contract_ids,
+        }
+    }
+
+    fn from_raw(raw: &RawEvent, value: &Value) -> Result<Self, serde_json::Error> {
+        let contract_ids: Vec<i32> = raw.conid.into_iter().collect();
+
+        let event = match WshEventType::from_tag(&raw.event_type) {
+            WshEventType::Earnings => {
+                let data: EarningsData = raw.data()?;
+                WshEvent::Earnings(EarningsEvent {
+                    date: data.earnings_date.or(raw.index_date),
+                    time_of_day: data.time_of_day,
+                    confidence: WshConfidence::from_status(data.earnings_date_status.as_deref()),
+                    contract_ids,
+                })
+            }
+            WshEventType::Dividend => {
+                let data: DividendData = raw.data()?;
+                WshEvent::Dividend(DividendEvent {
+                    ex_date: data.ex_date.or(raw.index_date),
+                    record_date: data.record_date,
+                    payment_date: data.pay_date,
+                    amount: data.amount,
+                    currency: data.currency,
+                    confidence: WshConfidence::from_status(data.date_status.as_deref()),
+                    contract_ids,
+                })
+            }
+            WshEventType::Split => {
+                let data: SplitData = raw.data()?;
+                WshEvent::Split(SplitEvent {
+                    date: data.split_date.or(raw.index_date),
+                    ratio: data.split_to.zip(data.split_from).map(|(to, from)| format!("{to}:{from}")),
+                    confidence: WshConfidence::from_status(data.date_status.as_deref()),
+                    contract_ids,
+                })
+            }
+            WshEventType::Conference => {
+                let data: ConferenceData = raw.data()?;
+                WshEvent::Conference(ConferenceEvent {
+                    name: data.name,
+                    start_date: data.start_date.or(raw.index_date),
+                    end_date: data.end_date,
+                    confidence: WshConfidence::from_status(data.date_status.as_deref()),
+                    contract_ids,
+                })
+            }
+            WshEventType::OptionExpiry => {
+                let data: DateStatusData = raw.data()?;
+                WshEvent::OptionExpiry(OptionExpiryEvent {
+                    date: raw.index_date,
+                    confidence: WshConfidence::from_status(data.date_status.as_deref()),
+                    contract_ids,
+                })
+            }
+            WshEventType::Other(event_type) => {
+                let data: DateStatusData = raw.data()?;
+                WshEvent::Other(OtherEvent {
+                    event_type,
+                    date: raw.index_date,
+                    confidence: WshConfidence::from_status(data.date_status.as_deref()),
+                    contract_ids,
+                    data_json: value.to_string(),
+                })
+            }
+        };
+
+        Ok(event)
+    }
+}
+
+impl WshEventData {
+    /// Decodes the events contained in [data_json](WshEventData::data_json).
+    ///
+    /// The payload is the WSH event list, either on its own or under the `data` key of the response.
+    pub fn events(&self) -> Result<Vec<WshEvent>, Error> {
+        if self.data_json.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let parse_error = |err: serde_json::Error| Error::Parse(0, self.data_json.clone(), err.to_string());
+
+        let payload: EventPayload = serde_json::from_str(&self.data_json).map_err(parse_error)?;
+        payload
+            .into_events()
+            .iter()
+            .map(|value| {
+                let raw = RawEvent::deserialize(value)?;
+                WshEvent::from_raw(&raw, value)
+            })
+            .collect::<Result<_, _>>()
+            .map_err(parse_error)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EventPayload {
+    Events(Vec<Value>),
+    Response {
+        #[serde(default)]
+        data: Vec<Value>,
+    },
+}
+
+impl EventPayload {
+    fn into_events(self) -> Vec<Value> {
+        match self {
+            EventPayload::Events(events) | EventPayload::Response { data: events } => events,
+        }
+    }
+}
+
+// An entry of the WSH event list. Fields specific to the event type are nested under `data`.
+#[derive(Deserialize)]
+struct RawEvent {
+    event_type: String,
+    #[serde(default, deserialize_with = "wsh_date")]
+    index_date: Option<Date>,
+    #[serde(default)]
+    conid: Option<i32>,
+    #[serde(default)]
+    data: Value,
+}
+
+impl RawEvent {
+    fn data<'a, T: Deserialize<'a> + Default>(&'a self) -> Result<T, serde_json::Error> {
+        if self.data.is_null() {
+            Ok(T::default())
+        } else {
+            T::deserialize(&self.data)
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct EarningsData {
+    #[serde(deserialize_with = "wsh_date")]
+    earnings_date: Option<Date>,
+    time_of_day: Option<String>,
+    earnings_date_status: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct DividendData {
+    #[serde(deserialize_with = "wsh_date")]
+    ex_date: Option<Date>,
+    #[serde(deserialize_with = "wsh_date")]
+    record_date: Option<Date>,
+    #[serde(deserialize_with = "wsh_date")]
+    pay_date: Option<Date>,
+    #[serde(deserialize_with = "wsh_number")]
+    amount: Option<f64>,
+    currency: Option<String>,
+    date_status: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct SplitData {
+    #[serde(deserialize_with = "wsh_date")]
+    split_date: Option<Date>,
+    #[serde(deserialize_with = "wsh_number")]
+    split_to: Option<f64>,
+    #[serde(deserialize_with = "wsh_number")]
+    split_from: Option<f64>,
+    date_status: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ConferenceData {
+    name: Option<String>,
+    #[serde(deserialize_with = "wsh_date")]
+    start_date: Option<Date>,
+    #[serde(deserialize_with = "wsh_date")]
+    end_date: Option<Date>,
+    date_status: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct DateStatusData {
+    date_status: Option<String>,
+}
+
+impl WshMetadata {
+    /// Event types offered by the WSH calendar, in the order they are listed.
+    pub fn event_types(&self) -> Result<Vec<WshEventTypeInfo>, Error> {
+        if self.data_json.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let payload: Value = serde_json::from_str(&self.data_json).map_err(|err| Error::Parse(0, self.data_json.clone(), err.to_string()))?;
+
+        let mut event_types = Vec::new();
+        collect_event_types(&payload, &mut event_types);
+        Ok(event_types)
+    }
+}
+
+// Event types are objects with a `tag`, possibly nested in groups of related types.
+fn collect_event_types(value: &Value, event_types: &mut Vec<WshEventTypeInfo>) {
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| collect_event_types(item, event_types)),
+        Value::Object(object) => {
+            if let Some(tag) = object.get("tag").and_then(Value::as_str) {
+                if !event_types.iter().any(|info| info.tag == tag) {
+                    let name = ["display_name", "name", "label"]
+                        .iter()
+                        .find_map(|key| object.get(*key).and_then(Value::as_str))
+                        .map(str::to_string);
+                    event_types.push(WshEventTypeInfo { tag: tag.to_string(), name });
+                }
+            }
+            object.values().for_each(|nested| collect_event_types(nested, event_types));
+        }
+        _ => {}
+    }
+}
+
+// WSH uses both `20240125` and `2024-01-25`.
+fn parse_date(value: &str) -> Option<Date> {
+    let value = value.get(..10).unwrap_or(value);
+    Date::parse(value, format_description!("[year]-[month]-[day]"))
+        .or_else(|_| Date::parse(value.get(..8).unwrap_or(value), format_description!("[year][month][day]")))
+        .ok()
+}
+
+fn wsh_date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Date>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) if !value.is_empty() => parse_date(&value)
+            .map(Some)
+            .ok_or_else(|| de::Error::custom(format!("invalid WSH date: {value}"))),
+        _ => Ok(None),
+    }
+}
+
+// Amounts and split factors arrive as JSON numbers or as decimal strings.
+fn wsh_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Number {
+        Number(f64),
+        Text(String),
+    }
+
+    match Option::<Number>::deserialize(deserializer)? {
+        Some(Number::Number(value)) => Ok(Some(value)),
+        Some(Number::Text(value)) if !value.is_empty() => value.parse().map(Some).map_err(de::Error::custom),
+        _ => Ok(None),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+    use crate::wsh::common::test_data::json_responses;
+
+    fn event_data(json: &str) -> WshEventData {
+        WshEventData { data_json: json.to_string() }
+    }
+
+    #[test]
+    fn test_events_from_wsh_payload() {
+        let events = event_data(json_responses::EVENT_DATA_WSH).events().unwrap();
+        assert_eq!(events.len(), 6);
+
+        assert_eq!(
+            events[0],
+            WshEvent::Earnings(EarningsEvent {
+                date: Some(date!(2024 - 02 - 01)),
+                time_of_day: Some("AMC".to_string()),
+                confidence: WshConfidence::Confirmed,
+                contract_ids: vec![265598],
+            })
+        );
+
+        assert_eq!(
+            events[1],
+            WshEvent::Dividend(DividendEvent {
+                ex_date: Some(date!(2024 - 02 - 09)),
+                record_date: Some(date!(2024 - 02 - 12)),
+                payment_date: Some(date!(2024 - 02 - 15)),
+                amount: Some(0.24),
+                currency: Some("USD".to_string()),
+                confidence: WshConfidence::Confirmed,
+                contract_ids: vec![265598],
+            })
+        );
+
+        let WshEvent::Split(split) = &events[2] else {
+            panic!("expected split: {:?}", events[2]);
+        };
+        assert_eq!(split.ratio.as_deref(), Some("10:1"));
+        assert_eq!(split.date, Some(date!(2024 - 06 - 10)));
+        assert_eq!(split.confidence, WshConfidence::Unconfirmed);
+
+        let WshEvent::Conference(conference) = &events[3] else {
+            panic!("expected conference: {:?}", events[3]);
+        };
+        assert_eq!(conference.name.as_deref(), Some("Tech Summit"));
+        assert_eq!(conference.end_date, Some(date!(2024 - 03 - 07)));
+        assert_eq!(conference.confidence, WshConfidence::Projected);
+
+        assert_eq!(events[4].event_type(), WshEventType::OptionExpiry);
+        assert_eq!(events[4].date(), Some(date!(2024 - 03 - 15)));
+        assert_eq!(events[4].confidence(), WshConfidence::Unknown);
+
+        let WshEvent::Other(other) = &events[5] else {
+            panic!("expected other: {:?}", events[5]);
+        };
+        assert_eq!(other.event_type, "wshe_bod");
+        assert_eq!(other.confidence, WshConfidence::Confirmed);
+        assert!(other.data_json.contains("\"event_key\":\"4f2d0c23\""));
+    }
+
+    #[test]
+    fn test_events_payload_shapes() {
+        let events = event_data(r#"[{"event_type":"wshe_ed","index_date":"2024-01-15","conid":8314}]"#)
+            .events()
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type(), WshEventType::Earnings);
+        assert_eq!(events[0].date(), Some(date!(2024 - 01 - 15)));
+        assert_eq!(events[0].contract_ids(), &[8314]);
+
+        assert!(event_data(json_responses::EVENT_DATA_EMPTY).events().unwrap().is_empty());
+        assert!(event_data("").events().unwrap().is_empty());
+        assert!(matches!(event_data("{not json").events(), Err(Error::Parse(..))));
+        assert!(matches!(event_data(r#"[{"index_date":"20240115"}]"#).events(), Err(Error::Parse(..))));
+        assert!(matches!(
+            event_data(r#"[{"event_type":"wshe_ed","index_date":"soon"}]"#).events(),
+            Err(Error::Parse(..))
+        ));
+    }
+
+    #[test]
+    fn test_metadata_event_types() {
+        let metadata = WshMetadata {
+            data_json: r#"{"validated":true,"data":{"metadata":{"event_types":[
+                {"tag":"wshe_ed","name":"Earnings Date"},
+                {"tag":"wshe_corporate","display_name":"Corporate","children":[{"tag":"wshe_div"},{"tag":"wshe_ss"}]},
+                {"tag":"wshe_ed","name":"Duplicate"}
+            ]}}}"#
+                .to_string(),
+        };
+
+        let event_types = metadata.event_types().unwrap();
+        let tags: Vec<&str> = event_types.iter().map(|info| info.tag.as_str()).collect();
+        assert_eq!(tags, vec!["wshe_ed", "wshe_corporate", "wshe_div", "wshe_ss"]);
+        assert_eq!(event_types[0].name.as_deref(), Some("Earnings Date"));
+        assert_eq!(event_types[1].name.as_deref(), Some("Corporate"));
+        assert_eq!(event_types[2].event_type(), WshEventType::Dividend);
+    }
+
+    #[test]
+    fn test_event_type_tags() {
+        for event_type in [
+            WshEventType::Earnings,
+            WshEventType::Dividend,
+            WshEventType::Split,
+            WshEventType::Conference,
+            WshEventType::OptionExpiry,
+            WshEventType::Other("wshe_bod".to_string()),
+        ] {
+            assert_eq!(WshEventType::from_tag(event_type.tag()), event_type);
+        }
+        assert_eq!(WshEventType::from_tag("Dividends"), WshEventType::Dividend);
+        assert_eq!(WshEventType::Earnings.to_string(), "wshe_ed");
+    }
+}
@@ -0,0 +1,202 @@
+//! Builder for the JSON filter accepted by `wsh_event_data_by_filter`.
+
+use serde_json::Value;
+
+use super::{WshEventType, WshMetadata};
+use crate::Error;
+
+/// Filter selecting Wall Street Horizon events by type and contract.
+///
+/// # Examples
+///
+/// ```
+/// use ibapi::wsh::{WshEventType, WshFilter, WshMetadata};
+///
+/// let metadata = WshMetadata {
+///     data_json: r#"{"event_types":[{"tag":"wshe_ed"},{"tag":"wshe_div"}]}"#.to_string(),
+/// };
+///
+/// let filter = WshFilter::new()
+///     .event_type(WshEventType::Earnings)
+///     .event_type(WshEventType::Dividend)
+///     .contract_id(76792991)
+///     .build(&metadata)
+///     .expect("invalid filter");
+///
+/// assert_eq!(filter, r#"{"watchlist":["76792991"],"wshe_ed":"true","wshe_div":"true"}"#);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WshFilter {
+    event_types: Vec<WshEventType>,
+    contract_ids: Vec<i32>,
+    country: Option<String>,
+    limit_region: Option<i32>,
+    limit: Option<i32>,
+}
+
+impl WshFilter {
+    /// Creates an empty filter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Includes events of `event_type`.
+    pub fn event_type(mut self, event_type: WshEventType) -> Self {
+        if !self.event_types.contains(&event_type) {
+            self.event_types.push(event_type);
+        }
+        self
+    }
+
+    /// Restricts events to a contract. May be called repeatedly.
+    pub fn contract_id(mut self, contract_id: i32) -> Self {
+        if !self.contract_ids.contains(&contract_id) {
+            self.contract_ids.push(contract_id);
+        }
+        self
+    }
+
+    /// Restricts events to companies listed in `country`, e.g. `US` or `All`.
+    pub fn country(mut self, country: &str) -> Self {
+        self.country = Some(country.to_string());
+        self
+    }
+
+    /// Maximum number of events per region.
+    pub fn limit_region(mut self, limit: i32) -> Self {
+        self.limit_region = Some(limit);
+        self
+    }
+
+    /// Maximum number of events.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Event types included by the filter.
+    pub fn event_types(&self) -> &[WshEventType] {
+        &self.event_types
+    }
+
+    /// Checks that the filter selects at least one event type and that all of them are offered by the WSH calendar.
+    pub fn validate(&self, metadata: &WshMetadata) -> Result<(), Error> {
+        if self.event_types.is_empty() {
+            return Err(Error::InvalidArgument("WSH filter requires at least one event type".to_string()));
+        }
+
+        let available = metadata.event_types()?;
+        let unknown: Vec<&str> = self
+            .event_types
+            .iter()
+            .map(WshEventType::tag)
+            .filter(|tag| !available.iter().any(|info| info.tag == *tag))
+            .collect();
+
+        if !unknown.is_empty() {
+            let available: Vec<&str> = available.iter().map(|info| info.tag.as_str()).collect();
+            return Err(Error::InvalidArgument(format!(
+                "WSH event types not offered by metadata: {} (available: {})",
+                unknown.join(", "),
+                available.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Validates the filter against `metadata` and serializes it.
+    pub fn build(&self, metadata: &WshMetadata) -> Result<String, Error> {
+        self.validate(metadata)?;
+        Ok(self.to_json())
+    }
+
+    /// Serializes the filter without validation.
+    pub fn to_json(&self) -> String {
+        // Written by hand so the key order is stable whatever serde_json features are enabled.
+        let mut entries: Vec<(&str, Value)> = Vec::new();
+
+        if let Some(country) = &self.country {
+            entries.push(("country", Value::from(country.as_str())));
+        }
+        if !self.contract_ids.is_empty() {
+            let watchlist = self.contract_ids.iter().map(|id| Value::from(id.to_string())).collect();
+            entries.push(("watchlist", Value::Array(watchlist)));
+        }
+        if let Some(limit_region) = self.limit_region {
+            entries.push(("limit_region", Value::from(limit_region)));
+        }
+        if let Some(limit) = self.limit {
+            entries.push(("limit", Value::from(limit)));
+        }
+        for event_type in &self.event_types {
+            entries.push((event_type.tag(), Value::from("true")));
+        }
+
+        let entries: Vec<String> = entries.iter().map(|(key, value)| format!("{}:{value}", Value::from(*key))).collect();
+        format!("{{{}}}", entries.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> WshMetadata {
+        WshMetadata {
+            data_json: r#"{"validated":true,"data":{"metadata":{"event_types":[{"tag":"wshe_ed"},{"tag":"wshe_div"},{"tag":"wshe_bod"}]}}}"#
+                .to_string(),
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        let filter = WshFilter::new()
+            .country("US")
+            .contract_id(8314)
+            .contract_id(76792991)
+            .contract_id(8314)
+            .limit_region(10)
+            .limit(25)
+            .event_type(WshEventType::Earnings)
+            .event_type(WshEventType::Other("wshe_bod".to_string()))
+            .event_type(WshEventType::Earnings);
+
+        assert_eq!(
+            filter.to_json(),
+            r#"{"country":"US","watchlist":["8314","76792991"],"limit_region":10,"limit":25,"wshe_ed":"true","wshe_bod":"true"}"#
+        );
+        assert_eq!(filter.event_types().len(), 2);
+        assert_eq!(WshFilter::new().to_json(), "{}");
+    }
+
+    #[test]
+    fn test_build_validates_against_metadata() {
+        let filter = WshFilter::new().event_type(WshEventType::Dividend).contract_id(265598);
+        assert_eq!(filter.build(&metadata()).unwrap(), r#"{"watchlist":["265598"],"wshe_div":"true"}"#);
+
+        let err = WshFilter::new()
+            .event_type(WshEventType::Earnings)
+            .event_type(WshEventType::Split)
+            .build(&metadata())
+            .unwrap_err();
+        let Error::InvalidArgument(message) = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert!(message.contains("wshe_ss"), "{message}");
+        assert!(message.contains("available: wshe_ed, wshe_div, wshe_bod"), "{message}");
+
+        assert!(matches!(
+            WshFilter::new().contract_id(1).validate(&metadata()),
+            Err(Error::InvalidArgument(_))
+        ));
+
+        let invalid = WshMetadata {
+            data_json: "not json".to_string(),
+        };
+        assert!(matches!(
+            WshFilter::new().event_type(WshEventType::Earnings).build(&invalid),
+            Err(Error::Parse(..))
+        ));
+    }
+}
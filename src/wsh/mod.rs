@@ -9,6 +9,13 @@ use std::str;
 use serde::{Deserialize, Serialize};
 
 mod common;
+mod events;
+mod filter;
+
+pub use events::{
+    ConferenceEvent, DividendEvent, EarningsEvent, OptionExpiryEvent, OtherEvent, SplitEvent, WshConfidence, WshEvent, WshEventType, WshEventTypeInfo,
+};
+pub use filter::WshFilter;
 
 // Re-export common functionality
 #[cfg(test)]
@@ -23,6 +30,8 @@ mod sync;
 mod r#async;
 
 /// Wall Street Horizon metadata containing configuration and setup information.
+///
+/// Use [WshMetadata::event_types] to list the event types available for a [WshFilter].
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WshMetadata {
@@ -31,6 +40,8 @@ pub struct WshMetadata {
 }
 
 /// Wall Street Horizon event data containing earnings calendar and corporate events.
+///
+/// Use [WshEventData::events] to decode the payload into typed [WshEvent]s.
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WshEventData {
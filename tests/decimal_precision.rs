@@ -0,0 +1,35 @@
+#![cfg(feature = "decimal")]
+
+use std::str::FromStr;
+
+use ibapi::messages::ResponseMessage;
+use ibapi::orders::{order_builder, Action};
+use ibapi::Decimal;
+
+#[test]
+fn test_next_decimal_preserves_wire_precision() {
+    let mut message = ResponseMessage::from("0.00012345\x00100.50\x001E-8\x00\x00");
+
+    assert_eq!(message.next_decimal().unwrap().to_string(), "0.00012345");
+    assert_eq!(message.next_decimal().unwrap().to_string(), "100.50");
+    assert_eq!(message.next_decimal().unwrap().to_string(), "0.00000001");
+    assert_eq!(message.next_decimal().unwrap(), Decimal::ZERO);
+}
+
+#[test]
+fn test_next_optional_decimal_unset() {
+    let mut message = ResponseMessage::from("\x00170141183460469231731687303715884105727\x000.5\x00");
+
+    assert_eq!(message.next_optional_decimal().unwrap(), None);
+    assert_eq!(message.next_optional_decimal().unwrap(), None);
+    assert_eq!(message.next_optional_decimal().unwrap(), Some(Decimal::from_str("0.5").unwrap()));
+}
+
+#[test]
+fn test_order_builder_quantity_is_exact() {
+    let order = order_builder::market_order(Action::Buy, 0.1);
+    assert_eq!(order.total_quantity.to_string(), "0.1");
+
+    let order = order_builder::limit_order(Action::Sell, 0.00012345, 60000.0);
+    assert_eq!(order.total_quantity, Decimal::from_str("0.00012345").unwrap());
+}
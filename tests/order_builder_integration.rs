@@ -2,6 +2,7 @@
 mod order_builder_tests {
     use ibapi::contracts::{Contract, Currency, Exchange, Symbol};
     use ibapi::orders::{Action, OrderBuilder};
+    use ibapi::Decimal;
 
     #[cfg(feature = "sync")]
     use ibapi::orders::OcaType;
@@ -32,7 +33,7 @@ mod order_builder_tests {
 
         let order = builder.build().unwrap();
         assert_eq!(order.action, Action::Buy);
-        assert_eq!(order.total_quantity, 100.0);
+        assert_eq!(order.total_quantity, Decimal::from(100));
         assert_eq!(order.order_type, "MKT");
 
         // Test limit order
@@ -45,7 +46,7 @@ mod order_builder_tests {
 
         let order = builder.build().unwrap();
         assert_eq!(order.action, Action::Sell);
-        assert_eq!(order.total_quantity, 200.0);
+        assert_eq!(order.total_quantity, Decimal::from(200));
         assert_eq!(order.order_type, "LMT");
         assert_eq!(order.limit_price, Some(150.50));
         assert_eq!(order.tif, ibapi::orders::TimeInForce::GoodTilCanceled);
@@ -85,7 +86,7 @@ mod order_builder_tests {
         let parent = &orders[0];
         assert_eq!(parent.action, Action::Buy);
         assert_eq!(parent.order_type, "LMT");
-        assert_eq!(parent.total_quantity, 100.0);
+        assert_eq!(parent.total_quantity, Decimal::from(100));
         assert_eq!(parent.limit_price, Some(50.0));
         assert!(!parent.transmit);
 
@@ -93,7 +94,7 @@ mod order_builder_tests {
         let take_profit = &orders[1];
         assert_eq!(take_profit.action, Action::Sell);
         assert_eq!(take_profit.order_type, "LMT");
-        assert_eq!(take_profit.total_quantity, 100.0);
+        assert_eq!(take_profit.total_quantity, Decimal::from(100));
         assert_eq!(take_profit.limit_price, Some(55.0));
         assert!(!take_profit.transmit);
 
@@ -101,7 +102,7 @@ mod order_builder_tests {
         let stop_loss = &orders[2];
         assert_eq!(stop_loss.action, Action::Sell);
         assert_eq!(stop_loss.order_type, "STP");
-        assert_eq!(stop_loss.total_quantity, 100.0);
+        assert_eq!(stop_loss.total_quantity, Decimal::from(100));
         assert_eq!(stop_loss.aux_price, Some(45.0));
         assert!(stop_loss.transmit);
     }
@@ -254,7 +255,7 @@ mod order_builder_tests {
 
         let order = builder.build().unwrap();
         assert_eq!(order.action, Action::Buy);
-        assert_eq!(order.total_quantity, 100.0);
+        assert_eq!(order.total_quantity, Decimal::from(100));
         assert_eq!(order.order_type, "MKT");
 
         // Test limit order
@@ -262,7 +263,7 @@ mod order_builder_tests {
 
         let order = builder.build().unwrap();
         assert_eq!(order.action, Action::Sell);
-        assert_eq!(order.total_quantity, 200.0);
+        assert_eq!(order.total_quantity, Decimal::from(200));
         assert_eq!(order.order_type, "LMT");
         assert_eq!(order.limit_price, Some(150.50));
         assert_eq!(order.tif, ibapi::orders::TimeInForce::GoodTilCanceled);
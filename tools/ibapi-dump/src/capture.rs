@@ -0,0 +1,325 @@
+//! Readers for the capture formats understood by `ibapi-dump`.
+//!
+//! * recorder directories written when `IBAPI_RECORDING_DIR` is set (`NNNN-request.msg` / `NNNN-response.msg`)
+//! * `.jsonl` files with one `{"direction", "message", "time"}` object per line
+//! * `.yaml` / `.json` trace exports (`TraceBuffer::export`, `tws_interactions.yaml`)
+//! * raw socket captures of length-prefixed frames for a single direction
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Direction of a message relative to the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Direction {
+    /// Sent by the client.
+    Request,
+    /// Sent by TWS or IB Gateway.
+    Response,
+}
+
+/// A single message read from a capture.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub direction: Direction,
+    pub fields: Vec<String>,
+    pub at: Option<OffsetDateTime>,
+}
+
+impl Frame {
+    fn new(direction: Direction, raw: &str, at: Option<OffsetDateTime>) -> Self {
+        Self {
+            direction,
+            fields: split_fields(raw),
+            at,
+        }
+    }
+}
+
+/// Messages read from a capture in the order they were recorded.
+#[derive(Debug, Default)]
+pub struct Capture {
+    /// Server version announced by the capture, if any.
+    pub server_version: Option<i32>,
+    pub frames: Vec<Frame>,
+}
+
+/// Reads a capture, picking the format from the path.
+///
+/// `direction` is only used for raw socket captures that do not start with the client handshake.
+pub fn read(path: &Path, direction: Option<Direction>) -> Result<Capture> {
+    if path.is_dir() {
+        return read_recorder_dir(path);
+    }
+
+    match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+        Some("jsonl") => read_jsonl(&fs::read_to_string(path)?),
+        Some("yaml" | "yml") => read_trace_export(serde_yaml::from_str(&fs::read_to_string(path)?)?),
+        Some("json") => read_trace_export(serde_json::from_str(&fs::read_to_string(path)?)?),
+        _ => read_raw(&fs::read(path)?, direction),
+    }
+}
+
+/// Reads a directory written by the message recorder. Files carry no timestamps, so their modification time is used.
+pub fn read_recorder_dir(dir: &Path) -> Result<Capture> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some(stem) = name.strip_suffix(".msg") else {
+            continue;
+        };
+        let Some((seq, kind)) = stem.split_once('-') else {
+            continue;
+        };
+        let direction = match kind {
+            "request" => Direction::Request,
+            "response" => Direction::Response,
+            _ => continue,
+        };
+        let Ok(seq) = seq.parse::<usize>() else {
+            continue;
+        };
+        let at = entry.metadata().and_then(|metadata| metadata.modified()).ok().map(OffsetDateTime::from);
+        entries.push((seq, direction, fs::read_to_string(entry.path())?, at));
+    }
+
+    if entries.is_empty() {
+        return Err(format!("no recorded messages found in {}", dir.display()).into());
+    }
+
+    entries.sort_by_key(|(seq, ..)| *seq);
+    let frames = entries
+        .into_iter()
+        .map(|(_, direction, raw, at)| Frame::new(direction, &raw, at))
+        .collect();
+
+    Ok(with_handshake_version(frames, None))
+}
+
+#[derive(Deserialize)]
+struct JsonLine {
+    direction: String,
+    #[serde(alias = "raw")]
+    message: String,
+    #[serde(default, alias = "at")]
+    time: Option<String>,
+}
+
+/// Reads one message per line. Directions may be given as `request`/`response`, `out`/`in` or `->`/`<-`.
+pub fn read_jsonl(text: &str) -> Result<Capture> {
+    let mut frames = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let entry: JsonLine = serde_json::from_str(line).map_err(|err| format!("line {}: {err}", i + 1))?;
+        let direction = match entry.direction.to_ascii_lowercase().as_str() {
+            "request" | "out" | "->" => Direction::Request,
+            "response" | "in" | "<-" => Direction::Response,
+            other => return Err(format!("line {}: unknown direction {other:?}", i + 1).into()),
+        };
+        let at = match entry.time {
+            Some(time) => Some(OffsetDateTime::parse(&time, &Rfc3339).map_err(|err| format!("line {}: invalid time {time:?}: {err}", i + 1))?),
+            None => None,
+        };
+        frames.push(Frame::new(direction, &entry.message, at));
+    }
+
+    Ok(with_handshake_version(frames, None))
+}
+
+#[derive(Deserialize)]
+struct TraceFile {
+    #[serde(default)]
+    header: Option<TraceFileHeader>,
+    #[serde(default)]
+    interactions: Vec<TraceFileInteraction>,
+}
+
+#[derive(Deserialize)]
+struct TraceFileHeader {
+    #[serde(default)]
+    server_version: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct TraceFileInteraction {
+    #[serde(default)]
+    request: Option<TraceFileMessage>,
+    #[serde(default)]
+    responses: Vec<TraceFileMessage>,
+}
+
+#[derive(Deserialize)]
+struct TraceFileMessage {
+    raw: String,
+    #[serde(default)]
+    at: Option<OffsetDateTime>,
+}
+
+fn read_trace_export(file: TraceFile) -> Result<Capture> {
+    let mut frames = Vec::new();
+    for interaction in file.interactions {
+        if let Some(request) = interaction.request {
+            frames.push(Frame::new(Direction::Request, &request.raw, request.at));
+        }
+        for response in interaction.responses {
+            frames.push(Frame::new(Direction::Response, &response.raw, response.at));
+        }
+    }
+
+    let server_version = file.header.and_then(|header| header.server_version);
+    Ok(with_handshake_version(frames, server_version))
+}
+
+/// Reads a socket capture of one direction: 4-byte big-endian length prefixed frames.
+///
+/// Captures of client traffic start with the `API\0` handshake and are read as requests.
+pub fn read_raw(bytes: &[u8], direction: Option<Direction>) -> Result<Capture> {
+    let (mut rest, direction) = match bytes.strip_prefix(b"API\0") {
+        Some(rest) => (rest, Direction::Request),
+        None => (bytes, direction.unwrap_or(Direction::Response)),
+    };
+
+    let mut frames = Vec::new();
+    while !rest.is_empty() {
+        if rest.len() < 4 {
+            return Err(format!("truncated frame header after {} frames", frames.len()).into());
+        }
+        let (header, body) = rest.split_at(4);
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        if body.len() < length {
+            return Err(format!("truncated frame {}: expected {length} bytes, found {}", frames.len(), body.len()).into());
+        }
+        let (payload, remaining) = body.split_at(length);
+        frames.push(Frame::new(direction, &String::from_utf8_lossy(payload), None));
+        rest = remaining;
+    }
+
+    Ok(with_handshake_version(frames, None))
+}
+
+/// Takes the server version from the handshake reply (`server_version|connection_time`) when the capture has no header.
+fn with_handshake_version(frames: Vec<Frame>, server_version: Option<i32>) -> Capture {
+    let server_version = server_version.or_else(|| {
+        frames
+            .iter()
+            .find(|frame| frame.direction == Direction::Response)
+            .and_then(handshake_version)
+    });
+    Capture { server_version, frames }
+}
+
+/// Returns the server version if `frame` is the reply to the client handshake.
+pub fn handshake_version(frame: &Frame) -> Option<i32> {
+    if frame.direction != Direction::Response || frame.fields.len() != 2 {
+        return None;
+    }
+    // Message ids are well below 100 while server versions start above it.
+    frame.fields[0].parse().ok().filter(|version| *version >= 100)
+}
+
+// Recorded messages are pipe-delimited; wire and trace formats use NUL.
+fn split_fields(raw: &str) -> Vec<String> {
+    let separator = if raw.contains('\0') { '\0' } else { '|' };
+    raw.split_terminator(separator).map(str::to_owned).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_recorder_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("0001-response.msg"), "173|20250415 19:38:30 British Summer Time|").unwrap();
+        fs::write(dir.path().join("0002-request.msg"), "9|8|9000|").unwrap();
+        fs::write(dir.path().join("0010-response.msg"), "10|9000|AAPL|").unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let capture = read(dir.path(), None).unwrap();
+
+        assert_eq!(capture.server_version, Some(173));
+        let directions: Vec<Direction> = capture.frames.iter().map(|frame| frame.direction).collect();
+        assert_eq!(directions, vec![Direction::Response, Direction::Request, Direction::Response]);
+        assert_eq!(capture.frames[2].fields, vec!["10", "9000", "AAPL"]);
+        assert!(capture.frames.iter().all(|frame| frame.at.is_some()));
+    }
+
+    #[test]
+    fn test_read_jsonl() {
+        let text = r#"{"direction": "request", "message": "9|8|9000|", "time": "2025-04-15T19:38:30Z"}
+
+{"direction": "<-", "raw": "10\u000090000\u0000AAPL\u0000"}
+"#;
+        let capture = read_jsonl(text).unwrap();
+
+        assert_eq!(capture.frames.len(), 2);
+        assert_eq!(capture.frames[0].direction, Direction::Request);
+        assert_eq!(capture.frames[0].at.map(|at| at.unix_timestamp()), Some(1744745910));
+        assert_eq!(capture.frames[1].fields, vec!["10", "90000", "AAPL"]);
+        assert_eq!(capture.frames[1].at, None);
+
+        let err = read_jsonl(r#"{"direction": "sideways", "message": "1|"}"#).unwrap_err();
+        assert!(err.to_string().contains("line 1"), "{err}");
+    }
+
+    #[test]
+    fn test_read_trace_export() {
+        let yaml = "
+header:
+  server_version: 176
+  recorded_at: '2025-04-15T19:38:30Z'
+interactions:
+- name: RequestContractData
+  request:
+    raw: \"9\\08\\09000\\0\"
+    fields: []
+  responses:
+  - raw: \"10\\09000\\0AAPL\\0\"
+    fields: []
+";
+        let capture = read_trace_export(serde_yaml::from_str(yaml).unwrap()).unwrap();
+
+        assert_eq!(capture.server_version, Some(176));
+        assert_eq!(capture.frames.len(), 2);
+        assert_eq!(capture.frames[0].fields, vec!["9", "8", "9000"]);
+        assert_eq!(capture.frames[1].direction, Direction::Response);
+    }
+
+    #[test]
+    fn test_read_raw() {
+        // Frames are written pipe-delimited for readability.
+        fn frame(payload: &str) -> Vec<u8> {
+            let payload = payload.replace('|', "\0");
+            let mut bytes = (payload.len() as u32).to_be_bytes().to_vec();
+            bytes.extend_from_slice(payload.as_bytes());
+            bytes
+        }
+
+        let mut client = b"API\0".to_vec();
+        client.extend(frame("v100..187"));
+        client.extend(frame("71|2|100||"));
+        let capture = read_raw(&client, None).unwrap();
+        assert_eq!(capture.frames.len(), 2);
+        assert!(capture.frames.iter().all(|frame| frame.direction == Direction::Request));
+
+        let mut server = frame("173|20250415 19:38:30 British Summer Time|");
+        server.extend(frame("15|1|DU1234567|"));
+        let capture = read_raw(&server, None).unwrap();
+        assert_eq!(capture.server_version, Some(173));
+        assert_eq!(capture.frames[1].fields, vec!["15", "1", "DU1234567"]);
+
+        server.truncate(server.len() - 2);
+        assert!(read_raw(&server, None).is_err());
+    }
+}
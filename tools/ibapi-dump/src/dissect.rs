@@ -0,0 +1,325 @@
+//! Decodes captured frames with the crate's message tables and field parsers.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use ibapi::messages::parser_registry::{MessageParserRegistry, ParsedField};
+use ibapi::messages::{order_id_index, request_id_index, IncomingMessages, OutgoingMessages, ResponseMessage};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::capture::{handshake_version, Direction, Frame};
+
+/// A frame with its message type, ids and named fields.
+#[derive(Debug, Clone)]
+pub struct Dissected {
+    /// Position of the frame in the capture.
+    pub seq: usize,
+    pub direction: Direction,
+    pub at: Option<OffsetDateTime>,
+    /// Numeric message id, `None` for handshake frames and malformed messages.
+    pub message_id: Option<i32>,
+    pub name: String,
+    pub request_id: Option<i32>,
+    pub order_id: Option<i32>,
+    pub fields: Vec<ParsedField>,
+    pub raw: String,
+}
+
+/// Decodes frames for a given server version.
+pub struct Dissector {
+    registry: MessageParserRegistry,
+    server_version: i32,
+}
+
+impl Dissector {
+    pub fn new(server_version: i32) -> Self {
+        Self {
+            registry: MessageParserRegistry::new(),
+            server_version,
+        }
+    }
+
+    pub fn dissect(&self, seq: usize, frame: &Frame) -> Dissected {
+        let parts: Vec<&str> = frame.fields.iter().map(String::as_str).collect();
+        let mut dissected = Dissected {
+            seq,
+            direction: frame.direction,
+            at: frame.at,
+            message_id: None,
+            name: String::from("Unknown"),
+            request_id: None,
+            order_id: None,
+            fields: Vec::new(),
+            raw: frame.fields.join("|"),
+        };
+
+        if let Some(version) = handshake_version(frame) {
+            dissected.name = String::from("Handshake");
+            dissected.fields = vec![field("server_version", &version.to_string()), field("connection_time", &frame.fields[1])];
+            return dissected;
+        }
+        if frame.direction == Direction::Request && parts.len() == 1 && parts[0].starts_with('v') && parts[0].contains("..") {
+            dissected.name = String::from("Handshake");
+            dissected.fields = vec![field("supported_versions", parts[0])];
+            return dissected;
+        }
+
+        dissected.message_id = parts.first().and_then(|id| id.parse().ok());
+        match frame.direction {
+            Direction::Request => self.dissect_request(&mut dissected, &parts),
+            Direction::Response => self.dissect_response(&mut dissected, &parts),
+        }
+        dissected
+    }
+
+    fn dissect_request(&self, dissected: &mut Dissected, parts: &[&str]) {
+        match parts.first().map(|id| OutgoingMessages::from_str(id)) {
+            Some(Ok(message_type)) => {
+                dissected.name = format!("{message_type:?}");
+                dissected.fields = self.registry.parse_request(message_type, parts);
+            }
+            _ => dissected.fields = ibapi::messages::parser_registry::parse_generic_message(parts),
+        }
+
+        // Requests only expose their ids through fields named by a registered parser.
+        let named = |name: &str| {
+            dissected
+                .fields
+                .iter()
+                .find(|field| field.name == name)
+                .and_then(|field| field.value.parse().ok())
+        };
+        dissected.request_id = named("request_id");
+        dissected.order_id = named("order_id");
+    }
+
+    fn dissect_response(&self, dissected: &mut Dissected, parts: &[&str]) {
+        let message = ResponseMessage::from(&format!("{}\0", parts.join("\0"))).with_server_version(self.server_version);
+        let message_type = message.message_type();
+        if message_type != IncomingMessages::NotValid {
+            dissected.name = format!("{message_type:?}");
+        }
+        dissected.fields = self.registry.parse_response(message_type, parts);
+
+        if message_type == IncomingMessages::Error {
+            let id = message.error_request_id();
+            if id >= 0 {
+                dissected.request_id = Some(id);
+                dissected.order_id = Some(id);
+            }
+            return;
+        }
+
+        dissected.request_id = message.request_id();
+        dissected.order_id = message.order_id();
+        // Name the id fields the generic decoder left anonymous.
+        for (index, name) in [(request_id_index(message_type), "request_id"), (order_id_index(message_type), "order_id")] {
+            if let Some(field) = index.and_then(|index| dissected.fields.get_mut(index)) {
+                if field.name.starts_with("field_") {
+                    field.name = name.to_string();
+                }
+            }
+        }
+    }
+}
+
+fn field(name: &str, value: &str) -> ParsedField {
+    ParsedField {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// Selects which messages are printed. Empty criteria match everything.
+#[derive(Debug, Default)]
+pub struct Filter {
+    pub request_ids: Vec<i32>,
+    /// Message type names (case-insensitive) or numeric message ids.
+    pub message_types: Vec<String>,
+    pub since: Option<OffsetDateTime>,
+    pub until: Option<OffsetDateTime>,
+}
+
+impl Filter {
+    pub fn matches(&self, message: &Dissected) -> bool {
+        if !self.request_ids.is_empty() {
+            let ids = [message.request_id, message.order_id];
+            if !self.request_ids.iter().any(|id| ids.contains(&Some(*id))) {
+                return false;
+            }
+        }
+
+        if !self.message_types.is_empty() && !self.message_types.iter().any(|message_type| matches_type(message, message_type)) {
+            return false;
+        }
+
+        // Frames without a timestamp cannot satisfy a time window.
+        if self.since.is_some() || self.until.is_some() {
+            let Some(at) = message.at else {
+                return false;
+            };
+            if self.since.is_some_and(|since| at < since) || self.until.is_some_and(|until| at > until) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn matches_type(message: &Dissected, message_type: &str) -> bool {
+    match message_type.parse::<i32>() {
+        Ok(id) => message.message_id == Some(id),
+        Err(_) => message.name.eq_ignore_ascii_case(message_type),
+    }
+}
+
+/// Formats a message as a summary line followed by one indented line per field.
+pub fn render(message: &Dissected, show_raw: bool) -> String {
+    let arrow = match message.direction {
+        Direction::Request => "->",
+        Direction::Response => "<-",
+    };
+
+    let mut out = format!("#{:04}", message.seq);
+    if let Some(at) = message.at {
+        let _ = write!(out, " {}", at.format(&Rfc3339).unwrap_or_default());
+    }
+    let _ = write!(out, " {arrow} {}", message.name);
+    if let Some(id) = message.message_id {
+        let _ = write!(out, " ({id})");
+    }
+    match (message.request_id, message.order_id) {
+        (Some(request_id), Some(order_id)) if request_id == order_id => {
+            let _ = write!(out, " id={request_id}");
+        }
+        (request_id, order_id) => {
+            if let Some(id) = request_id {
+                let _ = write!(out, " request_id={id}");
+            }
+            if let Some(id) = order_id {
+                let _ = write!(out, " order_id={id}");
+            }
+        }
+    }
+    out.push('\n');
+
+    if show_raw {
+        let _ = writeln!(out, "    raw: {}", message.raw);
+    }
+
+    let width = message.fields.iter().map(|field| field.name.len()).max().unwrap_or(0);
+    for field in &message.fields {
+        let _ = writeln!(out, "    {:width$} = {}", field.name, field.value);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn frame(direction: Direction, raw: &str) -> Frame {
+        Frame {
+            direction,
+            fields: raw.split_terminator('|').map(str::to_owned).collect(),
+            at: None,
+        }
+    }
+
+    #[test]
+    fn test_dissect_requests() {
+        let dissector = Dissector::new(173);
+
+        let message = dissector.dissect(1, &frame(Direction::Request, "62|1|9000|All|NetLiquidation|"));
+        assert_eq!(message.name, "RequestAccountSummary");
+        assert_eq!(message.message_id, Some(62));
+        assert_eq!(message.request_id, Some(9000));
+
+        let message = dissector.dissect(2, &frame(Direction::Request, "9|8|9000|"));
+        assert_eq!(message.name, "RequestContractData");
+        assert_eq!(message.request_id, None);
+
+        let message = dissector.dissect(3, &frame(Direction::Request, "v100..187"));
+        assert_eq!(message.name, "Handshake");
+    }
+
+    #[test]
+    fn test_dissect_responses() {
+        let dissector = Dissector::new(173);
+
+        let message = dissector.dissect(1, &frame(Direction::Response, "173|20250415 19:38:30 British Summer Time|"));
+        assert_eq!(message.name, "Handshake");
+        assert_eq!(message.fields[0].value, "173");
+
+        let message = dissector.dissect(2, &frame(Direction::Response, "10|9000|AAPL|STK|"));
+        assert_eq!(message.name, "ContractData");
+        assert_eq!(message.request_id, Some(9000));
+        assert_eq!(message.fields[1].name, "request_id");
+
+        let message = dissector.dissect(3, &frame(Direction::Response, "3|5|Filled|100|"));
+        assert_eq!(message.order_id, Some(5));
+        assert_eq!(message.fields[1].name, "order_id");
+
+        let message = dissector.dissect(4, &frame(Direction::Response, "4|2|9000|200|No security definition has been found|"));
+        assert_eq!(message.name, "Error");
+        assert_eq!(message.request_id, Some(9000));
+
+        let message = dissector.dissect(5, &frame(Direction::Response, "4|2|-1|2104|Market data farm connection is OK|"));
+        assert_eq!(message.request_id, None);
+    }
+
+    #[test]
+    fn test_filter() {
+        let dissector = Dissector::new(173);
+        let mut message = dissector.dissect(1, &frame(Direction::Response, "10|9000|AAPL|"));
+
+        assert!(Filter::default().matches(&message));
+
+        let by_id = |ids: Vec<i32>| Filter {
+            request_ids: ids,
+            ..Filter::default()
+        };
+        assert!(by_id(vec![1, 9000]).matches(&message));
+        assert!(!by_id(vec![1]).matches(&message));
+
+        let by_type = |types: &[&str]| Filter {
+            message_types: types.iter().map(|message_type| message_type.to_string()).collect(),
+            ..Filter::default()
+        };
+        assert!(by_type(&["contractdata"]).matches(&message));
+        assert!(by_type(&["10"]).matches(&message));
+        assert!(!by_type(&["ContractDataEnd", "52"]).matches(&message));
+
+        let since = Filter {
+            since: Some(datetime!(2025-04-15 19:00 UTC)),
+            ..Filter::default()
+        };
+        assert!(!since.matches(&message), "messages without a time are excluded");
+        message.at = Some(datetime!(2025-04-15 19:38:30 UTC));
+        assert!(since.matches(&message));
+        let until = Filter {
+            until: Some(datetime!(2025-04-15 19:30 UTC)),
+            ..Filter::default()
+        };
+        assert!(!until.matches(&message));
+    }
+
+    #[test]
+    fn test_render() {
+        let dissector = Dissector::new(173);
+        let mut message = dissector.dissect(7, &frame(Direction::Response, "10|9000|AAPL|"));
+        message.at = Some(datetime!(2025-04-15 19:38:30 UTC));
+
+        let rendered = render(&message, true);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "#0007 2025-04-15T19:38:30Z <- ContractData (10) request_id=9000");
+        assert_eq!(lines[1], "    raw: 10|9000|AAPL");
+        assert_eq!(lines[2], "    message_type = 10");
+        assert_eq!(lines[3], "    request_id   = 9000");
+        assert_eq!(lines[4], "    field_3      = AAPL");
+    }
+}
@@ -0,0 +1,99 @@
+//! Prints recorded TWS API sessions with decoded, named fields.
+//!
+//! ```text
+//! cargo run -p ibapi-dump -- /tmp/logs/2025-04-15-19-38-0
+//! cargo run -p ibapi-dump -- trace.yaml --request-id 9000
+//! cargo run -p ibapi-dump -- session.jsonl --type ContractData --type 52 --since 2025-04-15T19:38:00Z
+//! cargo run -p ibapi-dump -- server.bin --direction response --server-version 176
+//! ```
+
+mod capture;
+mod dissect;
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use capture::Direction;
+use dissect::{render, Dissector, Filter};
+
+/// Server version assumed when the capture does not contain the handshake.
+const DEFAULT_SERVER_VERSION: i32 = 176;
+
+#[derive(Parser, Debug)]
+#[command(name = "ibapi-dump", version, about = "Decode recorded TWS API messages")]
+struct Args {
+    /// Recorder directory, `.jsonl` message log, `.yaml`/`.json` trace export or raw socket capture.
+    input: PathBuf,
+
+    /// Only show messages for these request or order ids. Requests match when their decoder names the id field.
+    #[arg(long = "request-id", value_name = "ID")]
+    request_ids: Vec<i32>,
+
+    /// Only show these message types, by name (e.g. `ContractData`) or numeric id.
+    #[arg(long = "type", value_name = "TYPE")]
+    message_types: Vec<String>,
+
+    /// Only show messages recorded at or after this RFC 3339 time.
+    #[arg(long, value_parser = parse_time)]
+    since: Option<OffsetDateTime>,
+
+    /// Only show messages recorded at or before this RFC 3339 time.
+    #[arg(long, value_parser = parse_time)]
+    until: Option<OffsetDateTime>,
+
+    /// Direction of a raw socket capture that does not start with the client handshake.
+    #[arg(long, value_enum)]
+    direction: Option<Direction>,
+
+    /// Server version used for version dependent fields when the capture does not announce one.
+    #[arg(long)]
+    server_version: Option<i32>,
+
+    /// Also print the raw pipe-delimited message.
+    #[arg(long)]
+    raw: bool,
+}
+
+fn parse_time(value: &str) -> Result<OffsetDateTime, String> {
+    OffsetDateTime::parse(value, &Rfc3339).map_err(|err| format!("expected an RFC 3339 time: {err}"))
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let capture = match capture::read(&args.input, args.direction) {
+        Ok(capture) => capture,
+        Err(err) => {
+            eprintln!("ibapi-dump: failed to read {}: {err}", args.input.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let server_version = args.server_version.or(capture.server_version).unwrap_or(DEFAULT_SERVER_VERSION);
+    let dissector = Dissector::new(server_version);
+    let filter = Filter {
+        request_ids: args.request_ids,
+        message_types: args.message_types,
+        since: args.since,
+        until: args.until,
+    };
+
+    let mut out = io::stdout().lock();
+    for (seq, frame) in capture.frames.iter().enumerate() {
+        let message = dissector.dissect(seq, frame);
+        if !filter.matches(&message) {
+            continue;
+        }
+        // Stop quietly when the output is closed, e.g. piped into `head`.
+        if writeln!(out, "{}", render(&message, args.raw)).is_err() {
+            break;
+        }
+    }
+
+    ExitCode::SUCCESS
+}